@@ -0,0 +1,747 @@
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::Discriminator;
+use anyhow::{anyhow, bail, Result};
+use proof_of_touch_grass::state::{
+    Challenge, ChallengeStatus, Dispute, GeoPoint, GrassEmission, Justification, JuryVote,
+    PlatformLimits, UserLimits, Vote,
+};
+use proof_of_touch_grass::ID;
+use proof_of_touch_grass_client::accounts::AccountFetcher;
+use proof_of_touch_grass_client::{accounts, instructions, pda};
+use solana_keypair::read_keypair_file;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+use crate::rpc::{Cluster, Memcmp, RpcClient};
+use crate::spec::{ChallengeSpec, TemplateSpec};
+
+pub struct Context {
+    cluster: Box<dyn Cluster>,
+    payer: Box<dyn Signer>,
+    dry_run: bool,
+}
+
+impl Context {
+    pub fn new(url: &str, keypair: Option<&Path>, dry_run: bool) -> Result<Self> {
+        let keypair_path = match keypair {
+            Some(path) => path.to_path_buf(),
+            None => default_keypair_path()?,
+        };
+        let payer = read_keypair_file(&keypair_path)
+            .map_err(|err| anyhow!("failed to read keypair {}: {err}", keypair_path.display()))?;
+
+        Ok(Self::with_cluster(
+            Box::new(RpcClient::new(url)),
+            Box::new(payer),
+            dry_run,
+        ))
+    }
+
+    pub fn with_cluster(cluster: Box<dyn Cluster>, payer: Box<dyn Signer>, dry_run: bool) -> Self {
+        Self {
+            cluster,
+            payer,
+            dry_run,
+        }
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    /// Signs and sends (or simulates, with `--dry-run`) a transaction
+    fn send(&self, instructions: &[Instruction]) -> Result<()> {
+        let blockhash = self.cluster.get_latest_blockhash()?;
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.signer()),
+            &[self.payer.as_ref()],
+            blockhash,
+        );
+
+        if self.dry_run {
+            let simulation = self.cluster.simulate_transaction(&transaction)?;
+            for log in &simulation.logs {
+                println!("  {log}");
+            }
+            if let Some(units) = simulation.units_consumed {
+                println!("compute units consumed: {units}");
+            }
+            match simulation.error {
+                Some(error) => bail!("simulation failed: {error}"),
+                None => println!("simulation succeeded (dry run, nothing sent)"),
+            }
+        } else {
+            let signature = self.cluster.send_and_confirm_transaction(&transaction)?;
+            println!("signature: {signature}");
+        }
+        Ok(())
+    }
+
+    fn fetch_challenge(&self, address: &Pubkey) -> Result<Challenge> {
+        Ok(accounts::fetch_challenge(self, address)?)
+    }
+
+    /// The open dispute of a Disputed challenge
+    fn open_dispute(&self, challenge: &Pubkey) -> Result<Dispute> {
+        let state = self.fetch_challenge(challenge)?;
+        if state.status != ChallengeStatus::Disputed {
+            bail!("challenge is {}, no dispute is open", state.status);
+        }
+        let address = pda::dispute_pda(challenge, state.dispute_count - 1).0;
+        Ok(accounts::fetch_dispute(self, &address)?)
+    }
+
+    /// Every challenge matching `filters`, in addition to the discriminator filter
+    fn challenges(&self, mut filters: Vec<Memcmp>) -> Result<Vec<(Pubkey, Challenge)>> {
+        filters.insert(
+            0,
+            Memcmp {
+                offset: 0,
+                bytes: Challenge::DISCRIMINATOR.to_vec(),
+            },
+        );
+        self.cluster
+            .get_program_accounts(&ID, &filters)?
+            .into_iter()
+            .map(|(address, data)| Ok((address, accounts::decode(&data)?)))
+            .collect()
+    }
+}
+
+impl AccountFetcher for Context {
+    type Error = anyhow::Error;
+
+    fn fetch_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+        self.cluster.get_account_data(address)
+    }
+}
+
+fn default_keypair_path() -> Result<PathBuf> {
+    let home = std::env::var_os("HOME").ok_or_else(|| anyhow!("HOME is not set, pass --keypair"))?;
+    Ok(PathBuf::from(home).join(".config/solana/id.json"))
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+pub fn init_user(ctx: &Context) -> Result<()> {
+    ctx.send(&[instructions::initialize_user(&ctx.signer())])?;
+    println!("user profile: {}", pda::user_pda(&ctx.signer()).0);
+    Ok(())
+}
+
+pub fn set_limits(ctx: &Context, limits: UserLimits) -> Result<()> {
+    ctx.send(&[instructions::set_user_limits(&ctx.signer(), limits)])
+}
+
+/// Defaults to the current time in milliseconds; a reused ID fails on-chain
+/// Current time in milliseconds, unique enough for IDs picked by hand
+fn default_challenge_id() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+pub fn create_challenge(ctx: &Context, spec: &Path, challenge_id: Option<u64>) -> Result<()> {
+    let args = ChallengeSpec::load(spec)?.into_args()?;
+    let creator = ctx.signer();
+    let challenge_id = challenge_id.unwrap_or_else(default_challenge_id);
+    let challenge = pda::challenge_pda(&creator, challenge_id).0;
+
+    ctx.send(&[instructions::create_challenge(&creator, challenge_id, args)])?;
+    println!("challenge: {challenge} (id {challenge_id})");
+    Ok(())
+}
+
+pub fn create_template(ctx: &Context, spec: &Path, template_id: Option<u64>) -> Result<()> {
+    let args = TemplateSpec::load(spec)?.into_args()?;
+    let author = ctx.signer();
+    let template_id = template_id.unwrap_or_else(default_challenge_id);
+    let template = pda::template_pda(&author, template_id).0;
+
+    ctx.send(&[instructions::create_template(&author, template_id, args)])?;
+    println!("template: {template} (id {template_id})");
+    Ok(())
+}
+
+pub fn create_from_template(
+    ctx: &Context,
+    template: &Pubkey,
+    stake_amount: u64,
+    start_time: i64,
+    verifiers: Vec<Pubkey>,
+    verifier_weights: Vec<u8>,
+    challenge_id: Option<u64>,
+) -> Result<()> {
+    let creator = ctx.signer();
+    let challenge_id = challenge_id.unwrap_or_else(default_challenge_id);
+    let challenge = pda::challenge_pda(&creator, challenge_id).0;
+
+    ctx.send(&[instructions::create_challenge_from_template(
+        &creator,
+        template,
+        challenge_id,
+        stake_amount,
+        start_time,
+        verifiers,
+        verifier_weights,
+    )])?;
+    println!("challenge: {challenge} (id {challenge_id})");
+    Ok(())
+}
+
+pub fn retry(
+    ctx: &Context,
+    previous: &Pubkey,
+    stake_amount: Option<u64>,
+    start_time: Option<i64>,
+    challenge_id: Option<u64>,
+) -> Result<()> {
+    let state = ctx.fetch_challenge(previous)?;
+    let creator = ctx.signer();
+    let challenge_id = challenge_id.unwrap_or_else(default_challenge_id);
+    let challenge = pda::challenge_pda(&creator, challenge_id).0;
+
+    ctx.send(&[instructions::retry_challenge(
+        &creator,
+        previous,
+        state.template.as_ref(),
+        &state.verifiers,
+        challenge_id,
+        stake_amount.unwrap_or(state.stake_amount),
+        start_time.unwrap_or_else(now),
+    )])?;
+    println!("challenge: {challenge} (id {challenge_id})");
+    Ok(())
+}
+
+pub fn submit_evidence(
+    ctx: &Context,
+    challenge: &Pubkey,
+    ipfs_hash: String,
+    metadata: String,
+    location: Option<(i32, i32)>,
+) -> Result<()> {
+    let evidence_index = ctx.fetch_challenge(challenge)?.evidence_count;
+    let location = location.map(|(latitude, longitude)| GeoPoint { latitude, longitude });
+
+    ctx.send(&[instructions::submit_evidence(
+        &ctx.signer(),
+        challenge,
+        evidence_index,
+        ipfs_hash,
+        metadata,
+        location,
+    )])?;
+    println!("evidence: {}", pda::evidence_pda(challenge, evidence_index).0);
+    Ok(())
+}
+
+pub fn vote(
+    ctx: &Context,
+    challenge: &Pubkey,
+    vote: Vote,
+    justification: Option<Justification>,
+) -> Result<()> {
+    let state = ctx.fetch_challenge(challenge)?;
+    ctx.send(&[instructions::verify_evidence(
+        &ctx.signer(),
+        challenge,
+        &state.creator,
+        &state.verifiers,
+        vote,
+        justification,
+    )])
+}
+
+pub fn change_vote(
+    ctx: &Context,
+    challenge: &Pubkey,
+    vote: Vote,
+    justification: Option<Justification>,
+) -> Result<()> {
+    let state = ctx.fetch_challenge(challenge)?;
+    ctx.send(&[instructions::update_vote(
+        &ctx.signer(),
+        challenge,
+        &state.creator,
+        &state.verifiers,
+        vote,
+        justification,
+    )])
+}
+
+pub fn cancel(ctx: &Context, challenge: &Pubkey) -> Result<()> {
+    let verifiers = ctx.fetch_challenge(challenge)?.verifiers;
+    ctx.send(&[instructions::cancel_challenge(&ctx.signer(), challenge, &verifiers)])
+}
+
+pub fn request_extension(ctx: &Context, challenge: &Pubkey, new_end_time: i64) -> Result<()> {
+    ctx.send(&[instructions::request_extension(&ctx.signer(), challenge, new_end_time)])?;
+    println!("extension: {}", pda::extension_pda(challenge).0);
+    Ok(())
+}
+
+pub fn approve_extension(ctx: &Context, challenge: &Pubkey) -> Result<()> {
+    let state = ctx.fetch_challenge(challenge)?;
+    ctx.send(&[instructions::approve_extension(
+        &ctx.signer(),
+        challenge,
+        &state.creator,
+        &state.verifiers,
+    )])
+}
+
+pub fn withdraw_extension(ctx: &Context, challenge: &Pubkey) -> Result<()> {
+    ctx.send(&[instructions::withdraw_extension(&ctx.signer(), challenge)])
+}
+
+pub fn dispute(
+    ctx: &Context,
+    challenge: &Pubkey,
+    reason: String,
+    contested: Option<&Pubkey>,
+) -> Result<()> {
+    let dispute_index = ctx.fetch_challenge(challenge)?.dispute_count;
+    ctx.send(&[instructions::dispute_verification(
+        &ctx.signer(),
+        challenge,
+        dispute_index,
+        reason,
+        contested,
+    )])
+}
+
+pub fn escalate(ctx: &Context, challenge: &Pubkey) -> Result<()> {
+    let dispute = ctx.open_dispute(challenge)?;
+    let pool = accounts::fetch_juror_pool(ctx)?;
+    ctx.send(&[instructions::escalate_dispute(
+        &ctx.signer(),
+        challenge,
+        dispute.index,
+        &pool,
+    )])
+}
+
+pub fn jury_vote(ctx: &Context, challenge: &Pubkey, vote: JuryVote) -> Result<()> {
+    let dispute = ctx.open_dispute(challenge)?;
+    ctx.send(&[instructions::cast_jury_vote(&ctx.signer(), challenge, dispute.index, vote)])
+}
+
+pub fn settle_jury(ctx: &Context, challenge: &Pubkey) -> Result<()> {
+    let state = ctx.fetch_challenge(challenge)?;
+    let dispute = ctx.open_dispute(challenge)?;
+    ctx.send(&[instructions::settle_jury(
+        challenge,
+        &dispute,
+        &state.creator,
+        &state.verifiers,
+    )])
+}
+
+pub fn register_juror(ctx: &Context, stake: u64) -> Result<()> {
+    ctx.send(&[instructions::register_juror(&ctx.signer(), stake)])
+}
+
+pub fn unregister_juror(ctx: &Context) -> Result<()> {
+    ctx.send(&[instructions::unregister_juror(&ctx.signer())])
+}
+
+pub fn withdraw_juror_stake(ctx: &Context) -> Result<()> {
+    ctx.send(&[instructions::withdraw_juror_stake(&ctx.signer())])
+}
+
+pub fn claim(ctx: &Context, challenge: &Pubkey) -> Result<()> {
+    let state = ctx.fetch_challenge(challenge)?;
+    let as_verifier = match state.status {
+        // Approving verifiers claim their GRASS reward
+        ChallengeStatus::Completed => ctx.signer() != state.creator,
+        ChallengeStatus::Refunded => false,
+        // Nobody rejected: the creator settles the escrow
+        ChallengeStatus::Failed => state.rejection_count > 0,
+        status => bail!("challenge is {status}, nothing to claim"),
+    };
+    ctx.send(&[instructions::claim_funds(
+        &ctx.signer(),
+        challenge,
+        &state.creator,
+        &state.verifiers,
+        as_verifier,
+        accounts::grass_configured(ctx)?,
+    )])
+}
+
+pub fn curate_template(ctx: &Context, template: &Pubkey, curated: bool) -> Result<()> {
+    ctx.send(&[instructions::curate_template(&ctx.signer(), template, curated)])
+}
+
+pub fn configure_grass(ctx: &Context, emission: GrassEmission) -> Result<()> {
+    ctx.send(&[instructions::configure_grass(&ctx.signer(), emission)])
+}
+
+pub fn set_platform_limits(ctx: &Context, limits: PlatformLimits) -> Result<()> {
+    ctx.send(&[instructions::set_platform_limits(&ctx.signer(), limits)])
+}
+
+pub fn resolve_dispute(ctx: &Context, challenge: &Pubkey, overturn: bool) -> Result<()> {
+    let state = ctx.fetch_challenge(challenge)?;
+    let dispute = ctx.open_dispute(challenge)?;
+    ctx.send(&[instructions::resolve_dispute(
+        &ctx.signer(),
+        challenge,
+        &dispute,
+        &state.creator,
+        &state.verifiers,
+        overturn,
+    )])
+}
+
+/// Whether `update_challenge_state` would move the challenge at `now`
+fn transition_due(challenge: &Challenge, now: i64) -> bool {
+    match challenge.status {
+        ChallengeStatus::Created => now >= challenge.start_time,
+        ChallengeStatus::Active => now > challenge.end_time,
+        ChallengeStatus::PendingVerification => now > challenge.verification_period_end,
+        _ => false,
+    }
+}
+
+pub fn crank(ctx: &Context, only: Option<&Pubkey>) -> Result<()> {
+    let candidates = match only {
+        Some(address) => vec![(*address, ctx.fetch_challenge(address)?)],
+        None => ctx.challenges(Vec::new())?,
+    };
+
+    let now = now();
+    let mut failures = 0;
+    for (address, challenge) in candidates.iter().filter(|(_, c)| transition_due(c, now)) {
+        println!("cranking {address} ({})", challenge.status);
+        let ix = instructions::update_challenge_state(
+            &ctx.signer(),
+            address,
+            &challenge.creator,
+            &challenge.verifiers,
+        );
+        if let Err(err) = ctx.send(&[ix]) {
+            eprintln!("  failed: {err:#}");
+            failures += 1;
+        }
+    }
+    if failures > 0 {
+        bail!("{failures} challenge(s) failed to crank");
+    }
+    Ok(())
+}
+
+pub fn show_challenge(ctx: &Context, address: &Pubkey) -> Result<()> {
+    let challenge = ctx.fetch_challenge(address)?;
+    println!("challenge:           {address}");
+    println!("title:               {}", challenge.title);
+    println!("description:         {}", challenge.description);
+    println!("creator:             {}", challenge.creator);
+    println!("status:              {}", challenge.status);
+    println!("stake (lamports):    {}", challenge.stake_amount);
+    println!("start time:          {}", challenge.start_time);
+    println!("end time:            {}", challenge.end_time);
+    println!("verification ends:   {}", challenge.verification_period_end);
+    println!(
+        "evidence:            {}/{}",
+        challenge.evidence_count, challenge.required_proofs
+    );
+    println!(
+        "votes:               {} approve / {} reject / {} abstain",
+        challenge.approval_count, challenge.rejection_count, challenge.abstain_count
+    );
+    println!(
+        "weight:              {} approve / {} reject (need {} of {})",
+        challenge.approval_weight,
+        challenge.rejection_weight,
+        challenge.required_approval_weight,
+        challenge.total_weight()
+    );
+    println!("geofences:           {}", challenge.geofences.len());
+    println!("finalized at:        {}", challenge.finalized_at);
+    println!("claimed:             {}", challenge.claimed);
+    println!(
+        "on timeout:          {:?} (quorum {} votes)",
+        challenge.verification_rules.timeout_policy, challenge.verification_rules.min_votes
+    );
+    if let Some(min_score) = challenge.min_verifier_reputation {
+        println!("min reputation:      {min_score}");
+    }
+    if let Some(template) = challenge.template {
+        println!("template:            {template}");
+    }
+    if let Some(previous) = challenge.previous_challenge {
+        println!("retry of:            {previous}");
+    }
+    if let Some(retry) = challenge.retried_by {
+        println!("retried by:          {retry}");
+    }
+    println!("verifiers:");
+    for (verifier, weight) in challenge.verifiers.iter().zip(&challenge.verifier_weights) {
+        println!("  {verifier} (weight {weight})");
+    }
+    Ok(())
+}
+
+pub fn show_extension(ctx: &Context, challenge: &Pubkey) -> Result<()> {
+    let state = ctx.fetch_challenge(challenge)?;
+    let extension = accounts::fetch_extension(ctx, &pda::extension_pda(challenge).0)?;
+    println!("challenge:           {challenge}");
+    println!("end time:            {} -> {}", state.end_time, extension.new_end_time);
+    println!("fee (lamports):      {}", extension.fee);
+    let approval_weight: u8 = extension
+        .approvals
+        .iter()
+        .map(|verifier| state.weight_of(verifier).unwrap_or(0))
+        .sum();
+    println!(
+        "approval weight:     {}/{}",
+        approval_weight, state.required_approval_weight
+    );
+    for verifier in &extension.approvals {
+        println!("  {verifier}");
+    }
+    println!("requested at:        {}", extension.requested_at);
+    Ok(())
+}
+
+pub fn show_template(ctx: &Context, address: &Pubkey) -> Result<()> {
+    let template = accounts::fetch_template(ctx, address)?;
+    println!("template:            {address}");
+    println!("author:              {} (id {})", template.author, template.template_id);
+    println!("curated:             {}", template.curated);
+    println!("title:               {}", template.title);
+    println!("description:         {}", template.description);
+    println!("duration (s):        {}", template.duration);
+    println!("verification (s):    {}", template.verification_period);
+    println!(
+        "proofs / approval weight: {} / {}",
+        template.required_proofs, template.required_approval_weight
+    );
+    println!("geofences:           {}", template.geofences.len());
+    if let Some(min_score) = template.min_verifier_reputation {
+        println!("min reputation:      {min_score}");
+    }
+    println!("uses:                {}", template.uses);
+    Ok(())
+}
+
+pub fn show_vote(ctx: &Context, challenge: &Pubkey, verifier: &Pubkey) -> Result<()> {
+    let address = pda::verification_pda(challenge, verifier).0;
+    let verification = accounts::fetch_verification(ctx, &address)?;
+    println!("verification:        {address}");
+    println!("challenge:           {challenge}");
+    println!("verifier:            {verifier}");
+    println!("vote:                {}", verification.vote);
+    println!("voted at:            {}", verification.timestamp);
+    match verification.justification {
+        Some(justification) => {
+            println!("reason:              {}", justification.reason);
+            if let Some(attachment) = justification.attachment {
+                println!("attachment:          {attachment}");
+            }
+        }
+        None => println!("reason:              (none given)"),
+    }
+    Ok(())
+}
+
+pub fn show_reputation(ctx: &Context, verifier: &Pubkey) -> Result<()> {
+    let address = pda::reputation_pda(verifier).0;
+    let reputation = accounts::fetch_reputation(ctx, &address)?;
+    println!("verifier:            {verifier}");
+    println!("score:               {}", reputation.score());
+    println!("votes cast:          {}", reputation.votes_cast);
+    println!("votes won:           {}", reputation.votes_won);
+    println!("votes overturned:    {}", reputation.votes_overturned);
+    println!("missed windows:      {}", reputation.missed_windows);
+    Ok(())
+}
+
+pub fn show_user(ctx: &Context, authority: Option<&Pubkey>) -> Result<()> {
+    let authority = authority.copied().unwrap_or_else(|| ctx.signer());
+    let user = accounts::fetch_user(ctx, &pda::user_pda(&authority).0)?;
+    let rate = user.success_rate_bps();
+    println!("authority:           {authority}");
+    println!("challenges:          {}", user.total_challenges);
+    println!("active:              {}", user.active_challenges);
+    println!(
+        "outcomes:            {} completed / {} failed / {} cancelled / {} refunded",
+        user.completed, user.failed, user.cancelled, user.refunded
+    );
+    println!("success rate:        {}.{:02}%", rate / 100, rate % 100);
+    println!(
+        "retries:             {} ({} redeemed)",
+        user.retries, user.redemptions
+    );
+    println!(
+        "streak:              {} (longest {})",
+        user.current_streak, user.longest_streak
+    );
+    println!("staked (lamports):   {}", user.total_staked);
+    println!("won (lamports):      {}", user.total_won);
+    println!("lost (lamports):     {}", user.total_lost);
+    println!("forfeit (lamports):  {}", user.total_forfeited);
+    println!("at risk (lamports):  {}", user.staked_at_risk);
+    let now = now();
+    print_limits(user.limits_at(now));
+    if let Some(pending) = user.pending_limits.as_ref().filter(|_| user.pending_limits_at > now) {
+        println!("loosening at:        {}", user.pending_limits_at);
+        print_limits(pending);
+    }
+    if user.cooldown_until > now {
+        println!("cooling down until:  {}", user.cooldown_until);
+    }
+    Ok(())
+}
+
+fn print_limits(limits: &UserLimits) {
+    let or_platform = |limit: Option<String>| limit.unwrap_or_else(|| "platform max".into());
+    println!(
+        "max open:            {}",
+        or_platform(limits.max_active_challenges.map(|max| max.to_string()))
+    );
+    println!("max stake:           {}", or_platform(limits.max_stake.map(|max| max.to_string())));
+    println!(
+        "max at risk:         {}",
+        or_platform(limits.max_stake_at_risk.map(|max| max.to_string()))
+    );
+    println!(
+        "failure cooldown:    {}",
+        limits
+            .failure_cooldown
+            .map_or_else(|| "none".into(), |seconds| format!("{seconds}s"))
+    );
+}
+
+pub fn show_platform(ctx: &Context) -> Result<()> {
+    let limits = accounts::fetch_platform_limits(ctx)?;
+    println!("max open:            {}", limits.max_active_challenges);
+    println!("max stake:           {}", limits.max_stake);
+    println!("max at risk:         {}", limits.max_stake_at_risk);
+    println!("max cooldown:        {}s", limits.max_failure_cooldown);
+    Ok(())
+}
+
+pub fn show_grass(ctx: &Context) -> Result<()> {
+    let config = accounts::fetch_grass_config(ctx)?;
+    let emission = &config.emission;
+    let discount = emission.fee_discount_bps;
+    println!("mint:                {}", config.mint);
+    println!("creator reward:      {}", emission.creator_reward);
+    println!("verifier reward:     {} per vote weight", emission.verifier_reward);
+    println!("rate:                {} per SOL of fees", emission.grass_per_sol);
+    println!("fee discount:        {}.{:02}%", discount / 100, discount % 100);
+    println!("emitted:             {}", config.total_emitted);
+    Ok(())
+}
+
+fn print_dispute(address: &Pubkey, dispute: &Dispute) -> Result<()> {
+    println!("dispute #{}:          {address}", dispute.index);
+    println!("disputer:            {}", dispute.disputer);
+    println!("reason:              {}", dispute.reason);
+    if let Some(contested) = dispute.contested_verification {
+        println!("contested:           {contested}");
+    }
+    println!("disputed outcome:    {}", dispute.previous_status);
+    println!("bond (lamports):     {}", dispute.bond);
+    println!("filed at:            {}", dispute.timestamp);
+    if dispute.is_resolved() {
+        println!("resolved at:         {}", dispute.resolved_at);
+    }
+    if !dispute.is_escalated() {
+        println!("jury:                (not escalated)");
+        return Ok(());
+    }
+    let (uphold, overturn) = dispute.jury_tally();
+    println!("escalated at:        {}", dispute.escalated_at);
+    println!("voting ends:         {}", dispute.jury_deadline()?);
+    println!("tally:               {uphold} uphold / {overturn} overturn");
+    for seat in &dispute.jury {
+        let vote = seat.vote.as_ref().map_or_else(|| "-".into(), ToString::to_string);
+        println!("  {}  {vote}", seat.juror);
+    }
+    Ok(())
+}
+
+pub fn show_disputes(ctx: &Context, challenge: &Pubkey) -> Result<()> {
+    let disputes = accounts::fetch_disputes(ctx, challenge)?;
+    if disputes.is_empty() {
+        println!("no disputes filed");
+    }
+    for (position, (address, dispute)) in disputes.iter().enumerate() {
+        if position > 0 {
+            println!();
+        }
+        print_dispute(address, dispute)?;
+    }
+    Ok(())
+}
+
+pub fn show_juror(ctx: &Context, authority: Option<&Pubkey>) -> Result<()> {
+    let authority = authority.copied().unwrap_or_else(|| ctx.signer());
+    let juror = accounts::fetch_juror(ctx, &pda::juror_pda(&authority).0)?;
+    println!("authority:           {authority}");
+    println!("stake (lamports):    {}", juror.stake);
+    println!("registered at:       {}", juror.registered_at);
+    if juror.unbonding_at > 0 {
+        println!("withdrawable at:     {}", juror.unbonding_at);
+    }
+    println!("cases:               {}", juror.cases);
+    println!(
+        "votes:               {} with the majority / {} against or missed",
+        juror.majority_votes, juror.minority_votes
+    );
+    println!("pending verdicts:    {}", juror.pending_verdicts);
+    Ok(())
+}
+
+fn print_challenges(challenges: &[(Pubkey, Challenge)]) {
+    if challenges.is_empty() {
+        println!("no challenges found");
+    }
+    for (address, challenge) in challenges {
+        println!(
+            "{address}  {:<20}  {:>15} lamports  {}",
+            challenge.status.to_string(),
+            challenge.stake_amount,
+            challenge.title
+        );
+    }
+}
+
+pub fn list_mine(ctx: &Context) -> Result<()> {
+    let challenges = accounts::fetch_challenges_by(ctx, &ctx.signer())?;
+    print_challenges(&challenges);
+    Ok(())
+}
+
+pub fn list_assigned(ctx: &Context) -> Result<()> {
+    let assignments = accounts::fetch_assignments(ctx, &ctx.signer())?;
+    if assignments.is_empty() {
+        println!("no open assignments");
+    }
+    for assignment in assignments {
+        let challenge = ctx.fetch_challenge(&assignment.challenge)?;
+        println!(
+            "{}  {:<20}  review by {}  {}",
+            assignment.challenge,
+            challenge.status.to_string(),
+            assignment.verification_period_end,
+            challenge.title
+        );
+    }
+    Ok(())
+}
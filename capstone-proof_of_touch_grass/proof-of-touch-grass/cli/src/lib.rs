@@ -0,0 +1,6 @@
+//! Library half of `ptg`: the commands, the spec files they read and the
+//! cluster they talk to. `main.rs` only parses arguments.
+
+pub mod commands;
+pub mod rpc;
+pub mod spec;
@@ -0,0 +1,462 @@
+//! `ptg`: command-line tool for creators, verifiers and admins of the
+//! proof-of-touch-grass program.
+
+use std::path::PathBuf;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use proof_of_touch_grass::state::{GrassEmission, Justification, PlatformLimits, UserLimits};
+use proof_of_touch_grass_cli::commands;
+
+const DEFAULT_RPC_URL: &str = "https://api.devnet.solana.com";
+
+#[derive(Parser)]
+#[command(name = "ptg", version, about = "Operate the proof-of-touch-grass program")]
+struct Cli {
+    /// JSON-RPC endpoint
+    #[arg(long, short = 'u', global = true, env = "PTG_RPC_URL", default_value = DEFAULT_RPC_URL)]
+    url: String,
+    /// Signer keypair file [default: ~/.config/solana/id.json]
+    #[arg(long, short = 'k', global = true, env = "PTG_KEYPAIR")]
+    keypair: Option<PathBuf>,
+    /// Simulate transactions and print the logs instead of sending them
+    #[arg(long, global = true)]
+    dry_run: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create your user profile
+    InitUser,
+    /// Set limits on your own challenges; omitted limits fall back to the platform maximums.
+    /// Tighter limits apply at once, looser ones after a day.
+    SetLimits {
+        /// Most challenges open at once
+        #[arg(long)]
+        max_active: Option<u32>,
+        /// Largest stake for one challenge, in lamports
+        #[arg(long)]
+        max_stake: Option<u64>,
+        /// Largest total stake across open challenges, in lamports
+        #[arg(long)]
+        max_at_risk: Option<u64>,
+        /// Seconds to wait after a failed challenge before creating another
+        #[arg(long)]
+        cooldown: Option<i64>,
+    },
+    /// Create a challenge from a TOML spec
+    CreateChallenge {
+        spec: PathBuf,
+        /// Challenge ID, unique among your challenges [default: current time in ms]
+        #[arg(long)]
+        id: Option<u64>,
+    },
+    /// Publish a challenge template from a TOML spec
+    CreateTemplate {
+        spec: PathBuf,
+        /// Template ID, unique among your templates [default: current time in ms]
+        #[arg(long)]
+        id: Option<u64>,
+    },
+    /// Create a challenge from a template
+    CreateFromTemplate {
+        template: Pubkey,
+        /// Stake in lamports
+        #[arg(long)]
+        stake: u64,
+        /// Start time, unix seconds
+        #[arg(long)]
+        start: i64,
+        /// A verifier, repeated for each one
+        #[arg(long = "verifier", required = true)]
+        verifiers: Vec<Pubkey>,
+        /// Vote weight of each verifier, in order [default: 1 each]
+        #[arg(long = "weight")]
+        weights: Vec<u8>,
+        /// Challenge ID, unique among your challenges [default: current time in ms]
+        #[arg(long)]
+        id: Option<u64>,
+    },
+    /// Retry a failed challenge whose dispute window has closed, with the same terms
+    Retry {
+        /// The failed challenge
+        challenge: Pubkey,
+        /// Stake in lamports, at least the failed challenge's [default: the same stake]
+        #[arg(long)]
+        stake: Option<u64>,
+        /// Start time, unix seconds [default: now]
+        #[arg(long)]
+        start: Option<i64>,
+        /// Challenge ID, unique among your challenges [default: current time in ms]
+        #[arg(long)]
+        id: Option<u64>,
+    },
+    /// Submit the next piece of evidence for your challenge
+    SubmitEvidence {
+        challenge: Pubkey,
+        ipfs_hash: String,
+        #[arg(long, default_value = "")]
+        metadata: String,
+        /// Latitude in microdegrees
+        #[arg(long, requires = "longitude", allow_hyphen_values = true)]
+        latitude: Option<i32>,
+        /// Longitude in microdegrees
+        #[arg(long, requires = "latitude", allow_hyphen_values = true)]
+        longitude: Option<i32>,
+    },
+    /// Vote on a challenge you verify
+    Vote {
+        challenge: Pubkey,
+        vote: VoteArg,
+        #[command(flatten)]
+        justification: JustificationArgs,
+    },
+    /// Change the vote you cast on a challenge still pending verification
+    ChangeVote {
+        challenge: Pubkey,
+        vote: VoteArg,
+        /// Replaces the justification given with the earlier vote
+        #[command(flatten)]
+        justification: JustificationArgs,
+    },
+    /// Cancel your challenge before it is pending verification
+    Cancel { challenge: Pubkey },
+    /// Propose a later end time for your challenge; its verifiers must approve it
+    RequestExtension {
+        challenge: Pubkey,
+        /// Proposed end time, unix seconds
+        new_end_time: i64,
+    },
+    /// Approve the pending extension of a challenge you verify
+    ApproveExtension { challenge: Pubkey },
+    /// Withdraw your pending extension and get the held fee back
+    WithdrawExtension { challenge: Pubkey },
+    /// Dispute a finalized challenge within the dispute window, bonding 2% of its stake
+    Dispute {
+        challenge: Pubkey,
+        #[arg(long)]
+        reason: String,
+        /// The verifier whose vote you contest
+        #[arg(long)]
+        verifier: Option<Pubkey>,
+    },
+    /// Hand your dispute to a jury drawn from the juror pool
+    Escalate { challenge: Pubkey },
+    /// Vote on an escalated dispute you sit on the jury of
+    JuryVote {
+        challenge: Pubkey,
+        #[arg(value_enum)]
+        vote: JuryVoteArg,
+    },
+    /// Apply the jury's verdict once it is in, settling the jurors' stakes
+    SettleJury { challenge: Pubkey },
+    /// Claim funds once the dispute window has closed
+    Claim { challenge: Pubkey },
+    /// Juror pool membership
+    #[command(subcommand)]
+    Juror(JurorCommand),
+    /// Admin operations
+    #[command(subcommand)]
+    Admin(AdminCommand),
+    /// Show a single account
+    #[command(subcommand)]
+    Show(ShowCommand),
+    /// List challenges
+    #[command(subcommand)]
+    List(ListCommand),
+}
+
+#[derive(Subcommand)]
+enum JurorCommand {
+    /// Join the juror pool, bonding a stake
+    Register {
+        /// Stake in lamports
+        #[arg(long)]
+        stake: u64,
+    },
+    /// Leave the juror pool; the stake unbonds over the unbonding period
+    Unregister,
+    /// Withdraw your stake once it has unbonded and your verdicts are settled
+    Withdraw,
+}
+
+#[derive(Subcommand)]
+enum AdminCommand {
+    /// Advance every challenge whose time-based transition is due
+    Crank {
+        /// Only crank this challenge
+        #[arg(long)]
+        challenge: Option<Pubkey>,
+    },
+    /// Mark a template as curated, or withdraw the mark with --revoke
+    CurateTemplate {
+        template: Pubkey,
+        #[arg(long)]
+        revoke: bool,
+    },
+    /// Settle a disputed challenge, upholding its outcome unless --overturn
+    ResolveDispute {
+        challenge: Pubkey,
+        /// Reverse the disputed outcome
+        #[arg(long)]
+        overturn: bool,
+    },
+    /// Set the GRASS emission parameters, creating the mint on first use.
+    /// Amounts are in GRASS base units.
+    ConfigureGrass {
+        /// Minted to the creator of each completed challenge
+        #[arg(long)]
+        creator_reward: u64,
+        /// Minted to each verifier on the final outcome, per unit of vote weight
+        #[arg(long)]
+        verifier_reward: u64,
+        /// GRASS charged per SOL of platform fee, before the discount
+        #[arg(long)]
+        grass_per_sol: u64,
+        /// Discount on fees paid in GRASS, in basis points
+        #[arg(long, default_value_t = 0)]
+        fee_discount_bps: u64,
+    },
+    /// Set the ceilings on every user's limits
+    SetPlatformLimits {
+        /// Most challenges a user can have open at once
+        #[arg(long)]
+        max_active: u32,
+        /// Largest stake for one challenge, in lamports
+        #[arg(long)]
+        max_stake: u64,
+        /// Largest total stake across a user's open challenges, in lamports
+        #[arg(long)]
+        max_at_risk: u64,
+        /// Longest failure cooldown a user can set, in seconds
+        #[arg(long)]
+        max_cooldown: i64,
+    },
+}
+
+#[derive(Subcommand)]
+enum ShowCommand {
+    Challenge { address: Pubkey },
+    /// The pending extension of a challenge
+    Extension { challenge: Pubkey },
+    /// A challenge template
+    Template { address: Pubkey },
+    /// A verifier's vote on a challenge, with its justification
+    Vote { challenge: Pubkey, verifier: Pubkey },
+    /// A verifier's reputation record
+    Reputation { verifier: Pubkey },
+    /// A creator's profile, yours unless another authority is given
+    User { authority: Option<Pubkey> },
+    /// Every dispute filed on a challenge, with its bond and jury
+    Disputes { challenge: Pubkey },
+    /// A juror's stake and record, yours unless another authority is given
+    Juror { authority: Option<Pubkey> },
+    /// The GRASS mint and its emission parameters
+    Grass,
+    /// The platform ceilings on user limits
+    Platform,
+}
+
+#[derive(Subcommand)]
+enum ListCommand {
+    /// Challenges you created
+    Mine,
+    /// Open challenges naming you as a verifier, from your inbox
+    Assigned,
+}
+
+#[derive(Args)]
+struct JustificationArgs {
+    /// Why you voted this way, shown to the creator
+    #[arg(long)]
+    reason: Option<String>,
+    /// Content hash of supporting material, such as an annotated screenshot
+    #[arg(long, requires = "reason")]
+    attachment: Option<String>,
+}
+
+impl JustificationArgs {
+    fn into_justification(self) -> Option<Justification> {
+        let attachment = self.attachment;
+        self.reason.map(|reason| Justification { reason, attachment })
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum VoteArg {
+    Approve,
+    Reject,
+    Abstain,
+}
+
+impl From<VoteArg> for proof_of_touch_grass::state::Vote {
+    fn from(vote: VoteArg) -> Self {
+        match vote {
+            VoteArg::Approve => Self::Approve,
+            VoteArg::Reject => Self::Reject,
+            VoteArg::Abstain => Self::Abstain,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum JuryVoteArg {
+    Uphold,
+    Overturn,
+}
+
+impl From<JuryVoteArg> for proof_of_touch_grass::state::JuryVote {
+    fn from(vote: JuryVoteArg) -> Self {
+        match vote {
+            JuryVoteArg::Uphold => Self::Uphold,
+            JuryVoteArg::Overturn => Self::Overturn,
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let ctx = commands::Context::new(&cli.url, cli.keypair.as_deref(), cli.dry_run)?;
+
+    match cli.command {
+        Command::InitUser => commands::init_user(&ctx),
+        Command::SetLimits {
+            max_active,
+            max_stake,
+            max_at_risk,
+            cooldown,
+        } => commands::set_limits(
+            &ctx,
+            UserLimits {
+                max_active_challenges: max_active,
+                max_stake,
+                max_stake_at_risk: max_at_risk,
+                failure_cooldown: cooldown,
+            },
+        ),
+        Command::CreateChallenge { spec, id } => commands::create_challenge(&ctx, &spec, id),
+        Command::CreateTemplate { spec, id } => commands::create_template(&ctx, &spec, id),
+        Command::CreateFromTemplate {
+            template,
+            stake,
+            start,
+            verifiers,
+            weights,
+            id,
+        } => commands::create_from_template(&ctx, &template, stake, start, verifiers, weights, id),
+        Command::Retry {
+            challenge,
+            stake,
+            start,
+            id,
+        } => commands::retry(&ctx, &challenge, stake, start, id),
+        Command::SubmitEvidence {
+            challenge,
+            ipfs_hash,
+            metadata,
+            latitude,
+            longitude,
+        } => {
+            let location = latitude.zip(longitude);
+            commands::submit_evidence(&ctx, &challenge, ipfs_hash, metadata, location)
+        }
+        Command::Vote {
+            challenge,
+            vote,
+            justification,
+        } => commands::vote(&ctx, &challenge, vote.into(), justification.into_justification()),
+        Command::ChangeVote {
+            challenge,
+            vote,
+            justification,
+        } => commands::change_vote(
+            &ctx,
+            &challenge,
+            vote.into(),
+            justification.into_justification(),
+        ),
+        Command::Cancel { challenge } => commands::cancel(&ctx, &challenge),
+        Command::RequestExtension {
+            challenge,
+            new_end_time,
+        } => commands::request_extension(&ctx, &challenge, new_end_time),
+        Command::ApproveExtension { challenge } => commands::approve_extension(&ctx, &challenge),
+        Command::WithdrawExtension { challenge } => commands::withdraw_extension(&ctx, &challenge),
+        Command::Dispute {
+            challenge,
+            reason,
+            verifier,
+        } => commands::dispute(&ctx, &challenge, reason, verifier.as_ref()),
+        Command::Escalate { challenge } => commands::escalate(&ctx, &challenge),
+        Command::JuryVote { challenge, vote } => commands::jury_vote(&ctx, &challenge, vote.into()),
+        Command::SettleJury { challenge } => commands::settle_jury(&ctx, &challenge),
+        Command::Claim { challenge } => commands::claim(&ctx, &challenge),
+        Command::Juror(JurorCommand::Register { stake }) => commands::register_juror(&ctx, stake),
+        Command::Juror(JurorCommand::Unregister) => commands::unregister_juror(&ctx),
+        Command::Juror(JurorCommand::Withdraw) => commands::withdraw_juror_stake(&ctx),
+        Command::Admin(AdminCommand::Crank { challenge }) => {
+            commands::crank(&ctx, challenge.as_ref())
+        }
+        Command::Admin(AdminCommand::CurateTemplate { template, revoke }) => {
+            commands::curate_template(&ctx, &template, !revoke)
+        }
+        Command::Admin(AdminCommand::ResolveDispute { challenge, overturn }) => {
+            commands::resolve_dispute(&ctx, &challenge, overturn)
+        }
+        Command::Admin(AdminCommand::ConfigureGrass {
+            creator_reward,
+            verifier_reward,
+            grass_per_sol,
+            fee_discount_bps,
+        }) => commands::configure_grass(
+            &ctx,
+            GrassEmission {
+                creator_reward,
+                verifier_reward,
+                grass_per_sol,
+                fee_discount_bps,
+            },
+        ),
+        Command::Admin(AdminCommand::SetPlatformLimits {
+            max_active,
+            max_stake,
+            max_at_risk,
+            max_cooldown,
+        }) => commands::set_platform_limits(
+            &ctx,
+            PlatformLimits {
+                max_active_challenges: max_active,
+                max_stake,
+                max_stake_at_risk: max_at_risk,
+                max_failure_cooldown: max_cooldown,
+            },
+        ),
+        Command::Show(ShowCommand::Challenge { address }) => commands::show_challenge(&ctx, &address),
+        Command::Show(ShowCommand::Extension { challenge }) => {
+            commands::show_extension(&ctx, &challenge)
+        }
+        Command::Show(ShowCommand::Template { address }) => commands::show_template(&ctx, &address),
+        Command::Show(ShowCommand::Vote { challenge, verifier }) => {
+            commands::show_vote(&ctx, &challenge, &verifier)
+        }
+        Command::Show(ShowCommand::Reputation { verifier }) => {
+            commands::show_reputation(&ctx, &verifier)
+        }
+        Command::Show(ShowCommand::User { authority }) => {
+            commands::show_user(&ctx, authority.as_ref())
+        }
+        Command::Show(ShowCommand::Disputes { challenge }) => {
+            commands::show_disputes(&ctx, &challenge)
+        }
+        Command::Show(ShowCommand::Juror { authority }) => {
+            commands::show_juror(&ctx, authority.as_ref())
+        }
+        Command::Show(ShowCommand::Grass) => commands::show_grass(&ctx),
+        Command::Show(ShowCommand::Platform) => commands::show_platform(&ctx),
+        Command::List(ListCommand::Mine) => commands::list_mine(&ctx),
+        Command::List(ListCommand::Assigned) => commands::list_assigned(&ctx),
+    }
+}
@@ -0,0 +1,202 @@
+use std::thread::sleep;
+use std::time::Duration;
+
+use anchor_lang::prelude::Pubkey;
+use solana_hash::Hash;
+use anyhow::{anyhow, bail, Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde_json::{json, Value};
+use solana_transaction::Transaction;
+
+const CONFIRM_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const CONFIRM_ATTEMPTS: usize = 60;
+
+/// Cluster calls the commands make, served by [`RpcClient`] and, in tests, by
+/// an in-process runtime
+pub trait Cluster {
+    /// Returns the account's data, or `None` if the account does not exist
+    fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>>;
+
+    /// Returns `(address, data)` for every program account matching all filters
+    fn get_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        filters: &[Memcmp],
+    ) -> Result<Vec<(Pubkey, Vec<u8>)>>;
+
+    fn get_latest_blockhash(&self) -> Result<Hash>;
+
+    fn simulate_transaction(&self, transaction: &Transaction) -> Result<Simulation>;
+
+    /// Sends a signed transaction and waits until it is confirmed
+    fn send_and_confirm_transaction(&self, transaction: &Transaction) -> Result<String>;
+}
+
+/// Minimal JSON-RPC client covering the calls the CLI needs
+pub struct RpcClient {
+    url: String,
+    agent: ureq::Agent,
+}
+
+/// Result of `simulateTransaction`
+pub struct Simulation {
+    pub error: Option<Value>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+}
+
+/// Account filter for `getProgramAccounts`
+pub struct Memcmp {
+    pub offset: usize,
+    pub bytes: Vec<u8>,
+}
+
+impl RpcClient {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            agent: ureq::AgentBuilder::new().timeout(Duration::from_secs(30)).build(),
+        }
+    }
+
+    fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response: Value = self
+            .agent
+            .post(&self.url)
+            .send_json(request)
+            .with_context(|| format!("{method} request to {} failed", self.url))?
+            .into_json()
+            .with_context(|| format!("{method} returned invalid JSON"))?;
+
+        if let Some(error) = response.get("error") {
+            bail!("{method} failed: {error}");
+        }
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| anyhow!("{method} returned no result"))
+    }
+}
+
+impl Cluster for RpcClient {
+    fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+        let result = self.call(
+            "getAccountInfo",
+            json!([address.to_string(), { "encoding": "base64", "commitment": "confirmed" }]),
+        )?;
+        match result.get("value") {
+            Some(Value::Null) | None => Ok(None),
+            Some(account) => decode_account_data(account).map(Some),
+        }
+    }
+
+    fn get_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        filters: &[Memcmp],
+    ) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+        let filters: Vec<Value> = filters
+            .iter()
+            .map(|filter| {
+                json!({ "memcmp": {
+                    "offset": filter.offset,
+                    "bytes": bs58::encode(&filter.bytes).into_string(),
+                }})
+            })
+            .collect();
+        let result = self.call(
+            "getProgramAccounts",
+            json!([program_id.to_string(), {
+                "encoding": "base64",
+                "commitment": "confirmed",
+                "filters": filters,
+            }]),
+        )?;
+
+        result
+            .as_array()
+            .ok_or_else(|| anyhow!("getProgramAccounts returned a non-array result"))?
+            .iter()
+            .map(|entry| {
+                let address: Pubkey = entry["pubkey"]
+                    .as_str()
+                    .ok_or_else(|| anyhow!("account entry missing pubkey"))?
+                    .parse()?;
+                Ok((address, decode_account_data(&entry["account"])?))
+            })
+            .collect()
+    }
+
+    fn get_latest_blockhash(&self) -> Result<Hash> {
+        let result = self.call("getLatestBlockhash", json!([{ "commitment": "confirmed" }]))?;
+        result["value"]["blockhash"]
+            .as_str()
+            .ok_or_else(|| anyhow!("getLatestBlockhash returned no blockhash"))?
+            .parse()
+            .map_err(|err| anyhow!("invalid blockhash: {err:?}"))
+    }
+
+    fn simulate_transaction(&self, transaction: &Transaction) -> Result<Simulation> {
+        let result = self.call(
+            "simulateTransaction",
+            json!([encode_transaction(transaction)?, {
+                "encoding": "base64",
+                "commitment": "confirmed",
+                "sigVerify": false,
+                "replaceRecentBlockhash": true,
+            }]),
+        )?;
+        let value = &result["value"];
+        Ok(Simulation {
+            error: value.get("err").filter(|err| !err.is_null()).cloned(),
+            logs: value["logs"]
+                .as_array()
+                .map(|logs| logs.iter().filter_map(|log| log.as_str().map(String::from)).collect())
+                .unwrap_or_default(),
+            units_consumed: value["unitsConsumed"].as_u64(),
+        })
+    }
+
+    fn send_and_confirm_transaction(&self, transaction: &Transaction) -> Result<String> {
+        let signature = self
+            .call(
+                "sendTransaction",
+                json!([encode_transaction(transaction)?, {
+                    "encoding": "base64",
+                    "preflightCommitment": "confirmed",
+                }]),
+            )?
+            .as_str()
+            .ok_or_else(|| anyhow!("sendTransaction returned no signature"))?
+            .to_string();
+
+        for _ in 0..CONFIRM_ATTEMPTS {
+            let result = self.call("getSignatureStatuses", json!([[signature]]))?;
+            let status = &result["value"][0];
+            if !status.is_null() {
+                if let Some(err) = status.get("err").filter(|err| !err.is_null()) {
+                    bail!("transaction {signature} failed: {err}");
+                }
+                let confirmation = status["confirmationStatus"].as_str().unwrap_or_default();
+                if confirmation == "confirmed" || confirmation == "finalized" {
+                    return Ok(signature);
+                }
+            }
+            sleep(CONFIRM_POLL_INTERVAL);
+        }
+        bail!("timed out waiting for transaction {signature} to confirm")
+    }
+}
+
+fn decode_account_data(account: &Value) -> Result<Vec<u8>> {
+    let encoded = account["data"][0]
+        .as_str()
+        .ok_or_else(|| anyhow!("account data is not base64 encoded"))?;
+    Ok(BASE64.decode(encoded)?)
+}
+
+fn encode_transaction(transaction: &Transaction) -> Result<String> {
+    Ok(BASE64.encode(bincode::serialize(transaction)?))
+}
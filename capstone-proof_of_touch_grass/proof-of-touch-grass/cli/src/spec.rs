@@ -0,0 +1,283 @@
+use std::path::Path;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{bail, Context, Result};
+use proof_of_touch_grass::constants::{MAX_GEOFENCES, MAX_HOUR_WINDOWS};
+use proof_of_touch_grass::state::{
+    GeoPoint, Geofence, HourWindow, SubmissionRules, TimeoutPolicy, VerificationRules,
+};
+use proof_of_touch_grass_client::instructions::{CreateChallengeArgs, CreateTemplateArgs};
+use serde::Deserialize;
+
+/// Challenge definition read by `ptg create-challenge`.
+/// Coordinates are microdegrees, times are unix seconds.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChallengeSpec {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    pub stake_lamports: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub verification_period: i64,
+    pub required_proofs: u8,
+    pub required_approval_weight: u8,
+    pub verifiers: Vec<String>,
+    /// One weight per verifier; omitted, every vote weighs 1
+    #[serde(default)]
+    pub verifier_weights: Vec<u8>,
+    #[serde(default)]
+    pub geofences: Vec<GeofenceSpec>,
+    #[serde(default)]
+    pub rules: RulesSpec,
+    #[serde(default)]
+    pub verification: VerificationSpec,
+    /// Minimum reputation score every verifier must have
+    pub min_verifier_reputation: Option<u32>,
+    /// Burn the platform fee, discounted, from your GRASS account instead of paying it in SOL
+    #[serde(default)]
+    pub pay_fee_in_grass: bool,
+}
+
+/// Template definition read by `ptg create-template`: the terms of a
+/// challenge without its stake, start time and verifiers
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateSpec {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    /// Seconds from start to end
+    pub duration: i64,
+    pub verification_period: i64,
+    pub required_proofs: u8,
+    pub required_approval_weight: u8,
+    #[serde(default)]
+    pub geofences: Vec<GeofenceSpec>,
+    #[serde(default)]
+    pub rules: RulesSpec,
+    #[serde(default)]
+    pub verification: VerificationSpec,
+    /// Minimum reputation score every verifier must have
+    pub min_verifier_reputation: Option<u32>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum GeofenceSpec {
+    Circle {
+        latitude: i32,
+        longitude: i32,
+        radius_meters: u32,
+    },
+    Box {
+        south: i32,
+        west: i32,
+        north: i32,
+        east: i32,
+    },
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct RulesSpec {
+    pub min_interval: Option<i64>,
+    pub max_per_day: Option<u8>,
+    #[serde(default)]
+    pub allowed_hours: Vec<HourWindowSpec>,
+}
+
+/// What happens when verification runs out without a verdict, and how many
+/// votes a verdict needs
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct VerificationSpec {
+    #[serde(default)]
+    pub on_timeout: TimeoutSpec,
+    #[serde(default)]
+    pub min_votes: u8,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeoutSpec {
+    #[default]
+    Completed,
+    Failed,
+    Refund,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HourWindowSpec {
+    pub start_hour: u8,
+    pub end_hour: u8,
+}
+
+/// Rejects an approval threshold, geofences and cadence rules the program
+/// would refuse, before anything is signed
+fn validate(
+    required_approval_weight: u8,
+    geofences: &[Geofence],
+    rules: &SubmissionRules,
+) -> Result<()> {
+    if required_approval_weight == 0 {
+        bail!("required_approval_weight must be at least 1");
+    }
+    if geofences.len() > MAX_GEOFENCES {
+        bail!("{} geofences given, at most {MAX_GEOFENCES} allowed", geofences.len());
+    }
+    if let Some(position) = geofences.iter().position(|geofence| !geofence.is_valid()) {
+        bail!(
+            "geofence {} is invalid: coordinates must lie within ±90° latitude and ±180° \
+             longitude, a circle needs a radius and a box's south edge must not be north of \
+             its north edge",
+            position + 1
+        );
+    }
+    if !rules.is_valid() {
+        bail!(
+            "invalid rules: min_interval and max_per_day must be positive, and allowed_hours \
+             takes at most {MAX_HOUR_WINDOWS} windows of distinct hours within 0..=24"
+        );
+    }
+    Ok(())
+}
+
+impl ChallengeSpec {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        toml::from_str(&contents).with_context(|| format!("invalid challenge spec {}", path.display()))
+    }
+
+    pub fn into_args(self) -> Result<CreateChallengeArgs> {
+        let verifiers = self
+            .verifiers
+            .iter()
+            .map(|verifier| {
+                verifier
+                    .parse::<Pubkey>()
+                    .with_context(|| format!("invalid verifier pubkey {verifier}"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if let Some(position) = (1..verifiers.len())
+            .find(|&position| verifiers[..position].contains(&verifiers[position]))
+        {
+            bail!("verifier {} is listed twice", verifiers[position]);
+        }
+
+        let args = CreateChallengeArgs {
+            title: self.title,
+            description: self.description,
+            stake_amount: self.stake_lamports,
+            start_time: self.start_time,
+            end_time: self.end_time,
+            verification_period: self.verification_period,
+            required_proofs: self.required_proofs,
+            required_approval_weight: self.required_approval_weight,
+            verifiers,
+            verifier_weights: self.verifier_weights,
+            geofences: self.geofences.into_iter().map(Geofence::from).collect(),
+            submission_rules: self.rules.into(),
+            verification_rules: self.verification.into(),
+            min_verifier_reputation: self.min_verifier_reputation,
+            pay_fee_in_grass: self.pay_fee_in_grass,
+        };
+        validate(args.required_approval_weight, &args.geofences, &args.submission_rules)?;
+        if !args.verification_rules.is_valid(args.verifiers.len()) {
+            bail!(
+                "min_votes is {} but the challenge has {} verifiers",
+                args.verification_rules.min_votes,
+                args.verifiers.len()
+            );
+        }
+        Ok(args)
+    }
+}
+
+impl TemplateSpec {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        toml::from_str(&contents).with_context(|| format!("invalid template spec {}", path.display()))
+    }
+
+    pub fn into_args(self) -> Result<CreateTemplateArgs> {
+        let args = CreateTemplateArgs {
+            title: self.title,
+            description: self.description,
+            duration: self.duration,
+            verification_period: self.verification_period,
+            required_proofs: self.required_proofs,
+            required_approval_weight: self.required_approval_weight,
+            geofences: self.geofences.into_iter().map(Geofence::from).collect(),
+            submission_rules: self.rules.into(),
+            verification_rules: self.verification.into(),
+            min_verifier_reputation: self.min_verifier_reputation,
+        };
+        validate(args.required_approval_weight, &args.geofences, &args.submission_rules)?;
+        Ok(args)
+    }
+}
+
+impl From<VerificationSpec> for VerificationRules {
+    fn from(spec: VerificationSpec) -> Self {
+        VerificationRules {
+            timeout_policy: match spec.on_timeout {
+                TimeoutSpec::Completed => TimeoutPolicy::Completed,
+                TimeoutSpec::Failed => TimeoutPolicy::Failed,
+                TimeoutSpec::Refund => TimeoutPolicy::Refund,
+            },
+            min_votes: spec.min_votes,
+        }
+    }
+}
+
+impl From<RulesSpec> for SubmissionRules {
+    fn from(spec: RulesSpec) -> Self {
+        SubmissionRules {
+            min_interval: spec.min_interval,
+            max_per_day: spec.max_per_day,
+            allowed_hours: spec
+                .allowed_hours
+                .into_iter()
+                .map(|window| HourWindow {
+                    start_hour: window.start_hour,
+                    end_hour: window.end_hour,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<GeofenceSpec> for Geofence {
+    fn from(spec: GeofenceSpec) -> Self {
+        match spec {
+            GeofenceSpec::Circle {
+                latitude,
+                longitude,
+                radius_meters,
+            } => Geofence::Circle {
+                center: GeoPoint { latitude, longitude },
+                radius_meters,
+            },
+            GeofenceSpec::Box {
+                south,
+                west,
+                north,
+                east,
+            } => Geofence::BoundingBox {
+                south_west: GeoPoint {
+                    latitude: south,
+                    longitude: west,
+                },
+                north_east: GeoPoint {
+                    latitude: north,
+                    longitude: east,
+                },
+            },
+        }
+    }
+}
@@ -0,0 +1,789 @@
+//! Every subcommand against the program running in the harness. A dry run
+//! must build the instruction the client would, simulate it successfully
+//! and change nothing. The setup steps send through the same commands.
+
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anyhow::{anyhow, Result};
+use proof_of_touch_grass::constants::{ADMIN_PUBKEY, JUROR_UNBONDING_PERIOD, MIN_JUROR_STAKE};
+use proof_of_touch_grass::state::{
+    Challenge, ChallengeStatus, GeoPoint, GrassEmission, Justification, JuryVote, PlatformLimits,
+    UserLimits, Vote,
+};
+use proof_of_touch_grass_cli::commands::{self, Context};
+use proof_of_touch_grass_cli::rpc::{Cluster, Memcmp, Simulation};
+use proof_of_touch_grass_cli::spec::{ChallengeSpec, TemplateSpec};
+use proof_of_touch_grass_client::{accounts, instructions, pda};
+use proof_of_touch_grass_harness::{Account, Svm};
+use serde_json::json;
+use solana_hash::Hash;
+use solana_signature::Signature;
+use solana_signer::{Signer, SignerError};
+use solana_transaction::Transaction;
+
+const SOL: u64 = 1_000_000_000;
+const HOUR: i64 = 3_600;
+const DAY: i64 = 86_400;
+
+fn emission() -> GrassEmission {
+    GrassEmission {
+        creator_reward: 100_000_000,
+        verifier_reward: 10_000_000,
+        grass_per_sol: 1_000_000_000,
+        fee_discount_bps: 2_000,
+    }
+}
+
+/// Reads and sends go to one shared runtime; simulations run on a fork of it
+/// and record what they ran
+#[derive(Clone, Default)]
+struct InProcess(Rc<State>);
+
+#[derive(Default)]
+struct State {
+    svm: RefCell<Svm>,
+    simulated: RefCell<Vec<Instruction>>,
+}
+
+/// The instructions of a transaction. An account is writable in every
+/// instruction once any instruction, or the fee payer, writes it.
+fn decompile(transaction: &Transaction) -> Vec<Instruction> {
+    let message = &transaction.message;
+    message
+        .instructions
+        .iter()
+        .map(|compiled| Instruction {
+            program_id: message.account_keys[compiled.program_id_index as usize],
+            accounts: compiled
+                .accounts
+                .iter()
+                .map(|&index| {
+                    let index = index as usize;
+                    AccountMeta {
+                        pubkey: message.account_keys[index],
+                        is_signer: message.is_signer(index),
+                        is_writable: message.is_maybe_writable(index, None),
+                    }
+                })
+                .collect(),
+            data: compiled.data.clone(),
+        })
+        .collect()
+}
+
+impl Cluster for InProcess {
+    fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+        Ok(self.0.svm.borrow().account(address).map(|account| account.data.clone()))
+    }
+
+    fn get_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        filters: &[Memcmp],
+    ) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+        let svm = self.0.svm.borrow();
+        Ok(svm
+            .accounts()
+            .filter(|(_, account)| account.owner == *program_id)
+            .filter(|(_, account)| {
+                filters.iter().all(|filter| {
+                    account.data.get(filter.offset..filter.offset + filter.bytes.len())
+                        == Some(&filter.bytes[..])
+                })
+            })
+            .map(|(address, account)| (*address, account.data.clone()))
+            .collect())
+    }
+
+    fn get_latest_blockhash(&self) -> Result<Hash> {
+        Ok(Hash::default())
+    }
+
+    fn simulate_transaction(&self, transaction: &Transaction) -> Result<Simulation> {
+        let mut fork = self.0.svm.borrow().clone();
+        let mut error = None;
+        for instruction in decompile(transaction) {
+            if let Err(err) = fork.process(&instruction) {
+                error.get_or_insert(json!(err.to_string()));
+            }
+            self.0.simulated.borrow_mut().push(instruction);
+        }
+        Ok(Simulation {
+            error,
+            logs: Vec::new(),
+            units_consumed: None,
+        })
+    }
+
+    fn send_and_confirm_transaction(&self, transaction: &Transaction) -> Result<String> {
+        let mut svm = self.0.svm.borrow_mut();
+        for instruction in decompile(transaction) {
+            svm.process(&instruction).map_err(|err| anyhow!("{err}"))?;
+        }
+        Ok("confirmed".to_string())
+    }
+}
+
+/// Signs as any wallet. The runtime never checks signatures, but a
+/// transaction must carry one for every signer.
+struct Impersonator(Pubkey);
+
+impl Signer for Impersonator {
+    fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
+        Ok(self.0)
+    }
+
+    fn try_sign_message(&self, _message: &[u8]) -> Result<Signature, SignerError> {
+        Ok(Signature::from([1; 64]))
+    }
+
+    fn is_interactive(&self) -> bool {
+        false
+    }
+}
+
+/// A funded creator with a profile and three verifiers
+struct World {
+    cluster: InProcess,
+    creator: Pubkey,
+    verifiers: Vec<Pubkey>,
+}
+
+impl World {
+    fn new() -> Self {
+        let world = Self {
+            cluster: InProcess::default(),
+            creator: Pubkey::new_unique(),
+            verifiers: (0..3).map(|_| Pubkey::new_unique()).collect(),
+        };
+        world.airdrop(&world.creator, 100 * SOL);
+        for wallet in world.verifiers.iter().chain([&ADMIN_PUBKEY]) {
+            world.airdrop(wallet, SOL);
+        }
+        world.send(&world.creator, commands::init_user);
+        world.send(&ADMIN_PUBKEY, |ctx| commands::configure_grass(ctx, emission()));
+        world
+    }
+
+    fn svm(&self) -> std::cell::RefMut<'_, Svm> {
+        self.cluster.0.svm.borrow_mut()
+    }
+
+    fn airdrop(&self, wallet: &Pubkey, lamports: u64) {
+        self.svm().airdrop(wallet, lamports);
+    }
+
+    fn now(&self) -> i64 {
+        self.svm().now()
+    }
+
+    fn warp_to(&self, unix_timestamp: i64) {
+        self.svm().warp_to(unix_timestamp);
+    }
+
+    fn context(&self, signer: &Pubkey, dry_run: bool) -> Context {
+        Context::with_cluster(
+            Box::new(self.cluster.clone()),
+            Box::new(Impersonator(*signer)),
+            dry_run,
+        )
+    }
+
+    /// Runs `command` as `signer`, sending its transactions
+    fn send(&self, signer: &Pubkey, command: impl FnOnce(&Context) -> Result<()>) {
+        command(&self.context(signer, false)).unwrap();
+    }
+
+    fn snapshot(&self) -> Vec<(Pubkey, Account)> {
+        let mut accounts: Vec<_> = self
+            .svm()
+            .accounts()
+            .map(|(address, account)| (*address, account.clone()))
+            .collect();
+        accounts.sort_by_key(|(address, _)| *address);
+        accounts
+    }
+
+    /// Runs `command` as `signer` with `--dry-run`, checks that it changed
+    /// nothing and returns the instructions it simulated
+    fn dry_run(
+        &self,
+        signer: &Pubkey,
+        command: impl FnOnce(&Context) -> Result<()>,
+    ) -> Vec<Instruction> {
+        let before = self.snapshot();
+        command(&self.context(signer, true)).unwrap();
+        assert!(self.snapshot() == before, "a dry run changed accounts");
+        self.cluster.0.simulated.take()
+    }
+
+    fn challenge(&self, address: &Pubkey) -> Challenge {
+        accounts::fetch_challenge(&*self.svm(), address).unwrap()
+    }
+
+    /// A spec file for a challenge starting in an hour and running for a
+    /// week, with two proofs and two of three approvals
+    fn spec(&self) -> PathBuf {
+        let start_time = self.now() + HOUR;
+        let verifiers: Vec<String> = self.verifiers.iter().map(|v| format!("\"{v}\"")).collect();
+        let spec = format!(
+            r#"
+title = "Touch grass daily"
+stake_lamports = {SOL}
+start_time = {start_time}
+end_time = {end_time}
+verification_period = {DAY}
+required_proofs = 2
+required_approval_weight = 2
+verifiers = [{verifiers}]
+
+[[geofences]]
+kind = "circle"
+latitude = 40_782_865
+longitude = -73_965_355
+radius_meters = 1_000
+"#,
+            end_time = start_time + 7 * DAY,
+            verifiers = verifiers.join(", "),
+        );
+        let path = std::env::temp_dir().join(format!("ptg-{}.toml", Pubkey::new_unique()));
+        std::fs::write(&path, spec).unwrap();
+        path
+    }
+
+    fn create(&self, challenge_id: u64) -> Pubkey {
+        let spec = self.spec();
+        self.send(&self.creator, |ctx| {
+            commands::create_challenge(ctx, &spec, Some(challenge_id))
+        });
+        pda::challenge_pda(&self.creator, challenge_id).0
+    }
+
+    fn crank(&self, challenge: &Pubkey) {
+        self.send(&ADMIN_PUBKEY, |ctx| commands::crank(ctx, Some(challenge)));
+    }
+
+    fn active(&self, challenge_id: u64) -> Pubkey {
+        let challenge = self.create(challenge_id);
+        self.warp_to(self.challenge(&challenge).start_time);
+        self.crank(&challenge);
+        challenge
+    }
+
+    fn submit(&self, challenge: &Pubkey) {
+        self.send(&self.creator, |ctx| {
+            commands::submit_evidence(ctx, challenge, "QmProof".into(), String::new(), Some(PARK))
+        });
+    }
+
+    fn pending(&self, challenge_id: u64) -> Pubkey {
+        let challenge = self.active(challenge_id);
+        self.submit(&challenge);
+        self.submit(&challenge);
+        challenge
+    }
+
+    fn vote(&self, verifier: usize, challenge: &Pubkey, vote: Vote) {
+        self.send(&self.verifiers[verifier], |ctx| {
+            commands::vote(ctx, challenge, vote, None)
+        });
+    }
+
+    fn finalized(&self, challenge_id: u64, vote: Vote) -> Pubkey {
+        let challenge = self.pending(challenge_id);
+        self.vote(0, &challenge, vote.clone());
+        self.vote(1, &challenge, vote);
+        challenge
+    }
+
+    fn close_dispute_window(&self, challenge: &Pubkey) {
+        self.warp_to(self.challenge(challenge).dispute_window_end().unwrap() + 1);
+    }
+
+    /// `count` funded wallets registered as jurors with the minimum stake
+    fn jurors(&self, count: usize) -> Vec<Pubkey> {
+        (0..count)
+            .map(|_| {
+                let juror = Pubkey::new_unique();
+                self.airdrop(&juror, 2 * MIN_JUROR_STAKE);
+                self.send(&juror, |ctx| commands::register_juror(ctx, MIN_JUROR_STAKE));
+                juror
+            })
+            .collect()
+    }
+
+    /// A failed challenge the creator disputed and escalated to a jury
+    fn escalated(&self, challenge_id: u64) -> Pubkey {
+        self.jurors(3);
+        let challenge = self.finalized(challenge_id, Vote::Reject);
+        self.send(&self.creator, |ctx| {
+            commands::dispute(ctx, &challenge, "Photos are mine".into(), None)
+        });
+        self.send(&self.creator, |ctx| commands::escalate(ctx, &challenge));
+        challenge
+    }
+}
+
+type Command<'a> = dyn Fn(&Context) -> Result<()> + 'a;
+
+const PARK: (i32, i32) = (40_782_865, -73_965_355);
+
+/// `sent` is `expected` after a round trip through a transaction message,
+/// which merges the flags of repeated accounts and makes the fee payer
+/// writable
+fn assert_sent(sent: &[Instruction], expected: Instruction) {
+    assert_eq!(sent.len(), 1, "expected a single instruction");
+    let sent = &sent[0];
+    let expected = &decompile(&Transaction::new_with_payer(&[expected], None))[0];
+    assert_eq!(sent.program_id, expected.program_id);
+    assert_eq!(sent.data, expected.data);
+    assert_eq!(sent.accounts.len(), expected.accounts.len());
+    for (sent, expected) in sent.accounts.iter().zip(&expected.accounts) {
+        assert_eq!(sent.pubkey, expected.pubkey);
+        assert_eq!(sent.is_signer, expected.is_signer, "signer flag of {}", sent.pubkey);
+        assert!(sent.is_writable || !expected.is_writable, "{} is read-only", sent.pubkey);
+    }
+}
+
+#[test]
+fn init_user_and_set_limits() {
+    let world = World::new();
+    let wallet = Pubkey::new_unique();
+    world.airdrop(&wallet, SOL);
+    let sent = world.dry_run(&wallet, commands::init_user);
+    assert_sent(&sent, instructions::initialize_user(&wallet));
+
+    let limits = UserLimits {
+        max_active_challenges: Some(2),
+        max_stake: Some(SOL),
+        max_stake_at_risk: None,
+        failure_cooldown: Some(DAY),
+    };
+    let sent = world.dry_run(&world.creator, |ctx| commands::set_limits(ctx, limits.clone()));
+    assert_sent(&sent, instructions::set_user_limits(&world.creator, limits));
+}
+
+#[test]
+fn create_challenge_from_a_spec() {
+    let world = World::new();
+    let spec = world.spec();
+    let sent = world.dry_run(&world.creator, |ctx| {
+        commands::create_challenge(ctx, &spec, Some(7))
+    });
+    let args = ChallengeSpec::load(&spec).unwrap().into_args().unwrap();
+    assert_sent(&sent, instructions::create_challenge(&world.creator, 7, args));
+
+    // Sent for real, the challenge lands where the dry run said
+    let challenge = world.create(7);
+    assert_eq!(world.challenge(&challenge).status, ChallengeStatus::Created);
+}
+
+#[test]
+fn an_invalid_spec_is_refused_before_simulation() {
+    let world = World::new();
+    let spec = world.spec();
+    let contents = std::fs::read_to_string(&spec).unwrap();
+    std::fs::write(&spec, contents.replace("radius_meters = 1_000", "radius_meters = 0")).unwrap();
+    let err = commands::create_challenge(&world.context(&world.creator, true), &spec, Some(1))
+        .err()
+        .unwrap();
+    assert!(err.to_string().starts_with("geofence 1 is invalid"), "{err}");
+    assert!(world.cluster.0.simulated.borrow().is_empty());
+}
+
+#[test]
+fn templates() {
+    let world = World::new();
+    let spec = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("examples/template.toml");
+    let args = TemplateSpec::load(&spec).unwrap().into_args().unwrap();
+    let sent = world.dry_run(&world.creator, |ctx| commands::create_template(ctx, &spec, Some(3)));
+    assert_sent(&sent, instructions::create_template(&world.creator, 3, args));
+
+    world.send(&world.creator, |ctx| commands::create_template(ctx, &spec, Some(3)));
+    let template = pda::template_pda(&world.creator, 3).0;
+    let sent = world.dry_run(&ADMIN_PUBKEY, |ctx| commands::curate_template(ctx, &template, true));
+    assert_sent(&sent, instructions::curate_template(&ADMIN_PUBKEY, &template, true));
+
+    let start = world.now() + HOUR;
+    let verifiers = world.verifiers.clone();
+    let sent = world.dry_run(&world.creator, |ctx| {
+        commands::create_from_template(ctx, &template, SOL, start, verifiers.clone(), vec![], Some(4))
+    });
+    assert_sent(
+        &sent,
+        instructions::create_challenge_from_template(
+            &world.creator,
+            &template,
+            4,
+            SOL,
+            start,
+            verifiers,
+            vec![],
+        ),
+    );
+}
+
+#[test]
+fn crank_simulates_every_due_challenge() {
+    let world = World::new();
+    let first = world.create(1);
+    let second = world.create(2);
+    world.warp_to(world.challenge(&first).start_time);
+
+    let mut sent = world.dry_run(&ADMIN_PUBKEY, |ctx| commands::crank(ctx, None));
+    sent.sort_by_key(|instruction| instruction.accounts[0].pubkey);
+    let mut expected: Vec<_> = [first, second]
+        .iter()
+        .map(|challenge| {
+            instructions::update_challenge_state(
+                &ADMIN_PUBKEY,
+                challenge,
+                &world.creator,
+                &world.verifiers,
+            )
+        })
+        .collect();
+    expected.sort_by_key(|instruction| instruction.accounts[0].pubkey);
+    assert_eq!(sent.len(), 2);
+    for (sent, expected) in sent.chunks(1).zip(expected) {
+        assert_sent(sent, expected);
+    }
+
+    let sent = world.dry_run(&ADMIN_PUBKEY, |ctx| commands::crank(ctx, Some(&first)));
+    assert_eq!(sent.len(), 1);
+}
+
+#[test]
+fn evidence_and_votes() {
+    let world = World::new();
+    let challenge = world.active(1);
+    let sent = world.dry_run(&world.creator, |ctx| {
+        commands::submit_evidence(ctx, &challenge, "QmProof".into(), "{}".into(), Some(PARK))
+    });
+    let location = Some(GeoPoint {
+        latitude: PARK.0,
+        longitude: PARK.1,
+    });
+    assert_sent(
+        &sent,
+        instructions::submit_evidence(
+            &world.creator,
+            &challenge,
+            0,
+            "QmProof".into(),
+            "{}".into(),
+            location,
+        ),
+    );
+
+    world.submit(&challenge);
+    world.submit(&challenge);
+    let verifier = world.verifiers[0];
+    let justification = Justification {
+        reason: "Same bench as last week".into(),
+        attachment: None,
+    };
+    let sent = world.dry_run(&verifier, |ctx| {
+        commands::vote(ctx, &challenge, Vote::Reject, Some(justification.clone()))
+    });
+    assert_sent(
+        &sent,
+        instructions::verify_evidence(
+            &verifier,
+            &challenge,
+            &world.creator,
+            &world.verifiers,
+            Vote::Reject,
+            Some(justification),
+        ),
+    );
+
+    world.vote(0, &challenge, Vote::Reject);
+    let sent = world.dry_run(&verifier, |ctx| {
+        commands::change_vote(ctx, &challenge, Vote::Approve, None)
+    });
+    assert_sent(
+        &sent,
+        instructions::update_vote(
+            &verifier,
+            &challenge,
+            &world.creator,
+            &world.verifiers,
+            Vote::Approve,
+            None,
+        ),
+    );
+}
+
+#[test]
+fn cancel() {
+    let world = World::new();
+    let challenge = world.create(1);
+    let sent = world.dry_run(&world.creator, |ctx| commands::cancel(ctx, &challenge));
+    assert_sent(
+        &sent,
+        instructions::cancel_challenge(&world.creator, &challenge, &world.verifiers),
+    );
+}
+
+#[test]
+fn extensions() {
+    let world = World::new();
+    let challenge = world.active(1);
+    let new_end_time = world.challenge(&challenge).end_time + DAY;
+    let sent = world.dry_run(&world.creator, |ctx| {
+        commands::request_extension(ctx, &challenge, new_end_time)
+    });
+    assert_sent(&sent, instructions::request_extension(&world.creator, &challenge, new_end_time));
+
+    world.send(&world.creator, |ctx| commands::request_extension(ctx, &challenge, new_end_time));
+    let verifier = world.verifiers[0];
+    let sent = world.dry_run(&verifier, |ctx| commands::approve_extension(ctx, &challenge));
+    assert_sent(
+        &sent,
+        instructions::approve_extension(&verifier, &challenge, &world.creator, &world.verifiers),
+    );
+
+    let sent = world.dry_run(&world.creator, |ctx| commands::withdraw_extension(ctx, &challenge));
+    assert_sent(&sent, instructions::withdraw_extension(&world.creator, &challenge));
+}
+
+#[test]
+fn claims() {
+    let world = World::new();
+    let completed = world.finalized(1, Vote::Approve);
+    world.close_dispute_window(&completed);
+    let sent = world.dry_run(&world.creator, |ctx| commands::claim(ctx, &completed));
+    assert_sent(
+        &sent,
+        instructions::claim_funds(
+            &world.creator,
+            &completed,
+            &world.creator,
+            &world.verifiers,
+            false,
+            true,
+        ),
+    );
+
+    // An approving verifier claims their GRASS from a completed challenge
+    let approver = world.verifiers[0];
+    let sent = world.dry_run(&approver, |ctx| commands::claim(ctx, &completed));
+    assert_sent(
+        &sent,
+        instructions::claim_funds(
+            &approver,
+            &completed,
+            &world.creator,
+            &world.verifiers,
+            true,
+            true,
+        ),
+    );
+
+    // A rejecting verifier claims a failed challenge with their record
+    let failed = world.finalized(2, Vote::Reject);
+    world.close_dispute_window(&failed);
+    let verifier = world.verifiers[0];
+    let sent = world.dry_run(&verifier, |ctx| commands::claim(ctx, &failed));
+    assert_sent(
+        &sent,
+        instructions::claim_funds(
+            &verifier,
+            &failed,
+            &world.creator,
+            &world.verifiers,
+            true,
+            true,
+        ),
+    );
+
+    // Then the creator retries it
+    let start = world.now() + HOUR;
+    let sent = world.dry_run(&world.creator, |ctx| {
+        commands::retry(ctx, &failed, Some(2 * SOL), Some(start), Some(3))
+    });
+    assert_sent(
+        &sent,
+        instructions::retry_challenge(&world.creator, &failed, None, &world.verifiers, 3, 2 * SOL, start),
+    );
+}
+
+#[test]
+fn disputes_and_admin_resolution() {
+    let world = World::new();
+    let challenge = world.finalized(1, Vote::Reject);
+    let contested = world.verifiers[0];
+    let sent = world.dry_run(&world.creator, |ctx| {
+        commands::dispute(ctx, &challenge, "Photos are mine".into(), Some(&contested))
+    });
+    assert_sent(
+        &sent,
+        instructions::dispute_verification(
+            &world.creator,
+            &challenge,
+            0,
+            "Photos are mine".into(),
+            Some(&contested),
+        ),
+    );
+
+    world.send(&world.creator, |ctx| {
+        commands::dispute(ctx, &challenge, "Photos are mine".into(), None)
+    });
+    let dispute = accounts::fetch_disputes(&*world.svm(), &challenge).unwrap()[0].1.clone();
+    for overturn in [false, true] {
+        let sent = world.dry_run(&ADMIN_PUBKEY, |ctx| {
+            commands::resolve_dispute(ctx, &challenge, overturn)
+        });
+        assert_sent(
+            &sent,
+            instructions::resolve_dispute(
+                &ADMIN_PUBKEY,
+                &challenge,
+                &dispute,
+                &world.creator,
+                &world.verifiers,
+                overturn,
+            ),
+        );
+    }
+}
+
+#[test]
+fn jury_escalation() {
+    let world = World::new();
+    world.jurors(3);
+    let challenge = world.finalized(1, Vote::Reject);
+    world.send(&world.creator, |ctx| {
+        commands::dispute(ctx, &challenge, "Photos are mine".into(), None)
+    });
+    let sent = world.dry_run(&world.creator, |ctx| commands::escalate(ctx, &challenge));
+    let pool = accounts::fetch_juror_pool(&*world.svm()).unwrap();
+    assert_sent(&sent, instructions::escalate_dispute(&world.creator, &challenge, 0, &pool));
+
+    world.send(&world.creator, |ctx| commands::escalate(ctx, &challenge));
+    let dispute = || accounts::fetch_disputes(&*world.svm(), &challenge).unwrap()[0].1.clone();
+    let jury: Vec<Pubkey> = dispute().jury.iter().map(|seat| seat.juror).collect();
+    let sent = world.dry_run(&jury[0], |ctx| {
+        commands::jury_vote(ctx, &challenge, JuryVote::Overturn)
+    });
+    assert_sent(
+        &sent,
+        instructions::cast_jury_vote(&jury[0], &challenge, 0, JuryVote::Overturn),
+    );
+
+    for juror in &jury[..2] {
+        world.send(juror, |ctx| commands::jury_vote(ctx, &challenge, JuryVote::Overturn));
+    }
+    // Anyone can settle
+    let settler = Pubkey::new_unique();
+    world.airdrop(&settler, SOL);
+    let sent = world.dry_run(&settler, |ctx| commands::settle_jury(ctx, &challenge));
+    let expected = instructions::settle_jury(&challenge, &dispute(), &world.creator, &world.verifiers);
+    // The settler only pays the fee, it is not an account of the instruction
+    assert_sent(&sent, expected);
+}
+
+#[test]
+fn juror_membership() {
+    let world = World::new();
+    let juror = Pubkey::new_unique();
+    world.airdrop(&juror, 2 * MIN_JUROR_STAKE);
+    let sent = world.dry_run(&juror, |ctx| commands::register_juror(ctx, MIN_JUROR_STAKE));
+    assert_sent(&sent, instructions::register_juror(&juror, MIN_JUROR_STAKE));
+
+    world.send(&juror, |ctx| commands::register_juror(ctx, MIN_JUROR_STAKE));
+    let sent = world.dry_run(&juror, commands::unregister_juror);
+    assert_sent(&sent, instructions::unregister_juror(&juror));
+
+    world.send(&juror, commands::unregister_juror);
+    world.warp_to(world.now() + JUROR_UNBONDING_PERIOD);
+    let sent = world.dry_run(&juror, commands::withdraw_juror_stake);
+    assert_sent(&sent, instructions::withdraw_juror_stake(&juror));
+}
+
+#[test]
+fn grass_configuration() {
+    let world = World::new();
+    let doubled = GrassEmission {
+        creator_reward: 2 * emission().creator_reward,
+        ..emission()
+    };
+    let sent = world.dry_run(&ADMIN_PUBKEY, |ctx| commands::configure_grass(ctx, doubled.clone()));
+    assert_sent(&sent, instructions::configure_grass(&ADMIN_PUBKEY, doubled));
+
+    // Anyone else is refused in simulation
+    let err = commands::configure_grass(&world.context(&world.creator, true), emission())
+        .err()
+        .unwrap();
+    assert!(err.to_string().starts_with("simulation failed"), "{err}");
+}
+
+#[test]
+fn platform_limits() {
+    let world = World::new();
+    let limits = PlatformLimits {
+        max_stake: SOL,
+        ..PlatformLimits::default()
+    };
+    let sent = world.dry_run(&ADMIN_PUBKEY, |ctx| commands::set_platform_limits(ctx, limits.clone()));
+    assert_sent(&sent, instructions::set_platform_limits(&ADMIN_PUBKEY, limits.clone()));
+
+    // Anyone else is refused in simulation
+    let err = commands::set_platform_limits(&world.context(&world.creator, true), limits)
+        .err()
+        .unwrap();
+    assert!(err.to_string().starts_with("simulation failed"), "{err}");
+}
+
+#[test]
+fn a_failing_simulation_is_reported() {
+    let world = World::new();
+    let challenge = world.create(1);
+    // Not started yet, so there is nothing to vote on
+    let verifier = world.verifiers[0];
+    let err = commands::vote(&world.context(&verifier, true), &challenge, Vote::Approve, None)
+        .err()
+        .unwrap();
+    assert!(err.to_string().starts_with("simulation failed"), "{err}");
+}
+
+#[test]
+fn reads_send_nothing() {
+    let world = World::new();
+    let challenge = world.escalated(1);
+    let template = {
+        let spec = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("examples/template.toml");
+        world.send(&world.creator, |ctx| commands::create_template(ctx, &spec, Some(1)));
+        pda::template_pda(&world.creator, 1).0
+    };
+    let (creator, verifier) = (world.creator, world.verifiers[0]);
+    let juror = accounts::fetch_disputes(&*world.svm(), &challenge).unwrap()[0].1.jury[0].juror;
+    let reads: Vec<Box<Command>> = vec![
+        Box::new(|ctx| commands::show_challenge(ctx, &challenge)),
+        Box::new(|ctx| commands::show_template(ctx, &template)),
+        Box::new(|ctx| commands::show_vote(ctx, &challenge, &verifier)),
+        Box::new(|ctx| commands::show_reputation(ctx, &verifier)),
+        Box::new(|ctx| commands::show_user(ctx, Some(&creator))),
+        Box::new(|ctx| commands::show_disputes(ctx, &challenge)),
+        Box::new(|ctx| commands::show_juror(ctx, Some(&juror))),
+        Box::new(commands::show_grass),
+        Box::new(commands::show_platform),
+        Box::new(commands::list_mine),
+    ];
+    for read in reads {
+        assert!(world.dry_run(&creator, read).is_empty());
+    }
+    assert!(world.dry_run(&verifier, commands::list_assigned).is_empty());
+
+    // Extensions only exist while requested
+    let active = world.active(2);
+    let new_end_time = world.challenge(&active).end_time + DAY;
+    world.send(&creator, |ctx| commands::request_extension(ctx, &active, new_end_time));
+    assert!(world.dry_run(&creator, |ctx| commands::show_extension(ctx, &active)).is_empty());
+}
@@ -0,0 +1,242 @@
+//! TOML challenge and template specs: parsing, the example files and the
+//! checks run before anything is signed.
+
+use std::path::Path;
+
+use proof_of_touch_grass::state::{GeoPoint, Geofence, HourWindow, TimeoutPolicy};
+use proof_of_touch_grass_cli::spec::{ChallengeSpec, TemplateSpec};
+
+const VERIFIER: &str = "6wZQRRCWHeJriMqBpmpZF2PrJ3oVyTzEZMD5F5n388HU";
+
+/// A minimal valid challenge spec followed by `extra`
+fn challenge(extra: &str) -> String {
+    format!(
+        r#"
+title = "Walks"
+stake_lamports = 1_000_000_000
+start_time = 100
+end_time = 200
+verification_period = 50
+required_proofs = 1
+required_approval_weight = 1
+verifiers = ["{VERIFIER}"]
+{extra}
+"#
+    )
+}
+
+fn parse(spec: &str) -> anyhow::Result<ChallengeSpec> {
+    Ok(toml::from_str(spec)?)
+}
+
+/// The error `into_args` returns for `spec`, which must parse
+fn rejection(spec: &str) -> String {
+    match parse(spec).unwrap().into_args() {
+        Ok(_) => panic!("spec was accepted"),
+        Err(err) => err.to_string(),
+    }
+}
+
+fn example(name: &str) -> std::path::PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("examples").join(name)
+}
+
+#[test]
+fn the_examples_load() {
+    let args = ChallengeSpec::load(&example("challenge.toml"))
+        .unwrap()
+        .into_args()
+        .unwrap();
+    assert_eq!(args.stake_amount, 1_000_000_000);
+    assert_eq!(args.verifiers.len(), 2);
+    let park = Geofence::Circle {
+        center: GeoPoint {
+            latitude: 40_782_865,
+            longitude: -73_965_355,
+        },
+        radius_meters: 1_000,
+    };
+    assert!(args.geofences == vec![park]);
+    let mornings = HourWindow {
+        start_hour: 10,
+        end_hour: 14,
+    };
+    assert!(args.submission_rules.allowed_hours == vec![mornings]);
+
+    let args = TemplateSpec::load(&example("template.toml"))
+        .unwrap()
+        .into_args()
+        .unwrap();
+    assert_eq!(args.duration, 1_209_600);
+    assert_eq!(args.submission_rules.max_per_day, Some(1));
+}
+
+#[test]
+fn defaults_fill_optional_sections() {
+    let args = parse(&challenge("")).unwrap().into_args().unwrap();
+    assert_eq!(args.description, "");
+    assert!(args.geofences.is_empty() && args.verifier_weights.is_empty());
+    assert_eq!(args.submission_rules.min_interval, None);
+    assert_eq!(args.verification_rules.timeout_policy, TimeoutPolicy::Completed);
+    assert_eq!(args.min_verifier_reputation, None);
+    assert!(!args.pay_fee_in_grass);
+}
+
+#[test]
+fn boxes_and_timeout_policies_map_onto_program_types() {
+    let spec = challenge(
+        r#"
+[[geofences]]
+kind = "box"
+south = -10
+west = 170_000_000
+north = 10
+east = -170_000_000
+
+[verification]
+on_timeout = "refund"
+min_votes = 1
+"#,
+    );
+    let args = parse(&spec).unwrap().into_args().unwrap();
+    let wrapping = Geofence::BoundingBox {
+        south_west: GeoPoint {
+            latitude: -10,
+            longitude: 170_000_000,
+        },
+        north_east: GeoPoint {
+            latitude: 10,
+            longitude: -170_000_000,
+        },
+    };
+    assert!(args.geofences == vec![wrapping]);
+    assert_eq!(args.verification_rules.timeout_policy, TimeoutPolicy::Refund);
+    assert_eq!(args.verification_rules.min_votes, 1);
+}
+
+#[test]
+fn missing_fields_are_named() {
+    let spec = challenge("").replace("title = \"Walks\"\n", "");
+    let err = parse(&spec).err().unwrap().to_string();
+    assert!(err.contains("missing field `title`"), "{err}");
+
+    let spec = challenge("").replace("required_proofs = 1\n", "");
+    let err = parse(&spec).err().unwrap().to_string();
+    assert!(err.contains("missing field `required_proofs`"), "{err}");
+
+    let spec = challenge("[[geofences]]\nkind = \"circle\"\nlatitude = 0\nlongitude = 0\n");
+    let err = parse(&spec).err().unwrap().to_string();
+    assert!(err.contains("missing field `radius_meters`"), "{err}");
+}
+
+#[test]
+fn unknown_fields_and_kinds_are_rejected() {
+    let err = parse(&challenge("stake = 5")).err().unwrap().to_string();
+    assert!(err.contains("unknown field `stake`"), "{err}");
+
+    let spec = challenge("[[geofences]]\nkind = \"polygon\"\n");
+    let err = parse(&spec).err().unwrap().to_string();
+    assert!(err.contains("unknown variant `polygon`"), "{err}");
+
+    let spec = challenge("[verification]\non_timeout = \"never\"\n");
+    assert!(parse(&spec).is_err());
+}
+
+#[test]
+fn a_bad_verifier_pubkey_is_named() {
+    let spec = challenge("").replace(VERIFIER, "not-a-pubkey");
+    let err = rejection(&spec);
+    assert_eq!(err, "invalid verifier pubkey not-a-pubkey");
+}
+
+#[test]
+fn a_repeated_verifier_is_named() {
+    let spec = challenge("").replace(
+        &format!("[\"{VERIFIER}\"]"),
+        &format!("[\"{VERIFIER}\", \"{VERIFIER}\"]"),
+    );
+    let err = rejection(&spec);
+    assert_eq!(err, format!("verifier {VERIFIER} is listed twice"));
+}
+
+#[test]
+fn invalid_geofences_are_rejected() {
+    let circle = |latitude: i32, radius: u32| {
+        format!(
+            "[[geofences]]\nkind = \"circle\"\nlatitude = {latitude}\nlongitude = 0\n\
+             radius_meters = {radius}\n"
+        )
+    };
+    // Past the pole, without a radius, and a box upside down
+    for geofences in [
+        circle(90_000_001, 100),
+        circle(0, 0),
+        format!("{}{}", circle(0, 100), circle(-90_000_001, 100)),
+        "[[geofences]]\nkind = \"box\"\nsouth = 10\nwest = 0\nnorth = -10\neast = 1\n".into(),
+    ] {
+        let err = rejection(&challenge(&geofences));
+        assert!(err.starts_with("geofence "), "{err}");
+    }
+    let err = rejection(&challenge(&format!("{}{}", circle(0, 100), circle(1, 0))));
+    assert!(err.starts_with("geofence 2 is invalid"), "{err}");
+
+    let five = circle(0, 100).repeat(5);
+    let err = rejection(&challenge(&five));
+    assert_eq!(err, "5 geofences given, at most 4 allowed");
+}
+
+#[test]
+fn invalid_cadence_rules_are_rejected() {
+    for rules in [
+        "min_interval = 0",
+        "max_per_day = 0",
+        "allowed_hours = [{ start_hour = 8, end_hour = 8 }]",
+        "allowed_hours = [{ start_hour = 24, end_hour = 2 }]",
+        "allowed_hours = [{ start_hour = 1, end_hour = 25 }]",
+    ] {
+        let err = rejection(&challenge(&format!("[rules]\n{rules}\n")));
+        assert!(err.starts_with("invalid rules"), "{rules}: {err}");
+    }
+    // Wrapping past midnight is fine
+    let spec = challenge("[rules]\nallowed_hours = [{ start_hour = 22, end_hour = 2 }]\n");
+    parse(&spec).unwrap().into_args().unwrap();
+}
+
+#[test]
+fn an_approval_is_required() {
+    let spec = challenge("").replace("required_approval_weight = 1", "required_approval_weight = 0");
+    assert_eq!(rejection(&spec), "required_approval_weight must be at least 1");
+}
+
+#[test]
+fn the_quorum_cannot_exceed_the_verifiers() {
+    let err = rejection(&challenge("[verification]\nmin_votes = 2\n"));
+    assert_eq!(err, "min_votes is 2 but the challenge has 1 verifiers");
+}
+
+#[test]
+fn templates_are_checked_too() {
+    let spec = r#"
+title = "Walks"
+duration = 100
+verification_period = 50
+required_proofs = 1
+required_approval_weight = 1
+
+[rules]
+max_per_day = 0
+"#;
+    let template: TemplateSpec = toml::from_str(spec).unwrap();
+    let err = template.into_args().err().unwrap().to_string();
+    assert!(err.starts_with("invalid rules"), "{err}");
+
+    let err = toml::from_str::<TemplateSpec>("title = \"Walks\"").err().unwrap().to_string();
+    assert!(err.contains("missing field `duration`"), "{err}");
+}
+
+#[test]
+fn loading_names_the_file() {
+    let missing = example("missing.toml");
+    let err = ChallengeSpec::load(&missing).err().unwrap();
+    assert!(format!("{err:#}").contains("missing.toml"));
+}
@@ -0,0 +1,176 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AccountDeserialize;
+use proof_of_touch_grass::state::*;
+
+use crate::error::ClientError;
+use crate::pda;
+
+/// Source of raw account data, e.g. an RPC client or a test bank
+pub trait AccountFetcher {
+    type Error: Into<Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Returns the account's data, or `None` if the account does not exist
+    fn fetch_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, Self::Error>;
+}
+
+/// Decodes a program account, checking its discriminator
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T, ClientError> {
+    let mut data = data;
+    T::try_deserialize(&mut data).map_err(|err| ClientError::Decode(err.to_string()))
+}
+
+/// Fetches and decodes a program account, returning `None` if it does not exist
+pub fn fetch<T: AccountDeserialize, F: AccountFetcher>(
+    fetcher: &F,
+    address: &Pubkey,
+) -> Result<Option<T>, ClientError> {
+    let data = fetcher
+        .fetch_account_data(address)
+        .map_err(|err| ClientError::Fetch(err.into()))?;
+    data.map(|data| decode(&data)).transpose()
+}
+
+fn fetch_required<T: AccountDeserialize, F: AccountFetcher>(
+    fetcher: &F,
+    address: &Pubkey,
+) -> Result<T, ClientError> {
+    fetch(fetcher, address)?.ok_or(ClientError::AccountNotFound(*address))
+}
+
+pub fn fetch_user<F: AccountFetcher>(fetcher: &F, address: &Pubkey) -> Result<User, ClientError> {
+    fetch_required(fetcher, address)
+}
+
+pub fn fetch_challenge<F: AccountFetcher>(
+    fetcher: &F,
+    address: &Pubkey,
+) -> Result<Challenge, ClientError> {
+    fetch_required(fetcher, address)
+}
+
+pub fn fetch_evidence<F: AccountFetcher>(
+    fetcher: &F,
+    address: &Pubkey,
+) -> Result<Evidence, ClientError> {
+    fetch_required(fetcher, address)
+}
+
+pub fn fetch_verification<F: AccountFetcher>(
+    fetcher: &F,
+    address: &Pubkey,
+) -> Result<Verification, ClientError> {
+    fetch_required(fetcher, address)
+}
+
+pub fn fetch_dispute<F: AccountFetcher>(
+    fetcher: &F,
+    address: &Pubkey,
+) -> Result<Dispute, ClientError> {
+    fetch_required(fetcher, address)
+}
+
+pub fn fetch_reputation<F: AccountFetcher>(
+    fetcher: &F,
+    address: &Pubkey,
+) -> Result<VerifierReputation, ClientError> {
+    fetch_required(fetcher, address)
+}
+
+pub fn fetch_extension<F: AccountFetcher>(
+    fetcher: &F,
+    address: &Pubkey,
+) -> Result<ExtensionRequest, ClientError> {
+    fetch_required(fetcher, address)
+}
+
+pub fn fetch_template<F: AccountFetcher>(
+    fetcher: &F,
+    address: &Pubkey,
+) -> Result<ChallengeTemplate, ClientError> {
+    fetch_required(fetcher, address)
+}
+
+pub fn fetch_challenge_index<F: AccountFetcher>(
+    fetcher: &F,
+    address: &Pubkey,
+) -> Result<ChallengeIndex, ClientError> {
+    fetch_required(fetcher, address)
+}
+
+pub fn fetch_inbox<F: AccountFetcher>(
+    fetcher: &F,
+    address: &Pubkey,
+) -> Result<VerifierInbox, ClientError> {
+    fetch_required(fetcher, address)
+}
+
+pub fn fetch_juror<F: AccountFetcher>(
+    fetcher: &F,
+    address: &Pubkey,
+) -> Result<Juror, ClientError> {
+    fetch_required(fetcher, address)
+}
+
+/// Jurors that can be drawn for a jury, empty before the first registration
+pub fn fetch_juror_pool<F: AccountFetcher>(fetcher: &F) -> Result<Vec<Pubkey>, ClientError> {
+    let pool: Option<JurorPool> = fetch(fetcher, &pda::juror_pool_pda().0)?;
+    Ok(pool.map_or_else(Vec::new, |pool| pool.jurors))
+}
+
+/// The GRASS mint and emission parameters, once the admin has configured them
+pub fn fetch_grass_config<F: AccountFetcher>(fetcher: &F) -> Result<GrassConfig, ClientError> {
+    fetch_required(fetcher, &pda::grass_config_pda().0)
+}
+
+/// Whether the admin has configured GRASS, which claims need to know
+pub fn grass_configured<F: AccountFetcher>(fetcher: &F) -> Result<bool, ClientError> {
+    let config: Option<GrassConfig> = fetch(fetcher, &pda::grass_config_pda().0)?;
+    Ok(config.is_some())
+}
+
+/// The platform ceilings on user limits, the defaults until the admin sets any
+pub fn fetch_platform_limits<F: AccountFetcher>(fetcher: &F) -> Result<PlatformLimits, ClientError> {
+    let config: Option<PlatformConfig> = fetch(fetcher, &pda::platform_config_pda().0)?;
+    Ok(config.map_or_else(PlatformLimits::default, |config| config.limits))
+}
+
+/// Open assignments of the verifier, empty if they were never assigned one
+pub fn fetch_assignments<F: AccountFetcher>(
+    fetcher: &F,
+    verifier: &Pubkey,
+) -> Result<Vec<Assignment>, ClientError> {
+    let inbox: Option<VerifierInbox> = fetch(fetcher, &pda::inbox_pda(verifier).0)?;
+    Ok(inbox.map_or_else(Vec::new, |inbox| inbox.assignments))
+}
+
+/// Every challenge the authority created, in creation order, read through
+/// their challenge index
+pub fn fetch_challenges_by<F: AccountFetcher>(
+    fetcher: &F,
+    authority: &Pubkey,
+) -> Result<Vec<(Pubkey, Challenge)>, ClientError> {
+    let index = fetch_challenge_index(fetcher, &pda::challenge_index_pda(authority).0)?;
+    index
+        .challenge_ids
+        .iter()
+        .map(|&challenge_id| {
+            let address = pda::challenge_pda(authority, challenge_id).0;
+            Ok((address, fetch_challenge(fetcher, &address)?))
+        })
+        .collect()
+}
+
+/// Every dispute filed on the challenge, oldest first. While the challenge
+/// is Disputed the last one is open.
+pub fn fetch_disputes<F: AccountFetcher>(
+    fetcher: &F,
+    challenge: &Pubkey,
+) -> Result<Vec<(Pubkey, Dispute)>, ClientError> {
+    let dispute_count = fetch_challenge(fetcher, challenge)?.dispute_count;
+    (0..dispute_count)
+        .map(|dispute_index| {
+            let address = pda::dispute_pda(challenge, dispute_index).0;
+            Ok((address, fetch_dispute(fetcher, &address)?))
+        })
+        .collect()
+}
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::Pubkey;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("account {0} not found")]
+    AccountNotFound(Pubkey),
+    #[error("failed to decode account: {0}")]
+    Decode(String),
+    #[error("failed to fetch account: {0}")]
+    Fetch(Box<dyn std::error::Error + Send + Sync>),
+}
@@ -0,0 +1,672 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::sysvar::slot_hashes;
+use anchor_lang::system_program;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::{associated_token, token, token_2022};
+use proof_of_touch_grass::state::{
+    Dispute, GeoPoint, Geofence, GrassEmission, Justification, JuryVote, PlatformLimits,
+    SubmissionRules, UserLimits, VerificationRules, Vote,
+};
+use proof_of_touch_grass::constants::PLATFORM_PUBKEY;
+use proof_of_touch_grass::{accounts, instruction, ID};
+
+use crate::pda;
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// `[verification, reputation]` per verifier, as expected by reputation settlement
+fn reputation_metas(challenge: &Pubkey, verifiers: &[Pubkey]) -> Vec<AccountMeta> {
+    verifiers
+        .iter()
+        .flat_map(|verifier| {
+            [
+                AccountMeta::new_readonly(pda::verification_pda(challenge, verifier).0, false),
+                AccountMeta::new(pda::reputation_pda(verifier).0, false),
+            ]
+        })
+        .collect()
+}
+
+/// The inbox of each verifier, in the challenge's verifier order
+fn inbox_metas(verifiers: &[Pubkey]) -> Vec<AccountMeta> {
+    verifiers
+        .iter()
+        .map(|verifier| AccountMeta::new(pda::inbox_pda(verifier).0, false))
+        .collect()
+}
+
+/// Inboxes of the verifiers of a new challenge, followed by their reputation
+/// accounts, which the program creates where missing
+fn new_challenge_metas(verifiers: &[Pubkey]) -> Vec<AccountMeta> {
+    let mut metas = inbox_metas(verifiers);
+    metas.extend(
+        verifiers
+            .iter()
+            .map(|verifier| AccountMeta::new(pda::reputation_pda(verifier).0, false)),
+    );
+    metas
+}
+
+/// Parameters for `create_challenge`
+#[derive(Clone)]
+pub struct CreateChallengeArgs {
+    pub title: String,
+    pub description: String,
+    pub stake_amount: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub verification_period: i64,
+    pub required_proofs: u8,
+    pub required_approval_weight: u8,
+    pub verifiers: Vec<Pubkey>,
+    /// One weight per verifier, or empty to weigh every vote equally
+    pub verifier_weights: Vec<u8>,
+    pub geofences: Vec<Geofence>,
+    pub submission_rules: SubmissionRules,
+    pub verification_rules: VerificationRules,
+    /// Minimum `VerifierReputation::score` every listed verifier must have
+    pub min_verifier_reputation: Option<u32>,
+    /// Burn the platform fee, discounted, from the creator's GRASS account
+    /// instead of escrowing it in SOL
+    pub pay_fee_in_grass: bool,
+}
+
+pub fn initialize_user(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeUser {
+            user: pda::user_pda(authority).0,
+            challenge_index: pda::challenge_index_pda(authority).0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeUser {},
+    )
+}
+
+pub fn set_user_limits(authority: &Pubkey, limits: UserLimits) -> Instruction {
+    build(
+        accounts::SetUserLimits {
+            user: pda::user_pda(authority).0,
+            platform_config: pda::platform_config_pda().0,
+            authority: *authority,
+        },
+        instruction::SetUserLimits { limits },
+    )
+}
+
+fn create_challenge_accounts(
+    creator: &Pubkey,
+    challenge: &Pubkey,
+    pay_fee_in_grass: bool,
+) -> accounts::CreateChallenge {
+    let grass = |account: Pubkey| pay_fee_in_grass.then_some(account);
+    accounts::CreateChallenge {
+        challenge: *challenge,
+        escrow: pda::escrow_pda(challenge).0,
+        user: pda::user_pda(creator).0,
+        challenge_index: pda::challenge_index_pda(creator).0,
+        creator: *creator,
+        system_program: system_program::ID,
+        platform_config: pda::platform_config_pda().0,
+        grass_config: grass(pda::grass_config_pda().0),
+        grass_mint: grass(pda::grass_mint_pda().0),
+        grass_fee_account: grass(pda::grass_account(creator)),
+        token_program: grass(token::ID),
+    }
+}
+
+/// `challenge_id` is any ID the creator has not used before
+pub fn create_challenge(
+    creator: &Pubkey,
+    challenge_id: u64,
+    args: CreateChallengeArgs,
+) -> Instruction {
+    let challenge = pda::challenge_pda(creator, challenge_id).0;
+    let remaining = new_challenge_metas(&args.verifiers);
+    let mut instruction = build(
+        create_challenge_accounts(creator, &challenge, args.pay_fee_in_grass),
+        instruction::CreateChallenge {
+            challenge_id,
+            title: args.title,
+            description: args.description,
+            stake_amount: args.stake_amount,
+            start_time: args.start_time,
+            end_time: args.end_time,
+            verification_period: args.verification_period,
+            required_proofs: args.required_proofs,
+            required_approval_weight: args.required_approval_weight,
+            verifiers: args.verifiers,
+            verifier_weights: args.verifier_weights,
+            geofences: args.geofences,
+            submission_rules: args.submission_rules,
+            verification_rules: args.verification_rules,
+            min_verifier_reputation: args.min_verifier_reputation,
+        },
+    );
+    instruction.accounts.extend(remaining);
+    instruction
+}
+
+/// Retries `previous_challenge` under the unused `challenge_id`; `verifiers`
+/// lists the failed challenge's verifiers, who carry over to the retry, and
+/// `template` is the failed challenge's template, if it has one
+pub fn retry_challenge(
+    creator: &Pubkey,
+    previous_challenge: &Pubkey,
+    template: Option<&Pubkey>,
+    verifiers: &[Pubkey],
+    challenge_id: u64,
+    stake_amount: u64,
+    start_time: i64,
+) -> Instruction {
+    let challenge = pda::challenge_pda(creator, challenge_id).0;
+    let mut instruction = build(
+        accounts::RetryChallenge {
+            retry: create_challenge_accounts(creator, &challenge, false),
+            previous_challenge: *previous_challenge,
+            template: template.copied(),
+        },
+        instruction::RetryChallenge {
+            challenge_id,
+            stake_amount,
+            start_time,
+        },
+    );
+    instruction.accounts.extend(new_challenge_metas(verifiers));
+    instruction
+}
+
+/// Parameters for `create_template`
+#[derive(Clone)]
+pub struct CreateTemplateArgs {
+    pub title: String,
+    pub description: String,
+    /// Seconds from start to end
+    pub duration: i64,
+    pub verification_period: i64,
+    pub required_proofs: u8,
+    pub required_approval_weight: u8,
+    pub geofences: Vec<Geofence>,
+    pub submission_rules: SubmissionRules,
+    pub verification_rules: VerificationRules,
+    pub min_verifier_reputation: Option<u32>,
+}
+
+/// `template_id` is any ID the author has not used before
+pub fn create_template(author: &Pubkey, template_id: u64, args: CreateTemplateArgs) -> Instruction {
+    build(
+        accounts::CreateTemplate {
+            template: pda::template_pda(author, template_id).0,
+            author: *author,
+            system_program: system_program::ID,
+        },
+        instruction::CreateTemplate {
+            template_id,
+            title: args.title,
+            description: args.description,
+            duration: args.duration,
+            verification_period: args.verification_period,
+            required_proofs: args.required_proofs,
+            required_approval_weight: args.required_approval_weight,
+            geofences: args.geofences,
+            submission_rules: args.submission_rules,
+            verification_rules: args.verification_rules,
+            min_verifier_reputation: args.min_verifier_reputation,
+        },
+    )
+}
+
+pub fn curate_template(admin: &Pubkey, template: &Pubkey, curated: bool) -> Instruction {
+    build(
+        accounts::CurateTemplate {
+            template: *template,
+            admin: *admin,
+        },
+        instruction::CurateTemplate { curated },
+    )
+}
+
+pub fn create_challenge_from_template(
+    creator: &Pubkey,
+    template: &Pubkey,
+    challenge_id: u64,
+    stake_amount: u64,
+    start_time: i64,
+    verifiers: Vec<Pubkey>,
+    verifier_weights: Vec<u8>,
+) -> Instruction {
+    let challenge = pda::challenge_pda(creator, challenge_id).0;
+    let remaining = new_challenge_metas(&verifiers);
+    let mut instruction = build(
+        accounts::CreateChallengeFromTemplate {
+            challenge: create_challenge_accounts(creator, &challenge, false),
+            template: *template,
+        },
+        instruction::CreateChallengeFromTemplate {
+            challenge_id,
+            stake_amount,
+            start_time,
+            verifiers,
+            verifier_weights,
+        },
+    );
+    instruction.accounts.extend(remaining);
+    instruction
+}
+
+/// `verifiers` lists the challenge's verifiers, whose inboxes clear on finalization
+pub fn update_challenge_state(
+    admin: &Pubkey,
+    challenge: &Pubkey,
+    creator: &Pubkey,
+    verifiers: &[Pubkey],
+) -> Instruction {
+    let mut instruction = build(
+        accounts::UpdateChallengeState {
+            challenge: *challenge,
+            user: pda::user_pda(creator).0,
+            admin: *admin,
+        },
+        instruction::UpdateChallengeState {},
+    );
+    instruction.accounts.extend(inbox_metas(verifiers));
+    instruction
+}
+
+/// `evidence_index` must equal the challenge's current `evidence_count`
+pub fn submit_evidence(
+    creator: &Pubkey,
+    challenge: &Pubkey,
+    evidence_index: u8,
+    ipfs_hash: String,
+    metadata: String,
+    location: Option<GeoPoint>,
+) -> Instruction {
+    build(
+        accounts::SubmitEvidence {
+            evidence: pda::evidence_pda(challenge, evidence_index).0,
+            challenge: *challenge,
+            submitter: *creator,
+            system_program: system_program::ID,
+        },
+        instruction::SubmitEvidence {
+            ipfs_hash,
+            metadata,
+            location,
+        },
+    )
+}
+
+pub fn replace_evidence(
+    creator: &Pubkey,
+    challenge: &Pubkey,
+    evidence_index: u8,
+    ipfs_hash: String,
+    metadata: String,
+    location: Option<GeoPoint>,
+) -> Instruction {
+    build(
+        accounts::ReplaceEvidence {
+            evidence: pda::evidence_pda(challenge, evidence_index).0,
+            challenge: *challenge,
+            submitter: *creator,
+        },
+        instruction::ReplaceEvidence {
+            evidence_index,
+            ipfs_hash,
+            metadata,
+            location,
+        },
+    )
+}
+
+/// `last_evidence_index` must equal the challenge's `evidence_count - 1`
+pub fn retract_evidence(
+    creator: &Pubkey,
+    challenge: &Pubkey,
+    last_evidence_index: u8,
+) -> Instruction {
+    build(
+        accounts::RetractEvidence {
+            evidence: pda::evidence_pda(challenge, last_evidence_index).0,
+            challenge: *challenge,
+            submitter: *creator,
+        },
+        instruction::RetractEvidence {},
+    )
+}
+
+/// `verifiers` lists the challenge's verifiers, whose inboxes clear on finalization
+pub fn verify_evidence(
+    verifier: &Pubkey,
+    challenge: &Pubkey,
+    creator: &Pubkey,
+    verifiers: &[Pubkey],
+    vote: Vote,
+    justification: Option<Justification>,
+) -> Instruction {
+    let mut instruction = build(
+        accounts::VerifyEvidence {
+            verification: pda::verification_pda(challenge, verifier).0,
+            reputation: pda::reputation_pda(verifier).0,
+            challenge: *challenge,
+            user: pda::user_pda(creator).0,
+            verifier: *verifier,
+            system_program: system_program::ID,
+        },
+        instruction::VerifyEvidence { vote, justification },
+    );
+    instruction.accounts.extend(inbox_metas(verifiers));
+    instruction
+}
+
+/// `verifiers` lists the challenge's verifiers, whose inboxes are cleared if
+/// the change finalizes the challenge
+pub fn update_vote(
+    verifier: &Pubkey,
+    challenge: &Pubkey,
+    creator: &Pubkey,
+    verifiers: &[Pubkey],
+    vote: Vote,
+    justification: Option<Justification>,
+) -> Instruction {
+    let mut instruction = build(
+        accounts::UpdateVote {
+            verification: pda::verification_pda(challenge, verifier).0,
+            challenge: *challenge,
+            user: pda::user_pda(creator).0,
+            verifier: *verifier,
+        },
+        instruction::UpdateVote { vote, justification },
+    );
+    instruction.accounts.extend(inbox_metas(verifiers));
+    instruction
+}
+
+/// `verifiers` lists the challenge's verifiers, whose inboxes are cleared
+pub fn cancel_challenge(creator: &Pubkey, challenge: &Pubkey, verifiers: &[Pubkey]) -> Instruction {
+    let mut instruction = build(
+        accounts::CancelChallenge {
+            challenge: *challenge,
+            escrow: pda::escrow_pda(challenge).0,
+            user: pda::user_pda(creator).0,
+            platform: PLATFORM_PUBKEY,
+            creator: *creator,
+            system_program: system_program::ID,
+        },
+        instruction::CancelChallenge {},
+    );
+    instruction.accounts.extend(inbox_metas(verifiers));
+    instruction
+}
+
+pub fn request_extension(creator: &Pubkey, challenge: &Pubkey, new_end_time: i64) -> Instruction {
+    build(
+        accounts::RequestExtension {
+            extension: pda::extension_pda(challenge).0,
+            challenge: *challenge,
+            creator: *creator,
+            system_program: system_program::ID,
+        },
+        instruction::RequestExtension { new_end_time },
+    )
+}
+
+/// `verifiers` lists the challenge's verifiers, whose inbox deadlines move
+/// if this approval applies the extension
+pub fn approve_extension(
+    verifier: &Pubkey,
+    challenge: &Pubkey,
+    creator: &Pubkey,
+    verifiers: &[Pubkey],
+) -> Instruction {
+    let mut instruction = build(
+        accounts::ApproveExtension {
+            extension: pda::extension_pda(challenge).0,
+            challenge: *challenge,
+            creator: *creator,
+            platform: PLATFORM_PUBKEY,
+            verifier: *verifier,
+        },
+        instruction::ApproveExtension {},
+    );
+    instruction.accounts.extend(inbox_metas(verifiers));
+    instruction
+}
+
+pub fn withdraw_extension(creator: &Pubkey, challenge: &Pubkey) -> Instruction {
+    build(
+        accounts::WithdrawExtension {
+            extension: pda::extension_pda(challenge).0,
+            challenge: *challenge,
+            creator: *creator,
+        },
+        instruction::WithdrawExtension {},
+    )
+}
+
+/// `dispute_index` is the challenge's `dispute_count` and `contested` names
+/// the verifier whose vote the dispute is about, if any
+pub fn dispute_verification(
+    disputer: &Pubkey,
+    challenge: &Pubkey,
+    dispute_index: u8,
+    reason: String,
+    contested: Option<&Pubkey>,
+) -> Instruction {
+    build(
+        accounts::DisputeVerification {
+            dispute: pda::dispute_pda(challenge, dispute_index).0,
+            challenge: *challenge,
+            disputer: *disputer,
+            contested_verification: contested
+                .map(|verifier| pda::verification_pda(challenge, verifier).0),
+            system_program: system_program::ID,
+        },
+        instruction::DisputeVerification { reason },
+    )
+}
+
+/// Upholds or overturns the open dispute of a challenge. `verifiers` is the
+/// challenge's verifier list, whose reputations an overturn updates.
+pub fn resolve_dispute(
+    admin: &Pubkey,
+    challenge: &Pubkey,
+    dispute: &Dispute,
+    creator: &Pubkey,
+    verifiers: &[Pubkey],
+    overturn: bool,
+) -> Instruction {
+    let mut instruction = build(
+        accounts::ResolveDispute {
+            challenge: *challenge,
+            dispute: pda::dispute_pda(challenge, dispute.index).0,
+            user: pda::user_pda(creator).0,
+            disputer: dispute.disputer,
+            platform: PLATFORM_PUBKEY,
+            admin: *admin,
+        },
+        instruction::ResolveDispute { overturn },
+    );
+    if overturn {
+        instruction.accounts.extend(reputation_metas(challenge, verifiers));
+    }
+    instruction
+}
+
+pub fn register_juror(authority: &Pubkey, stake: u64) -> Instruction {
+    build(
+        accounts::RegisterJuror {
+            juror: pda::juror_pda(authority).0,
+            juror_pool: pda::juror_pool_pda().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::RegisterJuror { stake },
+    )
+}
+
+pub fn unregister_juror(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::UnregisterJuror {
+            juror: pda::juror_pda(authority).0,
+            juror_pool: pda::juror_pool_pda().0,
+            authority: *authority,
+        },
+        instruction::UnregisterJuror {},
+    )
+}
+
+pub fn withdraw_juror_stake(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::WithdrawJurorStake {
+            juror: pda::juror_pda(authority).0,
+            authority: *authority,
+        },
+        instruction::WithdrawJurorStake {},
+    )
+}
+
+/// `signer` is the disputer or the admin; `pool` lists the juror pool in order
+pub fn escalate_dispute(
+    signer: &Pubkey,
+    challenge: &Pubkey,
+    dispute_index: u8,
+    pool: &[Pubkey],
+) -> Instruction {
+    let mut instruction = build(
+        accounts::EscalateDispute {
+            dispute: pda::dispute_pda(challenge, dispute_index).0,
+            challenge: *challenge,
+            juror_pool: pda::juror_pool_pda().0,
+            slot_hashes: slot_hashes::ID,
+            signer: *signer,
+        },
+        instruction::EscalateDispute {},
+    );
+    instruction.accounts.extend(
+        pool
+            .iter()
+            .map(|juror| AccountMeta::new(pda::juror_pda(juror).0, false)),
+    );
+    instruction
+}
+
+pub fn cast_jury_vote(
+    juror: &Pubkey,
+    challenge: &Pubkey,
+    dispute_index: u8,
+    vote: JuryVote,
+) -> Instruction {
+    build(
+        accounts::CastJuryVote {
+            dispute: pda::dispute_pda(challenge, dispute_index).0,
+            juror: pda::juror_pda(juror).0,
+            authority: *juror,
+        },
+        instruction::CastJuryVote { vote },
+    )
+}
+
+/// Settles the escalated `dispute` with its jury's juror accounts in seat
+/// order. `verifiers` is the challenge's verifier list, whose reputations an
+/// overturn updates.
+pub fn settle_jury(
+    challenge: &Pubkey,
+    dispute: &Dispute,
+    creator: &Pubkey,
+    verifiers: &[Pubkey],
+) -> Instruction {
+    let mut instruction = build(
+        accounts::SettleJury {
+            challenge: *challenge,
+            dispute: pda::dispute_pda(challenge, dispute.index).0,
+            user: pda::user_pda(creator).0,
+            disputer: dispute.disputer,
+            platform: PLATFORM_PUBKEY,
+        },
+        instruction::SettleJury {},
+    );
+    instruction.accounts.extend(
+        dispute
+            .jury
+            .iter()
+            .map(|seat| AccountMeta::new(pda::juror_pda(&seat.juror).0, false)),
+    );
+    instruction.accounts.extend(reputation_metas(challenge, verifiers));
+    instruction
+}
+
+/// Creators claim a completed challenge; rejecting verifiers claim a failed
+/// one and approving verifiers their GRASS reward on a completed one.
+/// Verifier claims pass the claimer's verification record. `verifiers` is the
+/// challenge's verifier list, whose reputations the first claim settles.
+/// The GRASS accounts are passed when `grass_configured`; before the admin
+/// has configured GRASS there is no reward to mint.
+pub fn claim_funds(
+    claimer: &Pubkey,
+    challenge: &Pubkey,
+    creator: &Pubkey,
+    verifiers: &[Pubkey],
+    as_verifier: bool,
+    grass_configured: bool,
+) -> Instruction {
+    let grass = |account: Pubkey| grass_configured.then_some(account);
+    let mut instruction = build(
+        accounts::ClaimFunds {
+            challenge: *challenge,
+            escrow: pda::escrow_pda(challenge).0,
+            user: pda::user_pda(creator).0,
+            creator: *creator,
+            platform: PLATFORM_PUBKEY,
+            claimer: *claimer,
+            verification: as_verifier.then(|| pda::verification_pda(challenge, claimer).0),
+            badge_mint: pda::badge_mint_pda(challenge).0,
+            badge_account: pda::badge_account(creator, challenge),
+            badge_authority: pda::badge_authority_pda().0,
+            grass_config: grass(pda::grass_config_pda().0),
+            grass_mint: grass(pda::grass_mint_pda().0),
+            grass_account: grass(pda::grass_account(claimer)),
+            system_program: system_program::ID,
+            token_program: token::ID,
+            token_2022_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::ClaimFunds {},
+    );
+    instruction.accounts.extend(reputation_metas(challenge, verifiers));
+    instruction
+}
+
+/// Admin-only; creates the GRASS mint on the first call
+pub fn configure_grass(admin: &Pubkey, emission: GrassEmission) -> Instruction {
+    build(
+        accounts::ConfigureGrass {
+            grass_config: pda::grass_config_pda().0,
+            grass_mint: pda::grass_mint_pda().0,
+            admin: *admin,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ConfigureGrass { emission },
+    )
+}
+
+/// Admin-only; creates the platform config on the first call
+pub fn set_platform_limits(admin: &Pubkey, limits: PlatformLimits) -> Instruction {
+    build(
+        accounts::SetPlatformLimits {
+            platform_config: pda::platform_config_pda().0,
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::SetPlatformLimits { limits },
+    )
+}
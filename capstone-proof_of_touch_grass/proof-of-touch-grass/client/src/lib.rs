@@ -0,0 +1,13 @@
+//! Rust client SDK for the proof-of-touch-grass program.
+//!
+//! - [`pda`]: address derivation for every seed in `constants.rs`
+//! - [`instructions`]: typed builders for every program instruction
+//! - [`accounts`]: decoding and fetching of program accounts
+
+pub mod accounts;
+pub mod error;
+pub mod instructions;
+pub mod pda;
+
+pub use error::ClientError;
+pub use proof_of_touch_grass::{state, ID};
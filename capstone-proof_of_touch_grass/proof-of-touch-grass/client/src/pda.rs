@@ -0,0 +1,122 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::{
+    get_associated_token_address, get_associated_token_address_with_program_id,
+};
+use proof_of_touch_grass::constants::*;
+use proof_of_touch_grass::ID;
+
+/// `[USER_SEED, authority]`
+pub fn user_pda(authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[USER_SEED, authority.as_ref()], &ID)
+}
+
+/// `[CHALLENGE_INDEX_SEED, authority]`
+pub fn challenge_index_pda(authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CHALLENGE_INDEX_SEED, authority.as_ref()], &ID)
+}
+
+/// `[CHALLENGE_SEED, creator, challenge_id (u64 LE)]`
+pub fn challenge_pda(creator: &Pubkey, challenge_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[CHALLENGE_SEED, creator.as_ref(), &challenge_id.to_le_bytes()],
+        &ID,
+    )
+}
+
+/// `[ESCROW_SEED, challenge]`
+pub fn escrow_pda(challenge: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ESCROW_SEED, challenge.as_ref()], &ID)
+}
+
+/// `[EVIDENCE_SEED, challenge, evidence_index]`
+pub fn evidence_pda(challenge: &Pubkey, evidence_index: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[EVIDENCE_SEED, challenge.as_ref(), &evidence_index.to_le_bytes()],
+        &ID,
+    )
+}
+
+/// `[VERIFICATION_SEED, challenge, verifier]`
+pub fn verification_pda(challenge: &Pubkey, verifier: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VERIFICATION_SEED, challenge.as_ref(), verifier.as_ref()],
+        &ID,
+    )
+}
+
+/// `[DISPUTE_SEED, challenge, dispute_index]`
+pub fn dispute_pda(challenge: &Pubkey, dispute_index: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DISPUTE_SEED, challenge.as_ref(), &[dispute_index]], &ID)
+}
+
+/// `[REPUTATION_SEED, verifier]`
+pub fn reputation_pda(verifier: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REPUTATION_SEED, verifier.as_ref()], &ID)
+}
+
+/// `[INBOX_SEED, verifier]`
+pub fn inbox_pda(verifier: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[INBOX_SEED, verifier.as_ref()], &ID)
+}
+
+/// `[EXTENSION_SEED, challenge]`
+pub fn extension_pda(challenge: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EXTENSION_SEED, challenge.as_ref()], &ID)
+}
+
+/// `[TEMPLATE_SEED, author, template_id (u64 LE)]`
+pub fn template_pda(author: &Pubkey, template_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[TEMPLATE_SEED, author.as_ref(), &template_id.to_le_bytes()],
+        &ID,
+    )
+}
+
+/// `[JUROR_SEED, authority]`
+pub fn juror_pda(authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[JUROR_SEED, authority.as_ref()], &ID)
+}
+
+/// `[JUROR_POOL_SEED]`
+pub fn juror_pool_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[JUROR_POOL_SEED], &ID)
+}
+
+/// `[BADGE_SEED, challenge]`
+pub fn badge_mint_pda(challenge: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BADGE_SEED, challenge.as_ref()], &ID)
+}
+
+/// `[BADGE_AUTHORITY_SEED]`
+pub fn badge_authority_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BADGE_AUTHORITY_SEED], &ID)
+}
+
+/// The creator's Token-2022 associated token account holding the badge of `challenge`
+pub fn badge_account(creator: &Pubkey, challenge: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(
+        creator,
+        &badge_mint_pda(challenge).0,
+        &anchor_spl::token_2022::ID,
+    )
+}
+
+/// `[GRASS_CONFIG_SEED]`
+pub fn grass_config_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GRASS_CONFIG_SEED], &ID)
+}
+
+/// `[GRASS_MINT_SEED]`
+pub fn grass_mint_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GRASS_MINT_SEED], &ID)
+}
+
+/// `[PLATFORM_CONFIG_SEED]`
+pub fn platform_config_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PLATFORM_CONFIG_SEED], &ID)
+}
+
+/// The owner's associated token account holding GRASS
+pub fn grass_account(owner: &Pubkey) -> Pubkey {
+    get_associated_token_address(owner, &grass_mint_pda().0)
+}
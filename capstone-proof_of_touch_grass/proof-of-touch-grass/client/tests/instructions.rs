@@ -0,0 +1,844 @@
+//! Every builder in `instructions` against the program's own `accounts::*`
+//! metas and `instruction::*` data, plus the remaining accounts it appends.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::sysvar::slot_hashes;
+use anchor_lang::system_program;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::{associated_token, token, token_2022};
+use proof_of_touch_grass::constants::{ADMIN_PUBKEY, PLATFORM_PUBKEY};
+use proof_of_touch_grass::state::{
+    ChallengeStatus, Dispute, GeoPoint, Geofence, GrassEmission, Justification, JurySeat,
+    JuryVote, PlatformLimits, SubmissionRules, UserLimits, VerificationRules, Vote,
+};
+use proof_of_touch_grass::{accounts, instruction};
+use proof_of_touch_grass_client::instructions::{self, CreateChallengeArgs, CreateTemplateArgs};
+use proof_of_touch_grass_client::{pda, ID};
+
+/// `built` targets the program, lists `accounts` then `remaining`, carries
+/// `data` behind its discriminator and is signed by `signers` alone
+fn assert_built<D: InstructionData>(
+    built: Instruction,
+    accounts: impl ToAccountMetas,
+    remaining: Vec<AccountMeta>,
+    data: D,
+    signers: &[Pubkey],
+) {
+    assert_eq!(built.program_id, ID);
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(remaining);
+    assert_eq!(built.accounts, metas);
+    assert!(built.data.starts_with(D::DISCRIMINATOR));
+    assert_eq!(built.data, data.data());
+    let signed: Vec<Pubkey> = built
+        .accounts
+        .iter()
+        .filter(|meta| meta.is_signer)
+        .map(|meta| meta.pubkey)
+        .collect();
+    assert_eq!(signed, signers);
+}
+
+fn inboxes(verifiers: &[Pubkey]) -> Vec<AccountMeta> {
+    verifiers
+        .iter()
+        .map(|verifier| AccountMeta::new(pda::inbox_pda(verifier).0, false))
+        .collect()
+}
+
+/// Inboxes, then the reputation records a new challenge creates where missing
+fn new_challenge(verifiers: &[Pubkey]) -> Vec<AccountMeta> {
+    let mut metas = inboxes(verifiers);
+    metas.extend(
+        verifiers
+            .iter()
+            .map(|verifier| AccountMeta::new(pda::reputation_pda(verifier).0, false)),
+    );
+    metas
+}
+
+fn reputations(challenge: &Pubkey, verifiers: &[Pubkey]) -> Vec<AccountMeta> {
+    verifiers
+        .iter()
+        .flat_map(|verifier| {
+            [
+                AccountMeta::new_readonly(pda::verification_pda(challenge, verifier).0, false),
+                AccountMeta::new(pda::reputation_pda(verifier).0, false),
+            ]
+        })
+        .collect()
+}
+
+fn keys(count: usize) -> Vec<Pubkey> {
+    (0..count).map(|_| Pubkey::new_unique()).collect()
+}
+
+fn submission_rules() -> SubmissionRules {
+    SubmissionRules {
+        min_interval: Some(3_600),
+        max_per_day: Some(2),
+        allowed_hours: vec![],
+    }
+}
+
+fn geofence() -> Geofence {
+    Geofence::Circle {
+        center: GeoPoint {
+            latitude: 40_782_865,
+            longitude: -73_965_355,
+        },
+        radius_meters: 500,
+    }
+}
+
+fn create_challenge_accounts(creator: &Pubkey, challenge_id: u64) -> accounts::CreateChallenge {
+    let challenge = pda::challenge_pda(creator, challenge_id).0;
+    accounts::CreateChallenge {
+        challenge,
+        escrow: pda::escrow_pda(&challenge).0,
+        user: pda::user_pda(creator).0,
+        challenge_index: pda::challenge_index_pda(creator).0,
+        creator: *creator,
+        system_program: system_program::ID,
+        platform_config: pda::platform_config_pda().0,
+        grass_config: None,
+        grass_mint: None,
+        grass_fee_account: None,
+        token_program: None,
+    }
+}
+
+fn dispute(challenge: Pubkey, index: u8, jurors: &[Pubkey]) -> Dispute {
+    Dispute {
+        challenge,
+        index,
+        disputer: Pubkey::new_unique(),
+        bond: 10_000_000,
+        reason: "blurry".to_string(),
+        contested_verification: None,
+        previous_status: ChallengeStatus::Failed,
+        timestamp: 1,
+        escalated_at: 2,
+        jury: jurors
+            .iter()
+            .map(|&juror| JurySeat { juror, vote: None })
+            .collect(),
+        resolved_at: 0,
+        bump: 255,
+    }
+}
+
+#[test]
+fn initialize_user() {
+    let authority = Pubkey::new_unique();
+    assert_built(
+        instructions::initialize_user(&authority),
+        accounts::InitializeUser {
+            user: pda::user_pda(&authority).0,
+            challenge_index: pda::challenge_index_pda(&authority).0,
+            authority,
+            system_program: system_program::ID,
+        },
+        vec![],
+        instruction::InitializeUser {},
+        &[authority],
+    );
+}
+
+#[test]
+fn set_user_limits() {
+    let authority = Pubkey::new_unique();
+    let limits = UserLimits {
+        max_stake: Some(5),
+        ..UserLimits::default()
+    };
+    assert_built(
+        instructions::set_user_limits(&authority, limits.clone()),
+        accounts::SetUserLimits {
+            user: pda::user_pda(&authority).0,
+            platform_config: pda::platform_config_pda().0,
+            authority,
+        },
+        vec![],
+        instruction::SetUserLimits { limits },
+        &[authority],
+    );
+}
+
+#[test]
+fn create_challenge_appends_inboxes_then_reputations() {
+    let creator = Pubkey::new_unique();
+    let verifiers = keys(2);
+    let args = CreateChallengeArgs {
+        title: "Touch grass".to_string(),
+        description: "Daily walk".to_string(),
+        stake_amount: 1_000_000_000,
+        start_time: 100,
+        end_time: 200,
+        verification_period: 50,
+        required_proofs: 2,
+        required_approval_weight: 2,
+        verifiers: verifiers.clone(),
+        verifier_weights: vec![1, 2],
+        geofences: vec![geofence()],
+        submission_rules: submission_rules(),
+        verification_rules: VerificationRules::default(),
+        min_verifier_reputation: Some(10),
+        pay_fee_in_grass: false,
+    };
+    assert_built(
+        instructions::create_challenge(&creator, 9, args.clone()),
+        create_challenge_accounts(&creator, 9),
+        new_challenge(&verifiers),
+        instruction::CreateChallenge {
+            challenge_id: 9,
+            title: args.title,
+            description: args.description,
+            stake_amount: args.stake_amount,
+            start_time: args.start_time,
+            end_time: args.end_time,
+            verification_period: args.verification_period,
+            required_proofs: args.required_proofs,
+            required_approval_weight: args.required_approval_weight,
+            verifiers: args.verifiers,
+            verifier_weights: args.verifier_weights,
+            geofences: args.geofences,
+            submission_rules: args.submission_rules,
+            verification_rules: args.verification_rules,
+            min_verifier_reputation: args.min_verifier_reputation,
+        },
+        &[creator],
+    );
+}
+
+#[test]
+fn create_challenge_without_a_minimum_reputation_still_passes_reputations() {
+    let creator = Pubkey::new_unique();
+    let verifiers = keys(3);
+    let args = CreateChallengeArgs {
+        title: String::new(),
+        description: String::new(),
+        stake_amount: 1,
+        start_time: 1,
+        end_time: 2,
+        verification_period: 1,
+        required_proofs: 1,
+        required_approval_weight: 1,
+        verifiers: verifiers.clone(),
+        verifier_weights: vec![],
+        geofences: vec![],
+        submission_rules: submission_rules(),
+        verification_rules: VerificationRules::default(),
+        min_verifier_reputation: None,
+        pay_fee_in_grass: false,
+    };
+    let built = instructions::create_challenge(&creator, 1, args.clone());
+    assert_eq!(built.accounts[11..], new_challenge(&verifiers)[..]);
+
+    // Paying in GRASS fills the optional accounts with the creator's GRASS account
+    let args = CreateChallengeArgs {
+        pay_fee_in_grass: true,
+        ..args
+    };
+    let grass_accounts = accounts::CreateChallenge {
+        grass_config: Some(pda::grass_config_pda().0),
+        grass_mint: Some(pda::grass_mint_pda().0),
+        grass_fee_account: Some(pda::grass_account(&creator)),
+        token_program: Some(token::ID),
+        ..create_challenge_accounts(&creator, 1)
+    };
+    let built = instructions::create_challenge(&creator, 1, args);
+    let mut expected = grass_accounts.to_account_metas(None);
+    expected.extend(new_challenge(&verifiers));
+    assert_eq!(built.accounts, expected);
+}
+
+#[test]
+fn retry_challenge() {
+    let creator = Pubkey::new_unique();
+    let previous = Pubkey::new_unique();
+    let verifiers = keys(2);
+    assert_built(
+        instructions::retry_challenge(&creator, &previous, None, &verifiers, 4, 7, 300),
+        accounts::RetryChallenge {
+            retry: create_challenge_accounts(&creator, 4),
+            previous_challenge: previous,
+            template: None,
+        },
+        new_challenge(&verifiers),
+        instruction::RetryChallenge {
+            challenge_id: 4,
+            stake_amount: 7,
+            start_time: 300,
+        },
+        &[creator],
+    );
+}
+
+#[test]
+fn retry_challenge_from_a_template_passes_the_template() {
+    let creator = Pubkey::new_unique();
+    let previous = Pubkey::new_unique();
+    let template = Pubkey::new_unique();
+    let verifiers = keys(1);
+    assert_built(
+        instructions::retry_challenge(&creator, &previous, Some(&template), &verifiers, 4, 7, 300),
+        accounts::RetryChallenge {
+            retry: create_challenge_accounts(&creator, 4),
+            previous_challenge: previous,
+            template: Some(template),
+        },
+        new_challenge(&verifiers),
+        instruction::RetryChallenge {
+            challenge_id: 4,
+            stake_amount: 7,
+            start_time: 300,
+        },
+        &[creator],
+    );
+}
+
+#[test]
+fn create_template() {
+    let author = Pubkey::new_unique();
+    let args = CreateTemplateArgs {
+        title: "Park run".to_string(),
+        description: "Weekly".to_string(),
+        duration: 7 * 86_400,
+        verification_period: 86_400,
+        required_proofs: 3,
+        required_approval_weight: 2,
+        geofences: vec![geofence()],
+        submission_rules: submission_rules(),
+        verification_rules: VerificationRules::default(),
+        min_verifier_reputation: None,
+    };
+    assert_built(
+        instructions::create_template(&author, 2, args.clone()),
+        accounts::CreateTemplate {
+            template: pda::template_pda(&author, 2).0,
+            author,
+            system_program: system_program::ID,
+        },
+        vec![],
+        instruction::CreateTemplate {
+            template_id: 2,
+            title: args.title,
+            description: args.description,
+            duration: args.duration,
+            verification_period: args.verification_period,
+            required_proofs: args.required_proofs,
+            required_approval_weight: args.required_approval_weight,
+            geofences: args.geofences,
+            submission_rules: args.submission_rules,
+            verification_rules: args.verification_rules,
+            min_verifier_reputation: args.min_verifier_reputation,
+        },
+        &[author],
+    );
+}
+
+#[test]
+fn curate_template() {
+    let template = Pubkey::new_unique();
+    assert_built(
+        instructions::curate_template(&ADMIN_PUBKEY, &template, true),
+        accounts::CurateTemplate {
+            template,
+            admin: ADMIN_PUBKEY,
+        },
+        vec![],
+        instruction::CurateTemplate { curated: true },
+        &[ADMIN_PUBKEY],
+    );
+}
+
+#[test]
+fn create_challenge_from_template() {
+    let creator = Pubkey::new_unique();
+    let template = Pubkey::new_unique();
+    let verifiers = keys(2);
+    assert_built(
+        instructions::create_challenge_from_template(
+            &creator,
+            &template,
+            5,
+            8,
+            400,
+            verifiers.clone(),
+            vec![],
+        ),
+        accounts::CreateChallengeFromTemplate {
+            challenge: create_challenge_accounts(&creator, 5),
+            template,
+        },
+        new_challenge(&verifiers),
+        instruction::CreateChallengeFromTemplate {
+            challenge_id: 5,
+            stake_amount: 8,
+            start_time: 400,
+            verifiers,
+            verifier_weights: vec![],
+        },
+        &[creator],
+    );
+}
+
+#[test]
+fn update_challenge_state() {
+    let challenge = Pubkey::new_unique();
+    let creator = Pubkey::new_unique();
+    let verifiers = keys(3);
+    assert_built(
+        instructions::update_challenge_state(&ADMIN_PUBKEY, &challenge, &creator, &verifiers),
+        accounts::UpdateChallengeState {
+            challenge,
+            user: pda::user_pda(&creator).0,
+            admin: ADMIN_PUBKEY,
+        },
+        inboxes(&verifiers),
+        instruction::UpdateChallengeState {},
+        &[ADMIN_PUBKEY],
+    );
+}
+
+#[test]
+fn evidence_instructions() {
+    let creator = Pubkey::new_unique();
+    let challenge = Pubkey::new_unique();
+    let location = Some(GeoPoint {
+        latitude: 1,
+        longitude: -1,
+    });
+    assert_built(
+        instructions::submit_evidence(
+            &creator,
+            &challenge,
+            3,
+            "QmHash".to_string(),
+            "{}".to_string(),
+            location,
+        ),
+        accounts::SubmitEvidence {
+            evidence: pda::evidence_pda(&challenge, 3).0,
+            challenge,
+            submitter: creator,
+            system_program: system_program::ID,
+        },
+        vec![],
+        instruction::SubmitEvidence {
+            ipfs_hash: "QmHash".to_string(),
+            metadata: "{}".to_string(),
+            location,
+        },
+        &[creator],
+    );
+    assert_built(
+        instructions::replace_evidence(&creator, &challenge, 1, "QmNew".to_string(), String::new(), None),
+        accounts::ReplaceEvidence {
+            evidence: pda::evidence_pda(&challenge, 1).0,
+            challenge,
+            submitter: creator,
+        },
+        vec![],
+        instruction::ReplaceEvidence {
+            evidence_index: 1,
+            ipfs_hash: "QmNew".to_string(),
+            metadata: String::new(),
+            location: None,
+        },
+        &[creator],
+    );
+    assert_built(
+        instructions::retract_evidence(&creator, &challenge, 2),
+        accounts::RetractEvidence {
+            evidence: pda::evidence_pda(&challenge, 2).0,
+            challenge,
+            submitter: creator,
+        },
+        vec![],
+        instruction::RetractEvidence {},
+        &[creator],
+    );
+}
+
+#[test]
+fn vote_instructions() {
+    let challenge = Pubkey::new_unique();
+    let creator = Pubkey::new_unique();
+    let verifiers = keys(3);
+    let verifier = verifiers[1];
+    let justification = Some(Justification {
+        reason: "Looks right".to_string(),
+        attachment: Some("QmShot".to_string()),
+    });
+    assert_built(
+        instructions::verify_evidence(
+            &verifier,
+            &challenge,
+            &creator,
+            &verifiers,
+            Vote::Approve,
+            justification.clone(),
+        ),
+        accounts::VerifyEvidence {
+            verification: pda::verification_pda(&challenge, &verifier).0,
+            reputation: pda::reputation_pda(&verifier).0,
+            challenge,
+            user: pda::user_pda(&creator).0,
+            verifier,
+            system_program: system_program::ID,
+        },
+        inboxes(&verifiers),
+        instruction::VerifyEvidence {
+            vote: Vote::Approve,
+            justification,
+        },
+        &[verifier],
+    );
+    assert_built(
+        instructions::update_vote(&verifier, &challenge, &creator, &verifiers, Vote::Abstain, None),
+        accounts::UpdateVote {
+            verification: pda::verification_pda(&challenge, &verifier).0,
+            challenge,
+            user: pda::user_pda(&creator).0,
+            verifier,
+        },
+        inboxes(&verifiers),
+        instruction::UpdateVote {
+            vote: Vote::Abstain,
+            justification: None,
+        },
+        &[verifier],
+    );
+}
+
+#[test]
+fn cancel_challenge() {
+    let creator = Pubkey::new_unique();
+    let challenge = Pubkey::new_unique();
+    let verifiers = keys(2);
+    assert_built(
+        instructions::cancel_challenge(&creator, &challenge, &verifiers),
+        accounts::CancelChallenge {
+            challenge,
+            escrow: pda::escrow_pda(&challenge).0,
+            user: pda::user_pda(&creator).0,
+            platform: PLATFORM_PUBKEY,
+            creator,
+            system_program: system_program::ID,
+        },
+        inboxes(&verifiers),
+        instruction::CancelChallenge {},
+        &[creator],
+    );
+}
+
+#[test]
+fn extension_instructions() {
+    let creator = Pubkey::new_unique();
+    let challenge = Pubkey::new_unique();
+    let verifiers = keys(2);
+    let extension = pda::extension_pda(&challenge).0;
+    assert_built(
+        instructions::request_extension(&creator, &challenge, 1_000),
+        accounts::RequestExtension {
+            extension,
+            challenge,
+            creator,
+            system_program: system_program::ID,
+        },
+        vec![],
+        instruction::RequestExtension { new_end_time: 1_000 },
+        &[creator],
+    );
+    assert_built(
+        instructions::approve_extension(&verifiers[0], &challenge, &creator, &verifiers),
+        accounts::ApproveExtension {
+            extension,
+            challenge,
+            creator,
+            platform: PLATFORM_PUBKEY,
+            verifier: verifiers[0],
+        },
+        inboxes(&verifiers),
+        instruction::ApproveExtension {},
+        &[verifiers[0]],
+    );
+    assert_built(
+        instructions::withdraw_extension(&creator, &challenge),
+        accounts::WithdrawExtension {
+            extension,
+            challenge,
+            creator,
+        },
+        vec![],
+        instruction::WithdrawExtension {},
+        &[creator],
+    );
+}
+
+#[test]
+fn dispute_verification_names_the_contested_record() {
+    let disputer = Pubkey::new_unique();
+    let challenge = Pubkey::new_unique();
+    let contested = Pubkey::new_unique();
+    let built = instructions::dispute_verification(
+        &disputer,
+        &challenge,
+        1,
+        "wrong park".to_string(),
+        Some(&contested),
+    );
+    assert_built(
+        built,
+        accounts::DisputeVerification {
+            dispute: pda::dispute_pda(&challenge, 1).0,
+            challenge,
+            disputer,
+            contested_verification: Some(pda::verification_pda(&challenge, &contested).0),
+            system_program: system_program::ID,
+        },
+        vec![],
+        instruction::DisputeVerification {
+            reason: "wrong park".to_string(),
+        },
+        &[disputer],
+    );
+
+    // Without one, anchor's placeholder for a missing optional account
+    let built =
+        instructions::dispute_verification(&disputer, &challenge, 0, String::new(), None);
+    assert_eq!(built.accounts[3], AccountMeta::new_readonly(ID, false));
+}
+
+#[test]
+fn resolve_dispute_passes_reputations_only_on_an_overturn() {
+    let challenge = Pubkey::new_unique();
+    let creator = Pubkey::new_unique();
+    let verifiers = keys(2);
+    let dispute = dispute(challenge, 1, &[]);
+    let accounts = || accounts::ResolveDispute {
+        challenge,
+        dispute: pda::dispute_pda(&challenge, 1).0,
+        user: pda::user_pda(&creator).0,
+        disputer: dispute.disputer,
+        platform: PLATFORM_PUBKEY,
+        admin: ADMIN_PUBKEY,
+    };
+    assert_built(
+        instructions::resolve_dispute(&ADMIN_PUBKEY, &challenge, &dispute, &creator, &verifiers, true),
+        accounts(),
+        reputations(&challenge, &verifiers),
+        instruction::ResolveDispute { overturn: true },
+        &[ADMIN_PUBKEY],
+    );
+    assert_built(
+        instructions::resolve_dispute(&ADMIN_PUBKEY, &challenge, &dispute, &creator, &verifiers, false),
+        accounts(),
+        vec![],
+        instruction::ResolveDispute { overturn: false },
+        &[ADMIN_PUBKEY],
+    );
+}
+
+#[test]
+fn juror_instructions() {
+    let authority = Pubkey::new_unique();
+    let juror = pda::juror_pda(&authority).0;
+    let juror_pool = pda::juror_pool_pda().0;
+    assert_built(
+        instructions::register_juror(&authority, 42),
+        accounts::RegisterJuror {
+            juror,
+            juror_pool,
+            authority,
+            system_program: system_program::ID,
+        },
+        vec![],
+        instruction::RegisterJuror { stake: 42 },
+        &[authority],
+    );
+    assert_built(
+        instructions::unregister_juror(&authority),
+        accounts::UnregisterJuror {
+            juror,
+            juror_pool,
+            authority,
+        },
+        vec![],
+        instruction::UnregisterJuror {},
+        &[authority],
+    );
+    assert_built(
+        instructions::withdraw_juror_stake(&authority),
+        accounts::WithdrawJurorStake { juror, authority },
+        vec![],
+        instruction::WithdrawJurorStake {},
+        &[authority],
+    );
+}
+
+#[test]
+fn jury_instructions() {
+    let challenge = Pubkey::new_unique();
+    let signer = Pubkey::new_unique();
+    let dispute_address = pda::dispute_pda(&challenge, 2).0;
+    let pool = keys(4);
+    assert_built(
+        instructions::escalate_dispute(&signer, &challenge, 2, &pool),
+        accounts::EscalateDispute {
+            dispute: dispute_address,
+            challenge,
+            juror_pool: pda::juror_pool_pda().0,
+            slot_hashes: slot_hashes::ID,
+            signer,
+        },
+        pool.iter()
+            .map(|juror| AccountMeta::new(pda::juror_pda(juror).0, false))
+            .collect(),
+        instruction::EscalateDispute {},
+        &[signer],
+    );
+    assert_built(
+        instructions::cast_jury_vote(&signer, &challenge, 2, JuryVote::Overturn),
+        accounts::CastJuryVote {
+            dispute: dispute_address,
+            juror: pda::juror_pda(&signer).0,
+            authority: signer,
+        },
+        vec![],
+        instruction::CastJuryVote {
+            vote: JuryVote::Overturn,
+        },
+        &[signer],
+    );
+}
+
+#[test]
+fn settle_jury_appends_jurors_in_seat_order_then_reputations() {
+    let challenge = Pubkey::new_unique();
+    let creator = Pubkey::new_unique();
+    let jurors = keys(3);
+    let verifiers = keys(2);
+    let dispute = dispute(challenge, 2, &jurors);
+    let mut remaining: Vec<AccountMeta> = jurors
+        .iter()
+        .map(|juror| AccountMeta::new(pda::juror_pda(juror).0, false))
+        .collect();
+    remaining.extend(reputations(&challenge, &verifiers));
+    // Permissionless: nobody signs
+    assert_built(
+        instructions::settle_jury(&challenge, &dispute, &creator, &verifiers),
+        accounts::SettleJury {
+            challenge,
+            dispute: pda::dispute_pda(&challenge, 2).0,
+            user: pda::user_pda(&creator).0,
+            disputer: dispute.disputer,
+            platform: PLATFORM_PUBKEY,
+        },
+        remaining,
+        instruction::SettleJury {},
+        &[],
+    );
+}
+
+#[test]
+fn claim_funds_passes_the_verification_only_for_verifiers() {
+    let challenge = Pubkey::new_unique();
+    let creator = Pubkey::new_unique();
+    let verifiers = keys(2);
+    let accounts = |claimer: Pubkey, verification: Option<Pubkey>| accounts::ClaimFunds {
+        challenge,
+        escrow: pda::escrow_pda(&challenge).0,
+        user: pda::user_pda(&creator).0,
+        creator,
+        platform: PLATFORM_PUBKEY,
+        claimer,
+        verification,
+        badge_mint: pda::badge_mint_pda(&challenge).0,
+        badge_account: pda::badge_account(&creator, &challenge),
+        badge_authority: pda::badge_authority_pda().0,
+        grass_config: Some(pda::grass_config_pda().0),
+        grass_mint: Some(pda::grass_mint_pda().0),
+        grass_account: Some(pda::grass_account(&claimer)),
+        system_program: system_program::ID,
+        token_program: token::ID,
+        token_2022_program: token_2022::ID,
+        associated_token_program: associated_token::ID,
+    };
+    assert_built(
+        instructions::claim_funds(&creator, &challenge, &creator, &verifiers, false, true),
+        accounts(creator, None),
+        reputations(&challenge, &verifiers),
+        instruction::ClaimFunds {},
+        &[creator],
+    );
+    let verifier = verifiers[0];
+    assert_built(
+        instructions::claim_funds(&verifier, &challenge, &creator, &verifiers, true, true),
+        accounts(verifier, Some(pda::verification_pda(&challenge, &verifier).0)),
+        reputations(&challenge, &verifiers),
+        instruction::ClaimFunds {},
+        &[verifier],
+    );
+
+    // Before GRASS is configured the GRASS accounts are left out
+    assert_built(
+        instructions::claim_funds(&creator, &challenge, &creator, &verifiers, false, false),
+        accounts::ClaimFunds {
+            grass_config: None,
+            grass_mint: None,
+            grass_account: None,
+            ..accounts(creator, None)
+        },
+        reputations(&challenge, &verifiers),
+        instruction::ClaimFunds {},
+        &[creator],
+    );
+}
+
+#[test]
+fn configure_grass() {
+    let emission = GrassEmission {
+        creator_reward: 5,
+        verifier_reward: 1,
+        grass_per_sol: 1_000,
+        fee_discount_bps: 2_500,
+    };
+    assert_built(
+        instructions::configure_grass(&ADMIN_PUBKEY, emission.clone()),
+        accounts::ConfigureGrass {
+            grass_config: pda::grass_config_pda().0,
+            grass_mint: pda::grass_mint_pda().0,
+            admin: ADMIN_PUBKEY,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        vec![],
+        instruction::ConfigureGrass { emission },
+        &[ADMIN_PUBKEY],
+    );
+}
+
+#[test]
+fn set_platform_limits() {
+    let limits = PlatformLimits {
+        max_active_challenges: 10,
+        max_stake: 1_000,
+        max_stake_at_risk: 5_000,
+        max_failure_cooldown: 3_600,
+    };
+    assert_built(
+        instructions::set_platform_limits(&ADMIN_PUBKEY, limits.clone()),
+        accounts::SetPlatformLimits {
+            platform_config: pda::platform_config_pda().0,
+            admin: ADMIN_PUBKEY,
+            system_program: system_program::ID,
+        },
+        vec![],
+        instruction::SetPlatformLimits { limits },
+        &[ADMIN_PUBKEY],
+    );
+}
@@ -0,0 +1,153 @@
+//! Every derivation in `pda` against the seeds the program checks, spelled
+//! out as the byte strings the deployed program and the TS tests use.
+
+use anchor_lang::prelude::Pubkey;
+use proof_of_touch_grass_client::{pda, ID};
+
+fn derive(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &ID)
+}
+
+/// The bump is canonical, i.e. the one `find_program_address` settles on
+fn assert_canonical((address, bump): (Pubkey, u8), seeds: &[&[u8]]) {
+    let mut with_bump = seeds.to_vec();
+    let bump = [bump];
+    with_bump.push(&bump);
+    assert_eq!(Pubkey::create_program_address(&with_bump, &ID).unwrap(), address);
+}
+
+#[test]
+fn user_accounts() {
+    let authority = Pubkey::new_unique();
+    let seeds: &[&[u8]] = &[b"user", authority.as_ref()];
+    assert_eq!(pda::user_pda(&authority), derive(seeds));
+    assert_canonical(pda::user_pda(&authority), seeds);
+
+    let seeds: &[&[u8]] = &[b"challenge_index", authority.as_ref()];
+    assert_eq!(pda::challenge_index_pda(&authority), derive(seeds));
+}
+
+#[test]
+fn challenge_ids_are_little_endian() {
+    let creator = Pubkey::new_unique();
+    let challenge_id = 0x0102_0304_0506_0708u64;
+    let id_bytes = [8, 7, 6, 5, 4, 3, 2, 1];
+    let seeds: &[&[u8]] = &[b"challenge", creator.as_ref(), &id_bytes];
+    assert_eq!(pda::challenge_pda(&creator, challenge_id), derive(seeds));
+    assert_canonical(pda::challenge_pda(&creator, challenge_id), seeds);
+    assert_ne!(pda::challenge_pda(&creator, 1), pda::challenge_pda(&creator, 2));
+}
+
+#[test]
+fn challenge_accounts() {
+    let challenge = Pubkey::new_unique();
+    let verifier = Pubkey::new_unique();
+    assert_eq!(pda::escrow_pda(&challenge), derive(&[b"escrow", challenge.as_ref()]));
+    assert_eq!(pda::extension_pda(&challenge), derive(&[b"extension", challenge.as_ref()]));
+    assert_eq!(
+        pda::verification_pda(&challenge, &verifier),
+        derive(&[b"verification", challenge.as_ref(), verifier.as_ref()])
+    );
+}
+
+#[test]
+fn indexed_accounts_use_a_single_byte() {
+    let challenge = Pubkey::new_unique();
+    for index in [0u8, 1, 255] {
+        assert_eq!(
+            pda::evidence_pda(&challenge, index),
+            derive(&[b"evidence", challenge.as_ref(), &[index]])
+        );
+        assert_eq!(
+            pda::dispute_pda(&challenge, index),
+            derive(&[b"dispute", challenge.as_ref(), &[index]])
+        );
+    }
+}
+
+#[test]
+fn verifier_accounts() {
+    let verifier = Pubkey::new_unique();
+    assert_eq!(pda::reputation_pda(&verifier), derive(&[b"reputation", verifier.as_ref()]));
+    assert_eq!(pda::inbox_pda(&verifier), derive(&[b"inbox", verifier.as_ref()]));
+}
+
+#[test]
+fn template_accounts() {
+    let author = Pubkey::new_unique();
+    assert_eq!(
+        pda::template_pda(&author, 7),
+        derive(&[b"template", author.as_ref(), &7u64.to_le_bytes()])
+    );
+}
+
+#[test]
+fn juror_accounts() {
+    let authority = Pubkey::new_unique();
+    assert_eq!(pda::juror_pda(&authority), derive(&[b"juror", authority.as_ref()]));
+    assert_eq!(pda::juror_pool_pda(), derive(&[b"juror_pool"]));
+    assert_canonical(pda::juror_pool_pda(), &[b"juror_pool"]);
+}
+
+#[test]
+fn badge_accounts() {
+    let challenge = Pubkey::new_unique();
+    let creator = Pubkey::new_unique();
+    let mint = pda::badge_mint_pda(&challenge);
+    assert_eq!(mint, derive(&[b"badge", challenge.as_ref()]));
+    assert_eq!(pda::badge_authority_pda(), derive(&[b"badge_authority"]));
+    assert_canonical(pda::badge_authority_pda(), &[b"badge_authority"]);
+
+    // The creator's associated token account under Token-2022, not SPL Token
+    let token_2022 = anchor_spl::token_2022::ID;
+    let (account, _) = Pubkey::find_program_address(
+        &[creator.as_ref(), token_2022.as_ref(), mint.0.as_ref()],
+        &anchor_spl::associated_token::ID,
+    );
+    assert_eq!(pda::badge_account(&creator, &challenge), account);
+}
+
+#[test]
+fn platform_config() {
+    assert_eq!(pda::platform_config_pda(), derive(&[b"platform_config"]));
+    assert_canonical(pda::platform_config_pda(), &[b"platform_config"]);
+}
+
+#[test]
+fn grass_accounts() {
+    let owner = Pubkey::new_unique();
+    assert_eq!(pda::grass_config_pda(), derive(&[b"grass_config"]));
+    assert_canonical(pda::grass_config_pda(), &[b"grass_config"]);
+    let mint = pda::grass_mint_pda();
+    assert_eq!(mint, derive(&[b"grass_mint"]));
+    assert_canonical(mint, &[b"grass_mint"]);
+
+    // The owner's associated token account under SPL Token, not Token-2022
+    let token = anchor_spl::token::ID;
+    let (account, _) = Pubkey::find_program_address(
+        &[owner.as_ref(), token.as_ref(), mint.0.as_ref()],
+        &anchor_spl::associated_token::ID,
+    );
+    assert_eq!(pda::grass_account(&owner), account);
+}
+
+#[test]
+fn derivations_do_not_collide() {
+    // The same key under different seeds lands on different accounts
+    let key = Pubkey::new_unique();
+    let addresses = [
+        pda::user_pda(&key).0,
+        pda::challenge_index_pda(&key).0,
+        pda::escrow_pda(&key).0,
+        pda::extension_pda(&key).0,
+        pda::reputation_pda(&key).0,
+        pda::inbox_pda(&key).0,
+        pda::juror_pda(&key).0,
+        pda::badge_mint_pda(&key).0,
+    ];
+    for (i, a) in addresses.iter().enumerate() {
+        for b in &addresses[i + 1..] {
+            assert_ne!(a, b);
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use std::{marker::PhantomData, mem::ManuallyDrop};
+
+use solana_instruction::Instruction;
+use solana_stable_layout::{stable_instruction::StableInstruction, stable_vec::StableVec};
+
+/// Similarly to [`StableInstruction`], this type represents an instruction with a stable (`repr(C)` memory layout).
+/// Unlike `StableInstruction`, it does not semantically own the buffers inside the instruction, and they will not be dropped
+/// when the type is.
+pub(crate) struct StableInstructionBorrowed<'ix> {
+    /// A [`StableInstruction`] is constructed from a shared reference to an [`Instruction`] to ensure a valid memory layout.
+    /// [`ManuallyDrop`] is used to ensure the borrowed data is not dropped when the type is.
+    stabilized_instruction: ManuallyDrop<StableInstruction>,
+    /// We don't actually need access to the original instruction, but we do need to ensure it is borrowed for as long as this
+    /// type is accessible to ensure it is not moved/invalidated.
+    _marker: PhantomData<&'ix Instruction>,
+}
+
+impl<'ix> StableInstructionBorrowed<'ix> {
+    #[inline(always)]
+    pub(crate) fn new(ix: &'ix Instruction) -> Self {
+        let data = StableVecBorrowed::from(&ix.data);
+        let accounts = StableVecBorrowed::from(&ix.accounts);
+        // SAFETY:
+        // We transmute between two `repr(C)` types with the same layout (and verify this) assumption
+        // in `test_layout_matches`
+        // We then immediately move our constructed `StableInstruction` into `ManuallyDrop` to prevent it
+        // being dropped and freeing data we don't own.
+        let fake_stable_ix = unsafe {
+            ManuallyDrop::new(StableInstruction {
+                accounts: core::mem::transmute::<StableVecBorrowed<_>, StableVec<_>>(accounts),
+                data: core::mem::transmute::<StableVecBorrowed<_>, StableVec<_>>(data),
+                program_id: ix.program_id,
+            })
+        };
+
+        Self {
+            stabilized_instruction: fake_stable_ix,
+            _marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn instruction_addr(&self) -> *const u8 {
+        &self.stabilized_instruction as *const ManuallyDrop<StableInstruction> as *const u8
+    }
+}
+
+/// Similarly to [`StableVec`] this type represents a vector with a stable (`repr(C)` memory layout).
+/// However, unlike `StableVec` it does not own its contents, instead borrowing the data immutably.
+#[repr(C)]
+struct StableVecBorrowed<'vec, T> {
+    addr: u64,
+    cap: u64,
+    len: u64,
+    _marker: PhantomData<&'vec T>,
+}
+
+impl<'a, T> From<&'a Vec<T>> for StableVecBorrowed<'a, T> {
+    fn from(value: &'a Vec<T>) -> Self {
+        Self {
+            addr: value.as_ptr() as u64,
+            cap: value.capacity() as u64,
+            len: value.len() as u64,
+            _marker: PhantomData,
+        }
+    }
+}
+
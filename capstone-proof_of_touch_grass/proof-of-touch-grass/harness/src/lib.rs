@@ -0,0 +1,21 @@
+//! In-process test runtime for the proof-of-touch-grass program.
+//!
+//! Neither LiteSVM nor Mollusk can be used here (no SBF toolchain to build the
+//! program, and neither crate is vendored), so the program runs natively the
+//! way `solana-program-test`'s native processor does: [`Svm`] lays accounts
+//! out in the loader's input format, calls the anchor entrypoint and emulates
+//! the system program CPIs through syscall stubs. The clock is a plain value
+//! tests warp freely; every warp starts a new slot with a fresh SlotHashes
+//! entry.
+
+mod runtime;
+mod syscalls;
+mod system;
+mod token;
+
+pub use runtime::{Account, ExecutionError, Svm, GENESIS_TIMESTAMP};
+
+/// The failure a program returning `error` produces
+pub fn program_error(error: impl Into<anchor_lang::error::Error>) -> ExecutionError {
+    ExecutionError::Program(error.into().into())
+}
@@ -0,0 +1,385 @@
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::solana_program::entrypoint::{self, MAX_PERMITTED_DATA_INCREASE};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::sysvar::{self, slot_hashes};
+use anchor_lang::system_program;
+use proof_of_touch_grass_client::accounts::AccountFetcher;
+
+use crate::syscalls::{self, Invocation};
+use crate::token;
+
+const NATIVE_LOADER_ID: Pubkey = pubkey!("NativeLoader1111111111111111111111111111111");
+const NON_DUP_MARKER: u8 = u8::MAX;
+const BPF_ALIGN_OF_U128: usize = 8;
+
+/// Unix timestamp a fresh [`Svm`] starts at
+pub const GENESIS_TIMESTAMP: i64 = 1_750_000_000;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Account {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Pubkey,
+    pub executable: bool,
+}
+
+impl Account {
+    fn system(lamports: u64) -> Self {
+        Self {
+            lamports,
+            data: Vec::new(),
+            owner: system_program::ID,
+            executable: false,
+        }
+    }
+
+    fn program(loader: Pubkey) -> Self {
+        Self {
+            lamports: 1,
+            data: Vec::new(),
+            owner: loader,
+            executable: true,
+        }
+    }
+
+    /// SlotHashes holding a single entry for the slot before `slot`, with a
+    /// hash derived from the slot number
+    fn slot_hashes(slot: u64) -> Self {
+        let parent = slot.saturating_sub(1);
+        let mut data = Vec::with_capacity(48);
+        data.extend_from_slice(&1u64.to_le_bytes());
+        data.extend_from_slice(&parent.to_le_bytes());
+        for word in 0..4 {
+            data.extend_from_slice(&splitmix64(parent * 4 + word).to_le_bytes());
+        }
+        Self {
+            lamports: 1,
+            data,
+            owner: sysvar::ID,
+            executable: false,
+        }
+    }
+}
+
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum ExecutionError {
+    #[error("program error: {0}")]
+    Program(ProgramError),
+    #[error("program panicked: {0}")]
+    Panic(String),
+    #[error("account {0} was left with lamports below the rent-exempt minimum")]
+    InsufficientFundsForRent(Pubkey),
+    #[error("instruction changed the total lamport supply")]
+    UnbalancedInstruction,
+    #[error("read-only account {0} was modified")]
+    ReadonlyAccountModified(Pubkey),
+}
+
+impl From<ProgramError> for ExecutionError {
+    fn from(error: ProgramError) -> Self {
+        Self::Program(error)
+    }
+}
+
+/// In-process runtime for the proof-of-touch-grass program.
+///
+/// Instructions run natively against an account map, one at a time and
+/// atomically: on failure no account changes. Signatures are not checked,
+/// an account signs whenever its meta says so. After execution the runtime
+/// rules that matter for this program are enforced: lamports are conserved,
+/// read-only accounts are unchanged and no account is left rent-paying.
+/// Cloning forks the state, e.g. to simulate an instruction.
+#[derive(Clone)]
+pub struct Svm {
+    accounts: HashMap<Pubkey, Account>,
+    clock: Clock,
+    rent: Rent,
+}
+
+impl Default for Svm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Svm {
+    pub fn new() -> Self {
+        let mut accounts = HashMap::new();
+        accounts.insert(
+            proof_of_touch_grass::ID,
+            Account::program(bpf_loader_upgradeable::ID),
+        );
+        for program_id in token::PROGRAMS {
+            accounts.insert(program_id, Account::program(bpf_loader_upgradeable::ID));
+        }
+        accounts.insert(system_program::ID, Account::program(NATIVE_LOADER_ID));
+        accounts.insert(slot_hashes::ID, Account::slot_hashes(1));
+
+        Self {
+            accounts,
+            clock: Clock {
+                slot: 1,
+                epoch_start_timestamp: GENESIS_TIMESTAMP,
+                epoch: 0,
+                leader_schedule_epoch: 1,
+                unix_timestamp: GENESIS_TIMESTAMP,
+            },
+            rent: Rent::default(),
+        }
+    }
+
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    pub fn now(&self) -> i64 {
+        self.clock.unix_timestamp
+    }
+
+    /// Moves the clock to `unix_timestamp`, which may be in the past, and
+    /// starts a new slot
+    pub fn warp_to(&mut self, unix_timestamp: i64) {
+        self.clock.unix_timestamp = unix_timestamp;
+        self.clock.slot += 1;
+        self.accounts
+            .insert(slot_hashes::ID, Account::slot_hashes(self.clock.slot));
+    }
+
+    pub fn advance(&mut self, seconds: i64) {
+        self.warp_to(self.clock.unix_timestamp + seconds);
+    }
+
+    pub fn minimum_balance(&self, data_len: usize) -> u64 {
+        self.rent.minimum_balance(data_len)
+    }
+
+    pub fn airdrop(&mut self, address: &Pubkey, lamports: u64) {
+        self.accounts
+            .entry(*address)
+            .or_insert_with(|| Account::system(0))
+            .lamports += lamports;
+    }
+
+    pub fn account(&self, address: &Pubkey) -> Option<&Account> {
+        self.accounts.get(address)
+    }
+
+    /// Every account, in no particular order
+    pub fn accounts(&self) -> impl Iterator<Item = (&Pubkey, &Account)> {
+        self.accounts.iter()
+    }
+
+    pub fn set_account(&mut self, address: &Pubkey, account: Account) {
+        self.accounts.insert(*address, account);
+    }
+
+    pub fn lamports(&self, address: &Pubkey) -> u64 {
+        self.account(address).map_or(0, |account| account.lamports)
+    }
+
+    /// Executes `instruction`, committing its account changes only on success.
+    /// Top-level instructions can target the program or a token program.
+    pub fn process(
+        &mut self,
+        instruction: &Instruction,
+    ) -> std::result::Result<(), ExecutionError> {
+        let target = instruction.program_id;
+        if target != proof_of_touch_grass::ID && !token::PROGRAMS.contains(&target) {
+            return Err(ExecutionError::Program(ProgramError::IncorrectProgramId));
+        }
+        let frame = Frame::new(instruction, &self.accounts);
+        let mut buffer = frame.serialize(&instruction.data, &instruction.program_id);
+
+        let invocation = Invocation::new(target, self.clock.clone(), self.rent.clone());
+        let post = syscalls::with_invocation(invocation, || {
+            // SAFETY: `buffer` was laid out by `Frame::serialize` in the loader's
+            // aligned input format and outlives every `AccountInfo` borrowing it
+            let (program_id, infos, data) =
+                unsafe { entrypoint::deserialize(buffer.as_mut_ptr() as *mut u8) };
+            let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+                if *program_id == proof_of_touch_grass::ID {
+                    proof_of_touch_grass::entry(program_id, &infos, data)
+                } else {
+                    token::process(program_id, &infos, data).map(drop)
+                }
+            }));
+            match outcome {
+                Ok(Ok(())) => Ok(frame.collect(&infos)),
+                Ok(Err(error)) => Err(ExecutionError::Program(error)),
+                Err(payload) => Err(ExecutionError::Panic(panic_message(payload))),
+            }
+        })?;
+
+        frame.verify(&post, &self.rent)?;
+        for (key, account) in frame.keys.iter().zip(post) {
+            if account.lamports == 0 {
+                self.accounts.remove(key);
+            } else {
+                self.accounts.insert(*key, account);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl AccountFetcher for Svm {
+    type Error = std::convert::Infallible;
+
+    fn fetch_account_data(
+        &self,
+        address: &Pubkey,
+    ) -> std::result::Result<Option<Vec<u8>>, Self::Error> {
+        Ok(self.account(address).map(|account| account.data.clone()))
+    }
+}
+
+/// The deduplicated accounts of one instruction and their state before it ran
+struct Frame {
+    keys: Vec<Pubkey>,
+    is_signer: Vec<bool>,
+    is_writable: Vec<bool>,
+    pre: Vec<Account>,
+    /// Position of each instruction account in `keys`
+    positions: Vec<usize>,
+}
+
+impl Frame {
+    fn new(instruction: &Instruction, accounts: &HashMap<Pubkey, Account>) -> Self {
+        let mut frame = Self {
+            keys: Vec::new(),
+            is_signer: Vec::new(),
+            is_writable: Vec::new(),
+            pre: Vec::new(),
+            positions: Vec::new(),
+        };
+        for meta in &instruction.accounts {
+            let position = match frame.keys.iter().position(|key| *key == meta.pubkey) {
+                Some(position) => {
+                    frame.is_signer[position] |= meta.is_signer;
+                    frame.is_writable[position] |= meta.is_writable;
+                    position
+                }
+                None => {
+                    frame.keys.push(meta.pubkey);
+                    frame.is_signer.push(meta.is_signer);
+                    frame.is_writable.push(meta.is_writable);
+                    frame.pre.push(
+                        accounts
+                            .get(&meta.pubkey)
+                            .cloned()
+                            .unwrap_or_else(|| Account::system(0)),
+                    );
+                    frame.keys.len() - 1
+                }
+            };
+            frame.positions.push(position);
+        }
+        frame
+    }
+
+    /// Lays the accounts out like the BPF loader's aligned input region
+    fn serialize(&self, data: &[u8], program_id: &Pubkey) -> Vec<u64> {
+        let mut input = Vec::new();
+        input.extend_from_slice(&(self.positions.len() as u64).to_le_bytes());
+
+        let mut serialized = vec![false; self.keys.len()];
+        for &position in &self.positions {
+            if serialized[position] {
+                let first = self.positions.iter().position(|&p| p == position).unwrap();
+                input.push(first as u8);
+                input.extend_from_slice(&[0; 7]);
+                continue;
+            }
+            serialized[position] = true;
+
+            let account = &self.pre[position];
+            input.push(NON_DUP_MARKER);
+            input.push(self.is_signer[position] as u8);
+            input.push(self.is_writable[position] as u8);
+            input.push(account.executable as u8);
+            input.extend_from_slice(&[0; 4]);
+            input.extend_from_slice(self.keys[position].as_ref());
+            input.extend_from_slice(account.owner.as_ref());
+            input.extend_from_slice(&account.lamports.to_le_bytes());
+            input.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+            input.extend_from_slice(&account.data);
+            input.resize(input.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+            input.resize(input.len().next_multiple_of(BPF_ALIGN_OF_U128), 0);
+            input.extend_from_slice(&u64::MAX.to_le_bytes());
+        }
+
+        input.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        input.extend_from_slice(data);
+        input.extend_from_slice(program_id.as_ref());
+
+        // Backed by u64s so the region starts 8-byte aligned
+        let mut buffer = vec![0u64; input.len().div_ceil(8)];
+        for (word, chunk) in buffer.iter_mut().zip(input.chunks(8)) {
+            let mut bytes = [0; 8];
+            bytes[..chunk.len()].copy_from_slice(chunk);
+            *word = u64::from_le_bytes(bytes);
+        }
+        buffer
+    }
+
+    fn collect(&self, infos: &[AccountInfo]) -> Vec<Account> {
+        (0..self.keys.len())
+            .map(|position| {
+                let index = self.positions.iter().position(|&p| p == position).unwrap();
+                let info = &infos[index];
+                Account {
+                    lamports: info.lamports(),
+                    data: info.data.borrow().to_vec(),
+                    owner: *info.owner,
+                    executable: info.executable,
+                }
+            })
+            .collect()
+    }
+
+    fn verify(&self, post: &[Account], rent: &Rent) -> std::result::Result<(), ExecutionError> {
+        let total = |accounts: &[Account]| -> u128 {
+            accounts
+                .iter()
+                .map(|account| account.lamports as u128)
+                .sum()
+        };
+        if total(&self.pre) != total(post) {
+            return Err(ExecutionError::UnbalancedInstruction);
+        }
+
+        for (position, (pre, post)) in self.pre.iter().zip(post).enumerate() {
+            let key = self.keys[position];
+            if !self.is_writable[position] && pre != post {
+                return Err(ExecutionError::ReadonlyAccountModified(key));
+            }
+            let rent_paying = |account: &Account| {
+                account.lamports > 0 && account.lamports < rent.minimum_balance(account.data.len())
+            };
+            if pre != post && rent_paying(post) {
+                return Err(ExecutionError::InsufficientFundsForRent(key));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_default()
+}
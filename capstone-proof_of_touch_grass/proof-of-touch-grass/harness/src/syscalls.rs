@@ -0,0 +1,167 @@
+//! Syscall stubs for running the program natively.
+//!
+//! `solana_sysvar::program_stubs` is process-global, so the stubs read the
+//! clock, rent, call stack and return data from a thread-local set by
+//! [`crate::Svm::process`]. Each test thread drives its own `Svm`.
+
+use std::cell::RefCell;
+use std::sync::Once;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::system_program;
+use solana_sysvar::program_stubs::{self, SyscallStubs};
+
+use crate::{system, token};
+
+/// Sysvars, call stack and return data of the instruction currently
+/// executing on this thread
+pub(crate) struct Invocation {
+    /// Programs on the call stack, the executing one last
+    stack: Vec<Pubkey>,
+    clock: Clock,
+    rent: Rent,
+    return_data: Option<(Pubkey, Vec<u8>)>,
+}
+
+impl Invocation {
+    pub fn new(program_id: Pubkey, clock: Clock, rent: Rent) -> Self {
+        Self {
+            stack: vec![program_id],
+            clock,
+            rent,
+            return_data: None,
+        }
+    }
+}
+
+thread_local! {
+    static INVOCATION: RefCell<Option<Invocation>> = const { RefCell::new(None) };
+}
+
+const SUCCESS: u64 = 0;
+const UNSUPPORTED_SYSVAR: u64 = 2 << 32;
+
+/// Runs `f` with `invocation` visible to the syscall stubs
+pub(crate) fn with_invocation<R>(invocation: Invocation, f: impl FnOnce() -> R) -> R {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        program_stubs::set_syscall_stubs(Box::new(HarnessStubs));
+    });
+
+    INVOCATION.with(|current| *current.borrow_mut() = Some(invocation));
+    let result = f();
+    INVOCATION.with(|current| *current.borrow_mut() = None);
+    result
+}
+
+fn current<R>(f: impl FnOnce(&Invocation) -> R) -> Option<R> {
+    INVOCATION.with(|current| current.borrow().as_ref().map(f))
+}
+
+fn current_mut<R>(f: impl FnOnce(&mut Invocation) -> R) -> Option<R> {
+    INVOCATION.with(|current| current.borrow_mut().as_mut().map(f))
+}
+
+fn set_return_data(program_id: Pubkey, data: Vec<u8>) {
+    current_mut(|invocation| {
+        invocation.return_data = (!data.is_empty()).then_some((program_id, data));
+    });
+}
+
+struct HarnessStubs;
+
+impl SyscallStubs for HarnessStubs {
+    // Program logs go through `msg!`, which prints directly on host builds
+    fn sol_log(&self, _message: &str) {}
+
+    fn sol_log_data(&self, _fields: &[&[u8]]) {}
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        match current(|invocation| invocation.clock.clone()) {
+            Some(clock) => {
+                unsafe { std::ptr::write(var_addr as *mut Clock, clock) };
+                SUCCESS
+            }
+            None => UNSUPPORTED_SYSVAR,
+        }
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        match current(|invocation| invocation.rent.clone()) {
+            Some(rent) => {
+                unsafe { std::ptr::write(var_addr as *mut Rent, rent) };
+                SUCCESS
+            }
+            None => UNSUPPORTED_SYSVAR,
+        }
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        current(|invocation| invocation.return_data.clone()).flatten()
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        if let Some(program_id) = current(|invocation| *invocation.stack.last().unwrap()) {
+            set_return_data(program_id, data.to_vec());
+        }
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> std::result::Result<(), ProgramError> {
+        let caller = current(|invocation| *invocation.stack.last().unwrap())
+            .ok_or(ProgramError::UnsupportedSysvar)?;
+        let callee = instruction.program_id;
+        if callee != system_program::ID && !token::PROGRAMS.contains(&callee) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let pda_signers = signers_seeds
+            .iter()
+            .map(|seeds| Pubkey::create_program_address(seeds, &caller))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|_| ProgramError::InvalidSeeds)?;
+
+        // The callee only gets privileges the caller holds or derives
+        let mut accounts = Vec::with_capacity(instruction.accounts.len());
+        for meta in &instruction.accounts {
+            let info = account_infos
+                .iter()
+                .find(|info| *info.key == meta.pubkey)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            if meta.is_signer && !info.is_signer && !pda_signers.contains(info.key) {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if meta.is_writable && !info.is_writable {
+                return Err(ProgramError::InvalidArgument);
+            }
+            let mut info = info.clone();
+            info.is_signer = meta.is_signer;
+            info.is_writable = meta.is_writable;
+            accounts.push(info);
+        }
+
+        set_return_data(callee, Vec::new());
+        current_mut(|invocation| invocation.stack.push(callee));
+        let result = if callee == system_program::ID {
+            let accounts: Vec<_> = accounts
+                .iter()
+                .map(|info| system::Account {
+                    info,
+                    is_signer: info.is_signer,
+                })
+                .collect();
+            system::process(&instruction.data, &accounts)
+        } else {
+            token::process(&callee, &accounts, &instruction.data)
+                .map(|data| set_return_data(callee, data))
+        };
+        current_mut(|invocation| invocation.stack.pop());
+        result
+    }
+}
@@ -0,0 +1,141 @@
+//! The subset of the system program the proof-of-touch-grass program invokes:
+//! `CreateAccount`, `Assign`, `Transfer` and `Allocate` (anchor's `init` and
+//! `system_program::transfer`).
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::system_program;
+
+// `SystemError` codes, as returned by the real system program
+const ACCOUNT_ALREADY_IN_USE: u32 = 0;
+const RESULT_WITH_NEGATIVE_LAMPORTS: u32 = 1;
+const INVALID_ACCOUNT_DATA_LENGTH: u32 = 3;
+
+const MAX_PERMITTED_DATA_LENGTH: u64 = 10 * 1024 * 1024;
+
+const CREATE_ACCOUNT: u32 = 0;
+const ASSIGN: u32 = 1;
+const TRANSFER: u32 = 2;
+const ALLOCATE: u32 = 8;
+
+pub(crate) struct Account<'a, 'info> {
+    pub info: &'a AccountInfo<'info>,
+    pub is_signer: bool,
+}
+
+type ProgramResult = std::result::Result<(), ProgramError>;
+
+pub(crate) fn process(data: &[u8], accounts: &[Account]) -> ProgramResult {
+    let mut input = Input(data);
+    match input.u32()? {
+        CREATE_ACCOUNT => {
+            let lamports = input.u64()?;
+            let space = input.u64()?;
+            let owner = input.pubkey()?;
+            let (from, to) = (account(accounts, 0)?, account(accounts, 1)?);
+            if to.info.lamports() > 0 {
+                return Err(ProgramError::Custom(ACCOUNT_ALREADY_IN_USE));
+            }
+            allocate(to, space)?;
+            assign(to, &owner)?;
+            transfer(from, to, lamports)
+        }
+        ASSIGN => {
+            let owner = input.pubkey()?;
+            assign(account(accounts, 0)?, &owner)
+        }
+        TRANSFER => {
+            let lamports = input.u64()?;
+            transfer(account(accounts, 0)?, account(accounts, 1)?, lamports)
+        }
+        ALLOCATE => {
+            let space = input.u64()?;
+            allocate(account(accounts, 0)?, space)
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+fn account<'a, 'b, 'info>(
+    accounts: &'b [Account<'a, 'info>],
+    index: usize,
+) -> std::result::Result<&'b Account<'a, 'info>, ProgramError> {
+    accounts
+        .get(index)
+        .ok_or(ProgramError::NotEnoughAccountKeys)
+}
+
+fn transfer(from: &Account, to: &Account, lamports: u64) -> ProgramResult {
+    if !from.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if !from.info.data_is_empty() || *from.info.owner != system_program::ID {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let remaining = from
+        .info
+        .lamports()
+        .checked_sub(lamports)
+        .ok_or(ProgramError::Custom(RESULT_WITH_NEGATIVE_LAMPORTS))?;
+    let credited = to
+        .info
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **from.info.try_borrow_mut_lamports()? = remaining;
+    **to.info.try_borrow_mut_lamports()? = credited;
+    Ok(())
+}
+
+fn allocate(account: &Account, space: u64) -> ProgramResult {
+    if !account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if !account.info.data_is_empty() || *account.info.owner != system_program::ID {
+        return Err(ProgramError::Custom(ACCOUNT_ALREADY_IN_USE));
+    }
+    if space > MAX_PERMITTED_DATA_LENGTH {
+        return Err(ProgramError::Custom(INVALID_ACCOUNT_DATA_LENGTH));
+    }
+    account.info.resize(space as usize)
+}
+
+fn assign(account: &Account, owner: &Pubkey) -> ProgramResult {
+    if account.info.owner == owner {
+        return Ok(());
+    }
+    if !account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if *account.info.owner != system_program::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    account.info.assign(owner);
+    Ok(())
+}
+
+/// Little-endian reader over bincode-encoded `SystemInstruction` data
+struct Input<'a>(&'a [u8]);
+
+impl Input<'_> {
+    fn take<const N: usize>(&mut self) -> std::result::Result<[u8; N], ProgramError> {
+        let (head, rest) = self
+            .0
+            .split_first_chunk::<N>()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        self.0 = rest;
+        Ok(*head)
+    }
+
+    fn u32(&mut self) -> std::result::Result<u32, ProgramError> {
+        self.take().map(u32::from_le_bytes)
+    }
+
+    fn u64(&mut self) -> std::result::Result<u64, ProgramError> {
+        self.take().map(u64::from_le_bytes)
+    }
+
+    fn pubkey(&mut self) -> std::result::Result<Pubkey, ProgramError> {
+        self.take().map(Pubkey::new_from_array)
+    }
+}
@@ -0,0 +1,64 @@
+//! The SPL Token, Token-2022 and associated token account programs, run
+//! through their own processors.
+//!
+//! The token programs set return data through `solana_cpi`, which does
+//! nothing off-chain, so the one instruction whose return data callers read,
+//! `GetAccountDataSize`, is answered here.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::associated_token::{self, spl_associated_token_account};
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
+use spl_token_2022::instruction::TokenInstruction;
+use spl_token_2022::state::{Account, Mint};
+
+/// Programs [`process`] can run
+pub(crate) const PROGRAMS: [Pubkey; 3] = [spl_token::ID, spl_token_2022::ID, associated_token::ID];
+
+/// Runs one instruction of a token program, returning its return data
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> std::result::Result<Vec<u8>, ProgramError> {
+    if *program_id == associated_token::ID {
+        spl_associated_token_account::processor::process_instruction(program_id, accounts, data)?;
+        return Ok(Vec::new());
+    }
+    if *program_id == spl_token_2022::ID {
+        spl_token_2022::processor::Processor::process(program_id, accounts, data)?;
+    } else if *program_id == spl_token::ID {
+        spl_token::processor::Processor::process(program_id, accounts, data)?;
+    } else {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    match TokenInstruction::unpack(data) {
+        Ok(TokenInstruction::GetAccountDataSize { extension_types }) => {
+            let mint = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+            let len = account_data_size(program_id, mint, &extension_types)?;
+            Ok(len.to_le_bytes().to_vec())
+        }
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Size of a token account for `mint` holding `extension_types` on top of
+/// the ones the mint requires
+fn account_data_size(
+    program_id: &Pubkey,
+    mint: &AccountInfo,
+    extension_types: &[ExtensionType],
+) -> std::result::Result<usize, ProgramError> {
+    if *program_id == spl_token::ID {
+        return Ok(spl_token::state::Account::LEN);
+    }
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<Mint>::unpack(&data)?;
+    let mut types = ExtensionType::get_required_init_account_extensions(&mint.get_extension_types()?);
+    types.extend_from_slice(extension_types);
+    ExtensionType::try_calculate_account_len::<Account>(&types)
+}
@@ -0,0 +1,151 @@
+//! Completion badges: the Token-2022 mint a completed claim creates, its
+//! metadata, and that it is minted once and cannot leave the creator.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account;
+use anchor_spl::token_2022::spl_token_2022::error::TokenError;
+use anchor_spl::token_2022::spl_token_2022::extension::metadata_pointer::MetadataPointer;
+use anchor_spl::token_2022::spl_token_2022::extension::non_transferable::NonTransferable;
+use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use anchor_spl::token_2022::spl_token_2022::instruction::transfer_checked;
+use anchor_spl::token_2022::spl_token_2022::state::{Account, Mint};
+use anchor_spl::token_2022::{self, spl_token_2022};
+use anchor_spl::token_2022_extensions::spl_token_metadata_interface::state::TokenMetadata;
+use common::{Fixture, DAY};
+use proof_of_touch_grass::constants::{
+    BADGE_COMPLETED_AT_FIELD, BADGE_DURATION_FIELD, BADGE_SYMBOL,
+};
+use proof_of_touch_grass::errors::ErrorCode;
+use proof_of_touch_grass_client::pda;
+use proof_of_touch_grass_harness::{program_error, ExecutionError};
+
+/// A completed challenge whose creator has claimed
+fn claimed(fixture: &mut Fixture) -> Pubkey {
+    let challenge = fixture.completed();
+    fixture.close_dispute_window(&challenge);
+    let creator = fixture.creator;
+    fixture.claim(&creator, &challenge, false).unwrap();
+    challenge
+}
+
+fn token_data(fixture: &Fixture, address: &Pubkey) -> Vec<u8> {
+    let account = fixture.svm.account(address).unwrap();
+    assert_eq!(account.owner, token_2022::ID);
+    account.data.clone()
+}
+
+#[test]
+fn a_completed_claim_mints_the_badge_to_the_creator() {
+    let mut fixture = Fixture::new();
+    let challenge = claimed(&mut fixture);
+    let (mint, _) = pda::badge_mint_pda(&challenge);
+    let authority = pda::badge_authority_pda().0;
+
+    let data = token_data(&fixture, &mint);
+    let state = StateWithExtensions::<Mint>::unpack(&data).unwrap();
+    assert_eq!(state.base.supply, 1);
+    assert_eq!(state.base.decimals, 0);
+    assert_eq!(Option::<Pubkey>::from(state.base.mint_authority), Some(authority));
+    assert!(state.get_extension::<NonTransferable>().is_ok());
+
+    let data = token_data(&fixture, &pda::badge_account(&fixture.creator, &challenge));
+    let account = StateWithExtensions::<Account>::unpack(&data).unwrap();
+    assert_eq!(account.base.mint, mint);
+    assert_eq!(account.base.owner, fixture.creator);
+    assert_eq!(account.base.amount, 1);
+}
+
+#[test]
+fn the_badge_carries_the_title_duration_and_completion_date() {
+    let mut fixture = Fixture::new();
+    let challenge = claimed(&mut fixture);
+    let (mint, _) = pda::badge_mint_pda(&challenge);
+    let completed = fixture.challenge(&challenge);
+
+    let data = token_data(&fixture, &mint);
+    let state = StateWithExtensions::<Mint>::unpack(&data).unwrap();
+    let pointer = state.get_extension::<MetadataPointer>().unwrap();
+    assert_eq!(Option::<Pubkey>::from(pointer.metadata_address), Some(mint));
+
+    let metadata = state.get_variable_len_extension::<TokenMetadata>().unwrap();
+    assert_eq!(metadata.mint, mint);
+    assert_eq!(
+        Option::<Pubkey>::from(metadata.update_authority),
+        Some(pda::badge_authority_pda().0)
+    );
+    assert_eq!(metadata.name, completed.title);
+    assert_eq!(metadata.symbol, BADGE_SYMBOL);
+    assert_eq!(
+        metadata.additional_metadata,
+        vec![
+            (BADGE_DURATION_FIELD.to_string(), (7 * DAY).to_string()),
+            (BADGE_COMPLETED_AT_FIELD.to_string(), completed.finalized_at.to_string()),
+        ]
+    );
+}
+
+#[test]
+fn the_badge_is_minted_once() {
+    let mut fixture = Fixture::new();
+    let challenge = claimed(&mut fixture);
+    let creator = fixture.creator;
+    assert_eq!(
+        fixture.claim(&creator, &challenge, false),
+        Err(program_error(ErrorCode::AlreadyClaimed))
+    );
+
+    let data = token_data(&fixture, &pda::badge_mint_pda(&challenge).0);
+    assert_eq!(StateWithExtensions::<Mint>::unpack(&data).unwrap().base.supply, 1);
+}
+
+#[test]
+fn failed_challenges_mint_no_badge() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.failed();
+    fixture.close_dispute_window(&challenge);
+    let verifier = fixture.verifiers[0];
+    fixture.claim(&verifier, &challenge, true).unwrap();
+
+    assert!(fixture.svm.account(&pda::badge_mint_pda(&challenge).0).is_none());
+    assert_eq!(fixture.badge_rent(&challenge), 0);
+}
+
+#[test]
+fn the_badge_cannot_be_transferred() {
+    let mut fixture = Fixture::new();
+    let challenge = claimed(&mut fixture);
+    let (creator, outsider) = (fixture.creator, fixture.outsider);
+    let (mint, _) = pda::badge_mint_pda(&challenge);
+    let source = pda::badge_account(&creator, &challenge);
+    let destination = pda::badge_account(&outsider, &challenge);
+
+    // Anyone can open an account for the badge, but nothing can move into it
+    fixture
+        .process(create_associated_token_account(
+            &outsider,
+            &outsider,
+            &mint,
+            &token_2022::ID,
+        ))
+        .unwrap();
+    let transfer = transfer_checked(
+        &spl_token_2022::ID,
+        &source,
+        &mint,
+        &destination,
+        &creator,
+        &[],
+        1,
+        0,
+    )
+    .unwrap();
+    assert_eq!(
+        fixture.process(transfer),
+        Err(ExecutionError::Program(TokenError::NonTransferable.into()))
+    );
+
+    let data = token_data(&fixture, &source);
+    assert_eq!(StateWithExtensions::<Account>::unpack(&data).unwrap().base.amount, 1);
+}
@@ -0,0 +1,65 @@
+//! Creator-chosen challenge IDs and the per-user index that lists them.
+
+mod common;
+
+use common::Fixture;
+use proof_of_touch_grass::state::ChallengeIndex;
+use proof_of_touch_grass_client::{accounts, instructions, pda};
+
+fn index(fixture: &Fixture) -> ChallengeIndex {
+    let address = pda::challenge_index_pda(&fixture.creator).0;
+    accounts::fetch_challenge_index(&fixture.svm, &address).unwrap()
+}
+
+#[test]
+fn initialize_user_creates_an_empty_index() {
+    let fixture = Fixture::new();
+    let index = index(&fixture);
+    assert_eq!(index.authority, fixture.creator);
+    assert!(index.challenge_ids.is_empty());
+}
+
+#[test]
+fn ids_are_listed_in_creation_order() {
+    let mut fixture = Fixture::new();
+    let args = fixture.args();
+    let first = fixture.try_create_with_id(42, args).unwrap();
+    let args = fixture.args();
+    let second = fixture.try_create_with_id(7, args).unwrap();
+
+    assert_eq!(index(&fixture).challenge_ids, [42, 7]);
+    assert_eq!(fixture.challenge(&first).challenge_id, 42);
+
+    let listed: Vec<_> = accounts::fetch_challenges_by(&fixture.svm, &fixture.creator)
+        .unwrap()
+        .into_iter()
+        .map(|(address, _)| address)
+        .collect();
+    assert_eq!(listed, [first, second]);
+}
+
+#[test]
+fn transactions_built_together_do_not_collide() {
+    let mut fixture = Fixture::new();
+    let creator = fixture.creator;
+    // Neither instruction depends on state the other one changes
+    let first = instructions::create_challenge(&creator, 1, fixture.args());
+    let second = instructions::create_challenge(&creator, 2, fixture.args());
+    fixture.process(first).unwrap();
+    fixture.process(second).unwrap();
+
+    assert_eq!(index(&fixture).challenge_ids, [1, 2]);
+    assert_eq!(fixture.user().total_challenges, 2);
+}
+
+#[test]
+fn reusing_an_id_fails() {
+    let mut fixture = Fixture::new();
+    let args = fixture.args();
+    fixture.try_create_with_id(5, args).unwrap();
+    let args = fixture.args();
+    assert!(fixture.try_create_with_id(5, args).is_err());
+
+    assert_eq!(index(&fixture).challenge_ids, [5]);
+    assert_eq!(fixture.user().total_challenges, 1);
+}
@@ -0,0 +1,512 @@
+#![allow(dead_code)]
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::AccountSerialize;
+use anchor_spl::token::TokenAccount;
+use proof_of_touch_grass::constants::{ADMIN_PUBKEY, MIN_JUROR_STAKE, PLATFORM_PUBKEY};
+use proof_of_touch_grass::state::{
+    Challenge, ChallengeStatus, ChallengeTemplate, Dispute, ExtensionRequest, GrassConfig,
+    GrassEmission, Justification, Juror, JuryVote, PlatformLimits, SubmissionRules, User,
+    UserLimits, Verification, VerificationRules, VerifierReputation, Vote,
+};
+use proof_of_touch_grass_client::instructions::{self, CreateChallengeArgs, CreateTemplateArgs};
+use proof_of_touch_grass_client::{accounts, pda};
+use proof_of_touch_grass_harness::{ExecutionError, Svm};
+
+pub const SOL: u64 = 1_000_000_000;
+pub const STAKE: u64 = SOL;
+pub const HOUR: i64 = 3_600;
+pub const DAY: i64 = 86_400;
+/// One GRASS in base units
+pub const GRASS: u64 = 1_000_000;
+
+/// The emission every fixture starts with: 100 GRASS per completion, 10 per
+/// unit of winning vote weight, 1,000 GRASS to the SOL and 20% off fees
+pub fn emission() -> GrassEmission {
+    GrassEmission {
+        creator_reward: 100 * GRASS,
+        verifier_reward: 10 * GRASS,
+        grass_per_sol: 1_000 * GRASS,
+        fee_discount_bps: 2_000,
+    }
+}
+
+pub type Outcome = Result<(), ExecutionError>;
+
+/// A funded creator with a user profile, three verifiers, a platform wallet,
+/// an unrelated outsider and the GRASS mint configured with `emission()`
+pub struct Fixture {
+    pub svm: Svm,
+    pub creator: Pubkey,
+    pub verifiers: Vec<Pubkey>,
+    pub platform: Pubkey,
+    pub outsider: Pubkey,
+}
+
+impl Fixture {
+    pub fn new() -> Self {
+        let mut fixture = Self::without_grass();
+        fixture.configure_grass(emission()).unwrap();
+        fixture
+    }
+
+    /// A fixture before the admin has configured GRASS
+    pub fn without_grass() -> Self {
+        let mut svm = Svm::new();
+        let creator = Pubkey::new_unique();
+        let verifiers: Vec<_> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let platform = PLATFORM_PUBKEY;
+        let outsider = Pubkey::new_unique();
+
+        svm.airdrop(&creator, 100 * SOL);
+        for wallet in verifiers.iter().chain([&platform, &outsider, &ADMIN_PUBKEY]) {
+            svm.airdrop(wallet, SOL);
+        }
+        svm.process(&instructions::initialize_user(&creator))
+            .unwrap();
+
+        Self {
+            svm,
+            creator,
+            verifiers,
+            platform,
+            outsider,
+        }
+    }
+
+    /// Starts in an hour, runs for a week, two proofs, two of three approvals
+    pub fn args(&self) -> CreateChallengeArgs {
+        let start_time = self.svm.now() + HOUR;
+        let end_time = start_time + 7 * DAY;
+        CreateChallengeArgs {
+            title: "Touch grass daily".to_string(),
+            description: "Walk in the park".to_string(),
+            stake_amount: STAKE,
+            start_time,
+            end_time,
+            verification_period: DAY,
+            required_proofs: 2,
+            required_approval_weight: 2,
+            verifiers: self.verifiers.clone(),
+            verifier_weights: Vec::new(),
+            geofences: Vec::new(),
+            submission_rules: SubmissionRules {
+                min_interval: None,
+                max_per_day: None,
+                allowed_hours: Vec::new(),
+            },
+            verification_rules: VerificationRules::default(),
+            min_verifier_reputation: None,
+            pay_fee_in_grass: false,
+        }
+    }
+
+    pub fn user(&self) -> User {
+        accounts::fetch_user(&self.svm, &pda::user_pda(&self.creator).0).unwrap()
+    }
+
+    pub fn challenge(&self, address: &Pubkey) -> Challenge {
+        accounts::fetch_challenge(&self.svm, address).unwrap()
+    }
+
+    pub fn status(&self, address: &Pubkey) -> ChallengeStatus {
+        self.challenge(address).status
+    }
+
+    pub fn escrow(&self, challenge: &Pubkey) -> u64 {
+        self.svm.lamports(&pda::escrow_pda(challenge).0)
+    }
+
+    pub fn process(&mut self, instruction: Instruction) -> Outcome {
+        self.svm.process(&instruction)
+    }
+
+    /// Creates a challenge under the next unused ID
+    pub fn try_create(&mut self, args: CreateChallengeArgs) -> Result<Pubkey, ExecutionError> {
+        let challenge_id = u64::from(self.user().total_challenges);
+        self.try_create_with_id(challenge_id, args)
+    }
+
+    pub fn try_create_with_id(
+        &mut self,
+        challenge_id: u64,
+        args: CreateChallengeArgs,
+    ) -> Result<Pubkey, ExecutionError> {
+        self.process(instructions::create_challenge(&self.creator, challenge_id, args))?;
+        Ok(pda::challenge_pda(&self.creator, challenge_id).0)
+    }
+
+    pub fn create(&mut self) -> Pubkey {
+        let args = self.args();
+        self.try_create(args).unwrap()
+    }
+
+    /// Retries a failed challenge under the next unused ID, starting in an hour
+    pub fn retry(&mut self, previous: &Pubkey, stake_amount: u64) -> Result<Pubkey, ExecutionError> {
+        let challenge_id = u64::from(self.user().total_challenges);
+        let previous_state = self.challenge(previous);
+        let start_time = self.svm.now() + HOUR;
+        self.process(instructions::retry_challenge(
+            &self.creator,
+            previous,
+            previous_state.template.as_ref(),
+            &previous_state.verifiers,
+            challenge_id,
+            stake_amount,
+            start_time,
+        ))?;
+        Ok(pda::challenge_pda(&self.creator, challenge_id).0)
+    }
+
+    /// Runs for a week, two proofs, two approvals, no minimum reputation
+    pub fn template_args(&self) -> CreateTemplateArgs {
+        CreateTemplateArgs {
+            title: "Park walks".to_string(),
+            description: "Walk in the park every other day".to_string(),
+            duration: 7 * DAY,
+            verification_period: DAY,
+            required_proofs: 2,
+            required_approval_weight: 2,
+            geofences: Vec::new(),
+            submission_rules: SubmissionRules {
+                min_interval: None,
+                max_per_day: None,
+                allowed_hours: Vec::new(),
+            },
+            verification_rules: VerificationRules::default(),
+            min_verifier_reputation: None,
+        }
+    }
+
+    /// Publishes a template as the outsider under `template_id`
+    pub fn try_publish(
+        &mut self,
+        template_id: u64,
+        args: CreateTemplateArgs,
+    ) -> Result<Pubkey, ExecutionError> {
+        let author = self.outsider;
+        self.process(instructions::create_template(&author, template_id, args))?;
+        Ok(pda::template_pda(&author, template_id).0)
+    }
+
+    pub fn publish(&mut self) -> Pubkey {
+        let args = self.template_args();
+        self.try_publish(0, args).unwrap()
+    }
+
+    pub fn template(&self, address: &Pubkey) -> ChallengeTemplate {
+        accounts::fetch_template(&self.svm, address).unwrap()
+    }
+
+    /// Creates a challenge from a template under the next unused ID, staking
+    /// `STAKE` and starting in an hour
+    pub fn try_from_template(
+        &mut self,
+        template: &Pubkey,
+        verifiers: Vec<Pubkey>,
+    ) -> Result<Pubkey, ExecutionError> {
+        let challenge_id = u64::from(self.user().total_challenges);
+        let start_time = self.svm.now() + HOUR;
+        self.process(instructions::create_challenge_from_template(
+            &self.creator,
+            template,
+            challenge_id,
+            STAKE,
+            start_time,
+            verifiers,
+            Vec::new(),
+        ))?;
+        Ok(pda::challenge_pda(&self.creator, challenge_id).0)
+    }
+
+    pub fn set_limits(&mut self, limits: UserLimits) -> Outcome {
+        let creator = self.creator;
+        self.process(instructions::set_user_limits(&creator, limits))
+    }
+
+    pub fn set_platform_limits(&mut self, limits: PlatformLimits) -> Outcome {
+        self.process(instructions::set_platform_limits(&ADMIN_PUBKEY, limits))
+    }
+
+    pub fn crank_as(&mut self, admin: &Pubkey, challenge: &Pubkey) -> Outcome {
+        let creator = self.creator;
+        let verifiers = self.challenge(challenge).verifiers;
+        self.process(instructions::update_challenge_state(
+            admin, challenge, &creator, &verifiers,
+        ))
+    }
+
+    pub fn crank(&mut self, challenge: &Pubkey) -> Outcome {
+        self.crank_as(&ADMIN_PUBKEY, challenge)
+    }
+
+    pub fn submit(&mut self, challenge: &Pubkey) -> Outcome {
+        let index = self.challenge(challenge).evidence_count;
+        let creator = self.creator;
+        self.process(instructions::submit_evidence(
+            &creator,
+            challenge,
+            index,
+            format!("QmProof{index}"),
+            String::new(),
+            None,
+        ))
+    }
+
+    pub fn vote(&mut self, verifier: usize, challenge: &Pubkey, vote: Vote) -> Outcome {
+        self.justified_vote(verifier, challenge, vote, None)
+    }
+
+    pub fn justified_vote(
+        &mut self,
+        verifier: usize,
+        challenge: &Pubkey,
+        vote: Vote,
+        justification: Option<Justification>,
+    ) -> Outcome {
+        let (verifier, creator) = (self.verifiers[verifier], self.creator);
+        let verifiers = self.challenge(challenge).verifiers;
+        self.process(instructions::verify_evidence(
+            &verifier, challenge, &creator, &verifiers, vote, justification,
+        ))
+    }
+
+    pub fn change_vote(&mut self, verifier: usize, challenge: &Pubkey, vote: Vote) -> Outcome {
+        self.justified_change(verifier, challenge, vote, None)
+    }
+
+    pub fn justified_change(
+        &mut self,
+        verifier: usize,
+        challenge: &Pubkey,
+        vote: Vote,
+        justification: Option<Justification>,
+    ) -> Outcome {
+        let (verifier, creator) = (self.verifiers[verifier], self.creator);
+        let verifiers = self.challenge(challenge).verifiers;
+        self.process(instructions::update_vote(
+            &verifier, challenge, &creator, &verifiers, vote, justification,
+        ))
+    }
+
+    pub fn verification(&self, challenge: &Pubkey, verifier: usize) -> Verification {
+        let address = pda::verification_pda(challenge, &self.verifiers[verifier]).0;
+        accounts::fetch_verification(&self.svm, &address).unwrap()
+    }
+
+    pub fn cancel(&mut self, challenge: &Pubkey) -> Outcome {
+        let creator = self.creator;
+        let verifiers = self.challenge(challenge).verifiers;
+        self.process(instructions::cancel_challenge(&creator, challenge, &verifiers))
+    }
+
+    pub fn request_extension(&mut self, challenge: &Pubkey, new_end_time: i64) -> Outcome {
+        let creator = self.creator;
+        self.process(instructions::request_extension(&creator, challenge, new_end_time))
+    }
+
+    pub fn approve_extension(&mut self, verifier: usize, challenge: &Pubkey) -> Outcome {
+        let (verifier, creator) = (self.verifiers[verifier], self.creator);
+        let verifiers = self.challenge(challenge).verifiers;
+        self.process(instructions::approve_extension(&verifier, challenge, &creator, &verifiers))
+    }
+
+    pub fn withdraw_extension(&mut self, challenge: &Pubkey) -> Outcome {
+        let creator = self.creator;
+        self.process(instructions::withdraw_extension(&creator, challenge))
+    }
+
+    pub fn extension(&self, challenge: &Pubkey) -> Option<ExtensionRequest> {
+        accounts::fetch(&self.svm, &pda::extension_pda(challenge).0).unwrap()
+    }
+
+    pub fn dispute(&mut self, disputer: &Pubkey, challenge: &Pubkey) -> Outcome {
+        self.contest(disputer, challenge, None)
+    }
+
+    /// Disputes the outcome, naming the verifier whose vote is contested
+    pub fn contest(
+        &mut self,
+        disputer: &Pubkey,
+        challenge: &Pubkey,
+        contested: Option<&Pubkey>,
+    ) -> Outcome {
+        let dispute_index = self.challenge(challenge).dispute_count;
+        self.process(instructions::dispute_verification(
+            disputer,
+            challenge,
+            dispute_index,
+            "Photos are from last year".to_string(),
+            contested,
+        ))
+    }
+
+    /// The challenge's latest dispute, open while the challenge is Disputed
+    pub fn dispute_record(&self, challenge: &Pubkey) -> Dispute {
+        let mut disputes = accounts::fetch_disputes(&self.svm, challenge).unwrap();
+        disputes.pop().expect("challenge was never disputed").1
+    }
+
+    pub fn claim(&mut self, claimer: &Pubkey, challenge: &Pubkey, as_verifier: bool) -> Outcome {
+        let creator = self.creator;
+        let verifiers = self.challenge(challenge).verifiers;
+        let grass_configured = accounts::grass_configured(&self.svm).unwrap();
+        self.process(instructions::claim_funds(
+            claimer,
+            challenge,
+            &creator,
+            &verifiers,
+            as_verifier,
+            grass_configured,
+        ))
+    }
+
+    pub fn resolve(&mut self, challenge: &Pubkey, overturn: bool) -> Outcome {
+        let (creator, dispute) = (self.creator, self.dispute_record(challenge));
+        let verifiers = self.challenge(challenge).verifiers;
+        self.process(instructions::resolve_dispute(
+            &ADMIN_PUBKEY,
+            challenge,
+            &dispute,
+            &creator,
+            &verifiers,
+            overturn,
+        ))
+    }
+
+    /// `count` funded wallets, each registered as a juror with the minimum stake
+    pub fn jurors(&mut self, count: usize) -> Vec<Pubkey> {
+        (0..count)
+            .map(|_| {
+                let juror = Pubkey::new_unique();
+                self.svm.airdrop(&juror, 2 * MIN_JUROR_STAKE);
+                self.register_juror(&juror, MIN_JUROR_STAKE).unwrap();
+                juror
+            })
+            .collect()
+    }
+
+    pub fn register_juror(&mut self, juror: &Pubkey, stake: u64) -> Outcome {
+        self.process(instructions::register_juror(juror, stake))
+    }
+
+    pub fn juror(&self, authority: &Pubkey) -> Option<Juror> {
+        accounts::fetch(&self.svm, &pda::juror_pda(authority).0).unwrap()
+    }
+
+    pub fn escalate(&mut self, signer: &Pubkey, challenge: &Pubkey) -> Outcome {
+        let dispute_index = self.dispute_record(challenge).index;
+        let pool = accounts::fetch_juror_pool(&self.svm).unwrap();
+        self.process(instructions::escalate_dispute(signer, challenge, dispute_index, &pool))
+    }
+
+    /// The jury drawn for the challenge's dispute, in seat order
+    pub fn jury(&self, challenge: &Pubkey) -> Vec<Pubkey> {
+        let dispute = self.dispute_record(challenge);
+        dispute.jury.iter().map(|seat| seat.juror).collect()
+    }
+
+    pub fn jury_vote(&mut self, juror: &Pubkey, challenge: &Pubkey, vote: JuryVote) -> Outcome {
+        let dispute_index = self.dispute_record(challenge).index;
+        self.process(instructions::cast_jury_vote(juror, challenge, dispute_index, vote))
+    }
+
+    pub fn settle_jury(&mut self, challenge: &Pubkey) -> Outcome {
+        let (creator, dispute) = (self.creator, self.dispute_record(challenge));
+        let verifiers = self.challenge(challenge).verifiers;
+        self.process(instructions::settle_jury(challenge, &dispute, &creator, &verifiers))
+    }
+
+    /// A failed challenge the creator disputed and escalated to a jury drawn
+    /// from `pool_size` fresh jurors
+    pub fn escalated(&mut self, pool_size: usize) -> Pubkey {
+        self.jurors(pool_size);
+        let challenge = self.failed();
+        let creator = self.creator;
+        self.dispute(&creator, &challenge).unwrap();
+        self.escalate(&creator, &challenge).unwrap();
+        challenge
+    }
+
+    pub fn reputation(&self, verifier: &Pubkey) -> Option<VerifierReputation> {
+        accounts::fetch(&self.svm, &pda::reputation_pda(verifier).0).unwrap()
+    }
+
+    /// A challenge whose start time has passed and that has been cranked to `Active`
+    pub fn active(&mut self) -> Pubkey {
+        let challenge = self.create();
+        self.svm.warp_to(self.challenge(&challenge).start_time);
+        self.crank(&challenge).unwrap();
+        challenge
+    }
+
+    /// An active challenge with every proof submitted
+    pub fn pending(&mut self) -> Pubkey {
+        let challenge = self.active();
+        for _ in 0..self.challenge(&challenge).required_proofs {
+            self.submit(&challenge).unwrap();
+        }
+        challenge
+    }
+
+    /// A challenge approved by the first two verifiers
+    pub fn completed(&mut self) -> Pubkey {
+        let challenge = self.pending();
+        self.vote(0, &challenge, Vote::Approve).unwrap();
+        self.vote(1, &challenge, Vote::Approve).unwrap();
+        challenge
+    }
+
+    /// A challenge rejected by the first two verifiers
+    pub fn failed(&mut self) -> Pubkey {
+        let challenge = self.pending();
+        self.vote(0, &challenge, Vote::Reject).unwrap();
+        self.vote(1, &challenge, Vote::Reject).unwrap();
+        challenge
+    }
+
+    /// Moves the clock past the dispute window of a finalized challenge
+    pub fn close_dispute_window(&mut self, challenge: &Pubkey) {
+        let window_end = self.challenge(challenge).dispute_window_end().unwrap();
+        self.svm.warp_to(window_end + 1);
+    }
+
+    /// Lamports the creator put into the badge mint and token account of `challenge`
+    pub fn badge_rent(&self, challenge: &Pubkey) -> u64 {
+        self.svm.lamports(&pda::badge_mint_pda(challenge).0)
+            + self.svm.lamports(&pda::badge_account(&self.creator, challenge))
+    }
+
+    pub fn configure_grass(&mut self, emission: GrassEmission) -> Outcome {
+        self.process(instructions::configure_grass(&ADMIN_PUBKEY, emission))
+    }
+
+    pub fn grass_config(&self) -> GrassConfig {
+        accounts::fetch_grass_config(&self.svm).unwrap()
+    }
+
+    /// The owner's GRASS balance, 0 before their first reward
+    pub fn grass(&self, owner: &Pubkey) -> u64 {
+        let account: Option<TokenAccount> =
+            accounts::fetch(&self.svm, &pda::grass_account(owner)).unwrap();
+        account.map_or(0, |account| account.amount)
+    }
+
+    /// Lamports the owner put into their GRASS token account
+    pub fn grass_rent(&self, owner: &Pubkey) -> u64 {
+        self.svm.lamports(&pda::grass_account(owner))
+    }
+
+    /// Overwrites a challenge account, for states no instruction sequence reaches
+    pub fn patch_challenge(&mut self, address: &Pubkey, patch: impl FnOnce(&mut Challenge)) {
+        let mut challenge = self.challenge(address);
+        patch(&mut challenge);
+        let mut account = self.svm.account(address).unwrap().clone();
+        let mut data = Vec::with_capacity(account.data.len());
+        challenge.try_serialize(&mut data).unwrap();
+        data.resize(account.data.len(), 0);
+        account.data = data;
+        self.svm.set_account(address, account);
+    }
+}
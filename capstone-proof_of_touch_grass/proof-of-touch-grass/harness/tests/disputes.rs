@@ -0,0 +1,171 @@
+//! Dispute bonds and repeat disputes: the bond held on each dispute, where it
+//! goes on resolution, and later disputes filed by other parties, one each.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::{Fixture, STAKE};
+use proof_of_touch_grass::constants::*;
+use proof_of_touch_grass::errors::ErrorCode;
+use proof_of_touch_grass::fees;
+use proof_of_touch_grass::state::{ChallengeStatus, JuryVote};
+use proof_of_touch_grass_client::{accounts, pda};
+use proof_of_touch_grass_harness::program_error;
+
+const BOND: u64 = STAKE * DISPUTE_BOND_BPS / BASIS_POINTS;
+
+fn bond_account(challenge: &Pubkey, dispute_index: u8) -> Pubkey {
+    pda::dispute_pda(challenge, dispute_index).0
+}
+
+#[test]
+fn the_bond_scales_with_the_stake_above_a_floor() {
+    assert_eq!(fees::dispute_bond(STAKE).unwrap(), BOND);
+    assert_eq!(fees::dispute_bond(100 * STAKE).unwrap(), 100 * BOND);
+    assert_eq!(fees::dispute_bond(0).unwrap(), MIN_DISPUTE_BOND);
+}
+
+#[test]
+fn filing_bonds_the_disputer() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.failed();
+    let creator = fixture.creator;
+    let before = fixture.svm.lamports(&creator);
+    fixture.dispute(&creator, &challenge).unwrap();
+
+    let dispute = fixture.dispute_record(&challenge);
+    assert_eq!((dispute.index, dispute.bond), (0, BOND));
+    let rent = fixture.svm.lamports(&bond_account(&challenge, 0)) - BOND;
+    assert_eq!(fixture.svm.lamports(&creator), before - rent - BOND);
+    assert_eq!(fixture.challenge(&challenge).dispute_count, 1);
+}
+
+#[test]
+fn an_overturn_refunds_the_bond() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.failed();
+    let creator = fixture.creator;
+    fixture.dispute(&creator, &challenge).unwrap();
+    let before = fixture.svm.lamports(&creator);
+    fixture.resolve(&challenge, true).unwrap();
+
+    assert_eq!(fixture.svm.lamports(&creator), before + BOND);
+    assert!(fixture.dispute_record(&challenge).is_resolved());
+}
+
+#[test]
+fn an_upheld_dispute_forfeits_the_bond_to_the_platform() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.failed();
+    let creator = fixture.creator;
+    fixture.dispute(&creator, &challenge).unwrap();
+    let (disputer, admin) = (fixture.svm.lamports(&creator), fixture.svm.lamports(&ADMIN_PUBKEY));
+    let platform = fixture.svm.lamports(&PLATFORM_PUBKEY);
+    fixture.resolve(&challenge, false).unwrap();
+
+    assert_eq!(fixture.svm.lamports(&creator), disputer);
+    assert_eq!(fixture.svm.lamports(&PLATFORM_PUBKEY), platform + BOND);
+    assert_eq!(fixture.svm.lamports(&ADMIN_PUBKEY), admin);
+    // The record stays, holding only its rent
+    let dispute = bond_account(&challenge, 0);
+    assert_eq!(
+        fixture.svm.lamports(&dispute),
+        fixture.svm.minimum_balance(fixture.svm.account(&dispute).unwrap().data.len())
+    );
+}
+
+#[test]
+fn another_party_can_dispute_once_the_first_is_resolved() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.completed();
+    let (first, second) = (fixture.verifiers[0], fixture.verifiers[2]);
+    fixture.dispute(&first, &challenge).unwrap();
+    // One dispute is open at a time
+    assert!(fixture.dispute(&second, &challenge).is_err());
+    fixture.resolve(&challenge, false).unwrap();
+
+    fixture.dispute(&second, &challenge).unwrap();
+    fixture.resolve(&challenge, true).unwrap();
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Failed);
+
+    // Each party gets one dispute, whatever the outcome
+    assert_eq!(
+        fixture.dispute(&first, &challenge),
+        Err(program_error(ErrorCode::AlreadyDisputed))
+    );
+    let disputes = accounts::fetch_disputes(&fixture.svm, &challenge).unwrap();
+    assert_eq!(disputes.len(), 2);
+    assert_eq!(disputes[0].0, bond_account(&challenge, 0));
+    assert_eq!(disputes[1].1.disputer, second);
+    assert_eq!(disputes[1].1.index, 1);
+    assert!(disputes.iter().all(|(_, dispute)| dispute.is_resolved()));
+    assert_eq!(fixture.challenge(&challenge).disputers, vec![first, second]);
+}
+
+#[test]
+fn a_resolution_restarts_the_window_for_the_other_parties() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.failed();
+    let creator = fixture.creator;
+    fixture.dispute(&creator, &challenge).unwrap();
+    // The admin resolves after the original window has closed
+    let finalized_at = fixture.challenge(&challenge).finalized_at;
+    fixture.svm.warp_to(finalized_at + DISPUTE_WINDOW + 1);
+    fixture.resolve(&challenge, false).unwrap();
+
+    let resolved_at = fixture.svm.now();
+    assert_eq!(
+        fixture.challenge(&challenge).dispute_window_end().unwrap(),
+        resolved_at + DISPUTE_WINDOW
+    );
+    fixture.svm.warp_to(resolved_at + DISPUTE_WINDOW);
+    let verifier = fixture.verifiers[2];
+    fixture.dispute(&verifier, &challenge).unwrap();
+    fixture.resolve(&challenge, false).unwrap();
+
+    fixture.close_dispute_window(&challenge);
+    let verifier = fixture.verifiers[1];
+    assert_eq!(
+        fixture.dispute(&verifier, &challenge),
+        Err(program_error(ErrorCode::DisputeWindowExpired))
+    );
+}
+
+#[test]
+fn a_jury_verdict_settles_the_bond() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.escalated(3);
+    let jury = fixture.jury(&challenge);
+    let creator = fixture.creator;
+    let before = fixture.svm.lamports(&creator);
+    fixture.jury_vote(&jury[0], &challenge, JuryVote::Overturn).unwrap();
+    fixture.jury_vote(&jury[1], &challenge, JuryVote::Overturn).unwrap();
+    fixture.settle_jury(&challenge).unwrap();
+    assert_eq!(fixture.svm.lamports(&creator), before + BOND);
+
+    // The verifiers dispute the overturned outcome in turn, and lose
+    let verifier = fixture.verifiers[0];
+    fixture.dispute(&verifier, &challenge).unwrap();
+    fixture.escalate(&verifier, &challenge).unwrap();
+    let jury = fixture.jury(&challenge);
+    let platform = fixture.svm.lamports(&PLATFORM_PUBKEY);
+    fixture.jury_vote(&jury[0], &challenge, JuryVote::Uphold).unwrap();
+    fixture.jury_vote(&jury[1], &challenge, JuryVote::Uphold).unwrap();
+    fixture.settle_jury(&challenge).unwrap();
+
+    assert_eq!(fixture.svm.lamports(&PLATFORM_PUBKEY), platform + BOND);
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Completed);
+}
+
+#[test]
+fn jurors_cannot_vote_once_an_early_verdict_settles() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.escalated(3);
+    let jury = fixture.jury(&challenge);
+    fixture.jury_vote(&jury[0], &challenge, JuryVote::Uphold).unwrap();
+    fixture.jury_vote(&jury[1], &challenge, JuryVote::Uphold).unwrap();
+    fixture.settle_jury(&challenge).unwrap();
+
+    assert!(fixture.jury_vote(&jury[2], &challenge, JuryVote::Overturn).is_err());
+    assert_eq!(fixture.juror(&jury[2]).unwrap().pending_verdicts, 0);
+}
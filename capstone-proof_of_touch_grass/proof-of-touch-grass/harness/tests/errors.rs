@@ -0,0 +1,1236 @@
+//! A failing case for every `ErrorCode` variant, in declaration order.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::sysvar;
+use common::{emission, Fixture, Outcome, DAY, HOUR, STAKE};
+use proof_of_touch_grass::constants::*;
+use proof_of_touch_grass::errors::ErrorCode;
+use proof_of_touch_grass::state::{
+    ChallengeStatus, GeoPoint, Geofence, GrassEmission, HourWindow, Justification, JuryVote,
+    PlatformLimits, UserLimits, Vote,
+};
+use proof_of_touch_grass_client::instructions::{self, CreateChallengeArgs};
+use proof_of_touch_grass_client::{accounts, pda};
+use proof_of_touch_grass_harness::{program_error, ExecutionError};
+
+const CENTRAL_PARK: GeoPoint = GeoPoint {
+    latitude: 40_782_865,
+    longitude: -73_965_355,
+};
+
+fn assert_error(outcome: Outcome, code: ErrorCode) {
+    assert_eq!(outcome, Err(program_error(code)));
+}
+
+fn create_with(edit: impl FnOnce(&mut CreateChallengeArgs)) -> Outcome {
+    let mut fixture = Fixture::new();
+    let mut args = fixture.args();
+    edit(&mut args);
+    fixture.try_create(args).map(|_| ())
+}
+
+/// An active challenge created with `edit` applied to the default arguments
+fn active_with(fixture: &mut Fixture, edit: impl FnOnce(&mut CreateChallengeArgs)) -> Pubkey {
+    let mut args = fixture.args();
+    edit(&mut args);
+    let challenge = fixture.try_create(args).unwrap();
+    fixture
+        .svm
+        .warp_to(fixture.challenge(&challenge).start_time);
+    fixture.crank(&challenge).unwrap();
+    challenge
+}
+
+fn submit_with(
+    fixture: &mut Fixture,
+    challenge: &Pubkey,
+    ipfs_hash: String,
+    metadata: String,
+    location: Option<GeoPoint>,
+) -> Outcome {
+    let index = fixture.challenge(challenge).evidence_count;
+    let creator = fixture.creator;
+    fixture.process(instructions::submit_evidence(
+        &creator, challenge, index, ipfs_hash, metadata, location,
+    ))
+}
+
+fn replace(fixture: &mut Fixture, challenge: &Pubkey, index: u8) -> Outcome {
+    let creator = fixture.creator;
+    fixture.process(instructions::replace_evidence(
+        &creator,
+        challenge,
+        index,
+        "QmReplacement".to_string(),
+        String::new(),
+        None,
+    ))
+}
+
+#[test]
+fn title_too_long() {
+    let outcome = create_with(|args| args.title = "t".repeat(MAX_TITLE_LEN + 1));
+    assert_error(outcome, ErrorCode::TitleTooLong);
+}
+
+#[test]
+fn description_too_long() {
+    let outcome = create_with(|args| args.description = "d".repeat(MAX_DESCRIPTION_LEN + 1));
+    assert_error(outcome, ErrorCode::DescriptionTooLong);
+}
+
+#[test]
+fn invalid_stake_amount() {
+    assert_error(
+        create_with(|args| args.stake_amount = 0),
+        ErrorCode::InvalidStakeAmount,
+    );
+}
+
+#[test]
+fn invalid_time_range() {
+    let outcome = create_with(|args| args.end_time = args.start_time);
+    assert_error(outcome, ErrorCode::InvalidTimeRange);
+}
+
+#[test]
+fn invalid_verifier_count() {
+    assert_error(
+        create_with(|args| args.verifiers.clear()),
+        ErrorCode::InvalidVerifierCount,
+    );
+    let outcome = create_with(|args| {
+        args.verifiers = (0..=MAX_VERIFIERS).map(|_| Pubkey::new_unique()).collect()
+    });
+    assert_error(outcome, ErrorCode::InvalidVerifierCount);
+}
+
+#[test]
+fn invalid_approval_count() {
+    let outcome = create_with(|args| args.required_approval_weight = args.verifiers.len() as u8 + 1);
+    assert_error(outcome, ErrorCode::InvalidApprovalCount);
+    let outcome = create_with(|args| args.required_approval_weight = 0);
+    assert_error(outcome, ErrorCode::InvalidApprovalCount);
+}
+
+#[test]
+fn invalid_proof_count() {
+    assert_error(
+        create_with(|args| args.required_proofs = 0),
+        ErrorCode::InvalidProofCount,
+    );
+}
+
+#[test]
+fn ipfs_hash_too_long() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.active();
+    let hash = "Q".repeat(MAX_IPFS_HASH_LEN + 1);
+    let outcome = submit_with(&mut fixture, &challenge, hash, String::new(), None);
+    assert_error(outcome, ErrorCode::IpfsHashTooLong);
+}
+
+#[test]
+fn metadata_too_long() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.active();
+    let metadata = "m".repeat(MAX_METADATA_LEN + 1);
+    let outcome = submit_with(
+        &mut fixture,
+        &challenge,
+        "QmProof".to_string(),
+        metadata,
+        None,
+    );
+    assert_error(outcome, ErrorCode::MetadataTooLong);
+}
+
+#[test]
+fn unauthorized_submitter() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.active();
+    let outsider = fixture.outsider;
+    let outcome = fixture.process(instructions::submit_evidence(
+        &outsider,
+        &challenge,
+        0,
+        "QmNotMine".to_string(),
+        String::new(),
+        None,
+    ));
+    assert_error(outcome, ErrorCode::UnauthorizedSubmitter);
+}
+
+#[test]
+fn invalid_challenge_status() {
+    let mut fixture = Fixture::new();
+
+    // Evidence before the challenge has been activated
+    let created = fixture.create();
+    let outcome = fixture.submit(&created);
+    assert_error(outcome, ErrorCode::InvalidChallengeStatus);
+
+    // Votes before every proof is in
+    let active = fixture.active();
+    let outcome = fixture.vote(0, &active, Vote::Approve);
+    assert_error(outcome, ErrorCode::InvalidChallengeStatus);
+
+    // Disputes and claims before finalization
+    let creator = fixture.creator;
+    let outcome = fixture.dispute(&creator, &active);
+    assert_error(outcome, ErrorCode::InvalidChallengeStatus);
+    let outcome = fixture.claim(&creator, &active, false);
+    assert_error(outcome, ErrorCode::InvalidChallengeStatus);
+
+    // Claims on a disputed challenge
+    let disputed = fixture.completed();
+    fixture.dispute(&creator, &disputed).unwrap();
+    fixture.close_dispute_window(&disputed);
+    let outcome = fixture.claim(&creator, &disputed, false);
+    assert_error(outcome, ErrorCode::InvalidChallengeStatus);
+}
+
+#[test]
+fn challenge_expired() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.active();
+    fixture.submit(&challenge).unwrap();
+
+    // Not cranked yet, but the deadline is what counts
+    fixture.svm.warp_to(fixture.challenge(&challenge).end_time);
+    assert_error(fixture.submit(&challenge), ErrorCode::ChallengeExpired);
+    assert_error(
+        replace(&mut fixture, &challenge, 0),
+        ErrorCode::ChallengeExpired,
+    );
+    let creator = fixture.creator;
+    let outcome = fixture.process(instructions::retract_evidence(&creator, &challenge, 0));
+    assert_error(outcome, ErrorCode::ChallengeExpired);
+}
+
+#[test]
+fn all_evidence_submitted() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.active();
+
+    // The last proof moves the challenge out of `Active`, so this check only
+    // fires for a patched account
+    fixture.patch_challenge(&challenge, |state| {
+        state.evidence_count = state.required_proofs
+    });
+    assert_error(fixture.submit(&challenge), ErrorCode::AllEvidenceSubmitted);
+}
+
+#[test]
+fn verification_period_expired() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+
+    fixture
+        .svm
+        .warp_to(fixture.challenge(&challenge).verification_period_end);
+    let outcome = fixture.vote(0, &challenge, Vote::Approve);
+    assert_error(outcome, ErrorCode::VerificationPeriodExpired);
+}
+
+#[test]
+fn unauthorized_verifier() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+    let (outsider, creator) = (fixture.outsider, fixture.creator);
+    let verifiers = fixture.verifiers.clone();
+    let outcome = fixture.process(instructions::verify_evidence(
+        &outsider,
+        &challenge,
+        &creator,
+        &verifiers,
+        Vote::Reject,
+        None,
+    ));
+    assert_error(outcome, ErrorCode::UnauthorizedVerifier);
+
+    // Failed-challenge claims need the claimer's verification record
+    let failed = fixture.failed();
+    fixture.close_dispute_window(&failed);
+    let verifier = fixture.verifiers[0];
+    let outcome = fixture.claim(&verifier, &failed, false);
+    assert_error(outcome, ErrorCode::UnauthorizedVerifier);
+}
+
+#[test]
+fn verification_period_not_expired_is_never_raised() {
+    // Declared but unused: cranking early is a no-op rather than an error
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+    fixture.crank(&challenge).unwrap();
+    assert_eq!(
+        fixture.status(&challenge),
+        ChallengeStatus::PendingVerification
+    );
+}
+
+#[test]
+fn unauthorized_creator() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.create();
+    let outsider = fixture.outsider;
+    let mut cancel = instructions::cancel_challenge(&outsider, &challenge, &fixture.verifiers);
+    // Point at the creator's profile so the signer check is what rejects it
+    let outsider_profile = pda::user_pda(&outsider).0;
+    for meta in &mut cancel.accounts {
+        if meta.pubkey == outsider_profile {
+            meta.pubkey = pda::user_pda(&fixture.creator).0;
+        }
+    }
+    let outcome = fixture.process(cancel);
+    assert_error(outcome, ErrorCode::UnauthorizedCreator);
+
+    // Only the creator claims a completed challenge
+    let completed = fixture.completed();
+    fixture.close_dispute_window(&completed);
+    let verifier = fixture.verifiers[0];
+    let outcome = fixture.claim(&verifier, &completed, false);
+    assert_error(outcome, ErrorCode::UnauthorizedCreator);
+}
+
+#[test]
+fn cannot_cancel_challenge() {
+    let mut fixture = Fixture::new();
+    let pending = fixture.pending();
+    assert_error(fixture.cancel(&pending), ErrorCode::CannotCancelChallenge);
+
+    let completed = fixture.completed();
+    assert_error(fixture.cancel(&completed), ErrorCode::CannotCancelChallenge);
+}
+
+#[test]
+fn dispute_reason_too_long() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.completed();
+    let creator = fixture.creator;
+    let outcome = fixture.process(instructions::dispute_verification(
+        &creator,
+        &challenge,
+        0,
+        "r".repeat(MAX_DISPUTE_REASON_LEN + 1),
+        None,
+    ));
+    assert_error(outcome, ErrorCode::DisputeReasonTooLong);
+}
+
+#[test]
+fn dispute_window_expired() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.failed();
+    let creator = fixture.creator;
+
+    // The window is inclusive of its last second
+    let finalized_at = fixture.challenge(&challenge).finalized_at;
+    fixture.svm.warp_to(finalized_at + DISPUTE_WINDOW + 1);
+    let outcome = fixture.dispute(&creator, &challenge);
+    assert_error(outcome, ErrorCode::DisputeWindowExpired);
+
+    fixture.svm.warp_to(finalized_at + DISPUTE_WINDOW);
+    fixture.dispute(&creator, &challenge).unwrap();
+}
+
+#[test]
+fn unauthorized_disputer() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.completed();
+    let outsider = fixture.outsider;
+    let outcome = fixture.dispute(&outsider, &challenge);
+    assert_error(outcome, ErrorCode::UnauthorizedDisputer);
+}
+
+#[test]
+fn verifier_did_not_reject() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+    fixture.vote(2, &challenge, Vote::Approve).unwrap();
+    fixture.vote(0, &challenge, Vote::Reject).unwrap();
+    fixture.vote(1, &challenge, Vote::Reject).unwrap();
+    fixture.close_dispute_window(&challenge);
+
+    let approver = fixture.verifiers[2];
+    let outcome = fixture.claim(&approver, &challenge, true);
+    assert_error(outcome, ErrorCode::VerifierDidNotReject);
+}
+
+#[test]
+fn already_claimed() {
+    let mut fixture = Fixture::new();
+    let completed = fixture.completed();
+    fixture.close_dispute_window(&completed);
+    let creator = fixture.creator;
+    fixture.claim(&creator, &completed, false).unwrap();
+    let outcome = fixture.claim(&creator, &completed, false);
+    assert_error(outcome, ErrorCode::AlreadyClaimed);
+
+    let failed = fixture.failed();
+    fixture.close_dispute_window(&failed);
+    let verifier = fixture.verifiers[0];
+    fixture.claim(&verifier, &failed, true).unwrap();
+    let outcome = fixture.claim(&verifier, &failed, true);
+    assert_error(outcome, ErrorCode::AlreadyClaimed);
+}
+
+#[test]
+fn dispute_window_not_expired() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.completed();
+    let creator = fixture.creator;
+
+    let finalized_at = fixture.challenge(&challenge).finalized_at;
+    fixture.svm.warp_to(finalized_at + DISPUTE_WINDOW);
+    let outcome = fixture.claim(&creator, &challenge, false);
+    assert_error(outcome, ErrorCode::DisputeWindowNotExpired);
+
+    fixture.svm.advance(1);
+    fixture.claim(&creator, &challenge, false).unwrap();
+}
+
+#[test]
+fn unauthorized_admin() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.create();
+    fixture
+        .svm
+        .warp_to(fixture.challenge(&challenge).start_time);
+    let creator = fixture.creator;
+    let outcome = fixture.crank_as(&creator, &challenge);
+    assert_error(outcome, ErrorCode::UnauthorizedAdmin);
+}
+
+#[test]
+fn already_voted_is_never_raised() {
+    // Declared but unused: the verification PDA already exists, so the second
+    // vote fails inside the system program's `CreateAccount`
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+    fixture.vote(0, &challenge, Vote::Approve).unwrap();
+    let outcome = fixture.vote(0, &challenge, Vote::Reject);
+    assert_eq!(
+        outcome,
+        Err(ExecutionError::Program(ProgramError::Custom(0)))
+    );
+}
+
+#[test]
+fn too_many_geofences() {
+    let fence = Geofence::Circle {
+        center: CENTRAL_PARK,
+        radius_meters: 500,
+    };
+    let outcome = create_with(|args| args.geofences = vec![fence; MAX_GEOFENCES + 1]);
+    assert_error(outcome, ErrorCode::TooManyGeofences);
+}
+
+#[test]
+fn invalid_geofence() {
+    let outcome = create_with(|args| {
+        args.geofences = vec![Geofence::Circle {
+            center: CENTRAL_PARK,
+            radius_meters: 0,
+        }]
+    });
+    assert_error(outcome, ErrorCode::InvalidGeofence);
+}
+
+#[test]
+fn invalid_coordinates() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.active();
+    let location = GeoPoint {
+        latitude: MAX_LATITUDE + 1,
+        longitude: 0,
+    };
+    let outcome = submit_with(
+        &mut fixture,
+        &challenge,
+        "QmProof".to_string(),
+        String::new(),
+        Some(location),
+    );
+    assert_error(outcome, ErrorCode::InvalidCoordinates);
+}
+
+#[test]
+fn location_required() {
+    let mut fixture = Fixture::new();
+    let challenge = active_with(&mut fixture, |args| {
+        args.geofences = vec![Geofence::Circle {
+            center: CENTRAL_PARK,
+            radius_meters: 500,
+        }]
+    });
+    let outcome = submit_with(
+        &mut fixture,
+        &challenge,
+        "QmProof".to_string(),
+        String::new(),
+        None,
+    );
+    assert_error(outcome, ErrorCode::LocationRequired);
+}
+
+#[test]
+fn location_outside_geofence() {
+    let mut fixture = Fixture::new();
+    let challenge = active_with(&mut fixture, |args| {
+        args.geofences = vec![Geofence::Circle {
+            center: CENTRAL_PARK,
+            radius_meters: 500,
+        }]
+    });
+    let times_square = GeoPoint {
+        latitude: 40_758_896,
+        longitude: -73_985_130,
+    };
+    let outcome = submit_with(
+        &mut fixture,
+        &challenge,
+        "QmProof".to_string(),
+        String::new(),
+        Some(times_square),
+    );
+    assert_error(outcome, ErrorCode::LocationOutsideGeofence);
+
+    submit_with(
+        &mut fixture,
+        &challenge,
+        "QmProof".to_string(),
+        String::new(),
+        Some(CENTRAL_PARK),
+    )
+    .unwrap();
+}
+
+#[test]
+fn invalid_submission_rules() {
+    let outcome = create_with(|args| args.submission_rules.min_interval = Some(0));
+    assert_error(outcome, ErrorCode::InvalidSubmissionRules);
+}
+
+#[test]
+fn submission_too_soon() {
+    let mut fixture = Fixture::new();
+    let challenge = active_with(&mut fixture, |args| {
+        args.submission_rules.min_interval = Some(DAY)
+    });
+    fixture.submit(&challenge).unwrap();
+
+    fixture.svm.advance(DAY - 1);
+    assert_error(fixture.submit(&challenge), ErrorCode::SubmissionTooSoon);
+    fixture.svm.advance(1);
+    fixture.submit(&challenge).unwrap();
+}
+
+#[test]
+fn outside_allowed_hours() {
+    let mut fixture = Fixture::new();
+    let start_time = fixture.args().start_time;
+    let hour = (start_time.rem_euclid(DAY) / HOUR) as u8;
+    let challenge = active_with(&mut fixture, |args| {
+        args.submission_rules.allowed_hours = vec![HourWindow {
+            start_hour: (hour + 2) % HOURS_PER_DAY,
+            end_hour: (hour + 3) % HOURS_PER_DAY,
+        }]
+    });
+    assert_error(fixture.submit(&challenge), ErrorCode::OutsideAllowedHours);
+
+    fixture.svm.advance(2 * HOUR);
+    fixture.submit(&challenge).unwrap();
+}
+
+#[test]
+fn daily_submission_limit_reached() {
+    let mut fixture = Fixture::new();
+    let challenge = active_with(&mut fixture, |args| {
+        args.required_proofs = 3;
+        args.submission_rules.max_per_day = Some(1);
+    });
+    fixture.submit(&challenge).unwrap();
+    assert_error(
+        fixture.submit(&challenge),
+        ErrorCode::DailySubmissionLimitReached,
+    );
+
+    fixture.svm.advance(DAY);
+    fixture.submit(&challenge).unwrap();
+}
+
+#[test]
+fn evidence_locked() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+
+    // Editable until the first vote lands
+    replace(&mut fixture, &challenge, 0).unwrap();
+    fixture.vote(0, &challenge, Vote::Approve).unwrap();
+    assert_error(
+        replace(&mut fixture, &challenge, 0),
+        ErrorCode::EvidenceLocked,
+    );
+    let creator = fixture.creator;
+    let outcome = fixture.process(instructions::retract_evidence(&creator, &challenge, 1));
+    assert_error(outcome, ErrorCode::EvidenceLocked);
+}
+
+#[test]
+fn no_evidence_to_retract() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.active();
+    let creator = fixture.creator;
+
+    // The evidence account is derived from `evidence_count - 1`, so with no
+    // evidence anchor rejects the missing account before the handler runs
+    let outcome = fixture.process(instructions::retract_evidence(&creator, &challenge, 0));
+    assert_eq!(
+        outcome,
+        Err(program_error(
+            anchor_lang::error::ErrorCode::AccountNotInitialized
+        ))
+    );
+
+    // With a proof on record but a zeroed count, the handler's check fires
+    fixture.submit(&challenge).unwrap();
+    fixture.patch_challenge(&challenge, |state| state.evidence_count = 0);
+    let outcome = fixture.process(instructions::retract_evidence(&creator, &challenge, 0));
+    assert_error(outcome, ErrorCode::NoEvidenceToRetract);
+}
+
+#[test]
+fn revision_limit_reached() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.active();
+    fixture.submit(&challenge).unwrap();
+
+    for _ in 0..u8::MAX {
+        replace(&mut fixture, &challenge, 0).unwrap();
+    }
+    assert_error(
+        replace(&mut fixture, &challenge, 0),
+        ErrorCode::RevisionLimitReached,
+    );
+}
+
+#[test]
+fn arithmetic_overflow() {
+    let outcome = create_with(|args| args.verification_period = i64::MAX);
+    assert_error(outcome, ErrorCode::ArithmeticOverflow);
+}
+
+#[test]
+fn escrow_imbalance() {
+    // The escrow only ever holds the stake it was funded with, so a claim
+    // against a larger stake is only possible on a patched account
+    let mut fixture = Fixture::new();
+    let challenge = fixture.completed();
+    fixture.close_dispute_window(&challenge);
+    fixture.patch_challenge(&challenge, |state| state.stake_amount *= 2);
+
+    let creator = fixture.creator;
+    let outcome = fixture.claim(&creator, &challenge, false);
+    assert_error(outcome, ErrorCode::EscrowImbalance);
+}
+
+#[test]
+fn invalid_reputation_accounts() {
+    // New challenges pass every verifier's reputation account, minimum or not
+    let mut fixture = Fixture::new();
+    let args = fixture.args();
+    let creator = fixture.creator;
+    let mut instruction = instructions::create_challenge(&creator, 0, args);
+    instruction.accounts.pop();
+    assert_error(
+        fixture.process(instruction),
+        ErrorCode::InvalidReputationAccounts,
+    );
+
+    // Claims must pass the accounts the first claim settles
+    let challenge = fixture.completed();
+    fixture.close_dispute_window(&challenge);
+    let claim = instructions::claim_funds(&creator, &challenge, &creator, &[], false, true);
+    let outcome = fixture.process(claim);
+    assert_error(outcome, ErrorCode::InvalidReputationAccounts);
+}
+
+#[test]
+fn verifier_reputation_too_low() {
+    // Verifiers who never voted have no record, which scores zero
+    let outcome = create_with(|args| args.min_verifier_reputation = Some(1));
+    assert_error(outcome, ErrorCode::VerifierReputationTooLow);
+}
+
+#[test]
+fn invalid_user_limits() {
+    let mut fixture = Fixture::new();
+    for limits in [
+        UserLimits {
+            max_stake: Some(0),
+            ..UserLimits::default()
+        },
+        UserLimits {
+            max_active_challenges: Some(DEFAULT_MAX_ACTIVE_CHALLENGES + 1),
+            ..UserLimits::default()
+        },
+        UserLimits {
+            failure_cooldown: Some(-DAY),
+            ..UserLimits::default()
+        },
+    ] {
+        assert_error(fixture.set_limits(limits), ErrorCode::InvalidUserLimits);
+    }
+}
+
+#[test]
+fn too_many_active_challenges() {
+    let mut fixture = Fixture::new();
+    fixture
+        .set_limits(UserLimits {
+            max_active_challenges: Some(1),
+            ..UserLimits::default()
+        })
+        .unwrap();
+    let first = fixture.create();
+    let args = fixture.args();
+    assert_error(
+        fixture.try_create(args).map(|_| ()),
+        ErrorCode::TooManyActiveChallenges,
+    );
+
+    // Finishing the open challenge frees the slot
+    fixture.cancel(&first).unwrap();
+    fixture.create();
+}
+
+#[test]
+fn stake_above_limit() {
+    let outcome = create_with(|args| args.stake_amount = DEFAULT_MAX_STAKE_AMOUNT + 1);
+    assert_error(outcome, ErrorCode::StakeAboveLimit);
+
+    let mut fixture = Fixture::new();
+    fixture
+        .set_limits(UserLimits {
+            max_stake: Some(STAKE - 1),
+            ..UserLimits::default()
+        })
+        .unwrap();
+    let args = fixture.args();
+    assert_error(fixture.try_create(args).map(|_| ()), ErrorCode::StakeAboveLimit);
+}
+
+#[test]
+fn stake_at_risk_above_limit() {
+    let mut fixture = Fixture::new();
+    fixture
+        .set_limits(UserLimits {
+            max_stake_at_risk: Some(STAKE * 3 / 2),
+            ..UserLimits::default()
+        })
+        .unwrap();
+    fixture.create();
+    let args = fixture.args();
+    assert_error(
+        fixture.try_create(args).map(|_| ()),
+        ErrorCode::StakeAtRiskAboveLimit,
+    );
+}
+
+#[test]
+fn failure_cooldown_active() {
+    let mut fixture = Fixture::new();
+    fixture
+        .set_limits(UserLimits {
+            failure_cooldown: Some(DAY),
+            ..UserLimits::default()
+        })
+        .unwrap();
+    fixture.failed();
+    let args = fixture.args();
+    assert_error(
+        fixture.try_create(args).map(|_| ()),
+        ErrorCode::FailureCooldownActive,
+    );
+
+    // Dropping the setting does not cut a running cooldown short
+    fixture.set_limits(UserLimits::default()).unwrap();
+    let args = fixture.args();
+    assert_error(
+        fixture.try_create(args).map(|_| ()),
+        ErrorCode::FailureCooldownActive,
+    );
+
+    fixture.svm.advance(DAY);
+    fixture.create();
+}
+
+#[test]
+fn invalid_inbox_accounts() {
+    let mut fixture = Fixture::new();
+    let args = fixture.args();
+    let creator = fixture.creator;
+    let verifier_count = args.verifiers.len();
+    let mut instruction = instructions::create_challenge(&creator, 0, args);
+    // Drop every reputation account and the last inbox
+    let inbox_count = instruction.accounts.len() - verifier_count - 1;
+    instruction.accounts.truncate(inbox_count);
+    assert_error(fixture.process(instruction), ErrorCode::InvalidInboxAccounts);
+
+    // Inboxes listed out of order do not match their verifiers
+    let challenge = fixture.create();
+    let mut verifiers = fixture.verifiers.clone();
+    verifiers.reverse();
+    let outcome = fixture.process(instructions::cancel_challenge(&creator, &challenge, &verifiers));
+    assert_error(outcome, ErrorCode::InvalidInboxAccounts);
+}
+
+#[test]
+fn invalid_extension() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.active();
+    let end_time = fixture.challenge(&challenge).end_time;
+    for new_end_time in [end_time - DAY, end_time + MAX_EXTENSION + 1] {
+        let outcome = fixture.request_extension(&challenge, new_end_time);
+        assert_error(outcome, ErrorCode::InvalidExtension);
+    }
+}
+
+#[test]
+fn extension_already_approved() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.active();
+    let end_time = fixture.challenge(&challenge).end_time;
+    fixture.request_extension(&challenge, end_time + DAY).unwrap();
+    fixture.approve_extension(0, &challenge).unwrap();
+    let outcome = fixture.approve_extension(0, &challenge);
+    assert_error(outcome, ErrorCode::ExtensionAlreadyApproved);
+}
+
+#[test]
+fn already_retried() {
+    let mut fixture = Fixture::new();
+    let failed = fixture.failed();
+    fixture.close_dispute_window(&failed);
+    fixture.retry(&failed, STAKE).unwrap();
+    let outcome = fixture.retry(&failed, STAKE).map(|_| ());
+    assert_error(outcome, ErrorCode::AlreadyRetried);
+}
+
+#[test]
+fn retry_stake_too_low() {
+    let mut fixture = Fixture::new();
+    let failed = fixture.failed();
+    fixture.close_dispute_window(&failed);
+    let outcome = fixture.retry(&failed, STAKE / 2).map(|_| ());
+    assert_error(outcome, ErrorCode::RetryStakeTooLow);
+}
+
+#[test]
+fn invalid_template() {
+    let mut fixture = Fixture::new();
+    let mut args = fixture.template_args();
+    args.duration = 0;
+    let outcome = fixture.try_publish(0, args).map(|_| ());
+    assert_error(outcome, ErrorCode::InvalidTemplate);
+
+    // Challenges from it would time out as soon as they awaited verification
+    for verification_period in [0, -DAY] {
+        let mut args = fixture.template_args();
+        args.verification_period = verification_period;
+        let outcome = fixture.try_publish(0, args).map(|_| ());
+        assert_error(outcome, ErrorCode::InvalidTemplate);
+    }
+
+    let mut args = fixture.template_args();
+    args.required_approval_weight = 0;
+    let outcome = fixture.try_publish(0, args).map(|_| ());
+    assert_error(outcome, ErrorCode::InvalidTemplate);
+}
+
+#[test]
+fn invalid_verification_rules() {
+    let outcome = create_with(|args| args.verification_rules.min_votes = 4);
+    assert_error(outcome, ErrorCode::InvalidVerificationRules);
+}
+
+#[test]
+fn vote_unchanged() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+    fixture.vote(0, &challenge, Vote::Abstain).unwrap();
+    let outcome = fixture.change_vote(0, &challenge, Vote::Abstain);
+    assert_error(outcome, ErrorCode::VoteUnchanged);
+}
+
+#[test]
+fn invalid_verifier_weights() {
+    let outcome = create_with(|args| args.verifier_weights = vec![2, 1]);
+    assert_error(outcome, ErrorCode::InvalidVerifierWeights);
+    let outcome = create_with(|args| args.verifier_weights = vec![2, 0, 1]);
+    assert_error(outcome, ErrorCode::InvalidVerifierWeights);
+}
+
+#[test]
+fn justification_too_long() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+    let long_reason = Justification {
+        reason: "r".repeat(MAX_JUSTIFICATION_LEN + 1),
+        attachment: None,
+    };
+    let outcome = fixture.justified_vote(0, &challenge, Vote::Reject, Some(long_reason));
+    assert_error(outcome, ErrorCode::JustificationTooLong);
+
+    let long_attachment = Justification {
+        reason: "Blurry".to_string(),
+        attachment: Some("Q".repeat(MAX_IPFS_HASH_LEN + 1)),
+    };
+    let outcome = fixture.justified_vote(0, &challenge, Vote::Reject, Some(long_attachment));
+    assert_error(outcome, ErrorCode::JustificationTooLong);
+}
+
+#[test]
+fn invalid_contested_verification() {
+    let mut fixture = Fixture::new();
+    let failed = fixture.failed();
+    let other = fixture.failed();
+    let (creator, verifier) = (fixture.creator, fixture.verifiers[0]);
+    // The verifier voted on `other`, but that verification isn't part of `failed`
+    let mut instruction = instructions::dispute_verification(
+        &creator,
+        &failed,
+        0,
+        "Wrong vote".to_string(),
+        Some(&verifier),
+    );
+    instruction.accounts[3].pubkey = pda::verification_pda(&other, &verifier).0;
+    let outcome = fixture.process(instruction);
+    assert_error(outcome, ErrorCode::InvalidContestedVerification);
+}
+
+#[test]
+fn juror_stake_too_low() {
+    let mut fixture = Fixture::new();
+    let creator = fixture.creator;
+    let outcome = fixture.register_juror(&creator, MIN_JUROR_STAKE - 1);
+    assert_error(outcome, ErrorCode::JurorStakeTooLow);
+}
+
+#[test]
+fn juror_pool_full() {
+    let mut fixture = Fixture::new();
+    fixture.jurors(MAX_JURORS);
+    let creator = fixture.creator;
+    let outcome = fixture.register_juror(&creator, MIN_JUROR_STAKE);
+    assert_error(outcome, ErrorCode::JurorPoolFull);
+}
+
+#[test]
+fn juror_unbonding() {
+    let mut fixture = Fixture::new();
+    let juror = fixture.jurors(1)[0];
+    fixture.process(instructions::unregister_juror(&juror)).unwrap();
+    let outcome = fixture.process(instructions::unregister_juror(&juror));
+    assert_error(outcome, ErrorCode::JurorUnbonding);
+}
+
+#[test]
+fn juror_still_bonded() {
+    let mut fixture = Fixture::new();
+    let juror = fixture.jurors(1)[0];
+    // Still in the pool
+    let outcome = fixture.process(instructions::withdraw_juror_stake(&juror));
+    assert_error(outcome, ErrorCode::JurorStillBonded);
+
+    fixture.process(instructions::unregister_juror(&juror)).unwrap();
+    fixture.svm.advance(JUROR_UNBONDING_PERIOD - 1);
+    let outcome = fixture.process(instructions::withdraw_juror_stake(&juror));
+    assert_error(outcome, ErrorCode::JurorStillBonded);
+}
+
+#[test]
+fn unauthorized_escalation() {
+    let mut fixture = Fixture::new();
+    fixture.jurors(JURY_SIZE);
+    let challenge = fixture.failed();
+    let (creator, verifier) = (fixture.creator, fixture.verifiers[0]);
+    fixture.dispute(&creator, &challenge).unwrap();
+    // A verifier could have disputed, but this dispute is the creator's
+    let outcome = fixture.escalate(&verifier, &challenge);
+    assert_error(outcome, ErrorCode::UnauthorizedEscalation);
+}
+
+#[test]
+fn dispute_already_escalated() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.escalated(JURY_SIZE);
+    let creator = fixture.creator;
+    assert_error(
+        fixture.escalate(&creator, &challenge),
+        ErrorCode::DisputeAlreadyEscalated,
+    );
+    assert_error(
+        fixture.resolve(&challenge, false),
+        ErrorCode::DisputeAlreadyEscalated,
+    );
+}
+
+#[test]
+fn dispute_not_escalated() {
+    let mut fixture = Fixture::new();
+    let juror = fixture.jurors(1)[0];
+    let challenge = fixture.failed();
+    let creator = fixture.creator;
+    fixture.dispute(&creator, &challenge).unwrap();
+    assert_error(
+        fixture.jury_vote(&juror, &challenge, JuryVote::Uphold),
+        ErrorCode::DisputeNotEscalated,
+    );
+    assert_error(fixture.settle_jury(&challenge), ErrorCode::DisputeNotEscalated);
+}
+
+#[test]
+fn not_enough_jurors() {
+    let mut fixture = Fixture::new();
+    fixture.jurors(JURY_SIZE - 1);
+    let challenge = fixture.failed();
+    let creator = fixture.creator;
+    fixture.dispute(&creator, &challenge).unwrap();
+    let outcome = fixture.escalate(&creator, &challenge);
+    assert_error(outcome, ErrorCode::NotEnoughJurors);
+}
+
+#[test]
+fn slot_hashes_unavailable() {
+    let mut fixture = Fixture::new();
+    fixture.jurors(JURY_SIZE);
+    let challenge = fixture.failed();
+    let creator = fixture.creator;
+    fixture.dispute(&creator, &challenge).unwrap();
+    let mut slot_hashes = fixture.svm.account(&sysvar::slot_hashes::ID).unwrap().clone();
+    slot_hashes.data = 0u64.to_le_bytes().to_vec();
+    fixture.svm.set_account(&sysvar::slot_hashes::ID, slot_hashes);
+    let outcome = fixture.escalate(&creator, &challenge);
+    assert_error(outcome, ErrorCode::SlotHashesUnavailable);
+}
+
+#[test]
+fn not_on_jury() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.escalated(JURY_SIZE + 1);
+    let jury = fixture.jury(&challenge);
+    let bystander = *accounts::fetch_juror_pool(&fixture.svm)
+        .unwrap()
+        .iter()
+        .find(|juror| !jury.contains(juror))
+        .unwrap();
+    let outcome = fixture.jury_vote(&bystander, &challenge, JuryVote::Overturn);
+    assert_error(outcome, ErrorCode::NotOnJury);
+}
+
+#[test]
+fn juror_already_voted() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.escalated(JURY_SIZE);
+    let juror = fixture.jury(&challenge)[0];
+    fixture.jury_vote(&juror, &challenge, JuryVote::Uphold).unwrap();
+    let outcome = fixture.jury_vote(&juror, &challenge, JuryVote::Overturn);
+    assert_error(outcome, ErrorCode::JurorAlreadyVoted);
+}
+
+#[test]
+fn jury_voting_closed() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.escalated(JURY_SIZE);
+    let juror = fixture.jury(&challenge)[0];
+    fixture.svm.advance(JURY_VOTING_PERIOD);
+    let outcome = fixture.jury_vote(&juror, &challenge, JuryVote::Uphold);
+    assert_error(outcome, ErrorCode::JuryVotingClosed);
+}
+
+#[test]
+fn jury_still_voting() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.escalated(JURY_SIZE);
+    let juror = fixture.jury(&challenge)[0];
+    fixture.jury_vote(&juror, &challenge, JuryVote::Overturn).unwrap();
+    assert_error(fixture.settle_jury(&challenge), ErrorCode::JuryStillVoting);
+}
+
+#[test]
+fn invalid_juror_accounts() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.escalated(JURY_SIZE);
+    fixture.svm.advance(JURY_VOTING_PERIOD);
+    let (creator, mut dispute) = (fixture.creator, fixture.dispute_record(&challenge));
+    let verifiers = fixture.verifiers.clone();
+    dispute.jury.swap(0, 1);
+    let outcome = fixture.process(instructions::settle_jury(&challenge, &dispute, &creator, &verifiers));
+    assert_error(outcome, ErrorCode::InvalidJurorAccounts);
+}
+
+#[test]
+fn dispute_already_resolved() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.escalated(JURY_SIZE);
+    let jury = fixture.jury(&challenge);
+    fixture.jury_vote(&jury[0], &challenge, JuryVote::Uphold).unwrap();
+    fixture.jury_vote(&jury[1], &challenge, JuryVote::Uphold).unwrap();
+    fixture.settle_jury(&challenge).unwrap();
+    let outcome = fixture.jury_vote(&jury[2], &challenge, JuryVote::Overturn);
+    assert_error(outcome, ErrorCode::DisputeAlreadyResolved);
+}
+
+#[test]
+fn invalid_disputer() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.failed();
+    let (creator, outsider) = (fixture.creator, fixture.outsider);
+    fixture.dispute(&creator, &challenge).unwrap();
+    // The bond would be refunded to someone other than the disputer
+    let mut dispute = fixture.dispute_record(&challenge);
+    dispute.disputer = outsider;
+    let verifiers = fixture.verifiers.clone();
+    let outcome = fixture.process(instructions::resolve_dispute(
+        &ADMIN_PUBKEY,
+        &challenge,
+        &dispute,
+        &creator,
+        &verifiers,
+        true,
+    ));
+    assert_error(outcome, ErrorCode::InvalidDisputer);
+}
+
+#[test]
+fn invalid_template_account() {
+    let mut fixture = Fixture::new();
+    let template = fixture.publish();
+    let failed = fixture.failed();
+    fixture.close_dispute_window(&failed);
+    // The failed challenge wasn't created from a template
+    let (creator, verifiers) = (fixture.creator, fixture.challenge(&failed).verifiers);
+    let challenge_id = u64::from(fixture.user().total_challenges);
+    let start_time = fixture.svm.now() + HOUR;
+    let outcome = fixture.process(instructions::retry_challenge(
+        &creator,
+        &failed,
+        Some(&template),
+        &verifiers,
+        challenge_id,
+        STAKE,
+        start_time,
+    ));
+    assert_error(outcome, ErrorCode::InvalidTemplateAccount);
+}
+
+#[test]
+fn already_disputed() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.failed();
+    let creator = fixture.creator;
+    fixture.dispute(&creator, &challenge).unwrap();
+    fixture.resolve(&challenge, false).unwrap();
+    let outcome = fixture.dispute(&creator, &challenge);
+    assert_error(outcome, ErrorCode::AlreadyDisputed);
+}
+
+#[test]
+fn invalid_grass_config() {
+    let mut fixture = Fixture::new();
+    let outcome = fixture.configure_grass(GrassEmission {
+        grass_per_sol: 0,
+        ..emission()
+    });
+    assert_error(outcome, ErrorCode::InvalidGrassConfig);
+
+    let outcome = fixture.configure_grass(GrassEmission {
+        fee_discount_bps: BASIS_POINTS + 1,
+        ..emission()
+    });
+    assert_error(outcome, ErrorCode::InvalidGrassConfig);
+}
+
+#[test]
+fn invalid_grass_accounts() {
+    let mut fixture = Fixture::new();
+    let completed = fixture.completed();
+    fixture.close_dispute_window(&completed);
+    let creator = fixture.creator;
+    fixture.claim(&creator, &completed, false).unwrap();
+
+    // Paying in GRASS without the token program
+    let mut args = fixture.args();
+    args.pay_fee_in_grass = true;
+    let challenge_id = u64::from(fixture.user().total_challenges);
+    let mut instruction = instructions::create_challenge(&creator, challenge_id, args.clone());
+    let token_program = instruction
+        .accounts
+        .iter_mut()
+        .find(|meta| meta.pubkey == anchor_spl::token::ID)
+        .unwrap();
+    token_program.pubkey = proof_of_touch_grass::ID;
+    assert_error(fixture.process(instruction), ErrorCode::InvalidGrassAccounts);
+
+    // Paying from someone else's GRASS
+    let approver = fixture.verifiers[0];
+    fixture.claim(&approver, &completed, true).unwrap();
+    let mut instruction = instructions::create_challenge(&creator, challenge_id, args);
+    let fee_account = instruction
+        .accounts
+        .iter_mut()
+        .find(|meta| meta.pubkey == pda::grass_account(&creator))
+        .unwrap();
+    fee_account.pubkey = pda::grass_account(&approver);
+    assert_error(fixture.process(instruction), ErrorCode::InvalidGrassAccounts);
+
+    // Claiming a reward into someone else's GRASS
+    let second = fixture.verifiers[1];
+    let verifiers = fixture.verifiers.clone();
+    let mut instruction =
+        instructions::claim_funds(&second, &completed, &creator, &verifiers, true, true);
+    let reward_account = instruction
+        .accounts
+        .iter_mut()
+        .find(|meta| meta.pubkey == pda::grass_account(&second))
+        .unwrap();
+    reward_account.pubkey = pda::grass_account(&approver);
+    assert_error(fixture.process(instruction), ErrorCode::InvalidGrassAccounts);
+}
+
+#[test]
+fn verifier_did_not_approve() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+    fixture.vote(2, &challenge, Vote::Reject).unwrap();
+    fixture.vote(0, &challenge, Vote::Approve).unwrap();
+    fixture.vote(1, &challenge, Vote::Approve).unwrap();
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Completed);
+    fixture.close_dispute_window(&challenge);
+
+    let rejecter = fixture.verifiers[2];
+    let outcome = fixture.claim(&rejecter, &challenge, true);
+    assert_error(outcome, ErrorCode::VerifierDidNotApprove);
+}
+
+#[test]
+fn invalid_platform_limits() {
+    let mut fixture = Fixture::new();
+    let outcome = fixture.set_platform_limits(PlatformLimits {
+        max_stake: 0,
+        ..PlatformLimits::default()
+    });
+    assert_error(outcome, ErrorCode::InvalidPlatformLimits);
+}
+
+#[test]
+fn duplicate_verifier() {
+    // One verifier listed twice would meet a 2-of-3 threshold alone
+    let outcome = create_with(|args| args.verifiers[2] = args.verifiers[0]);
+    assert_error(outcome, ErrorCode::DuplicateVerifier);
+}
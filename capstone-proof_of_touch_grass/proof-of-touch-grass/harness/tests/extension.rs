@@ -0,0 +1,145 @@
+//! Extending a challenge's end time through a request verifiers approve.
+
+mod common;
+
+use anchor_lang::error::ErrorCode as AnchorError;
+use anchor_lang::Space;
+use common::{Fixture, DAY, STAKE};
+use proof_of_touch_grass::constants::{MAX_EXTENSION, PLATFORM_PUBKEY};
+use proof_of_touch_grass::fees;
+use proof_of_touch_grass::state::{ChallengeStatus, ExtensionRequest};
+use proof_of_touch_grass_client::{accounts, instructions, pda};
+use proof_of_touch_grass_harness::program_error;
+
+#[test]
+fn approvals_up_to_the_threshold_apply_the_extension() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.active();
+    let before = fixture.challenge(&challenge);
+    let new_end_time = before.end_time + 3 * DAY;
+    fixture.request_extension(&challenge, new_end_time).unwrap();
+
+    fixture.approve_extension(2, &challenge).unwrap();
+    assert_eq!(fixture.challenge(&challenge).end_time, before.end_time);
+    assert_eq!(fixture.extension(&challenge).unwrap().approvals, [fixture.verifiers[2]]);
+
+    fixture.approve_extension(0, &challenge).unwrap();
+    let after = fixture.challenge(&challenge);
+    assert_eq!(after.end_time, new_end_time);
+    assert_eq!(
+        after.verification_period_end,
+        before.verification_period_end + 3 * DAY
+    );
+    assert!(fixture.extension(&challenge).is_none());
+
+    // Verifier inboxes carry the new deadline
+    let assignments = accounts::fetch_assignments(&fixture.svm, &fixture.verifiers[1]).unwrap();
+    assert_eq!(assignments[0].verification_period_end, after.verification_period_end);
+}
+
+#[test]
+fn the_fee_is_held_until_the_extension_applies() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.active();
+    let fee = fees::extension_fee(STAKE).unwrap();
+    let extension = pda::extension_pda(&challenge).0;
+    let rent = fixture.svm.minimum_balance(8 + ExtensionRequest::INIT_SPACE);
+    let creator_before = fixture.svm.lamports(&fixture.creator);
+    let platform_before = fixture.svm.lamports(&fixture.platform);
+
+    let end_time = fixture.challenge(&challenge).end_time;
+    fixture.request_extension(&challenge, end_time + DAY).unwrap();
+    assert_eq!(fixture.svm.lamports(&extension), rent + fee);
+    assert_eq!(fixture.svm.lamports(&fixture.creator), creator_before - rent - fee);
+
+    fixture.approve_extension(0, &challenge).unwrap();
+    fixture.approve_extension(1, &challenge).unwrap();
+    assert_eq!(fixture.svm.lamports(&extension), 0);
+    assert_eq!(fixture.svm.lamports(&fixture.platform), platform_before + fee);
+    // Only the fee is spent; the rent comes back
+    assert_eq!(fixture.svm.lamports(&fixture.creator), creator_before - fee);
+    // The escrow is untouched
+    assert_eq!(fixture.escrow(&challenge), fees::escrow_deposit(STAKE, false).unwrap());
+}
+
+#[test]
+fn the_fee_only_goes_to_the_platform_wallet() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.active();
+    let end_time = fixture.challenge(&challenge).end_time;
+    fixture.request_extension(&challenge, end_time + DAY).unwrap();
+    fixture.approve_extension(0, &challenge).unwrap();
+
+    // The approval that applies the extension cannot send the fee elsewhere
+    let (verifier, creator, outsider) = (fixture.verifiers[1], fixture.creator, fixture.outsider);
+    let verifiers = fixture.verifiers.clone();
+    let mut approve = instructions::approve_extension(&verifier, &challenge, &creator, &verifiers);
+    for meta in &mut approve.accounts {
+        if meta.pubkey == PLATFORM_PUBKEY {
+            meta.pubkey = outsider;
+        }
+    }
+    let outcome = fixture.process(approve);
+    assert_eq!(outcome, Err(program_error(AnchorError::ConstraintAddress)));
+    fixture.approve_extension(1, &challenge).unwrap();
+}
+
+#[test]
+fn withdrawing_refunds_the_fee_and_allows_a_new_request() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.create();
+    let creator_before = fixture.svm.lamports(&fixture.creator);
+    let end_time = fixture.challenge(&challenge).end_time;
+
+    fixture.request_extension(&challenge, end_time + DAY).unwrap();
+    fixture.approve_extension(0, &challenge).unwrap();
+    // A second request can't replace a pending one
+    assert!(fixture.request_extension(&challenge, end_time + 2 * DAY).is_err());
+
+    fixture.withdraw_extension(&challenge).unwrap();
+    assert_eq!(fixture.svm.lamports(&fixture.creator), creator_before);
+
+    // Approvals do not carry over to the new proposal
+    fixture.request_extension(&challenge, end_time + 2 * DAY).unwrap();
+    assert!(fixture.extension(&challenge).unwrap().approvals.is_empty());
+}
+
+#[test]
+fn extensions_are_bounded_and_only_for_running_challenges() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.active();
+    let end_time = fixture.challenge(&challenge).end_time;
+    assert!(fixture.request_extension(&challenge, end_time).is_err());
+    assert!(fixture
+        .request_extension(&challenge, end_time + MAX_EXTENSION + 1)
+        .is_err());
+    fixture
+        .request_extension(&challenge, end_time + MAX_EXTENSION)
+        .unwrap();
+    fixture.withdraw_extension(&challenge).unwrap();
+
+    // Once the end time has passed the challenge can no longer be extended
+    let pending = fixture.pending();
+    let end_time = fixture.challenge(&pending).end_time;
+    assert!(fixture.request_extension(&pending, end_time + DAY).is_err());
+    fixture.svm.warp_to(end_time);
+    assert!(fixture.request_extension(&challenge, end_time + DAY).is_err());
+}
+
+#[test]
+fn an_extension_keeps_a_challenge_running_past_its_old_end() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.active();
+    let end_time = fixture.challenge(&challenge).end_time;
+    fixture.request_extension(&challenge, end_time + 2 * DAY).unwrap();
+    fixture.approve_extension(0, &challenge).unwrap();
+    fixture.approve_extension(1, &challenge).unwrap();
+
+    // Without the extension this crank would fail the challenge
+    fixture.svm.warp_to(end_time + DAY);
+    fixture.crank(&challenge).unwrap();
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Active);
+    fixture.submit(&challenge).unwrap();
+    fixture.submit(&challenge).unwrap();
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::PendingVerification);
+}
@@ -0,0 +1,92 @@
+//! Fixed-point geofence math: radius boundaries, the antimeridian, high
+//! latitudes and the poles.
+
+use proof_of_touch_grass::constants::{MAX_LATITUDE, MAX_LONGITUDE, METERS_PER_DEGREE};
+use proof_of_touch_grass::geo::{within_box, within_radius};
+use proof_of_touch_grass::state::GeoPoint;
+
+fn point(latitude: i32, longitude: i32) -> GeoPoint {
+    GeoPoint { latitude, longitude }
+}
+
+#[test]
+fn a_point_exactly_on_the_radius_is_inside() {
+    let center = point(0, 0);
+    // One degree of latitude is exactly METERS_PER_DEGREE
+    let radius = METERS_PER_DEGREE as u32;
+    assert!(within_radius(&center, &point(1_000_000, 0), radius));
+    assert!(within_radius(&center, &point(-1_000_000, 0), radius));
+    assert!(within_radius(&center, &point(0, 1_000_000), radius));
+}
+
+#[test]
+fn a_point_just_past_the_radius_is_outside() {
+    let center = point(0, 0);
+    let radius = METERS_PER_DEGREE as u32;
+    assert!(!within_radius(&center, &point(1_000_001, 0), radius));
+    assert!(!within_radius(&center, &point(0, -1_000_001), radius));
+    assert!(!within_radius(&center, &point(1_000_000, 0), radius - 1));
+}
+
+#[test]
+fn distances_wrap_across_the_antimeridian() {
+    // 0.002° apart across the 180th meridian, about 222.4 m
+    let east = point(0, MAX_LONGITUDE - 1_000);
+    let west = point(0, -MAX_LONGITUDE + 1_000);
+    assert!(within_radius(&east, &west, 223));
+    assert!(within_radius(&west, &east, 223));
+    assert!(!within_radius(&east, &west, 222));
+}
+
+#[test]
+fn longitude_shrinks_with_latitude() {
+    // At 60° a degree of longitude is half as long, about 55_597.5 m
+    let center = point(60_000_000, 0);
+    let east = point(60_000_000, 1_000_000);
+    assert!(within_radius(&center, &east, 55_598));
+    assert!(!within_radius(&center, &east, 55_597));
+    // The same longitude offset at the equator is twice as far
+    assert!(!within_radius(&point(0, 0), &point(0, 1_000_000), 55_598));
+}
+
+#[test]
+fn every_longitude_meets_at_the_poles() {
+    for latitude in [MAX_LATITUDE, -MAX_LATITUDE] {
+        let pole = point(latitude, 0);
+        assert!(within_radius(&pole, &point(latitude, MAX_LONGITUDE), 1));
+        assert!(within_radius(&pole, &point(latitude, -123_456_789), 1));
+    }
+}
+
+#[test]
+fn near_the_pole_latitude_dominates() {
+    // 0.001° of latitude from the pole is about 111 m whatever the longitude
+    let pole = point(MAX_LATITUDE, 0);
+    let near = point(MAX_LATITUDE - 1_000, 123_000_000);
+    assert!(within_radius(&pole, &near, 200));
+    assert!(!within_radius(&pole, &near, 100));
+    let south = point(-MAX_LATITUDE + 1_000, -123_000_000);
+    assert!(within_radius(&point(-MAX_LATITUDE, 0), &south, 200));
+}
+
+#[test]
+fn box_edges_are_inclusive() {
+    let south_west = point(10_000_000, 20_000_000);
+    let north_east = point(11_000_000, 21_000_000);
+    assert!(within_box(&south_west, &north_east, &south_west));
+    assert!(within_box(&south_west, &north_east, &north_east));
+    assert!(!within_box(&south_west, &north_east, &point(9_999_999, 20_500_000)));
+    assert!(!within_box(&south_west, &north_east, &point(10_500_000, 21_000_001)));
+}
+
+#[test]
+fn a_box_can_wrap_the_antimeridian() {
+    let south_west = point(-10_000_000, 170_000_000);
+    let north_east = point(10_000_000, -170_000_000);
+    assert!(within_box(&south_west, &north_east, &point(0, MAX_LONGITUDE)));
+    assert!(within_box(&south_west, &north_east, &point(0, -MAX_LONGITUDE)));
+    assert!(within_box(&south_west, &north_east, &point(0, 175_000_000)));
+    assert!(within_box(&south_west, &north_east, &point(0, -175_000_000)));
+    assert!(!within_box(&south_west, &north_east, &point(0, 0)));
+    assert!(!within_box(&south_west, &north_east, &point(0, 169_999_999)));
+}
@@ -0,0 +1,318 @@
+//! GRASS: the program-owned mint and its emission config, the rewards minted
+//! to creators and winning verifiers on claim, and platform fees paid in
+//! GRASS at a discount.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::token::spl_token::error::TokenError;
+use anchor_spl::token::{self, Mint};
+use common::{emission, Fixture, GRASS, STAKE};
+use proof_of_touch_grass::constants::{
+    BASIS_POINTS, CANCEL_PENALTY_BPS, GRASS_DECIMALS, PLATFORM_FEE_BPS,
+};
+use proof_of_touch_grass::errors::ErrorCode;
+use proof_of_touch_grass::fees;
+use proof_of_touch_grass::state::{ChallengeStatus, GrassEmission, Vote};
+use proof_of_touch_grass_client::{accounts, instructions, pda};
+use proof_of_touch_grass_harness::{program_error, ExecutionError};
+
+const PLATFORM_FEE: u64 = STAKE * PLATFORM_FEE_BPS / BASIS_POINTS;
+
+fn mint(fixture: &Fixture) -> Mint {
+    let address = pda::grass_mint_pda().0;
+    assert_eq!(fixture.svm.account(&address).unwrap().owner, token::ID);
+    accounts::fetch(&fixture.svm, &address).unwrap().unwrap()
+}
+
+/// A completed challenge whose creator has claimed, earning the creator reward
+fn claimed(fixture: &mut Fixture) -> Pubkey {
+    let challenge = fixture.completed();
+    fixture.close_dispute_window(&challenge);
+    let creator = fixture.creator;
+    fixture.claim(&creator, &challenge, false).unwrap();
+    challenge
+}
+
+/// Creates a challenge paying the platform fee in GRASS
+fn create_paying_grass(fixture: &mut Fixture) -> Result<Pubkey, ExecutionError> {
+    let mut args = fixture.args();
+    args.pay_fee_in_grass = true;
+    fixture.try_create(args)
+}
+
+#[test]
+fn the_program_owns_the_mint() {
+    let fixture = Fixture::new();
+    let config = fixture.grass_config();
+    let mint = mint(&fixture);
+    assert_eq!(config.mint, pda::grass_mint_pda().0);
+    assert_eq!(config.emission, emission());
+    assert_eq!(config.total_emitted, 0);
+    assert_eq!(Option::from(mint.mint_authority), Some(pda::grass_config_pda().0));
+    assert_eq!(Option::<Pubkey>::from(mint.freeze_authority), None);
+    assert_eq!(mint.decimals, GRASS_DECIMALS);
+    assert_eq!(mint.supply, 0);
+}
+
+#[test]
+fn reconfiguring_keeps_the_mint_and_the_emitted_total() {
+    let mut fixture = Fixture::new();
+    claimed(&mut fixture);
+    let doubled = GrassEmission {
+        creator_reward: 200 * GRASS,
+        ..emission()
+    };
+    fixture.configure_grass(doubled.clone()).unwrap();
+
+    let config = fixture.grass_config();
+    assert_eq!(config.emission, doubled);
+    assert_eq!(config.mint, pda::grass_mint_pda().0);
+    assert_eq!(config.total_emitted, 100 * GRASS);
+    assert_eq!(mint(&fixture).supply, 100 * GRASS);
+}
+
+#[test]
+fn only_the_admin_configures_grass() {
+    let mut fixture = Fixture::new();
+    let outsider = fixture.outsider;
+    let configure = instructions::configure_grass(&outsider, GrassEmission::default());
+    assert_eq!(
+        fixture.process(configure),
+        Err(program_error(ErrorCode::UnauthorizedAdmin))
+    );
+    assert_eq!(fixture.grass_config().emission, emission());
+}
+
+#[test]
+fn a_completed_claim_rewards_the_creator() {
+    let mut fixture = Fixture::new();
+    let creator = fixture.creator;
+    claimed(&mut fixture);
+    assert_eq!(fixture.grass(&creator), 100 * GRASS);
+    assert_eq!(fixture.grass_config().total_emitted, 100 * GRASS);
+}
+
+#[test]
+fn approving_verifiers_claim_their_reward_from_a_completed_challenge() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.completed();
+    fixture.close_dispute_window(&challenge);
+    let approver = fixture.verifiers[0];
+    let approver_before = fixture.svm.lamports(&approver);
+    let escrow_before = fixture.escrow(&challenge);
+
+    // Approvers may claim before the creator; the escrow waits for the creator
+    fixture.claim(&approver, &challenge, true).unwrap();
+    assert_eq!(fixture.grass(&approver), 10 * GRASS);
+    assert_eq!(
+        fixture.svm.lamports(&approver),
+        approver_before - fixture.grass_rent(&approver)
+    );
+    assert_eq!(fixture.escrow(&challenge), escrow_before);
+    assert!(fixture.verification(&challenge, 0).claimed);
+    assert!(!fixture.challenge(&challenge).claimed);
+    assert_eq!(
+        fixture.claim(&approver, &challenge, true),
+        Err(program_error(ErrorCode::AlreadyClaimed))
+    );
+
+    let creator = fixture.creator;
+    fixture.claim(&creator, &challenge, false).unwrap();
+    let second = fixture.verifiers[1];
+    fixture.claim(&second, &challenge, true).unwrap();
+    assert_eq!(fixture.grass(&second), 10 * GRASS);
+    assert_eq!(fixture.grass_config().total_emitted, 120 * GRASS);
+
+    // The outcome is settled into reputations once, by the creator's claim
+    let reputation = fixture.reputation(&approver).unwrap();
+    assert_eq!(reputation.votes_won, 1);
+}
+
+#[test]
+fn rejecting_verifiers_are_rewarded_by_vote_weight() {
+    let mut fixture = Fixture::new();
+    let mut args = fixture.args();
+    args.verifier_weights = vec![2, 1, 1];
+    args.required_approval_weight = 3;
+    let challenge = fixture.try_create(args).unwrap();
+    fixture.svm.warp_to(fixture.challenge(&challenge).start_time);
+    fixture.crank(&challenge).unwrap();
+    fixture.submit(&challenge).unwrap();
+    fixture.submit(&challenge).unwrap();
+    fixture.vote(2, &challenge, Vote::Approve).unwrap();
+    fixture.vote(0, &challenge, Vote::Reject).unwrap();
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Failed);
+    fixture.close_dispute_window(&challenge);
+
+    let (heavy, approver) = (fixture.verifiers[0], fixture.verifiers[2]);
+    fixture.claim(&heavy, &challenge, true).unwrap();
+    assert_eq!(fixture.grass(&heavy), 20 * GRASS);
+
+    // Neither the losing side nor the failed creator earns anything
+    assert_eq!(
+        fixture.claim(&approver, &challenge, true),
+        Err(program_error(ErrorCode::VerifierDidNotReject))
+    );
+    assert_eq!(fixture.grass(&approver), 0);
+    assert_eq!(fixture.grass(&fixture.creator), 0);
+}
+
+#[test]
+fn challenges_without_a_verdict_mint_nothing() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.active();
+    let end = fixture.challenge(&challenge).end_time;
+    fixture.svm.warp_to(end + 1);
+    fixture.crank(&challenge).unwrap();
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Failed);
+    fixture.close_dispute_window(&challenge);
+
+    let creator = fixture.creator;
+    fixture.claim(&creator, &challenge, false).unwrap();
+    assert_eq!(fixture.grass(&creator), 0);
+    assert_eq!(fixture.grass_rent(&creator), 0);
+}
+
+#[test]
+fn claims_pay_out_before_grass_is_configured() {
+    let mut fixture = Fixture::without_grass();
+    assert!(!accounts::grass_configured(&fixture.svm).unwrap());
+    let creator = fixture.creator;
+    let completed = fixture.completed();
+    fixture.close_dispute_window(&completed);
+    fixture.claim(&creator, &completed, false).unwrap();
+    assert_eq!(fixture.escrow(&completed), 0);
+    assert_eq!(fixture.grass(&creator), 0);
+
+    // Verifiers still collect their slash share, with no reward to mint
+    let failed = fixture.failed();
+    fixture.close_dispute_window(&failed);
+    let rejecter = fixture.verifiers[0];
+    let before = fixture.svm.lamports(&rejecter);
+    fixture.claim(&rejecter, &failed, true).unwrap();
+    assert!(fixture.svm.lamports(&rejecter) > before);
+    assert_eq!(fixture.grass(&rejecter), 0);
+    assert!(fixture.svm.account(&pda::grass_mint_pda().0).is_none());
+}
+
+#[test]
+fn switching_emission_off_mints_nothing() {
+    let mut fixture = Fixture::new();
+    fixture
+        .configure_grass(GrassEmission {
+            creator_reward: 0,
+            verifier_reward: 0,
+            ..emission()
+        })
+        .unwrap();
+    let challenge = claimed(&mut fixture);
+    let approver = fixture.verifiers[0];
+    fixture.claim(&approver, &challenge, true).unwrap();
+
+    // No reward, so no token account for it either
+    let creator = fixture.creator;
+    assert_eq!(fixture.grass_rent(&creator), 0);
+    assert_eq!(fixture.grass_rent(&approver), 0);
+    assert_eq!(mint(&fixture).supply, 0);
+}
+
+#[test]
+fn the_grass_fee_is_the_discounted_sol_fee() {
+    // 0.005 SOL of fee at 1,000 GRASS to the SOL, less 20%
+    assert_eq!(fees::grass_fee(STAKE, &emission()).unwrap(), 4 * GRASS);
+    let full_price = GrassEmission {
+        fee_discount_bps: 0,
+        ..emission()
+    };
+    assert_eq!(fees::grass_fee(STAKE, &full_price).unwrap(), 5 * GRASS);
+    let free = GrassEmission {
+        fee_discount_bps: BASIS_POINTS,
+        ..emission()
+    };
+    assert_eq!(fees::grass_fee(STAKE, &free).unwrap(), 0);
+}
+
+#[test]
+fn paying_the_fee_in_grass_burns_it_and_escrows_only_the_stake() {
+    let mut fixture = Fixture::new();
+    claimed(&mut fixture);
+    let creator = fixture.creator;
+    let (grass_before, lamports_before) = (fixture.grass(&creator), fixture.svm.lamports(&creator));
+
+    let challenge = create_paying_grass(&mut fixture).unwrap();
+    let fee = fees::grass_fee(STAKE, &emission()).unwrap();
+    assert_eq!(fixture.grass(&creator), grass_before - fee);
+    assert_eq!(mint(&fixture).supply, grass_before - fee);
+    assert_eq!(fixture.escrow(&challenge), STAKE);
+    assert!(fixture.challenge(&challenge).fee_paid_in_grass);
+
+    // The creator paid the stake and the rent of their new accounts, no fee
+    let rent = fixture.svm.lamports(&challenge);
+    let paid = lamports_before - fixture.svm.lamports(&creator);
+    assert!(paid >= STAKE + rent && paid < STAKE + rent + PLATFORM_FEE);
+}
+
+#[test]
+fn a_challenge_paid_in_grass_returns_the_stake_without_a_bonus() {
+    let mut fixture = Fixture::new();
+    claimed(&mut fixture);
+    let challenge = create_paying_grass(&mut fixture).unwrap();
+    fixture.svm.warp_to(fixture.challenge(&challenge).start_time);
+    fixture.crank(&challenge).unwrap();
+    fixture.submit(&challenge).unwrap();
+    fixture.submit(&challenge).unwrap();
+    fixture.vote(0, &challenge, Vote::Approve).unwrap();
+    fixture.vote(1, &challenge, Vote::Approve).unwrap();
+    fixture.close_dispute_window(&challenge);
+
+    // The bonus comes out of the SOL fee, which this challenge never paid
+    let creator = fixture.creator;
+    let creator_before = fixture.svm.lamports(&creator);
+    let platform_before = fixture.svm.lamports(&fixture.platform);
+    let won_before = fixture.user().total_won;
+    fixture.claim(&creator, &challenge, false).unwrap();
+    assert_eq!(
+        fixture.svm.lamports(&creator),
+        creator_before + STAKE - fixture.badge_rent(&challenge)
+    );
+    assert_eq!(fixture.svm.lamports(&fixture.platform), platform_before);
+    assert_eq!(fixture.escrow(&challenge), 0);
+    assert_eq!(fixture.user().total_won, won_before);
+}
+
+#[test]
+fn cancelling_a_challenge_paid_in_grass_refunds_only_the_stake() {
+    let mut fixture = Fixture::new();
+    claimed(&mut fixture);
+    let challenge = create_paying_grass(&mut fixture).unwrap();
+    fixture.svm.warp_to(fixture.challenge(&challenge).start_time);
+    fixture.crank(&challenge).unwrap();
+
+    let creator = fixture.creator;
+    let grass_before = fixture.grass(&creator);
+    let creator_before = fixture.svm.lamports(&creator);
+    fixture.cancel(&challenge).unwrap();
+    let penalty = STAKE * CANCEL_PENALTY_BPS / BASIS_POINTS;
+    assert_eq!(fixture.svm.lamports(&creator), creator_before + STAKE - penalty);
+    assert_eq!(fixture.escrow(&challenge), 0);
+    // The burned fee stays burned
+    assert_eq!(fixture.grass(&creator), grass_before);
+}
+
+#[test]
+fn a_creator_without_enough_grass_cannot_pay_in_grass() {
+    let mut fixture = Fixture::new();
+    fixture
+        .configure_grass(GrassEmission {
+            creator_reward: GRASS,
+            ..emission()
+        })
+        .unwrap();
+    claimed(&mut fixture);
+
+    assert_eq!(
+        create_paying_grass(&mut fixture),
+        Err(ExecutionError::Program(TokenError::InsufficientFunds.into()))
+    );
+}
@@ -0,0 +1,121 @@
+//! Per-verifier inboxes listing the open challenges they are assigned to.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::Fixture;
+use proof_of_touch_grass::state::{Assignment, VerifierInbox, Vote};
+use proof_of_touch_grass_client::{accounts, pda};
+
+fn assigned(fixture: &Fixture, verifier: usize) -> Vec<Pubkey> {
+    accounts::fetch_assignments(&fixture.svm, &fixture.verifiers[verifier])
+        .unwrap()
+        .into_iter()
+        .map(|assignment| assignment.challenge)
+        .collect()
+}
+
+fn inbox_size(fixture: &Fixture, verifier: usize) -> usize {
+    let address = pda::inbox_pda(&fixture.verifiers[verifier]).0;
+    fixture.svm.account(&address).unwrap().data.len()
+}
+
+#[test]
+fn creating_assigns_every_verifier_with_the_deadline() {
+    let mut fixture = Fixture::new();
+    assert!(assigned(&fixture, 0).is_empty());
+
+    let challenge = fixture.create();
+    let period_end = fixture.challenge(&challenge).verification_period_end;
+    for verifier in fixture.verifiers.clone() {
+        let inbox = accounts::fetch_inbox(&fixture.svm, &pda::inbox_pda(&verifier).0).unwrap();
+        assert_eq!(inbox.verifier, verifier);
+        assert_eq!(
+            inbox.assignments,
+            [Assignment {
+                challenge,
+                verification_period_end: period_end,
+            }]
+        );
+    }
+}
+
+#[test]
+fn only_the_listed_verifiers_are_assigned() {
+    let mut fixture = Fixture::new();
+    let mut args = fixture.args();
+    args.verifiers = vec![fixture.verifiers[1]];
+    args.required_approval_weight = 1;
+    let challenge = fixture.try_create(args).unwrap();
+
+    assert!(assigned(&fixture, 0).is_empty());
+    assert_eq!(assigned(&fixture, 1), [challenge]);
+}
+
+#[test]
+fn finalizing_by_vote_clears_every_inbox() {
+    let mut fixture = Fixture::new();
+    let open = fixture.create();
+    let challenge = fixture.pending();
+    fixture.vote(0, &challenge, Vote::Approve).unwrap();
+    // Voting alone leaves the assignment open until the outcome is known
+    assert_eq!(assigned(&fixture, 0), [open, challenge]);
+
+    fixture.vote(1, &challenge, Vote::Approve).unwrap();
+    for verifier in 0..3 {
+        assert_eq!(assigned(&fixture, verifier), [open]);
+    }
+}
+
+#[test]
+fn cranking_to_an_outcome_clears_every_inbox() {
+    let mut fixture = Fixture::new();
+    // Runs out of time without evidence → Failed
+    let missed = fixture.active();
+    let end_time = fixture.challenge(&missed).end_time;
+    fixture.svm.warp_to(end_time + 1);
+    fixture.crank(&missed).unwrap();
+    assert!(assigned(&fixture, 2).is_empty());
+
+    // Nobody votes before the verification period ends → Completed
+    let challenge = fixture.pending();
+    assert_eq!(assigned(&fixture, 2), [challenge]);
+    let period_end = fixture.challenge(&challenge).verification_period_end;
+    fixture.svm.warp_to(period_end + 1);
+    fixture.crank(&challenge).unwrap();
+    assert!(assigned(&fixture, 2).is_empty());
+}
+
+#[test]
+fn cancelling_clears_every_inbox() {
+    let mut fixture = Fixture::new();
+    let unstarted = fixture.create();
+    let started = fixture.active();
+    fixture.cancel(&unstarted).unwrap();
+    assert_eq!(assigned(&fixture, 0), [started]);
+    fixture.cancel(&started).unwrap();
+    assert!(assigned(&fixture, 0).is_empty());
+}
+
+#[test]
+fn inboxes_grow_once_and_reuse_freed_room() {
+    let mut fixture = Fixture::new();
+    let first = fixture.create();
+    let second = fixture.create();
+    assert_eq!(inbox_size(&fixture, 0), VerifierInbox::space(2));
+
+    fixture.cancel(&first).unwrap();
+    fixture.cancel(&second).unwrap();
+    fixture.create();
+    assert_eq!(inbox_size(&fixture, 0), VerifierInbox::space(2));
+}
+
+#[test]
+fn prefunded_inbox_addresses_are_still_created() {
+    let mut fixture = Fixture::new();
+    let address = pda::inbox_pda(&fixture.verifiers[0]).0;
+    fixture.svm.airdrop(&address, 1_000);
+
+    let challenge = fixture.create();
+    assert_eq!(assigned(&fixture, 0), [challenge]);
+}
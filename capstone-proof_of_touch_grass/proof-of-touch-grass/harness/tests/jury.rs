@@ -0,0 +1,255 @@
+//! Juror pool and escalated disputes: drawing the jury, voting, settling the
+//! verdict and the jurors' stakes, and leaving the pool.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Space;
+use common::Fixture;
+use proof_of_touch_grass::constants::*;
+use proof_of_touch_grass::errors::ErrorCode;
+use proof_of_touch_grass::jury;
+use proof_of_touch_grass::state::{ChallengeStatus, Juror, JuryVote};
+use proof_of_touch_grass_client::{accounts, instructions, pda};
+use proof_of_touch_grass_harness::program_error;
+use solana_transaction::Transaction;
+
+const PENALTY: u64 = MIN_JUROR_STAKE / 10;
+/// Largest serialized transaction a validator accepts
+const PACKET_DATA_SIZE: usize = 1_232;
+
+fn stake(fixture: &Fixture, juror: &Pubkey) -> u64 {
+    fixture.juror(juror).unwrap().stake
+}
+
+#[test]
+fn registering_joins_the_pool_and_bonds_the_stake() {
+    let mut fixture = Fixture::new();
+    let jurors = fixture.jurors(2);
+
+    assert_eq!(accounts::fetch_juror_pool(&fixture.svm).unwrap(), jurors);
+    let juror = fixture.juror(&jurors[0]).unwrap();
+    assert_eq!(juror.stake, MIN_JUROR_STAKE);
+    assert_eq!(juror.unbonding_at, 0);
+    assert_eq!(
+        fixture.svm.lamports(&pda::juror_pda(&jurors[0]).0),
+        fixture.svm.minimum_balance(8 + Juror::INIT_SPACE) + MIN_JUROR_STAKE
+    );
+}
+
+#[test]
+fn the_jury_leaves_out_the_creator_and_verifiers() {
+    let mut fixture = Fixture::new();
+    // Every party to the challenge is in the pool too
+    let parties: Vec<_> = fixture.verifiers.iter().chain([&fixture.creator]).copied().collect();
+    for party in &parties {
+        fixture.svm.airdrop(party, MIN_JUROR_STAKE);
+        fixture.register_juror(party, MIN_JUROR_STAKE).unwrap();
+    }
+    let challenge = fixture.escalated(3);
+
+    let jury = fixture.jury(&challenge);
+    assert_eq!(jury.len(), JURY_SIZE);
+    assert!(jury.iter().all(|juror| !parties.contains(juror)));
+    let dispute = fixture.dispute_record(&challenge);
+    assert_eq!(dispute.escalated_at, fixture.svm.now());
+    assert!(dispute.jury.iter().all(|seat| seat.vote.is_none()));
+}
+
+#[test]
+fn draws_are_distinct_and_follow_the_seed() {
+    let pool: Vec<_> = (0..10).map(|_| Pubkey::new_unique()).collect();
+    let excluded = &pool[..2];
+    let draws: Vec<_> = (0..20).map(|seed| jury::draw(&pool, excluded, seed).unwrap()).collect();
+
+    for jury in &draws {
+        assert_eq!(jury.len(), JURY_SIZE);
+        assert!(jury.iter().all(|juror| !excluded.contains(juror)));
+        assert!(jury.iter().enumerate().all(|(i, juror)| !jury[..i].contains(juror)));
+    }
+    assert_eq!(draws[0], jury::draw(&pool, excluded, 0).unwrap());
+    assert!(draws.iter().any(|jury| *jury != draws[0]));
+}
+
+#[test]
+fn a_majority_overturns_early_and_the_minority_pays_it() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.escalated(3);
+    let jury = fixture.jury(&challenge);
+
+    fixture.jury_vote(&jury[0], &challenge, JuryVote::Overturn).unwrap();
+    fixture.jury_vote(&jury[1], &challenge, JuryVote::Uphold).unwrap();
+    assert!(fixture.settle_jury(&challenge).is_err());
+    fixture.jury_vote(&jury[2], &challenge, JuryVote::Overturn).unwrap();
+    fixture.settle_jury(&challenge).unwrap();
+
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Completed);
+    let user = fixture.user();
+    assert_eq!((user.completed, user.failed), (1, 0));
+    // Both verifiers rejected the challenge the jury completed
+    for verifier in &fixture.verifiers[..2] {
+        assert_eq!(fixture.reputation(verifier).unwrap().votes_overturned, 1);
+    }
+
+    assert_eq!(stake(&fixture, &jury[1]), MIN_JUROR_STAKE - PENALTY);
+    assert_eq!(stake(&fixture, &jury[0]), MIN_JUROR_STAKE + PENALTY / 2);
+    assert_eq!(stake(&fixture, &jury[2]), MIN_JUROR_STAKE + PENALTY / 2);
+    let majority = fixture.juror(&jury[0]).unwrap();
+    assert_eq!((majority.cases, majority.majority_votes, majority.pending_verdicts), (1, 1, 0));
+    assert_eq!(fixture.juror(&jury[1]).unwrap().minority_votes, 1);
+}
+
+#[test]
+fn without_a_majority_the_outcome_stands_once_voting_ends() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.escalated(4);
+    let jury = fixture.jury(&challenge);
+    fixture.jury_vote(&jury[0], &challenge, JuryVote::Uphold).unwrap();
+    assert!(fixture.settle_jury(&challenge).is_err());
+
+    let deadline = fixture.dispute_record(&challenge).jury_deadline().unwrap();
+    fixture.svm.warp_to(deadline);
+    assert!(fixture.jury_vote(&jury[1], &challenge, JuryVote::Overturn).is_err());
+    fixture.settle_jury(&challenge).unwrap();
+
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Failed);
+    // Jurors who never voted pay the one who did
+    assert_eq!(stake(&fixture, &jury[0]), MIN_JUROR_STAKE + 2 * PENALTY);
+    assert_eq!(stake(&fixture, &jury[1]), MIN_JUROR_STAKE - PENALTY);
+    assert_eq!(stake(&fixture, &jury[2]), MIN_JUROR_STAKE - PENALTY);
+}
+
+#[test]
+fn nobody_pays_when_no_juror_votes() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.escalated(3);
+    let deadline = fixture.dispute_record(&challenge).jury_deadline().unwrap();
+    fixture.svm.warp_to(deadline);
+    fixture.settle_jury(&challenge).unwrap();
+
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Failed);
+    for juror in fixture.jury(&challenge) {
+        let juror = fixture.juror(&juror).unwrap();
+        assert_eq!((juror.stake, juror.minority_votes), (MIN_JUROR_STAKE, 1));
+    }
+}
+
+#[test]
+fn an_escalated_dispute_is_the_jury_s_alone() {
+    let mut fixture = Fixture::new();
+    fixture.jurors(3);
+    let challenge = fixture.failed();
+    let (creator, outsider) = (fixture.creator, fixture.outsider);
+    fixture.dispute(&creator, &challenge).unwrap();
+
+    assert!(fixture.escalate(&outsider, &challenge).is_err());
+    fixture.escalate(&ADMIN_PUBKEY, &challenge).unwrap();
+    assert!(fixture.escalate(&creator, &challenge).is_err());
+    assert!(fixture.resolve(&challenge, true).is_err());
+}
+
+#[test]
+fn a_leaving_juror_is_bonded_until_their_verdicts_settle() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.escalated(3);
+    let juror = fixture.jury(&challenge)[0];
+    fixture.jury_vote(&juror, &challenge, JuryVote::Uphold).unwrap();
+
+    fixture.process(instructions::unregister_juror(&juror)).unwrap();
+    assert!(!accounts::fetch_juror_pool(&fixture.svm).unwrap().contains(&juror));
+    let withdraw = instructions::withdraw_juror_stake(&juror);
+    assert!(fixture.process(withdraw.clone()).is_err());
+
+    // Unbonded, but the verdict they voted on is still open
+    fixture.svm.advance(JUROR_UNBONDING_PERIOD);
+    assert!(fixture.process(withdraw.clone()).is_err());
+    fixture.settle_jury(&challenge).unwrap();
+
+    let before = fixture.svm.lamports(&juror);
+    let held = fixture.svm.lamports(&pda::juror_pda(&juror).0);
+    fixture.process(withdraw).unwrap();
+    assert_eq!(fixture.svm.lamports(&juror), before + held);
+    assert!(fixture.juror(&juror).is_none());
+}
+
+#[test]
+fn a_silent_juror_stays_bonded_until_settlement() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.escalated(3);
+    let jury = fixture.jury(&challenge);
+    fixture.jury_vote(&jury[0], &challenge, JuryVote::Overturn).unwrap();
+    fixture.jury_vote(&jury[1], &challenge, JuryVote::Overturn).unwrap();
+
+    // The silent juror leaves but can't withdraw before anyone settles
+    fixture.process(instructions::unregister_juror(&jury[2])).unwrap();
+    fixture.svm.advance(JUROR_UNBONDING_PERIOD);
+    let withdraw = instructions::withdraw_juror_stake(&jury[2]);
+    assert_eq!(
+        fixture.process(withdraw.clone()),
+        Err(program_error(ErrorCode::JurorStillBonded))
+    );
+    fixture.settle_jury(&challenge).unwrap();
+
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Completed);
+    // Voting had closed by the time it settled, so the silent seat pays
+    assert_eq!(stake(&fixture, &jury[2]), MIN_JUROR_STAKE - PENALTY);
+    assert_eq!(stake(&fixture, &jury[0]), MIN_JUROR_STAKE + PENALTY / 2);
+    fixture.process(withdraw).unwrap();
+    assert!(fixture.juror(&jury[2]).is_none());
+}
+
+#[test]
+fn drawn_jurors_are_bonded_before_they_vote() {
+    let mut fixture = Fixture::new();
+    let pool = fixture.jurors(4);
+    let challenge = fixture.failed();
+    let creator = fixture.creator;
+    fixture.dispute(&creator, &challenge).unwrap();
+    fixture.escalate(&creator, &challenge).unwrap();
+
+    let jury = fixture.jury(&challenge);
+    for juror in &pool {
+        let pending = fixture.juror(juror).unwrap().pending_verdicts;
+        assert_eq!(pending, u8::from(jury.contains(juror)));
+    }
+}
+
+#[test]
+fn a_full_pool_escalates_in_one_legacy_transaction() {
+    let mut fixture = Fixture::new();
+    let pool = fixture.jurors(MAX_JURORS);
+    let challenge = fixture.failed();
+    let creator = fixture.creator;
+    fixture.dispute(&creator, &challenge).unwrap();
+
+    let dispute_index = fixture.dispute_record(&challenge).index;
+    let instruction = instructions::escalate_dispute(&creator, &challenge, dispute_index, &pool);
+    let transaction =
+        Transaction::new_with_payer(std::slice::from_ref(&instruction), Some(&creator));
+    let size = bincode::serialized_size(&transaction).unwrap() as usize;
+    assert!(size <= PACKET_DATA_SIZE, "{size} bytes");
+    // One more juror account, a 32-byte key and a 1-byte index, would not fit
+    assert!(size + 33 > PACKET_DATA_SIZE, "{size} bytes");
+
+    fixture.process(instruction).unwrap();
+    assert_eq!(fixture.jury(&challenge).len(), JURY_SIZE);
+}
+
+#[test]
+fn an_early_verdict_leaves_the_silent_juror_alone() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.escalated(3);
+    let jury = fixture.jury(&challenge);
+    fixture.jury_vote(&jury[0], &challenge, JuryVote::Uphold).unwrap();
+    fixture.jury_vote(&jury[1], &challenge, JuryVote::Uphold).unwrap();
+    fixture.settle_jury(&challenge).unwrap();
+
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Failed);
+    let silent = fixture.juror(&jury[2]).unwrap();
+    assert_eq!(silent.stake, MIN_JUROR_STAKE);
+    assert_eq!(
+        (silent.cases, silent.majority_votes, silent.minority_votes, silent.pending_verdicts),
+        (1, 0, 0, 0)
+    );
+    assert_eq!(stake(&fixture, &jury[0]), MIN_JUROR_STAKE);
+}
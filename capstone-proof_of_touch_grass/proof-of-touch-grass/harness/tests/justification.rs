@@ -0,0 +1,99 @@
+//! Vote justifications and disputes that name the contested verification.
+
+mod common;
+
+use common::Fixture;
+use proof_of_touch_grass::state::{ChallengeStatus, Justification, Vote};
+use proof_of_touch_grass_client::pda;
+
+fn blurry() -> Justification {
+    Justification {
+        reason: "The page count isn't readable".to_string(),
+        attachment: Some("QmAnnotatedScreenshot".to_string()),
+    }
+}
+
+#[test]
+fn a_justification_is_stored_with_the_vote() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+    fixture
+        .justified_vote(0, &challenge, Vote::Reject, Some(blurry()))
+        .unwrap();
+    fixture.vote(1, &challenge, Vote::Reject).unwrap();
+
+    assert_eq!(fixture.verification(&challenge, 0).justification, Some(blurry()));
+    assert_eq!(fixture.verification(&challenge, 1).justification, None);
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Failed);
+}
+
+#[test]
+fn the_attachment_is_optional() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+    let reason_only = Justification {
+        attachment: None,
+        ..blurry()
+    };
+    fixture
+        .justified_vote(0, &challenge, Vote::Abstain, Some(reason_only.clone()))
+        .unwrap();
+    assert_eq!(fixture.verification(&challenge, 0).justification, Some(reason_only));
+}
+
+#[test]
+fn changing_a_vote_replaces_its_justification() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+    fixture
+        .justified_vote(0, &challenge, Vote::Reject, Some(blurry()))
+        .unwrap();
+
+    let second_look = Justification {
+        reason: "Found the count in the second photo".to_string(),
+        attachment: None,
+    };
+    fixture
+        .justified_change(0, &challenge, Vote::Approve, Some(second_look.clone()))
+        .unwrap();
+    assert_eq!(fixture.verification(&challenge, 0).justification, Some(second_look));
+
+    // A change without a justification clears the old one
+    fixture.change_vote(0, &challenge, Vote::Abstain).unwrap();
+    assert_eq!(fixture.verification(&challenge, 0).justification, None);
+}
+
+#[test]
+fn a_dispute_can_name_the_contested_verification() {
+    let mut fixture = Fixture::new();
+    let failed = fixture.failed();
+    let (creator, verifier) = (fixture.creator, fixture.verifiers[1]);
+    fixture.contest(&creator, &failed, Some(&verifier)).unwrap();
+
+    let dispute = fixture.dispute_record(&failed);
+    assert_eq!(
+        dispute.contested_verification,
+        Some(pda::verification_pda(&failed, &verifier).0)
+    );
+    assert_eq!(fixture.status(&failed), ChallengeStatus::Disputed);
+}
+
+#[test]
+fn naming_a_verification_is_optional() {
+    let mut fixture = Fixture::new();
+    let failed = fixture.failed();
+    let creator = fixture.creator;
+    fixture.dispute(&creator, &failed).unwrap();
+    assert_eq!(fixture.dispute_record(&failed).contested_verification, None);
+}
+
+#[test]
+fn only_cast_votes_can_be_contested() {
+    let mut fixture = Fixture::new();
+    let failed = fixture.failed();
+    // The third verifier never voted, so there is no verification to name
+    let (creator, silent) = (fixture.creator, fixture.verifiers[2]);
+    assert!(fixture.contest(&creator, &failed, Some(&silent)).is_err());
+    let voter = fixture.verifiers[0];
+    fixture.contest(&creator, &failed, Some(&voter)).unwrap();
+}
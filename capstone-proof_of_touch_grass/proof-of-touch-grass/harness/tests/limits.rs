@@ -0,0 +1,184 @@
+//! Self-imposed user limits and the admin's platform ceilings: tightening
+//! applies at once, loosening only after `LIMITS_LOOSENING_DELAY`, and no
+//! user limit reaches past the ceilings.
+
+mod common;
+
+use common::{Fixture, DAY, STAKE};
+use proof_of_touch_grass::constants::LIMITS_LOOSENING_DELAY;
+use proof_of_touch_grass::errors::ErrorCode;
+use proof_of_touch_grass::state::{PlatformLimits, UserLimits};
+use proof_of_touch_grass_client::{accounts, instructions};
+use proof_of_touch_grass_harness::program_error;
+
+fn one_at_a_time() -> UserLimits {
+    UserLimits {
+        max_active_challenges: Some(1),
+        ..UserLimits::default()
+    }
+}
+
+#[test]
+fn tightening_applies_at_once() {
+    let mut fixture = Fixture::new();
+    fixture.set_limits(one_at_a_time()).unwrap();
+    let user = fixture.user();
+    assert_eq!(user.limits, one_at_a_time());
+    assert_eq!(user.pending_limits, None);
+
+    fixture.create();
+    let args = fixture.args();
+    assert_eq!(
+        fixture.try_create(args).map(|_| ()),
+        Err(program_error(ErrorCode::TooManyActiveChallenges))
+    );
+}
+
+#[test]
+fn loosening_waits_out_the_delay() {
+    let mut fixture = Fixture::new();
+    fixture.set_limits(one_at_a_time()).unwrap();
+    fixture.create();
+
+    // Lifting the limit in the same breath as a new challenge does not work
+    fixture.set_limits(UserLimits::default()).unwrap();
+    let user = fixture.user();
+    assert_eq!(user.limits, one_at_a_time());
+    assert_eq!(user.pending_limits, Some(UserLimits::default()));
+    assert_eq!(user.pending_limits_at, fixture.svm.now() + LIMITS_LOOSENING_DELAY);
+    let args = fixture.args();
+    assert_eq!(
+        fixture.try_create(args).map(|_| ()),
+        Err(program_error(ErrorCode::TooManyActiveChallenges))
+    );
+
+    fixture.svm.advance(LIMITS_LOOSENING_DELAY);
+    fixture.create();
+}
+
+#[test]
+fn the_tightened_part_of_a_mixed_change_applies_at_once() {
+    let mut fixture = Fixture::new();
+    fixture.set_limits(one_at_a_time()).unwrap();
+
+    let requested = UserLimits {
+        max_active_challenges: Some(2),
+        max_stake: Some(STAKE - 1),
+        ..UserLimits::default()
+    };
+    fixture.set_limits(requested.clone()).unwrap();
+    let user = fixture.user();
+    assert_eq!(
+        user.limits,
+        UserLimits {
+            max_active_challenges: Some(1),
+            max_stake: Some(STAKE - 1),
+            ..UserLimits::default()
+        }
+    );
+    assert_eq!(user.pending_limits, Some(requested));
+    let args = fixture.args();
+    assert_eq!(
+        fixture.try_create(args).map(|_| ()),
+        Err(program_error(ErrorCode::StakeAboveLimit))
+    );
+}
+
+#[test]
+fn tightening_again_cancels_a_scheduled_loosening() {
+    let mut fixture = Fixture::new();
+    fixture.set_limits(one_at_a_time()).unwrap();
+    fixture.set_limits(UserLimits::default()).unwrap();
+    fixture.set_limits(one_at_a_time()).unwrap();
+    assert_eq!(fixture.user().pending_limits, None);
+
+    fixture.svm.advance(LIMITS_LOOSENING_DELAY);
+    fixture.create();
+    let args = fixture.args();
+    assert_eq!(
+        fixture.try_create(args).map(|_| ()),
+        Err(program_error(ErrorCode::TooManyActiveChallenges))
+    );
+}
+
+#[test]
+fn a_matured_cooldown_applies_to_the_next_failure() {
+    let mut fixture = Fixture::new();
+    fixture
+        .set_limits(UserLimits {
+            failure_cooldown: Some(DAY),
+            ..UserLimits::default()
+        })
+        .unwrap();
+    fixture
+        .set_limits(UserLimits {
+            failure_cooldown: Some(DAY / 2),
+            ..UserLimits::default()
+        })
+        .unwrap();
+    assert_eq!(fixture.user().limits.failure_cooldown, Some(DAY));
+
+    fixture.svm.advance(LIMITS_LOOSENING_DELAY);
+    fixture.failed();
+    let user = fixture.user();
+    assert_eq!(user.cooldown_until, fixture.svm.now() + DAY / 2);
+}
+
+#[test]
+fn platform_limits_default_until_the_admin_sets_them() {
+    let mut fixture = Fixture::new();
+    assert_eq!(
+        accounts::fetch_platform_limits(&fixture.svm).unwrap(),
+        PlatformLimits::default()
+    );
+
+    let limits = PlatformLimits {
+        max_stake: STAKE / 2,
+        ..PlatformLimits::default()
+    };
+    fixture.set_platform_limits(limits.clone()).unwrap();
+    assert_eq!(accounts::fetch_platform_limits(&fixture.svm).unwrap(), limits);
+
+    // Only the admin sets them
+    let creator = fixture.creator;
+    assert_eq!(
+        fixture.process(instructions::set_platform_limits(&creator, PlatformLimits::default())),
+        Err(program_error(ErrorCode::UnauthorizedAdmin))
+    );
+}
+
+#[test]
+fn the_platform_ceiling_caps_every_user_limit() {
+    let mut fixture = Fixture::new();
+    fixture
+        .set_limits(UserLimits {
+            max_stake: Some(STAKE),
+            ..UserLimits::default()
+        })
+        .unwrap();
+    fixture
+        .set_platform_limits(PlatformLimits {
+            max_stake: STAKE / 2,
+            ..PlatformLimits::default()
+        })
+        .unwrap();
+
+    // The user limit set earlier no longer reaches past the lowered ceiling
+    let args = fixture.args();
+    assert_eq!(
+        fixture.try_create(args).map(|_| ()),
+        Err(program_error(ErrorCode::StakeAboveLimit))
+    );
+    // Nor can a new one be set above it
+    assert_eq!(
+        fixture.set_limits(UserLimits {
+            max_stake: Some(STAKE),
+            ..UserLimits::default()
+        }),
+        Err(program_error(ErrorCode::InvalidUserLimits))
+    );
+
+    let mut args = fixture.args();
+    args.stake_amount = STAKE / 2;
+    fixture.try_create(args).unwrap();
+}
@@ -0,0 +1,274 @@
+//! Lamport flows of `create_challenge`, `cancel_challenge` and both
+//! `claim_funds` paths.
+
+mod common;
+
+use anchor_lang::error::ErrorCode as AnchorError;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::Space;
+use common::{Fixture, STAKE};
+use proof_of_touch_grass::constants::{
+    BASIS_POINTS, CANCEL_PENALTY_BPS, CREATOR_BONUS_BPS, PLATFORM_FEE_BPS, PLATFORM_PUBKEY,
+    SLASH_PENALTY_BPS,
+};
+use proof_of_touch_grass::state::{
+    ChallengeIndex, ChallengeStatus, VerifierInbox, VerifierReputation, Vote,
+};
+use proof_of_touch_grass_client::{instructions, pda};
+use proof_of_touch_grass_harness::program_error;
+
+const PLATFORM_FEE: u64 = STAKE * PLATFORM_FEE_BPS / BASIS_POINTS;
+
+#[test]
+fn create_moves_stake_and_platform_fee_into_escrow() {
+    let mut fixture = Fixture::new();
+    let index = pda::challenge_index_pda(&fixture.creator).0;
+    let before = fixture.svm.lamports(&fixture.creator);
+    let index_before = fixture.svm.lamports(&index);
+    let challenge = fixture.create();
+
+    assert_eq!(PLATFORM_FEE, 5_000_000);
+    assert_eq!(fixture.escrow(&challenge), STAKE + PLATFORM_FEE);
+
+    // The creator also pays rent for the challenge account, for the
+    // challenge index growing by one ID and for each verifier's new inbox
+    // and reputation record
+    let rent = fixture.svm.lamports(&challenge);
+    let index_rent = fixture.svm.lamports(&index) - index_before;
+    assert_eq!(
+        index_rent,
+        fixture.svm.minimum_balance(ChallengeIndex::space(1))
+            - fixture.svm.minimum_balance(ChallengeIndex::space(0))
+    );
+    let inbox_rent = fixture.svm.minimum_balance(VerifierInbox::space(1));
+    let reputation_rent = fixture.svm.minimum_balance(8 + VerifierReputation::INIT_SPACE);
+    let verifiers_rent = (inbox_rent + reputation_rent) * fixture.verifiers.len() as u64;
+    assert_eq!(
+        fixture.svm.lamports(&fixture.creator),
+        before - STAKE - PLATFORM_FEE - rent - index_rent - verifiers_rent
+    );
+}
+
+#[test]
+fn cancelling_before_start_refunds_everything() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.create();
+    let creator_before = fixture.svm.lamports(&fixture.creator);
+    let platform_before = fixture.svm.lamports(&fixture.platform);
+
+    fixture.cancel(&challenge).unwrap();
+
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Cancelled);
+    assert_eq!(
+        fixture.svm.lamports(&fixture.creator),
+        creator_before + STAKE + PLATFORM_FEE
+    );
+    assert_eq!(fixture.svm.lamports(&fixture.platform), platform_before);
+    assert_eq!(fixture.escrow(&challenge), 0);
+}
+
+#[test]
+fn cancelling_an_active_challenge_costs_the_penalty() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.active();
+    fixture.submit(&challenge).unwrap();
+    let creator_before = fixture.svm.lamports(&fixture.creator);
+    let platform_before = fixture.svm.lamports(&fixture.platform);
+
+    fixture.cancel(&challenge).unwrap();
+
+    let penalty = STAKE * CANCEL_PENALTY_BPS / BASIS_POINTS;
+    assert_eq!(penalty, 20_000_000);
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Cancelled);
+    assert_eq!(
+        fixture.svm.lamports(&fixture.creator),
+        creator_before + STAKE + PLATFORM_FEE - penalty
+    );
+    assert_eq!(
+        fixture.svm.lamports(&fixture.platform),
+        platform_before + penalty
+    );
+    assert_eq!(fixture.escrow(&challenge), 0);
+}
+
+#[test]
+fn creator_claims_stake_and_bonus_from_a_completed_challenge() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.completed();
+    fixture.close_dispute_window(&challenge);
+    let creator_before = fixture.svm.lamports(&fixture.creator);
+    let platform_before = fixture.svm.lamports(&fixture.platform);
+
+    let creator = fixture.creator;
+    fixture.claim(&creator, &challenge, false).unwrap();
+
+    // The creator also pays the rent of the completion badge and of the
+    // token account their GRASS reward is minted to
+    let bonus = STAKE * CREATOR_BONUS_BPS / BASIS_POINTS;
+    let badge_rent = fixture.badge_rent(&challenge);
+    let grass_rent = fixture.grass_rent(&creator);
+    assert!(badge_rent > 0 && grass_rent > 0);
+    assert_eq!(
+        fixture.svm.lamports(&fixture.creator),
+        creator_before + STAKE + bonus - badge_rent - grass_rent
+    );
+    assert_eq!(
+        fixture.svm.lamports(&fixture.platform),
+        platform_before + PLATFORM_FEE - bonus
+    );
+    assert_eq!(fixture.escrow(&challenge), 0);
+    assert!(fixture.challenge(&challenge).claimed);
+}
+
+#[test]
+fn creator_claims_a_challenge_completed_by_timeout() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+    let period_end = fixture.challenge(&challenge).verification_period_end;
+    fixture.svm.warp_to(period_end + 1);
+    fixture.crank(&challenge).unwrap();
+    fixture.close_dispute_window(&challenge);
+
+    let creator = fixture.creator;
+    fixture.claim(&creator, &challenge, false).unwrap();
+    assert_eq!(fixture.escrow(&challenge), 0);
+}
+
+#[test]
+fn rejecting_verifiers_split_the_slashed_stake() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.failed();
+    fixture.close_dispute_window(&challenge);
+    let (first, second) = (fixture.verifiers[0], fixture.verifiers[1]);
+    let creator_before = fixture.svm.lamports(&fixture.creator);
+    let platform_before = fixture.svm.lamports(&fixture.platform);
+    let first_before = fixture.svm.lamports(&first);
+    let second_before = fixture.svm.lamports(&second);
+
+    let slashed = STAKE * SLASH_PENALTY_BPS / BASIS_POINTS;
+    let share = slashed / 2;
+
+    // The first claim also pays the platform fee and refunds the unslashed
+    // stake. Each verifier pays the rent of their GRASS reward's token account.
+    fixture.claim(&first, &challenge, true).unwrap();
+    assert_eq!(
+        fixture.svm.lamports(&first),
+        first_before + share - fixture.grass_rent(&first)
+    );
+    assert_eq!(
+        fixture.svm.lamports(&fixture.platform),
+        platform_before + PLATFORM_FEE
+    );
+    assert_eq!(
+        fixture.svm.lamports(&fixture.creator),
+        creator_before + STAKE - slashed
+    );
+    assert_eq!(fixture.escrow(&challenge), share);
+
+    fixture.claim(&second, &challenge, true).unwrap();
+    assert_eq!(
+        fixture.svm.lamports(&second),
+        second_before + share - fixture.grass_rent(&second)
+    );
+    assert_eq!(
+        fixture.svm.lamports(&fixture.creator),
+        creator_before + STAKE - slashed
+    );
+    assert_eq!(fixture.escrow(&challenge), 0);
+
+    for verifier in [first, second] {
+        let verification = proof_of_touch_grass_client::accounts::fetch_verification(
+            &fixture.svm,
+            &pda::verification_pda(&challenge, &verifier).0,
+        )
+        .unwrap();
+        assert!(verification.claimed);
+    }
+}
+
+#[test]
+fn approving_verifier_of_a_failed_challenge_is_paid_nothing() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+    fixture.vote(2, &challenge, Vote::Approve).unwrap();
+    fixture.vote(0, &challenge, Vote::Reject).unwrap();
+    fixture.vote(1, &challenge, Vote::Reject).unwrap();
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Failed);
+    fixture.close_dispute_window(&challenge);
+
+    let approver = fixture.verifiers[2];
+    let before = fixture.svm.lamports(&approver);
+    assert!(fixture.claim(&approver, &challenge, true).is_err());
+    assert_eq!(fixture.svm.lamports(&approver), before);
+}
+
+#[test]
+fn remainder_of_an_uneven_slash_goes_back_to_the_creator() {
+    let mut fixture = Fixture::new();
+    let stake = STAKE + 4;
+    let mut args = fixture.args();
+    args.stake_amount = stake;
+    let challenge = fixture.try_create(args).unwrap();
+    fixture
+        .svm
+        .warp_to(fixture.challenge(&challenge).start_time);
+    fixture.crank(&challenge).unwrap();
+    fixture.submit(&challenge).unwrap();
+    fixture.submit(&challenge).unwrap();
+    fixture.vote(0, &challenge, Vote::Reject).unwrap();
+    fixture.vote(1, &challenge, Vote::Reject).unwrap();
+    fixture.close_dispute_window(&challenge);
+    let creator_before = fixture.svm.lamports(&fixture.creator);
+
+    let slashed = stake * SLASH_PENALTY_BPS / BASIS_POINTS;
+    assert_eq!(slashed % 2, 1);
+
+    let (first, second) = (fixture.verifiers[0], fixture.verifiers[1]);
+    fixture.claim(&first, &challenge, true).unwrap();
+    fixture.claim(&second, &challenge, true).unwrap();
+
+    assert_eq!(
+        fixture.svm.lamports(&fixture.creator),
+        creator_before + stake - slashed + 1
+    );
+    assert_eq!(fixture.escrow(&challenge), 0);
+}
+
+/// `instruction` with every `from` account swapped for `to`
+fn redirect(mut instruction: Instruction, from: &Pubkey, to: &Pubkey) -> Instruction {
+    for meta in &mut instruction.accounts {
+        if meta.pubkey == *from {
+            meta.pubkey = *to;
+        }
+    }
+    instruction
+}
+
+#[test]
+fn payouts_only_reach_the_creator_and_platform_wallets() {
+    let mut fixture = Fixture::new();
+    let (creator, outsider) = (fixture.creator, fixture.outsider);
+    let verifiers = fixture.verifiers.clone();
+    let wrong_address = Err(program_error(AnchorError::ConstraintAddress));
+
+    let challenge = fixture.create();
+    let cancel = instructions::cancel_challenge(&creator, &challenge, &verifiers);
+    let outcome = fixture.process(redirect(cancel, &PLATFORM_PUBKEY, &outsider));
+    assert_eq!(outcome, wrong_address);
+
+    let challenge = fixture.completed();
+    fixture.close_dispute_window(&challenge);
+    let claim = instructions::claim_funds(&creator, &challenge, &creator, &verifiers, false, true);
+    let outcome = fixture.process(redirect(claim, &PLATFORM_PUBKEY, &outsider));
+    assert_eq!(outcome, wrong_address);
+
+    // A rejecting verifier's first claim refunds the creator's unslashed stake
+    let challenge = fixture.failed();
+    fixture.close_dispute_window(&challenge);
+    let verifier = verifiers[0];
+    let claim = instructions::claim_funds(&verifier, &challenge, &creator, &verifiers, true, true);
+    let outcome = fixture.process(redirect(claim, &creator, &outsider));
+    assert_eq!(outcome, wrong_address);
+    fixture.claim(&verifier, &challenge, true).unwrap();
+}
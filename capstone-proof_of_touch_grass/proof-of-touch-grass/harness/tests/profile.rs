@@ -0,0 +1,89 @@
+//! Creator profile counters, recorded once when a challenge finalizes.
+
+mod common;
+
+use common::{Fixture, STAKE};
+use proof_of_touch_grass::fees;
+
+#[test]
+fn streaks_count_consecutive_completions() {
+    let mut fixture = Fixture::new();
+    fixture.completed();
+    fixture.completed();
+    let user = fixture.user();
+    assert_eq!((user.current_streak, user.longest_streak), (2, 2));
+
+    fixture.failed();
+    let user = fixture.user();
+    assert_eq!((user.current_streak, user.longest_streak), (0, 2));
+
+    fixture.completed();
+    let user = fixture.user();
+    assert_eq!((user.current_streak, user.longest_streak), (1, 2));
+    assert_eq!((user.completed, user.failed), (3, 1));
+    assert_eq!(user.success_rate_bps(), 7_500);
+}
+
+#[test]
+fn active_challenges_drop_as_challenges_finish() {
+    let mut fixture = Fixture::new();
+    let completed = fixture.completed();
+    let failed = fixture.failed();
+    let unstarted = fixture.create();
+    let started = fixture.active();
+    let user = fixture.user();
+    assert_eq!(user.active_challenges, 2);
+    assert_eq!(user.staked_at_risk, 2 * STAKE);
+
+    fixture.cancel(&unstarted).unwrap();
+    fixture.cancel(&started).unwrap();
+    let user = fixture.user();
+    assert_eq!((user.active_challenges, user.staked_at_risk), (0, 0));
+    assert_eq!(user.cancelled, 2);
+    assert_eq!(user.total_challenges, 4);
+
+    // Claims pay out but record nothing further
+    let creator = fixture.creator;
+    let verifier = fixture.verifiers[0];
+    fixture.close_dispute_window(&failed);
+    fixture.claim(&creator, &completed, false).unwrap();
+    fixture.claim(&verifier, &failed, true).unwrap();
+    let after = fixture.user();
+    assert_eq!((after.completed, after.failed), (1, 1));
+    assert_eq!((after.total_won, after.total_lost), (user.total_won, user.total_lost));
+}
+
+#[test]
+fn lamports_won_lost_and_forfeited() {
+    let mut fixture = Fixture::new();
+    fixture.completed();
+    fixture.failed();
+    let unstarted = fixture.create();
+    fixture.cancel(&unstarted).unwrap();
+    let started = fixture.active();
+    fixture.cancel(&started).unwrap();
+
+    let user = fixture.user();
+    assert_eq!(user.total_won, fees::creator_bonus(STAKE, false).unwrap());
+    assert_eq!(user.total_lost, STAKE / 4);
+    // Only the cancellation after the start paid a penalty
+    assert_eq!(
+        user.total_forfeited,
+        fees::cancel_payout(STAKE, false, true).unwrap().penalty
+    );
+}
+
+#[test]
+fn overturning_a_dispute_moves_the_outcome() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.failed();
+    let creator = fixture.creator;
+    fixture.dispute(&creator, &challenge).unwrap();
+    fixture.resolve(&challenge, true).unwrap();
+
+    let user = fixture.user();
+    assert_eq!((user.completed, user.failed), (1, 0));
+    assert_eq!((user.total_won, user.total_lost), (fees::creator_bonus(STAKE, false).unwrap(), 0));
+    assert_eq!((user.current_streak, user.longest_streak), (1, 1));
+    assert_eq!(user.active_challenges, 0);
+}
@@ -0,0 +1,155 @@
+//! Verifier reputation records and the dispute resolutions that feed them.
+
+mod common;
+
+use common::Fixture;
+use proof_of_touch_grass::state::{ChallengeStatus, Vote};
+
+#[test]
+fn listing_a_verifier_creates_their_reputation_record() {
+    let mut fixture = Fixture::new();
+    let verifier = fixture.verifiers[0];
+    assert!(fixture.reputation(&verifier).is_none());
+
+    let first = fixture.pending();
+    let reputation = fixture.reputation(&verifier).unwrap();
+    assert_eq!(reputation.verifier, verifier);
+    assert_eq!(reputation.votes_cast, 0);
+
+    // Votes count on the record, and a later listing leaves it as it is
+    fixture.vote(0, &first, Vote::Approve).unwrap();
+    let second = fixture.pending();
+    fixture.vote(0, &second, Vote::Reject).unwrap();
+
+    let reputation = fixture.reputation(&verifier).unwrap();
+    assert_eq!(reputation.votes_cast, 2);
+    assert_eq!(reputation.votes_won, 0);
+}
+
+#[test]
+fn first_claim_credits_votes_on_the_winning_side() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+    fixture.vote(2, &challenge, Vote::Reject).unwrap();
+    fixture.vote(0, &challenge, Vote::Approve).unwrap();
+    fixture.vote(1, &challenge, Vote::Approve).unwrap();
+    fixture.close_dispute_window(&challenge);
+
+    let creator = fixture.creator;
+    fixture.claim(&creator, &challenge, false).unwrap();
+
+    let won: Vec<u32> = fixture
+        .verifiers
+        .iter()
+        .map(|verifier| fixture.reputation(verifier).unwrap().votes_won)
+        .collect();
+    assert_eq!(won, [1, 1, 0]);
+}
+
+#[test]
+fn silence_until_the_period_runs_out_is_a_missed_window() {
+    // Verifiers who have never voted anywhere are counted too
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+    fixture.vote(0, &challenge, Vote::Approve).unwrap();
+    let period_end = fixture.challenge(&challenge).verification_period_end;
+    fixture.svm.warp_to(period_end + 1);
+    fixture.crank(&challenge).unwrap();
+    fixture.close_dispute_window(&challenge);
+
+    let creator = fixture.creator;
+    fixture.claim(&creator, &challenge, false).unwrap();
+
+    let missed: Vec<u32> = fixture
+        .verifiers
+        .iter()
+        .map(|verifier| fixture.reputation(verifier).unwrap().missed_windows)
+        .collect();
+    assert_eq!(missed, [0, 1, 1]);
+    assert_eq!(
+        fixture.reputation(&fixture.verifiers[0]).unwrap().votes_won,
+        1
+    );
+}
+
+#[test]
+fn early_finalization_is_not_a_missed_window() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.completed();
+    fixture.close_dispute_window(&challenge);
+    let creator = fixture.creator;
+    fixture.claim(&creator, &challenge, false).unwrap();
+
+    let silent = fixture.reputation(&fixture.verifiers[2]).unwrap();
+    assert_eq!(silent.missed_windows, 0);
+}
+
+#[test]
+fn upheld_dispute_restores_the_outcome() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.completed();
+    let creator = fixture.creator;
+    fixture.dispute(&creator, &challenge).unwrap();
+
+    fixture.resolve(&challenge, false).unwrap();
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Completed);
+    assert_eq!(fixture.user().completed, 1);
+
+    // The original dispute window still applies to the claim
+    fixture.close_dispute_window(&challenge);
+    fixture.claim(&creator, &challenge, false).unwrap();
+    assert_eq!(fixture.escrow(&challenge), 0);
+}
+
+#[test]
+fn overturned_dispute_flips_the_outcome_and_marks_the_votes() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.failed();
+    let creator = fixture.creator;
+    fixture.dispute(&creator, &challenge).unwrap();
+
+    fixture.resolve(&challenge, true).unwrap();
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Completed);
+    let user = fixture.user();
+    assert_eq!((user.completed, user.failed), (1, 0));
+
+    let (first, second) = (fixture.verifiers[0], fixture.verifiers[1]);
+    for verifier in [first, second] {
+        assert_eq!(fixture.reputation(&verifier).unwrap().votes_overturned, 1);
+    }
+
+    // Settling the reversed outcome credits nobody: both votes lost
+    fixture.close_dispute_window(&challenge);
+    fixture.claim(&creator, &challenge, false).unwrap();
+    for verifier in [first, second] {
+        assert_eq!(fixture.reputation(&verifier).unwrap().votes_won, 0);
+    }
+}
+
+#[test]
+fn resolving_requires_a_disputed_challenge() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.completed();
+    let creator = fixture.creator;
+    fixture.dispute(&creator, &challenge).unwrap();
+    fixture.resolve(&challenge, false).unwrap();
+
+    assert!(fixture.resolve(&challenge, true).is_err());
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Completed);
+}
+
+#[test]
+fn verifiers_meeting_the_minimum_reputation_can_be_listed() {
+    let mut fixture = Fixture::new();
+    let warmup = fixture.pending();
+    fixture.vote(2, &warmup, Vote::Approve).unwrap();
+    fixture.vote(0, &warmup, Vote::Approve).unwrap();
+    fixture.close_dispute_window(&warmup);
+    let creator = fixture.creator;
+    fixture.claim(&creator, &warmup, false).unwrap();
+
+    let mut args = fixture.args();
+    args.verifiers = vec![fixture.verifiers[0], fixture.verifiers[2]];
+    args.min_verifier_reputation = Some(1);
+    fixture.try_create(args).unwrap();
+}
@@ -0,0 +1,120 @@
+//! Retrying a failed challenge: copied terms, the link between the two, the
+//! checks carried over from the failed challenge and the redemption count on
+//! the profile.
+
+mod common;
+
+use common::{Fixture, HOUR, STAKE};
+use proof_of_touch_grass::errors::ErrorCode;
+use proof_of_touch_grass::fees;
+use proof_of_touch_grass::state::{ChallengeStatus, Vote};
+use proof_of_touch_grass_harness::program_error;
+
+/// A failed challenge whose dispute window has closed
+fn settled_failure(fixture: &mut Fixture) -> anchor_lang::prelude::Pubkey {
+    let failed = fixture.failed();
+    fixture.close_dispute_window(&failed);
+    failed
+}
+
+#[test]
+fn a_retry_copies_the_terms_and_links_both_challenges() {
+    let mut fixture = Fixture::new();
+    let failed = settled_failure(&mut fixture);
+    let retry = fixture.retry(&failed, 2 * STAKE).unwrap();
+
+    let (before, after) = (fixture.challenge(&failed), fixture.challenge(&retry));
+    assert_eq!(after.previous_challenge, Some(failed));
+    assert_eq!(before.retried_by, Some(retry));
+    assert_eq!(after.status, ChallengeStatus::Created);
+    assert_eq!(after.title, before.title);
+    assert_eq!(after.verifiers, before.verifiers);
+    assert_eq!(after.verifier_weights, before.verifier_weights);
+    assert_eq!(after.min_verifier_reputation, before.min_verifier_reputation);
+    assert_eq!(
+        (after.required_proofs, after.required_approval_weight),
+        (before.required_proofs, before.required_approval_weight)
+    );
+    assert_eq!(after.start_time, fixture.svm.now() + HOUR);
+    assert_eq!(after.end_time - after.start_time, before.end_time - before.start_time);
+    assert_eq!(
+        after.verification_period_end - after.end_time,
+        before.verification_period_end - before.end_time
+    );
+    assert_eq!(after.stake_amount, 2 * STAKE);
+    assert_eq!(fixture.escrow(&retry), fees::escrow_deposit(2 * STAKE, false).unwrap());
+
+    let user = fixture.user();
+    assert_eq!((user.retries, user.total_challenges), (1, 2));
+}
+
+#[test]
+fn completing_a_retry_is_a_redemption() {
+    let mut fixture = Fixture::new();
+    let failed = settled_failure(&mut fixture);
+    let retry = fixture.retry(&failed, STAKE).unwrap();
+    fixture.svm.warp_to(fixture.challenge(&retry).start_time);
+    fixture.crank(&retry).unwrap();
+    fixture.submit(&retry).unwrap();
+    fixture.submit(&retry).unwrap();
+    fixture.vote(0, &retry, Vote::Approve).unwrap();
+    fixture.vote(1, &retry, Vote::Approve).unwrap();
+
+    let user = fixture.user();
+    assert_eq!(user.redemptions, 1);
+    // Plain completions don't count
+    fixture.completed();
+    assert_eq!(fixture.user().redemptions, 1);
+}
+
+#[test]
+fn only_settled_failures_can_be_retried() {
+    let mut fixture = Fixture::new();
+    let completed = fixture.completed();
+    fixture.close_dispute_window(&completed);
+    assert!(fixture.retry(&completed, STAKE).is_err());
+
+    // Still disputable
+    let failed = fixture.failed();
+    assert!(fixture.retry(&failed, STAKE).is_err());
+
+    fixture.close_dispute_window(&failed);
+    fixture.retry(&failed, STAKE).unwrap();
+}
+
+#[test]
+fn a_failure_is_retried_once_and_never_for_less() {
+    let mut fixture = Fixture::new();
+    let failed = settled_failure(&mut fixture);
+    assert!(fixture.retry(&failed, STAKE - 1).is_err());
+    fixture.retry(&failed, STAKE).unwrap();
+    assert!(fixture.retry(&failed, 2 * STAKE).is_err());
+    assert_eq!(fixture.user().retries, 1);
+}
+
+#[test]
+fn a_verifier_claiming_their_share_does_not_block_a_retry() {
+    let mut fixture = Fixture::new();
+    let failed = settled_failure(&mut fixture);
+    let verifier = fixture.verifiers[0];
+    fixture.claim(&verifier, &failed, true).unwrap();
+
+    let retry = fixture.retry(&failed, STAKE).unwrap();
+    assert_eq!(fixture.challenge(&failed).retried_by, Some(retry));
+    // The other rejecting verifier still collects from the failed escrow
+    let other = fixture.verifiers[1];
+    fixture.claim(&other, &failed, true).unwrap();
+}
+
+#[test]
+fn a_retry_checks_the_minimum_reputation_again() {
+    let mut fixture = Fixture::new();
+    let failed = settled_failure(&mut fixture);
+    // Neither verifier has won a settled vote yet
+    fixture.patch_challenge(&failed, |challenge| challenge.min_verifier_reputation = Some(1));
+
+    assert_eq!(
+        fixture.retry(&failed, STAKE),
+        Err(program_error(ErrorCode::VerifierReputationTooLow))
+    );
+}
@@ -0,0 +1,411 @@
+//! Random interleavings of the lifecycle instructions and clock jumps, with
+//! the state machine's invariants checked after every step.
+//!
+//! Most generated steps fail validation; that is expected. What matters is
+//! that the ones that succeed never break an invariant.
+
+mod common;
+
+use std::collections::HashSet;
+
+use anchor_lang::prelude::Pubkey;
+use common::{Fixture, DAY, HOUR, SOL};
+use proof_of_touch_grass::fees;
+use proof_of_touch_grass::state::{
+    Challenge, ChallengeStatus, TimeoutPolicy, Verification, VerificationRules, Vote,
+};
+use proof_of_touch_grass_client::{accounts, instructions, pda};
+use proptest::collection::vec;
+use proptest::prelude::*;
+use proptest::test_runner::TestCaseError;
+
+/// Creator, the three verifiers, then the outsider
+const PARTIES: usize = 5;
+const MAX_CHALLENGES: usize = 2;
+
+#[derive(Clone, Debug)]
+enum Step {
+    Create {
+        stake: u64,
+        timeout_policy: TimeoutPolicy,
+        min_votes: u8,
+        weights: Vec<u8>,
+        required_approval_weight: u8,
+    },
+    Submit { challenge: usize },
+    Retract { challenge: usize },
+    Vote { challenge: usize, verifier: usize, vote: Vote },
+    ChangeVote { challenge: usize, verifier: usize, vote: Vote },
+    Crank { challenge: usize },
+    Cancel { challenge: usize },
+    Dispute { challenge: usize, party: usize },
+    Claim { challenge: usize, party: usize },
+    Advance { seconds: i64 },
+}
+
+fn vote() -> impl Strategy<Value = Vote> {
+    prop_oneof![
+        3 => Just(Vote::Approve),
+        3 => Just(Vote::Reject),
+        1 => Just(Vote::Abstain),
+    ]
+}
+
+fn step() -> impl Strategy<Value = Step> {
+    let challenge = 0..MAX_CHALLENGES;
+    // Weighted so that most runs get past `Active`: cancelling is always
+    // possible early on and would otherwise end most challenges there
+    prop_oneof![
+        4 => (
+            SOL / 10..2 * SOL,
+            prop_oneof![
+                Just(TimeoutPolicy::Completed),
+                Just(TimeoutPolicy::Failed),
+                Just(TimeoutPolicy::Refund),
+            ],
+            0..=3u8,
+            vec(1..=3u8, 3),
+            1..=6u8,
+        )
+            .prop_map(
+                |(stake, timeout_policy, min_votes, weights, required_approval_weight)| {
+                    Step::Create {
+                        stake,
+                        timeout_policy,
+                        min_votes,
+                        weights,
+                        required_approval_weight,
+                    }
+                }
+            ),
+        16 => challenge.clone().prop_map(|challenge| Step::Submit { challenge }),
+        2 => challenge.clone().prop_map(|challenge| Step::Retract { challenge }),
+        16 => (challenge.clone(), 0..3usize, vote()).prop_map(
+            |(challenge, verifier, vote)| Step::Vote { challenge, verifier, vote }
+        ),
+        4 => (challenge.clone(), 0..3usize, vote()).prop_map(
+            |(challenge, verifier, vote)| Step::ChangeVote { challenge, verifier, vote }
+        ),
+        12 => challenge.clone().prop_map(|challenge| Step::Crank { challenge }),
+        1 => challenge.clone().prop_map(|challenge| Step::Cancel { challenge }),
+        3 => (challenge.clone(), 0..PARTIES)
+            .prop_map(|(challenge, party)| Step::Dispute { challenge, party }),
+        12 => (challenge, 0..PARTIES).prop_map(|(challenge, party)| Step::Claim { challenge, party }),
+        12 => prop_oneof![
+            6 => HOUR..DAY,
+            3 => DAY..3 * DAY,
+            1 => 3 * DAY..10 * DAY,
+        ]
+        .prop_map(|seconds| Step::Advance { seconds }),
+    ]
+}
+
+fn legal_transition(from: &ChallengeStatus, to: &ChallengeStatus) -> bool {
+    use ChallengeStatus::*;
+    from == to
+        || matches!(
+            (from, to),
+            (Created, Active)
+                | (Created, Cancelled)
+                | (Active, PendingVerification)
+                | (Active, Failed)
+                | (Active, Cancelled)
+                | (PendingVerification, Active)
+                | (PendingVerification, Completed)
+                | (PendingVerification, Failed)
+                | (PendingVerification, Refunded)
+                | (Completed, Disputed)
+                | (Failed, Disputed)
+        )
+}
+
+struct Model {
+    fixture: Fixture,
+    challenges: Vec<Pubkey>,
+    statuses: Vec<ChallengeStatus>,
+    /// (challenge, party) pairs that have received a payout from escrow
+    paid: HashSet<(usize, Pubkey)>,
+    /// Challenges that have ever reached `Completed` / `Failed`
+    completed: u32,
+    failed: u32,
+    streak: u32,
+}
+
+impl Model {
+    fn new() -> Self {
+        Self {
+            fixture: Fixture::new(),
+            challenges: Vec::new(),
+            statuses: Vec::new(),
+            paid: HashSet::new(),
+            completed: 0,
+            failed: 0,
+            streak: 0,
+        }
+    }
+
+    fn parties(&self) -> Vec<Pubkey> {
+        let fixture = &self.fixture;
+        let mut parties = vec![fixture.creator];
+        parties.extend(&fixture.verifiers);
+        parties.push(fixture.outsider);
+        parties
+    }
+
+    fn run(&mut self, step: &Step) -> Result<(), TestCaseError> {
+        if let Step::Create {
+            stake,
+            ref timeout_policy,
+            min_votes,
+            ref weights,
+            required_approval_weight,
+        } = *step
+        {
+            if self.challenges.len() == MAX_CHALLENGES {
+                return Ok(());
+            }
+            let mut args = self.fixture.args();
+            args.stake_amount = stake;
+            args.verification_rules = VerificationRules {
+                timeout_policy: timeout_policy.clone(),
+                min_votes,
+            };
+            args.verifier_weights = weights.clone();
+            args.required_approval_weight = required_approval_weight;
+            if let Ok(challenge) = self.fixture.try_create(args) {
+                self.challenges.push(challenge);
+                self.statuses.push(ChallengeStatus::Created);
+            }
+            return Ok(());
+        }
+        if let Step::Advance { seconds } = *step {
+            self.fixture.svm.advance(seconds);
+            return Ok(());
+        }
+        if self.challenges.is_empty() {
+            return Ok(());
+        }
+
+        let parties = self.parties();
+        let watched: Vec<Pubkey> = parties
+            .iter()
+            .copied()
+            .chain([self.fixture.platform])
+            .collect();
+        let before: Vec<u64> = watched
+            .iter()
+            .map(|wallet| self.fixture.svm.lamports(wallet))
+            .collect();
+
+        let fixture = &mut self.fixture;
+        let (index, outcome, pays_out) = match *step {
+            Step::Submit { challenge } => {
+                let index = challenge % self.challenges.len();
+                (index, fixture.submit(&self.challenges[index]), false)
+            }
+            Step::Retract { challenge } => {
+                let index = challenge % self.challenges.len();
+                let address = self.challenges[index];
+                let last = fixture.challenge(&address).evidence_count.saturating_sub(1);
+                let creator = fixture.creator;
+                let outcome =
+                    fixture.process(instructions::retract_evidence(&creator, &address, last));
+                (index, outcome, false)
+            }
+            Step::Vote {
+                challenge,
+                verifier,
+                ref vote,
+            } => {
+                let index = challenge % self.challenges.len();
+                (index, fixture.vote(verifier, &self.challenges[index], vote.clone()), false)
+            }
+            Step::ChangeVote {
+                challenge,
+                verifier,
+                ref vote,
+            } => {
+                let index = challenge % self.challenges.len();
+                (index, fixture.change_vote(verifier, &self.challenges[index], vote.clone()), false)
+            }
+            Step::Crank { challenge } => {
+                let index = challenge % self.challenges.len();
+                (index, fixture.crank(&self.challenges[index]), false)
+            }
+            Step::Cancel { challenge } => {
+                let index = challenge % self.challenges.len();
+                (index, fixture.cancel(&self.challenges[index]), true)
+            }
+            Step::Dispute { challenge, party } => {
+                let index = challenge % self.challenges.len();
+                (index, fixture.dispute(&parties[party], &self.challenges[index]), false)
+            }
+            Step::Claim { challenge, party } => {
+                let index = challenge % self.challenges.len();
+                let outcome = fixture.claim(&parties[party], &self.challenges[index], party != 0);
+                (index, outcome, true)
+            }
+            Step::Create { .. } | Step::Advance { .. } => unreachable!(),
+        };
+
+        if outcome.is_ok() && pays_out {
+            for (wallet, before) in watched.iter().zip(before) {
+                if self.fixture.svm.lamports(wallet) > before {
+                    prop_assert!(
+                        self.paid.insert((index, *wallet)),
+                        "{wallet} was paid twice from challenge {index} by {step:?}"
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check_invariants(&mut self) -> Result<(), TestCaseError> {
+        for (index, address) in self.challenges.iter().enumerate() {
+            let challenge = self.fixture.challenge(address);
+            let previous = &self.statuses[index];
+            prop_assert!(
+                legal_transition(previous, &challenge.status),
+                "challenge {index} moved {previous} -> {}",
+                challenge.status
+            );
+            if *previous != challenge.status {
+                match challenge.status {
+                    ChallengeStatus::Completed => {
+                        self.completed += 1;
+                        self.streak += 1;
+                    }
+                    ChallengeStatus::Failed => {
+                        self.failed += 1;
+                        self.streak = 0;
+                    }
+                    _ => {}
+                }
+            }
+            self.statuses[index] = challenge.status.clone();
+
+            // The tallies always match the votes on record, however often they changed
+            let votes = self.verifications(address);
+            let tally = |wanted: Vote| {
+                let cast = votes.iter().filter(|verification| verification.vote == wanted);
+                let weight = cast
+                    .clone()
+                    .map(|verification| challenge.weight_of(&verification.verifier).unwrap())
+                    .sum::<u8>();
+                (cast.count() as u8, weight)
+            };
+            let (approvals, rejections) = (tally(Vote::Approve), tally(Vote::Reject));
+            prop_assert_eq!(
+                (challenge.approval_count, challenge.rejection_count, challenge.abstain_count),
+                (approvals.0, rejections.0, tally(Vote::Abstain).0),
+                "vote tallies of challenge {}",
+                index
+            );
+            prop_assert_eq!(
+                (challenge.approval_weight, challenge.rejection_weight),
+                (approvals.1, rejections.1),
+                "weight tallies of challenge {}",
+                index
+            );
+
+            let escrow = self.fixture.escrow(address);
+            let expected = self.expected_escrow(address, &challenge);
+            prop_assert_eq!(
+                escrow,
+                expected,
+                "escrow of challenge {} ({}) holds the wrong amount",
+                index,
+                &challenge.status
+            );
+        }
+
+        let user = self.fixture.user();
+        prop_assert!(
+            u64::from(user.completed) + u64::from(user.failed) <= u64::from(user.total_challenges),
+            "completed {} + failed {} > total {}",
+            user.completed,
+            user.failed,
+            user.total_challenges
+        );
+        // Each finalized challenge is counted once, however often it is claimed
+        prop_assert_eq!(user.completed, self.completed, "user.completed");
+        prop_assert_eq!(user.failed, self.failed, "user.failed");
+        prop_assert_eq!(user.current_streak, self.streak, "user.current_streak");
+        let count = |wanted: &[ChallengeStatus]| {
+            self.statuses.iter().filter(|status| wanted.contains(status)).count() as u32
+        };
+        prop_assert_eq!(user.cancelled, count(&[ChallengeStatus::Cancelled]), "user.cancelled");
+        prop_assert_eq!(user.refunded, count(&[ChallengeStatus::Refunded]), "user.refunded");
+        prop_assert_eq!(
+            user.active_challenges,
+            count(&[
+                ChallengeStatus::Created,
+                ChallengeStatus::Active,
+                ChallengeStatus::PendingVerification,
+            ]),
+            "user.active_challenges"
+        );
+        Ok(())
+    }
+
+    fn verifications(&self, address: &Pubkey) -> Vec<Verification> {
+        self.fixture
+            .verifiers
+            .iter()
+            .filter_map(|verifier| {
+                accounts::fetch_verification(
+                    &self.fixture.svm,
+                    &pda::verification_pda(address, verifier).0,
+                )
+                .ok()
+            })
+            .collect()
+    }
+
+    /// What the escrow must still hold given how far the challenge has got
+    fn expected_escrow(&self, address: &Pubkey, challenge: &Challenge) -> u64 {
+        let fee_in_grass = challenge.fee_paid_in_grass;
+        let deposit = fees::escrow_deposit(challenge.stake_amount, fee_in_grass).unwrap();
+        match challenge.status {
+            ChallengeStatus::Cancelled => 0,
+            // The creator settles these in a single claim
+            ChallengeStatus::Completed | ChallengeStatus::Refunded if challenge.claimed => 0,
+            ChallengeStatus::Failed if challenge.rejection_weight == 0 && challenge.claimed => 0,
+            ChallengeStatus::Failed if challenge.rejection_weight > 0 => {
+                let payout = fees::failed_payout(
+                    challenge.stake_amount,
+                    fee_in_grass,
+                    challenge.rejection_weight,
+                )
+                .unwrap();
+                let unclaimed_weight: u64 = self
+                    .verifications(address)
+                    .into_iter()
+                    .filter(|verification| verification.vote == Vote::Reject && !verification.claimed)
+                    .map(|verification| u64::from(challenge.weight_of(&verification.verifier).unwrap()))
+                    .sum();
+                let first_claim = if challenge.claimed {
+                    0
+                } else {
+                    payout.platform_fee + payout.creator_refund
+                };
+                unclaimed_weight * payout.share_per_weight + first_claim
+            }
+            _ => deposit,
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(128))]
+
+    #[test]
+    fn random_step_sequences_preserve_invariants(steps in vec(step(), 20..150)) {
+        let mut model = Model::new();
+        for step in &steps {
+            model.run(step)?;
+            model.check_invariants()?;
+        }
+    }
+}
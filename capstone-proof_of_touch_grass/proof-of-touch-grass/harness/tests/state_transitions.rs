@@ -0,0 +1,204 @@
+//! Every transition `update_challenge_state` makes, and the ones it refuses.
+
+mod common;
+
+use common::{Fixture, DAY};
+use proof_of_touch_grass_client::instructions;
+use proof_of_touch_grass::state::{ChallengeStatus, Vote};
+
+#[test]
+fn created_stays_created_before_start_time() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.create();
+    let start_time = fixture.challenge(&challenge).start_time;
+
+    fixture.svm.warp_to(start_time - 1);
+    fixture.crank(&challenge).unwrap();
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Created);
+}
+
+#[test]
+fn created_becomes_active_at_start_time() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.create();
+
+    // The boundary is inclusive
+    fixture
+        .svm
+        .warp_to(fixture.challenge(&challenge).start_time);
+    fixture.crank(&challenge).unwrap();
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Active);
+}
+
+#[test]
+fn active_stays_active_until_end_time_has_passed() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.active();
+
+    fixture.svm.warp_to(fixture.challenge(&challenge).end_time);
+    fixture.crank(&challenge).unwrap();
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Active);
+}
+
+#[test]
+fn active_without_enough_evidence_fails_after_end_time() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.active();
+    fixture.submit(&challenge).unwrap();
+
+    let end_time = fixture.challenge(&challenge).end_time;
+    fixture.svm.warp_to(end_time + 1);
+    fixture.crank(&challenge).unwrap();
+
+    let state = fixture.challenge(&challenge);
+    assert_eq!(state.status, ChallengeStatus::Failed);
+    assert_eq!(state.finalized_at, end_time + 1);
+    assert_eq!(fixture.user().failed, 1);
+}
+
+#[test]
+fn active_with_all_evidence_moves_to_pending_verification_after_end_time() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.active();
+    fixture.submit(&challenge).unwrap();
+
+    // `submit_evidence` already moves the challenge once the last proof lands,
+    // so an active challenge holding every proof only exists if patched in
+    fixture.patch_challenge(&challenge, |state| {
+        state.evidence_count = state.required_proofs
+    });
+    fixture
+        .svm
+        .warp_to(fixture.challenge(&challenge).end_time + 1);
+    fixture.crank(&challenge).unwrap();
+
+    let state = fixture.challenge(&challenge);
+    assert_eq!(state.status, ChallengeStatus::PendingVerification);
+    assert_eq!(state.finalized_at, 0);
+}
+
+#[test]
+fn last_proof_moves_active_to_pending_verification() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+    assert_eq!(
+        fixture.status(&challenge),
+        ChallengeStatus::PendingVerification
+    );
+}
+
+#[test]
+fn pending_verification_stays_pending_until_the_period_has_passed() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+
+    fixture
+        .svm
+        .warp_to(fixture.challenge(&challenge).verification_period_end);
+    fixture.crank(&challenge).unwrap();
+    assert_eq!(
+        fixture.status(&challenge),
+        ChallengeStatus::PendingVerification
+    );
+}
+
+#[test]
+fn pending_verification_completes_when_verifiers_stay_silent() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+    fixture.vote(0, &challenge, Vote::Approve).unwrap();
+
+    let period_end = fixture.challenge(&challenge).verification_period_end;
+    fixture.svm.warp_to(period_end + 1);
+    fixture.crank(&challenge).unwrap();
+
+    let state = fixture.challenge(&challenge);
+    assert_eq!(state.status, ChallengeStatus::Completed);
+    assert_eq!(state.finalized_at, period_end + 1);
+    assert_eq!(fixture.user().completed, 1);
+}
+
+#[test]
+fn approvals_complete_a_pending_challenge() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.completed();
+
+    let state = fixture.challenge(&challenge);
+    assert_eq!(state.status, ChallengeStatus::Completed);
+    assert_eq!(state.approval_count, 2);
+    assert_eq!(state.finalized_at, fixture.svm.now());
+}
+
+#[test]
+fn rejections_fail_a_pending_challenge() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.failed();
+
+    let state = fixture.challenge(&challenge);
+    assert_eq!(state.status, ChallengeStatus::Failed);
+    assert_eq!(state.rejection_count, 2);
+    assert_eq!(fixture.user().failed, 1);
+}
+
+#[test]
+fn terminal_states_are_never_moved_by_the_crank() {
+    let mut fixture = Fixture::new();
+    let completed = fixture.completed();
+    let failed = fixture.failed();
+    let cancelled = fixture.create();
+    fixture.cancel(&cancelled).unwrap();
+    let disputed = fixture.completed();
+    let creator = fixture.creator;
+    fixture.dispute(&creator, &disputed).unwrap();
+
+    fixture.svm.advance(365 * DAY);
+    for (challenge, status) in [
+        (completed, ChallengeStatus::Completed),
+        (failed, ChallengeStatus::Failed),
+        (cancelled, ChallengeStatus::Cancelled),
+        (disputed, ChallengeStatus::Disputed),
+    ] {
+        fixture.crank(&challenge).unwrap();
+        assert_eq!(fixture.status(&challenge), status);
+    }
+}
+
+#[test]
+fn retracting_the_last_proof_reopens_a_pending_challenge() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+    let creator = fixture.creator;
+
+    fixture
+        .process(instructions::retract_evidence(&creator, &challenge, 1))
+        .unwrap();
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Active);
+
+    // With a proof missing at the deadline the crank fails it
+    fixture
+        .svm
+        .warp_to(fixture.challenge(&challenge).end_time + 1);
+    fixture.crank(&challenge).unwrap();
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Failed);
+}
+
+#[test]
+fn a_retracted_proof_can_be_resubmitted_at_once() {
+    let mut fixture = Fixture::new();
+    let mut args = fixture.args();
+    args.submission_rules.min_interval = Some(DAY);
+    let challenge = fixture.try_create(args).unwrap();
+    fixture.svm.warp_to(fixture.challenge(&challenge).start_time);
+    fixture.crank(&challenge).unwrap();
+    fixture.submit(&challenge).unwrap();
+    fixture.svm.advance(DAY);
+    fixture.submit(&challenge).unwrap();
+
+    // The correction doesn't wait a day after the proof it replaces
+    let creator = fixture.creator;
+    fixture
+        .process(instructions::retract_evidence(&creator, &challenge, 1))
+        .unwrap();
+    fixture.submit(&challenge).unwrap();
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::PendingVerification);
+}
@@ -0,0 +1,132 @@
+//! Challenge templates: publishing, curation and creating challenges from
+//! a template's terms.
+
+mod common;
+
+use common::{Fixture, DAY, HOUR, STAKE};
+use proof_of_touch_grass::constants::ADMIN_PUBKEY;
+use proof_of_touch_grass::errors::ErrorCode;
+use proof_of_touch_grass::state::ChallengeStatus;
+use proof_of_touch_grass_client::instructions;
+use proof_of_touch_grass_harness::program_error;
+
+#[test]
+fn a_challenge_takes_its_terms_from_the_template() {
+    let mut fixture = Fixture::new();
+    let template = fixture.publish();
+    let verifiers = fixture.verifiers.clone();
+    let challenge = fixture.try_from_template(&template, verifiers.clone()).unwrap();
+
+    let terms = fixture.template(&template);
+    let state = fixture.challenge(&challenge);
+    assert_eq!(state.template, Some(template));
+    assert_eq!(state.status, ChallengeStatus::Created);
+    assert_eq!(state.title, terms.title);
+    assert_eq!(state.description, terms.description);
+    assert_eq!(
+        (state.required_proofs, state.required_approval_weight),
+        (terms.required_proofs, terms.required_approval_weight)
+    );
+    assert_eq!(state.verifiers, verifiers);
+    assert_eq!(state.verification_rules, terms.verification_rules);
+    assert_eq!(state.stake_amount, STAKE);
+    assert_eq!(state.start_time, fixture.svm.now() + HOUR);
+    assert_eq!(state.end_time, state.start_time + 7 * DAY);
+    assert_eq!(state.verification_period_end, state.end_time + DAY);
+    assert_eq!(terms.uses, 1);
+    assert_eq!(fixture.user().total_challenges, 1);
+
+    // Plain challenges carry no template
+    let plain = fixture.create();
+    assert_eq!(fixture.challenge(&plain).template, None);
+}
+
+#[test]
+fn every_use_is_counted() {
+    let mut fixture = Fixture::new();
+    let template = fixture.publish();
+    let verifiers = fixture.verifiers.clone();
+    fixture.try_from_template(&template, verifiers.clone()).unwrap();
+    fixture.try_from_template(&template, verifiers[..2].to_vec()).unwrap();
+    assert_eq!(fixture.template(&template).uses, 2);
+}
+
+#[test]
+fn only_the_admin_curates() {
+    let mut fixture = Fixture::new();
+    let template = fixture.publish();
+    assert!(!fixture.template(&template).curated);
+
+    let outsider = fixture.outsider;
+    assert!(fixture
+        .process(instructions::curate_template(&outsider, &template, true))
+        .is_err());
+
+    fixture
+        .process(instructions::curate_template(&ADMIN_PUBKEY, &template, true))
+        .unwrap();
+    assert!(fixture.template(&template).curated);
+    fixture
+        .process(instructions::curate_template(&ADMIN_PUBKEY, &template, false))
+        .unwrap();
+    assert!(!fixture.template(&template).curated);
+}
+
+#[test]
+fn the_approval_threshold_must_fit_the_chosen_verifiers() {
+    let mut fixture = Fixture::new();
+    let template = fixture.publish();
+    let one = fixture.verifiers[..1].to_vec();
+    assert!(fixture.try_from_template(&template, one).is_err());
+}
+
+#[test]
+fn the_template_minimum_reputation_applies() {
+    let mut fixture = Fixture::new();
+    let mut args = fixture.template_args();
+    args.min_verifier_reputation = Some(1);
+    let template = fixture.try_publish(0, args).unwrap();
+    let verifiers = fixture.verifiers.clone();
+    assert!(fixture.try_from_template(&template, verifiers).is_err());
+
+    // Without a minimum the same verifiers are fine
+    let args = fixture.template_args();
+    let open = fixture.try_publish(1, args).unwrap();
+    let verifiers = fixture.verifiers.clone();
+    fixture.try_from_template(&open, verifiers).unwrap();
+}
+
+#[test]
+fn a_retry_keeps_the_template_and_counts_as_a_use() {
+    let mut fixture = Fixture::new();
+    let template = fixture.publish();
+    let verifiers = fixture.verifiers.clone();
+    let challenge = fixture.try_from_template(&template, verifiers).unwrap();
+    fixture.svm.warp_to(fixture.challenge(&challenge).start_time);
+    fixture.crank(&challenge).unwrap();
+    fixture.svm.warp_to(fixture.challenge(&challenge).end_time + 1);
+    fixture.crank(&challenge).unwrap();
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Failed);
+
+    fixture.close_dispute_window(&challenge);
+    // The template has to come along
+    let (creator, verifiers) = (fixture.creator, fixture.challenge(&challenge).verifiers);
+    let challenge_id = u64::from(fixture.user().total_challenges);
+    let start_time = fixture.svm.now() + HOUR;
+    assert_eq!(
+        fixture.process(instructions::retry_challenge(
+            &creator,
+            &challenge,
+            None,
+            &verifiers,
+            challenge_id,
+            STAKE,
+            start_time,
+        )),
+        Err(program_error(ErrorCode::InvalidTemplateAccount))
+    );
+
+    let retry = fixture.retry(&challenge, STAKE).unwrap();
+    assert_eq!(fixture.challenge(&retry).template, Some(template));
+    assert_eq!(fixture.template(&template).uses, 2);
+}
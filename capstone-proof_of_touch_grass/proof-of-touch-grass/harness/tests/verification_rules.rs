@@ -0,0 +1,183 @@
+//! Timeout policies and the vote quorum: how a challenge settles when its
+//! verification period runs out, and when votes may decide it early.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::{Fixture, STAKE};
+use proof_of_touch_grass::constants::{BASIS_POINTS, PLATFORM_FEE_BPS, SLASH_PENALTY_BPS};
+use proof_of_touch_grass::state::{ChallengeStatus, TimeoutPolicy, VerificationRules, Vote};
+
+const PLATFORM_FEE: u64 = STAKE * PLATFORM_FEE_BPS / BASIS_POINTS;
+const SLASHED: u64 = STAKE * SLASH_PENALTY_BPS / BASIS_POINTS;
+
+/// A challenge with every proof in, awaiting votes under `rules`
+fn pending_with(fixture: &mut Fixture, rules: VerificationRules) -> Pubkey {
+    let mut args = fixture.args();
+    args.verification_rules = rules;
+    let challenge = fixture.try_create(args).unwrap();
+    fixture.svm.warp_to(fixture.challenge(&challenge).start_time);
+    fixture.crank(&challenge).unwrap();
+    fixture.submit(&challenge).unwrap();
+    fixture.submit(&challenge).unwrap();
+    challenge
+}
+
+fn on_timeout(timeout_policy: TimeoutPolicy) -> VerificationRules {
+    VerificationRules {
+        timeout_policy,
+        min_votes: 0,
+    }
+}
+
+fn time_out(fixture: &mut Fixture, challenge: &Pubkey) {
+    let period_end = fixture.challenge(challenge).verification_period_end;
+    fixture.svm.warp_to(period_end + 1);
+    fixture.crank(challenge).unwrap();
+}
+
+#[test]
+fn silence_completes_by_default() {
+    let mut fixture = Fixture::new();
+    let challenge = pending_with(&mut fixture, VerificationRules::default());
+    time_out(&mut fixture, &challenge);
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Completed);
+}
+
+#[test]
+fn a_failing_policy_lets_the_creator_settle_without_rejecters() {
+    let mut fixture = Fixture::new();
+    let challenge = pending_with(&mut fixture, on_timeout(TimeoutPolicy::Failed));
+    time_out(&mut fixture, &challenge);
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Failed);
+    let user = fixture.user();
+    assert_eq!((user.failed, user.total_lost), (1, SLASHED));
+
+    fixture.close_dispute_window(&challenge);
+    let (creator, platform) = (fixture.creator, fixture.platform);
+    let creator_before = fixture.svm.lamports(&creator);
+    let platform_before = fixture.svm.lamports(&platform);
+    fixture.claim(&creator, &challenge, false).unwrap();
+
+    // Nobody rejected, so the slash goes to the platform
+    assert_eq!(fixture.svm.lamports(&creator), creator_before + STAKE - SLASHED);
+    assert_eq!(
+        fixture.svm.lamports(&platform),
+        platform_before + PLATFORM_FEE + SLASHED
+    );
+    assert_eq!(fixture.escrow(&challenge), 0);
+    assert!(fixture.claim(&creator, &challenge, false).is_err());
+}
+
+#[test]
+fn missing_evidence_can_be_settled_by_the_creator() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.active();
+    fixture.svm.warp_to(fixture.challenge(&challenge).end_time + 1);
+    fixture.crank(&challenge).unwrap();
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Failed);
+
+    fixture.close_dispute_window(&challenge);
+    let creator = fixture.creator;
+    fixture.claim(&creator, &challenge, false).unwrap();
+    assert_eq!(fixture.escrow(&challenge), 0);
+}
+
+#[test]
+fn a_refund_returns_the_stake_right_away() {
+    let mut fixture = Fixture::new();
+    fixture.completed();
+    let challenge = pending_with(&mut fixture, on_timeout(TimeoutPolicy::Refund));
+    time_out(&mut fixture, &challenge);
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Refunded);
+
+    // Neither a completion nor a failure
+    let user = fixture.user();
+    assert_eq!((user.completed, user.failed, user.refunded), (1, 0, 1));
+    assert_eq!(user.current_streak, 1);
+    assert_eq!((user.active_challenges, user.staked_at_risk), (0, 0));
+
+    // Nothing to dispute, and no window to wait for
+    let outsider = fixture.outsider;
+    assert!(fixture.dispute(&outsider, &challenge).is_err());
+    let (creator, platform) = (fixture.creator, fixture.platform);
+    assert!(fixture.claim(&outsider, &challenge, false).is_err());
+    let creator_before = fixture.svm.lamports(&creator);
+    let platform_before = fixture.svm.lamports(&platform);
+    fixture.claim(&creator, &challenge, false).unwrap();
+
+    assert_eq!(fixture.svm.lamports(&creator), creator_before + STAKE);
+    assert_eq!(fixture.svm.lamports(&platform), platform_before + PLATFORM_FEE);
+    assert_eq!(fixture.escrow(&challenge), 0);
+}
+
+#[test]
+fn a_refund_credits_no_vote_but_records_missed_windows() {
+    let mut fixture = Fixture::new();
+    // Gives every verifier a reputation record
+    let warmup = pending_with(&mut fixture, VerificationRules {
+        timeout_policy: TimeoutPolicy::Completed,
+        min_votes: 3,
+    });
+    for verifier in 0..3 {
+        fixture.vote(verifier, &warmup, Vote::Approve).unwrap();
+    }
+
+    let challenge = pending_with(&mut fixture, VerificationRules {
+        timeout_policy: TimeoutPolicy::Refund,
+        min_votes: 3,
+    });
+    fixture.vote(0, &challenge, Vote::Approve).unwrap();
+    fixture.vote(1, &challenge, Vote::Approve).unwrap();
+    time_out(&mut fixture, &challenge);
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Refunded);
+
+    let won_before: Vec<u32> = fixture
+        .verifiers
+        .iter()
+        .map(|verifier| fixture.reputation(verifier).unwrap().votes_won)
+        .collect();
+    let creator = fixture.creator;
+    fixture.claim(&creator, &challenge, false).unwrap();
+
+    let after: Vec<(u32, u32)> = fixture
+        .verifiers
+        .iter()
+        .map(|verifier| {
+            let reputation = fixture.reputation(verifier).unwrap();
+            (reputation.votes_won, reputation.missed_windows)
+        })
+        .collect();
+    assert_eq!(
+        after,
+        vec![(won_before[0], 0), (won_before[1], 0), (won_before[2], 1)]
+    );
+}
+
+#[test]
+fn thresholds_wait_for_the_quorum() {
+    let mut fixture = Fixture::new();
+    let challenge = pending_with(&mut fixture, VerificationRules {
+        timeout_policy: TimeoutPolicy::Completed,
+        min_votes: 3,
+    });
+    fixture.vote(0, &challenge, Vote::Approve).unwrap();
+    fixture.vote(1, &challenge, Vote::Approve).unwrap();
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::PendingVerification);
+
+    fixture.vote(2, &challenge, Vote::Reject).unwrap();
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Completed);
+}
+
+#[test]
+fn a_missed_quorum_falls_back_to_the_timeout_policy() {
+    let mut fixture = Fixture::new();
+    let challenge = pending_with(&mut fixture, VerificationRules {
+        timeout_policy: TimeoutPolicy::Failed,
+        min_votes: 3,
+    });
+    fixture.vote(0, &challenge, Vote::Approve).unwrap();
+    fixture.vote(1, &challenge, Vote::Approve).unwrap();
+    time_out(&mut fixture, &challenge);
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Failed);
+}
@@ -0,0 +1,126 @@
+//! Abstentions and vote changes: how they move the tallies, the quorum and
+//! early finalization.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::Fixture;
+use proof_of_touch_grass::state::{ChallengeStatus, VerificationRules, Vote};
+use proof_of_touch_grass_client::{accounts, pda};
+
+fn tallies(fixture: &Fixture, challenge: &Pubkey) -> (u8, u8, u8) {
+    let challenge = fixture.challenge(challenge);
+    (challenge.approval_count, challenge.rejection_count, challenge.abstain_count)
+}
+
+fn pending_with_quorum(fixture: &mut Fixture, min_votes: u8) -> Pubkey {
+    let mut args = fixture.args();
+    args.verification_rules = VerificationRules {
+        min_votes,
+        ..VerificationRules::default()
+    };
+    let challenge = fixture.try_create(args).unwrap();
+    fixture.svm.warp_to(fixture.challenge(&challenge).start_time);
+    fixture.crank(&challenge).unwrap();
+    fixture.submit(&challenge).unwrap();
+    fixture.submit(&challenge).unwrap();
+    challenge
+}
+
+#[test]
+fn abstentions_count_toward_the_quorum_only() {
+    let mut fixture = Fixture::new();
+    let challenge = pending_with_quorum(&mut fixture, 3);
+    fixture.vote(0, &challenge, Vote::Approve).unwrap();
+    fixture.vote(1, &challenge, Vote::Approve).unwrap();
+    // Both approvals are in, but the quorum is still one vote short
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::PendingVerification);
+
+    fixture.vote(2, &challenge, Vote::Abstain).unwrap();
+    assert_eq!(tallies(&fixture, &challenge), (2, 0, 1));
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Completed);
+}
+
+#[test]
+fn abstentions_alone_never_decide() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+    for verifier in 0..3 {
+        fixture.vote(verifier, &challenge, Vote::Abstain).unwrap();
+    }
+    assert_eq!(tallies(&fixture, &challenge), (0, 0, 3));
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::PendingVerification);
+}
+
+#[test]
+fn changing_a_vote_moves_it_between_tallies() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+    fixture.vote(0, &challenge, Vote::Reject).unwrap();
+    assert_eq!(tallies(&fixture, &challenge), (0, 1, 0));
+
+    fixture.change_vote(0, &challenge, Vote::Abstain).unwrap();
+    assert_eq!(tallies(&fixture, &challenge), (0, 0, 1));
+    fixture.change_vote(0, &challenge, Vote::Approve).unwrap();
+    assert_eq!(tallies(&fixture, &challenge), (1, 0, 0));
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::PendingVerification);
+
+    let verifier = fixture.verifiers[0];
+    let address = pda::verification_pda(&challenge, &verifier).0;
+    let verification = accounts::fetch_verification(&fixture.svm, &address).unwrap();
+    assert_eq!(verification.vote, Vote::Approve);
+    // Changing a vote is not a second vote
+    assert_eq!(fixture.reputation(&verifier).unwrap().votes_cast, 1);
+}
+
+#[test]
+fn a_change_can_finalize_the_challenge() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+    fixture.vote(0, &challenge, Vote::Approve).unwrap();
+    fixture.vote(1, &challenge, Vote::Reject).unwrap();
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::PendingVerification);
+
+    fixture.change_vote(1, &challenge, Vote::Approve).unwrap();
+    assert_eq!(tallies(&fixture, &challenge), (2, 0, 0));
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Completed);
+    // The finalized challenge is off every verifier's inbox
+    let verifier = fixture.verifiers[0];
+    assert!(accounts::fetch_assignments(&fixture.svm, &verifier).unwrap().is_empty());
+}
+
+#[test]
+fn only_a_cast_vote_can_change() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+    assert!(fixture.change_vote(0, &challenge, Vote::Approve).is_err());
+}
+
+#[test]
+fn votes_are_final_once_the_challenge_is() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+    fixture.vote(0, &challenge, Vote::Approve).unwrap();
+    fixture.vote(1, &challenge, Vote::Approve).unwrap();
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Completed);
+    assert!(fixture.change_vote(0, &challenge, Vote::Reject).is_err());
+}
+
+#[test]
+fn an_abstention_neither_wins_nor_misses() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+    fixture.vote(2, &challenge, Vote::Abstain).unwrap();
+    fixture.vote(0, &challenge, Vote::Approve).unwrap();
+    fixture.vote(1, &challenge, Vote::Approve).unwrap();
+    fixture.close_dispute_window(&challenge);
+    let creator = fixture.creator;
+    fixture.claim(&creator, &challenge, false).unwrap();
+
+    let abstainer = fixture.verifiers[2];
+    let reputation = fixture.reputation(&abstainer).unwrap();
+    assert_eq!(
+        (reputation.votes_cast, reputation.votes_won, reputation.missed_windows),
+        (1, 0, 0)
+    );
+}
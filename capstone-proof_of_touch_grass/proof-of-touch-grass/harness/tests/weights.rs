@@ -0,0 +1,129 @@
+//! Weighted verifiers: approval and rejection thresholds measured in vote
+//! weight, extensions approved by weight and the slash split by weight.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::{Fixture, DAY, STAKE};
+use proof_of_touch_grass::constants::{BASIS_POINTS, SLASH_PENALTY_BPS};
+use proof_of_touch_grass::state::{ChallengeStatus, Vote};
+
+const SLASHED: u64 = STAKE * SLASH_PENALTY_BPS / BASIS_POINTS;
+
+/// An active challenge whose verifiers carry `weights`
+fn weighted(fixture: &mut Fixture, weights: Vec<u8>, required_approval_weight: u8) -> Pubkey {
+    let mut args = fixture.args();
+    args.verifier_weights = weights;
+    args.required_approval_weight = required_approval_weight;
+    let challenge = fixture.try_create(args).unwrap();
+    fixture.svm.warp_to(fixture.challenge(&challenge).start_time);
+    fixture.crank(&challenge).unwrap();
+    challenge
+}
+
+/// A weighted challenge with every proof in, awaiting votes
+fn weighted_pending(fixture: &mut Fixture, weights: Vec<u8>, required_approval_weight: u8) -> Pubkey {
+    let challenge = weighted(fixture, weights, required_approval_weight);
+    fixture.submit(&challenge).unwrap();
+    fixture.submit(&challenge).unwrap();
+    challenge
+}
+
+#[test]
+fn without_weights_every_vote_weighs_one() {
+    let mut fixture = Fixture::new();
+    let challenge = fixture.pending();
+    assert_eq!(fixture.challenge(&challenge).verifier_weights, [1, 1, 1]);
+
+    fixture.vote(0, &challenge, Vote::Approve).unwrap();
+    let state = fixture.challenge(&challenge);
+    assert_eq!((state.approval_count, state.approval_weight), (1, 1));
+}
+
+#[test]
+fn a_heavy_vote_alone_can_meet_the_threshold() {
+    let mut fixture = Fixture::new();
+    let challenge = weighted_pending(&mut fixture, vec![3, 1, 1], 3);
+    fixture.vote(0, &challenge, Vote::Approve).unwrap();
+
+    let state = fixture.challenge(&challenge);
+    assert_eq!(state.status, ChallengeStatus::Completed);
+    assert_eq!((state.approval_count, state.approval_weight), (1, 3));
+}
+
+#[test]
+fn light_votes_add_up_by_weight() {
+    let mut fixture = Fixture::new();
+    let challenge = weighted_pending(&mut fixture, vec![3, 1, 1], 3);
+    fixture.vote(1, &challenge, Vote::Approve).unwrap();
+    fixture.vote(2, &challenge, Vote::Approve).unwrap();
+    // Two approvals, but only weight 2 of the 3 needed
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::PendingVerification);
+
+    // Rejection weight 3 makes approval weight 3 out of reach (5 - 3 < 3)
+    fixture.vote(0, &challenge, Vote::Reject).unwrap();
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::Failed);
+}
+
+#[test]
+fn changing_a_heavy_vote_moves_its_whole_weight() {
+    let mut fixture = Fixture::new();
+    let challenge = weighted_pending(&mut fixture, vec![3, 1, 1], 4);
+    fixture.vote(0, &challenge, Vote::Abstain).unwrap();
+    fixture.vote(1, &challenge, Vote::Approve).unwrap();
+    assert_eq!(fixture.status(&challenge), ChallengeStatus::PendingVerification);
+
+    fixture.change_vote(0, &challenge, Vote::Approve).unwrap();
+    let state = fixture.challenge(&challenge);
+    assert_eq!(state.approval_weight, 4);
+    assert_eq!(state.status, ChallengeStatus::Completed);
+}
+
+#[test]
+fn rejecting_verifiers_split_the_slash_by_weight() {
+    let mut fixture = Fixture::new();
+    // Any rejection weight above 1 puts approval weight 4 of 5 out of reach
+    let challenge = weighted_pending(&mut fixture, vec![2, 1, 2], 4);
+    fixture.vote(1, &challenge, Vote::Reject).unwrap();
+    fixture.vote(0, &challenge, Vote::Reject).unwrap();
+    let state = fixture.challenge(&challenge);
+    assert_eq!(state.status, ChallengeStatus::Failed);
+    assert_eq!((state.rejection_count, state.rejection_weight), (2, 3));
+    fixture.close_dispute_window(&challenge);
+
+    let (heavy, light) = (fixture.verifiers[0], fixture.verifiers[1]);
+    let heavy_before = fixture.svm.lamports(&heavy);
+    let light_before = fixture.svm.lamports(&light);
+    fixture.claim(&heavy, &challenge, true).unwrap();
+    fixture.claim(&light, &challenge, true).unwrap();
+
+    let share_per_weight = SLASHED / 3;
+    let heavy_after = heavy_before + 2 * share_per_weight - fixture.grass_rent(&heavy);
+    let light_after = light_before + share_per_weight - fixture.grass_rent(&light);
+    assert_eq!(fixture.svm.lamports(&heavy), heavy_after);
+    assert_eq!(fixture.svm.lamports(&light), light_after);
+    assert_eq!(fixture.escrow(&challenge), 0);
+}
+
+#[test]
+fn extensions_are_approved_by_weight() {
+    let mut fixture = Fixture::new();
+    let challenge = weighted(&mut fixture, vec![1, 1, 3], 3);
+    let new_end_time = fixture.challenge(&challenge).end_time + DAY;
+    fixture.request_extension(&challenge, new_end_time).unwrap();
+
+    fixture.approve_extension(2, &challenge).unwrap();
+    assert!(fixture.extension(&challenge).is_none());
+    assert_eq!(fixture.challenge(&challenge).end_time, new_end_time);
+}
+
+#[test]
+fn the_approval_weight_cannot_exceed_the_total() {
+    let mut fixture = Fixture::new();
+    let mut args = fixture.args();
+    args.verifier_weights = vec![2, 2, 2];
+    args.required_approval_weight = 7;
+    assert!(fixture.try_create(args.clone()).is_err());
+    args.required_approval_weight = 6;
+    assert!(fixture.try_create(args).is_ok());
+}
@@ -0,0 +1,13 @@
+#[derive(Debug, thiserror::Error)]
+pub enum IndexerError {
+    #[error("sqlite: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+    #[error("invalid base64 event payload: {0}")]
+    Base64(#[from] base64::DecodeError),
+    #[error("failed to decode event: {0}")]
+    Decode(std::io::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("invalid transaction JSON: {0}")]
+    Json(#[from] serde_json::Error),
+}
@@ -0,0 +1,71 @@
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use proof_of_touch_grass::events::*;
+
+use crate::error::IndexerError;
+
+macro_rules! program_events {
+    ($($name:ident),* $(,)?) => {
+        /// Every event the program emits
+        pub enum ProgramEvent {
+            $($name($name),)*
+        }
+
+        impl ProgramEvent {
+            /// Decodes an event payload, returning `None` for unknown discriminators
+            pub fn decode(data: &[u8]) -> Result<Option<Self>, IndexerError> {
+                $(
+                    if let Some(mut payload) = data.strip_prefix($name::DISCRIMINATOR) {
+                        let event = $name::deserialize(&mut payload).map_err(IndexerError::Decode)?;
+                        return Ok(Some(ProgramEvent::$name(event)));
+                    }
+                )*
+                Ok(None)
+            }
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(ProgramEvent::$name(_) => stringify!($name),)*
+                }
+            }
+        }
+    };
+}
+
+program_events!(
+    UserInitialized,
+    UserLimitsUpdated,
+    UserLimitsScheduled,
+    PlatformLimitsUpdated,
+    ChallengeCreated,
+    TemplateCreated,
+    TemplateCurated,
+    ChallengeCreatedFromTemplate,
+    EvidenceSubmitted,
+    EvidenceReplaced,
+    EvidenceRetracted,
+    VoteCast,
+    VoteChanged,
+    ChallengeFinalized,
+    ChallengeRetried,
+    ChallengeCancelled,
+    ExtensionRequested,
+    ExtensionApproved,
+    ChallengeExtended,
+    ExtensionWithdrawn,
+    DisputeFiled,
+    DisputeResolved,
+    JurorRegistered,
+    JurorUnbonding,
+    JurorWithdrawn,
+    DisputeEscalated,
+    JuryVoteCast,
+    JurorSettled,
+    VerifierReputationUpdated,
+    RewardsClaimed,
+    SuccessRewardClaimed,
+    ChallengeStateUpdated,
+    FundsClaimed,
+    GrassConfigured,
+    GrassRewarded,
+    GrassFeeBurned,
+);
@@ -0,0 +1,13 @@
+//! Rebuilds proof-of-touch-grass challenge history into SQLite by replaying
+//! the program's `emit!` events from transaction logs.
+
+pub mod error;
+pub mod events;
+pub mod logs;
+pub mod store;
+pub mod transaction;
+
+pub use error::IndexerError;
+pub use events::ProgramEvent;
+pub use store::Indexer;
+pub use transaction::RecordedTransaction;
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::Pubkey;
+
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+/// Base64 payloads of events emitted by `program_id`, in log order.
+/// Tracks the invoke stack so data logged by other programs (or by CPIs made
+/// from this one) is skipped.
+pub fn program_data<'a>(logs: &'a [String], program_id: &Pubkey) -> Vec<&'a str> {
+    let program_id = program_id.to_string();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut payloads = Vec::new();
+
+    for log in logs {
+        if let Some(payload) = log.strip_prefix(PROGRAM_DATA_PREFIX) {
+            if stack.last() == Some(&program_id.as_str()) {
+                payloads.push(payload);
+            }
+            continue;
+        }
+        let Some(rest) = log.strip_prefix("Program ") else {
+            continue;
+        };
+        // Frame lines name a program; `Program log: failed …` is program output
+        let mut words = rest.split_whitespace();
+        let Some(program) = words.next().filter(|word| word.parse::<Pubkey>().is_ok()) else {
+            continue;
+        };
+        match words.next() {
+            Some("invoke") => stack.push(program),
+            Some("success") => {
+                stack.pop();
+            }
+            Some(word) if word.starts_with("failed") => {
+                stack.pop();
+            }
+            _ => {}
+        }
+    }
+    payloads
+}
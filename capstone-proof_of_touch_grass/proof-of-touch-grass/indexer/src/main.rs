@@ -0,0 +1,31 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+use proof_of_touch_grass_indexer::{Indexer, RecordedTransaction};
+
+/// Replays recorded `getTransaction` responses into a SQLite database
+#[derive(Parser)]
+#[command(name = "ptg-indexer", version)]
+struct Args {
+    /// SQLite database to create or update
+    #[arg(long, default_value = "ptg-index.sqlite")]
+    db: PathBuf,
+    /// JSON files holding one transaction or an array of them, in chain order
+    #[arg(required = true)]
+    files: Vec<PathBuf>,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let mut indexer = Indexer::open(&args.db)?;
+
+    for file in &args.files {
+        let mut applied = 0;
+        for transaction in RecordedTransaction::load_all(file)? {
+            applied += indexer.ingest(&transaction)?;
+        }
+        println!("{}: {applied} event(s) applied", file.display());
+    }
+    Ok(())
+}
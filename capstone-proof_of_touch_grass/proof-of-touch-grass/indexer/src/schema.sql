@@ -0,0 +1,197 @@
+CREATE TABLE IF NOT EXISTS transactions (
+    signature   TEXT PRIMARY KEY,
+    slot        INTEGER NOT NULL,
+    block_time  INTEGER
+);
+
+-- Self-imposed limits; NULL falls back to the platform maximum. Looser limits
+-- wait in the pending_* columns and take over at pending_effective_at.
+CREATE TABLE IF NOT EXISTS users (
+    authority                       TEXT PRIMARY KEY,
+    initialized_at                  INTEGER NOT NULL,
+    max_active_challenges           INTEGER,
+    max_stake                       INTEGER,
+    max_stake_at_risk               INTEGER,
+    failure_cooldown                INTEGER,
+    limits_updated_at               INTEGER,
+    pending_max_active_challenges   INTEGER,
+    pending_max_stake               INTEGER,
+    pending_max_stake_at_risk       INTEGER,
+    pending_failure_cooldown        INTEGER,
+    pending_effective_at            INTEGER
+);
+
+-- The latest platform ceilings on user limits, from PlatformLimitsUpdated; a single row
+CREATE TABLE IF NOT EXISTS platform_limits (
+    id                      INTEGER PRIMARY KEY CHECK (id = 1),
+    max_active_challenges   INTEGER NOT NULL,
+    max_stake               INTEGER NOT NULL,
+    max_stake_at_risk       INTEGER NOT NULL,
+    max_failure_cooldown    INTEGER NOT NULL,
+    updated_at              INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS challenges (
+    address                   TEXT PRIMARY KEY,
+    creator                   TEXT NOT NULL,
+    challenge_id              INTEGER NOT NULL,
+    title                     TEXT NOT NULL,
+    stake_amount              INTEGER NOT NULL,
+    platform_fee              INTEGER NOT NULL,
+    start_time                INTEGER NOT NULL,
+    end_time                  INTEGER NOT NULL,
+    required_proofs           INTEGER NOT NULL,
+    required_approval_weight  INTEGER NOT NULL,
+    verifier_count            INTEGER NOT NULL,
+    evidence_count            INTEGER NOT NULL DEFAULT 0,
+    approval_count            INTEGER NOT NULL DEFAULT 0,
+    rejection_count           INTEGER NOT NULL DEFAULT 0,
+    abstain_count             INTEGER NOT NULL DEFAULT 0,
+    approval_weight           INTEGER NOT NULL DEFAULT 0,
+    rejection_weight          INTEGER NOT NULL DEFAULT 0,
+    status                    TEXT NOT NULL,
+    created_at                INTEGER NOT NULL,
+    finalized_at              INTEGER,
+    previous_challenge        TEXT,
+    template                  TEXT,
+    retried_by                TEXT,
+    updated_at                INTEGER NOT NULL
+);
+CREATE UNIQUE INDEX IF NOT EXISTS challenges_by_creator ON challenges (creator, challenge_id);
+
+CREATE TABLE IF NOT EXISTS templates (
+    address      TEXT PRIMARY KEY,
+    author       TEXT NOT NULL,
+    template_id  INTEGER NOT NULL,
+    title        TEXT NOT NULL,
+    curated      INTEGER NOT NULL DEFAULT 0,
+    uses         INTEGER NOT NULL DEFAULT 0,
+    created_at   INTEGER NOT NULL,
+    updated_at   INTEGER NOT NULL
+);
+
+-- Latest content per evidence slot; `retracted` marks slots freed by retract_evidence
+CREATE TABLE IF NOT EXISTS evidence (
+    challenge       TEXT NOT NULL,
+    evidence_index  INTEGER NOT NULL,
+    address         TEXT NOT NULL,
+    ipfs_hash       TEXT NOT NULL,
+    revision        INTEGER NOT NULL DEFAULT 0,
+    retracted       INTEGER NOT NULL DEFAULT 0,
+    submitted_at    INTEGER NOT NULL,
+    updated_at      INTEGER NOT NULL,
+    PRIMARY KEY (challenge, evidence_index)
+);
+
+CREATE TABLE IF NOT EXISTS votes (
+    challenge   TEXT NOT NULL,
+    verifier    TEXT NOT NULL,
+    vote        TEXT NOT NULL,
+    reason      TEXT,
+    attachment  TEXT,
+    cast_at     INTEGER NOT NULL,
+    changed_at  INTEGER,
+    signature   TEXT NOT NULL,
+    PRIMARY KEY (challenge, verifier)
+);
+
+-- kind: 'claim' (claim_funds) or 'refund' (cancel_challenge)
+CREATE TABLE IF NOT EXISTS payouts (
+    id                INTEGER PRIMARY KEY AUTOINCREMENT,
+    challenge         TEXT NOT NULL,
+    recipient         TEXT NOT NULL,
+    amount            INTEGER NOT NULL,
+    platform_fee      INTEGER NOT NULL,
+    kind              TEXT NOT NULL,
+    challenge_status  TEXT NOT NULL,
+    paid_at           INTEGER NOT NULL,
+    signature         TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS payouts_by_challenge ON payouts (challenge);
+
+-- The latest GRASS emission parameters, from GrassConfigured; a single row
+CREATE TABLE IF NOT EXISTS grass_config (
+    id                INTEGER PRIMARY KEY CHECK (id = 1),
+    mint              TEXT NOT NULL,
+    creator_reward    INTEGER NOT NULL,
+    verifier_reward   INTEGER NOT NULL,
+    grass_per_sol     INTEGER NOT NULL,
+    fee_discount_bps  INTEGER NOT NULL,
+    updated_at        INTEGER NOT NULL
+);
+
+-- kind: 'reward' (minted by claim_funds) or 'fee' (burned by create_challenge)
+CREATE TABLE IF NOT EXISTS grass_transfers (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    challenge   TEXT NOT NULL,
+    account     TEXT NOT NULL,
+    amount      INTEGER NOT NULL,
+    kind        TEXT NOT NULL,
+    at          INTEGER NOT NULL,
+    signature   TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS grass_transfers_by_account ON grass_transfers (account);
+
+CREATE TABLE IF NOT EXISTS disputes (
+    id                 INTEGER PRIMARY KEY AUTOINCREMENT,
+    challenge          TEXT NOT NULL,
+    dispute_index      INTEGER NOT NULL,
+    disputer           TEXT NOT NULL,
+    bond               INTEGER NOT NULL,
+    previous_status    TEXT NOT NULL,
+    reason             TEXT NOT NULL,
+    contested_verifier TEXT,
+    filed_at           INTEGER NOT NULL,
+    signature          TEXT NOT NULL,
+    escalated_at       INTEGER,
+    voting_ends        INTEGER,
+    resolved_at        INTEGER,
+    overturned         INTEGER,
+    by_jury            INTEGER,
+    UNIQUE (challenge, dispute_index)
+);
+
+-- Juror pool membership and bonded stake, from the Juror* events
+CREATE TABLE IF NOT EXISTS jurors (
+    authority       TEXT PRIMARY KEY,
+    stake           INTEGER NOT NULL,
+    registered_at   INTEGER NOT NULL,
+    withdrawable_at INTEGER,
+    withdrawn_at    INTEGER,
+    updated_at      INTEGER NOT NULL
+);
+
+-- One row per seat on an escalated dispute's jury
+CREATE TABLE IF NOT EXISTS jury_seats (
+    challenge     TEXT NOT NULL,
+    dispute_index INTEGER NOT NULL,
+    juror         TEXT NOT NULL,
+    vote          TEXT,
+    voted_at      INTEGER,
+    in_majority   INTEGER,
+    penalty       INTEGER,
+    reward        INTEGER,
+    PRIMARY KEY (challenge, dispute_index, juror)
+);
+
+-- Latest counters per verifier, from VerifierReputationUpdated
+CREATE TABLE IF NOT EXISTS verifier_reputation (
+    verifier          TEXT PRIMARY KEY,
+    votes_cast        INTEGER NOT NULL,
+    votes_won         INTEGER NOT NULL,
+    votes_overturned  INTEGER NOT NULL,
+    missed_windows    INTEGER NOT NULL,
+    updated_at        INTEGER NOT NULL
+);
+
+-- Every decoded event, in chain order, for per-challenge timelines
+CREATE TABLE IF NOT EXISTS challenge_events (
+    signature   TEXT NOT NULL,
+    log_index   INTEGER NOT NULL,
+    slot        INTEGER NOT NULL,
+    challenge   TEXT,
+    name        TEXT NOT NULL,
+    timestamp   INTEGER NOT NULL,
+    PRIMARY KEY (signature, log_index)
+);
+CREATE INDEX IF NOT EXISTS challenge_events_by_challenge ON challenge_events (challenge);
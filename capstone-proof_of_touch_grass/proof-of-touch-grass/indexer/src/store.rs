@@ -0,0 +1,616 @@
+use std::path::Path;
+
+use anchor_lang::prelude::Pubkey;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use rusqlite::{params, Connection, Transaction};
+
+use crate::error::IndexerError;
+use crate::events::ProgramEvent;
+use crate::logs;
+use crate::transaction::RecordedTransaction;
+
+const SCHEMA: &str = include_str!("schema.sql");
+
+/// Materializes program events into SQLite
+pub struct Indexer {
+    conn: Connection,
+    program_id: Pubkey,
+}
+
+/// Where an event was observed
+struct Origin<'a> {
+    signature: &'a str,
+    slot: u64,
+    log_index: usize,
+}
+
+impl Indexer {
+    pub fn open(path: &Path) -> Result<Self, IndexerError> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, IndexerError> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self, IndexerError> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self {
+            conn,
+            program_id: proof_of_touch_grass::ID,
+        })
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Applies every program event in the transaction's logs and returns how many were applied.
+    /// Failed and already indexed transactions are skipped, so replays are idempotent.
+    pub fn ingest(&mut self, transaction: &RecordedTransaction) -> Result<usize, IndexerError> {
+        if !transaction.succeeded() {
+            return Ok(0);
+        }
+
+        let db = self.conn.transaction()?;
+        let inserted = db.execute(
+            "INSERT OR IGNORE INTO transactions (signature, slot, block_time) VALUES (?1, ?2, ?3)",
+            params![transaction.signature(), transaction.slot, transaction.block_time],
+        )?;
+        if inserted == 0 {
+            return Ok(0);
+        }
+
+        let payloads = logs::program_data(&transaction.meta.log_messages, &self.program_id);
+        let mut applied = 0;
+        for (log_index, payload) in payloads.into_iter().enumerate() {
+            let Some(event) = ProgramEvent::decode(&BASE64.decode(payload)?)? else {
+                continue;
+            };
+            let origin = Origin {
+                signature: transaction.signature(),
+                slot: transaction.slot,
+                log_index,
+            };
+            apply(&db, &event, &origin)?;
+            applied += 1;
+        }
+
+        db.commit()?;
+        Ok(applied)
+    }
+}
+
+fn record_history(
+    db: &Transaction,
+    origin: &Origin,
+    name: &str,
+    challenge: Option<&Pubkey>,
+    timestamp: i64,
+) -> rusqlite::Result<()> {
+    db.execute(
+        "INSERT INTO challenge_events (signature, log_index, slot, challenge, name, timestamp)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            origin.signature,
+            origin.log_index,
+            origin.slot,
+            challenge.map(Pubkey::to_string),
+            name,
+            timestamp
+        ],
+    )?;
+    Ok(())
+}
+
+fn set_status(db: &Transaction, challenge: &Pubkey, status: &str, at: i64) -> rusqlite::Result<()> {
+    db.execute(
+        "UPDATE challenges SET status = ?2, updated_at = ?3 WHERE address = ?1",
+        params![challenge.to_string(), status, at],
+    )?;
+    Ok(())
+}
+
+fn apply(db: &Transaction, event: &ProgramEvent, origin: &Origin) -> rusqlite::Result<()> {
+    match event {
+        ProgramEvent::UserInitialized(e) => {
+            db.execute(
+                "INSERT OR IGNORE INTO users (authority, initialized_at) VALUES (?1, ?2)",
+                params![e.authority.to_string(), e.timestamp],
+            )?;
+            record_history(db, origin, event.name(), None, e.timestamp)?;
+        }
+        ProgramEvent::UserLimitsUpdated(e) => {
+            db.execute(
+                "UPDATE users SET max_active_challenges = ?2, max_stake = ?3,
+                    max_stake_at_risk = ?4, failure_cooldown = ?5, limits_updated_at = ?6,
+                    pending_max_active_challenges = NULL, pending_max_stake = NULL,
+                    pending_max_stake_at_risk = NULL, pending_failure_cooldown = NULL,
+                    pending_effective_at = NULL
+                 WHERE authority = ?1",
+                params![
+                    e.authority.to_string(),
+                    e.max_active_challenges,
+                    e.max_stake,
+                    e.max_stake_at_risk,
+                    e.failure_cooldown,
+                    e.timestamp
+                ],
+            )?;
+            record_history(db, origin, event.name(), None, e.timestamp)?;
+        }
+        // Always follows the UserLimitsUpdated of the same call
+        ProgramEvent::UserLimitsScheduled(e) => {
+            db.execute(
+                "UPDATE users SET pending_max_active_challenges = ?2, pending_max_stake = ?3,
+                    pending_max_stake_at_risk = ?4, pending_failure_cooldown = ?5,
+                    pending_effective_at = ?6
+                 WHERE authority = ?1",
+                params![
+                    e.authority.to_string(),
+                    e.max_active_challenges,
+                    e.max_stake,
+                    e.max_stake_at_risk,
+                    e.failure_cooldown,
+                    e.effective_at
+                ],
+            )?;
+            record_history(db, origin, event.name(), None, e.timestamp)?;
+        }
+        ProgramEvent::PlatformLimitsUpdated(e) => {
+            db.execute(
+                "INSERT OR REPLACE INTO platform_limits
+                    (id, max_active_challenges, max_stake, max_stake_at_risk, max_failure_cooldown, updated_at)
+                 VALUES (1, ?1, ?2, ?3, ?4, ?5)",
+                params![
+                    e.max_active_challenges,
+                    e.max_stake,
+                    e.max_stake_at_risk,
+                    e.max_failure_cooldown,
+                    e.timestamp
+                ],
+            )?;
+            record_history(db, origin, event.name(), None, e.timestamp)?;
+        }
+        ProgramEvent::ChallengeCreated(e) => {
+            db.execute(
+                "INSERT OR REPLACE INTO challenges (
+                    address, creator, challenge_id, title, stake_amount, platform_fee, start_time,
+                    end_time, required_proofs, required_approval_weight, verifier_count, status,
+                    created_at, updated_at
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, 'Created', ?12, ?12)",
+                params![
+                    e.challenge.to_string(),
+                    e.creator.to_string(),
+                    e.challenge_id,
+                    e.title,
+                    e.stake_amount,
+                    e.platform_fee,
+                    e.start_time,
+                    e.end_time,
+                    e.required_proofs,
+                    e.required_approval_weight,
+                    e.verifier_count,
+                    e.timestamp
+                ],
+            )?;
+            record_history(db, origin, event.name(), Some(&e.challenge), e.timestamp)?;
+        }
+        ProgramEvent::TemplateCreated(e) => {
+            db.execute(
+                "INSERT OR REPLACE INTO templates (
+                    address, author, template_id, title, created_at, updated_at
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?5)",
+                params![
+                    e.template.to_string(),
+                    e.author.to_string(),
+                    e.template_id,
+                    e.title,
+                    e.timestamp
+                ],
+            )?;
+            record_history(db, origin, event.name(), None, e.timestamp)?;
+        }
+        ProgramEvent::TemplateCurated(e) => {
+            db.execute(
+                "UPDATE templates SET curated = ?2, updated_at = ?3 WHERE address = ?1",
+                params![e.template.to_string(), e.curated, e.timestamp],
+            )?;
+            record_history(db, origin, event.name(), None, e.timestamp)?;
+        }
+        ProgramEvent::ChallengeCreatedFromTemplate(e) => {
+            // ChallengeCreated is logged first, in the same transaction
+            db.execute(
+                "UPDATE challenges SET template = ?2, updated_at = ?3 WHERE address = ?1",
+                params![e.challenge.to_string(), e.template.to_string(), e.timestamp],
+            )?;
+            db.execute(
+                "UPDATE templates SET uses = ?2, updated_at = ?3 WHERE address = ?1",
+                params![e.template.to_string(), e.uses, e.timestamp],
+            )?;
+            record_history(db, origin, event.name(), Some(&e.challenge), e.timestamp)?;
+        }
+        ProgramEvent::EvidenceSubmitted(e) => {
+            db.execute(
+                "INSERT OR REPLACE INTO evidence
+                    (challenge, evidence_index, address, ipfs_hash, submitted_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?5)",
+                params![
+                    e.challenge.to_string(),
+                    e.evidence_index,
+                    e.evidence.to_string(),
+                    e.ipfs_hash,
+                    e.timestamp
+                ],
+            )?;
+            db.execute(
+                "UPDATE challenges SET evidence_count = ?2, updated_at = ?3 WHERE address = ?1",
+                params![e.challenge.to_string(), e.total_evidence, e.timestamp],
+            )?;
+            if e.total_evidence == e.required_proofs {
+                set_status(db, &e.challenge, "PendingVerification", e.timestamp)?;
+            }
+            record_history(db, origin, event.name(), Some(&e.challenge), e.timestamp)?;
+        }
+        ProgramEvent::EvidenceReplaced(e) => {
+            db.execute(
+                "UPDATE evidence SET ipfs_hash = ?3, revision = ?4, updated_at = ?5
+                 WHERE challenge = ?1 AND evidence_index = ?2",
+                params![
+                    e.challenge.to_string(),
+                    e.evidence_index,
+                    e.new_ipfs_hash,
+                    e.revision,
+                    e.timestamp
+                ],
+            )?;
+            record_history(db, origin, event.name(), Some(&e.challenge), e.timestamp)?;
+        }
+        ProgramEvent::EvidenceRetracted(e) => {
+            db.execute(
+                "UPDATE evidence SET retracted = 1, updated_at = ?3
+                 WHERE challenge = ?1 AND evidence_index = ?2",
+                params![e.challenge.to_string(), e.evidence_index, e.timestamp],
+            )?;
+            db.execute(
+                "UPDATE challenges SET evidence_count = ?2, updated_at = ?3,
+                    status = CASE status WHEN 'PendingVerification' THEN 'Active' ELSE status END
+                 WHERE address = ?1",
+                params![e.challenge.to_string(), e.total_evidence, e.timestamp],
+            )?;
+            record_history(db, origin, event.name(), Some(&e.challenge), e.timestamp)?;
+        }
+        ProgramEvent::VoteCast(e) => {
+            db.execute(
+                "INSERT OR REPLACE INTO votes
+                    (challenge, verifier, vote, reason, attachment, cast_at, signature)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    e.challenge.to_string(),
+                    e.verifier.to_string(),
+                    e.vote,
+                    e.justification.as_ref().map(|j| &j.reason),
+                    e.justification.as_ref().and_then(|j| j.attachment.as_ref()),
+                    e.timestamp,
+                    origin.signature
+                ],
+            )?;
+            db.execute(
+                "UPDATE challenges
+                 SET approval_count = ?2, rejection_count = ?3, abstain_count = ?4,
+                     approval_weight = ?5, rejection_weight = ?6, updated_at = ?7
+                 WHERE address = ?1",
+                params![
+                    e.challenge.to_string(),
+                    e.approval_count,
+                    e.rejection_count,
+                    e.abstain_count,
+                    e.approval_weight,
+                    e.rejection_weight,
+                    e.timestamp
+                ],
+            )?;
+            record_history(db, origin, event.name(), Some(&e.challenge), e.timestamp)?;
+        }
+        ProgramEvent::VoteChanged(e) => {
+            db.execute(
+                "UPDATE votes
+                 SET vote = ?3, reason = ?4, attachment = ?5, changed_at = ?6, signature = ?7
+                 WHERE challenge = ?1 AND verifier = ?2",
+                params![
+                    e.challenge.to_string(),
+                    e.verifier.to_string(),
+                    e.vote,
+                    e.justification.as_ref().map(|j| &j.reason),
+                    e.justification.as_ref().and_then(|j| j.attachment.as_ref()),
+                    e.timestamp,
+                    origin.signature
+                ],
+            )?;
+            db.execute(
+                "UPDATE challenges
+                 SET approval_count = ?2, rejection_count = ?3, abstain_count = ?4,
+                     approval_weight = ?5, rejection_weight = ?6, updated_at = ?7
+                 WHERE address = ?1",
+                params![
+                    e.challenge.to_string(),
+                    e.approval_count,
+                    e.rejection_count,
+                    e.abstain_count,
+                    e.approval_weight,
+                    e.rejection_weight,
+                    e.timestamp
+                ],
+            )?;
+            record_history(db, origin, event.name(), Some(&e.challenge), e.timestamp)?;
+        }
+        ProgramEvent::ChallengeFinalized(e) => {
+            db.execute(
+                "UPDATE challenges SET status = ?2, finalized_at = ?3, updated_at = ?3
+                 WHERE address = ?1",
+                params![e.challenge.to_string(), e.status, e.timestamp],
+            )?;
+            record_history(db, origin, event.name(), Some(&e.challenge), e.timestamp)?;
+        }
+        ProgramEvent::ChallengeStateUpdated(e) => {
+            set_status(db, &e.challenge, &e.new_status, e.timestamp)?;
+            if e.new_status == "Completed" || e.new_status == "Failed" {
+                db.execute(
+                    "UPDATE challenges SET finalized_at = ?2 WHERE address = ?1",
+                    params![e.challenge.to_string(), e.timestamp],
+                )?;
+            }
+            record_history(db, origin, event.name(), Some(&e.challenge), e.timestamp)?;
+        }
+        ProgramEvent::ChallengeCancelled(e) => {
+            set_status(db, &e.challenge, "Cancelled", e.timestamp)?;
+            db.execute(
+                "INSERT INTO payouts
+                    (challenge, recipient, amount, platform_fee, kind, challenge_status, paid_at, signature)
+                 VALUES (?1, ?2, ?3, ?4, 'refund', 'Cancelled', ?5, ?6)",
+                params![
+                    e.challenge.to_string(),
+                    e.creator.to_string(),
+                    e.refund_amount,
+                    e.penalty,
+                    e.timestamp,
+                    origin.signature
+                ],
+            )?;
+            record_history(db, origin, event.name(), Some(&e.challenge), e.timestamp)?;
+        }
+        ProgramEvent::ExtensionRequested(e) => {
+            record_history(db, origin, event.name(), Some(&e.challenge), e.timestamp)?;
+        }
+        ProgramEvent::ExtensionApproved(e) => {
+            record_history(db, origin, event.name(), Some(&e.challenge), e.timestamp)?;
+        }
+        ProgramEvent::ChallengeExtended(e) => {
+            db.execute(
+                "UPDATE challenges SET end_time = ?2, updated_at = ?3 WHERE address = ?1",
+                params![e.challenge.to_string(), e.new_end_time, e.timestamp],
+            )?;
+            record_history(db, origin, event.name(), Some(&e.challenge), e.timestamp)?;
+        }
+        ProgramEvent::ChallengeRetried(e) => {
+            // ChallengeCreated for the retry is logged first, in the same transaction
+            // The retry keeps the failed challenge's template
+            db.execute(
+                "UPDATE challenges SET previous_challenge = ?2, updated_at = ?3,
+                    template = (SELECT template FROM challenges WHERE address = ?2)
+                 WHERE address = ?1",
+                params![
+                    e.challenge.to_string(),
+                    e.previous_challenge.to_string(),
+                    e.timestamp
+                ],
+            )?;
+            db.execute(
+                "UPDATE challenges SET retried_by = ?2, updated_at = ?3 WHERE address = ?1",
+                params![
+                    e.previous_challenge.to_string(),
+                    e.challenge.to_string(),
+                    e.timestamp
+                ],
+            )?;
+            record_history(db, origin, event.name(), Some(&e.challenge), e.timestamp)?;
+        }
+        ProgramEvent::ExtensionWithdrawn(e) => {
+            record_history(db, origin, event.name(), Some(&e.challenge), e.timestamp)?;
+        }
+        ProgramEvent::DisputeFiled(e) => {
+            set_status(db, &e.challenge, "Disputed", e.timestamp)?;
+            db.execute(
+                "INSERT INTO disputes
+                    (challenge, dispute_index, disputer, bond, previous_status, reason,
+                     contested_verifier, filed_at, signature)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    e.challenge.to_string(),
+                    e.dispute_index,
+                    e.disputer.to_string(),
+                    e.bond,
+                    e.previous_status,
+                    e.reason,
+                    e.contested_verifier.map(|verifier| verifier.to_string()),
+                    e.timestamp,
+                    origin.signature
+                ],
+            )?;
+            record_history(db, origin, event.name(), Some(&e.challenge), e.timestamp)?;
+        }
+        ProgramEvent::DisputeResolved(e) => {
+            set_status(db, &e.challenge, &e.status, e.timestamp)?;
+            db.execute(
+                "UPDATE disputes SET resolved_at = ?3, overturned = ?4, by_jury = ?5
+                 WHERE challenge = ?1 AND dispute_index = ?2",
+                params![
+                    e.challenge.to_string(),
+                    e.dispute_index,
+                    e.timestamp,
+                    e.overturned,
+                    e.by_jury
+                ],
+            )?;
+            record_history(db, origin, event.name(), Some(&e.challenge), e.timestamp)?;
+        }
+        ProgramEvent::JurorRegistered(e) => {
+            // A juror who withdrew and registers again starts a fresh row
+            db.execute(
+                "INSERT OR REPLACE INTO jurors (authority, stake, registered_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?3)",
+                params![e.juror.to_string(), e.stake, e.timestamp],
+            )?;
+            record_history(db, origin, event.name(), None, e.timestamp)?;
+        }
+        ProgramEvent::JurorUnbonding(e) => {
+            db.execute(
+                "UPDATE jurors SET withdrawable_at = ?2, updated_at = ?3 WHERE authority = ?1",
+                params![e.juror.to_string(), e.withdrawable_at, e.timestamp],
+            )?;
+            record_history(db, origin, event.name(), None, e.timestamp)?;
+        }
+        ProgramEvent::JurorWithdrawn(e) => {
+            db.execute(
+                "UPDATE jurors SET stake = 0, withdrawn_at = ?2, updated_at = ?2
+                 WHERE authority = ?1",
+                params![e.juror.to_string(), e.timestamp],
+            )?;
+            record_history(db, origin, event.name(), None, e.timestamp)?;
+        }
+        ProgramEvent::DisputeEscalated(e) => {
+            db.execute(
+                "UPDATE disputes SET escalated_at = ?3, voting_ends = ?4
+                 WHERE challenge = ?1 AND dispute_index = ?2",
+                params![e.challenge.to_string(), e.dispute_index, e.timestamp, e.voting_ends],
+            )?;
+            for juror in &e.jurors {
+                db.execute(
+                    "INSERT OR IGNORE INTO jury_seats (challenge, dispute_index, juror)
+                     VALUES (?1, ?2, ?3)",
+                    params![e.challenge.to_string(), e.dispute_index, juror.to_string()],
+                )?;
+            }
+            record_history(db, origin, event.name(), Some(&e.challenge), e.timestamp)?;
+        }
+        ProgramEvent::JuryVoteCast(e) => {
+            db.execute(
+                "UPDATE jury_seats SET vote = ?4, voted_at = ?5
+                 WHERE challenge = ?1 AND dispute_index = ?2 AND juror = ?3",
+                params![
+                    e.challenge.to_string(),
+                    e.dispute_index,
+                    e.juror.to_string(),
+                    e.vote,
+                    e.timestamp
+                ],
+            )?;
+            record_history(db, origin, event.name(), Some(&e.challenge), e.timestamp)?;
+        }
+        ProgramEvent::JurorSettled(e) => {
+            db.execute(
+                "UPDATE jury_seats SET in_majority = ?4, penalty = ?5, reward = ?6
+                 WHERE challenge = ?1 AND dispute_index = ?2 AND juror = ?3",
+                params![
+                    e.challenge.to_string(),
+                    e.dispute_index,
+                    e.juror.to_string(),
+                    e.in_majority,
+                    e.penalty,
+                    e.reward
+                ],
+            )?;
+            db.execute(
+                "UPDATE jurors SET stake = ?2, updated_at = ?3 WHERE authority = ?1",
+                params![e.juror.to_string(), e.stake, e.timestamp],
+            )?;
+            record_history(db, origin, event.name(), Some(&e.challenge), e.timestamp)?;
+        }
+        ProgramEvent::VerifierReputationUpdated(e) => {
+            db.execute(
+                "INSERT OR REPLACE INTO verifier_reputation
+                    (verifier, votes_cast, votes_won, votes_overturned, missed_windows, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    e.verifier.to_string(),
+                    e.votes_cast,
+                    e.votes_won,
+                    e.votes_overturned,
+                    e.missed_windows,
+                    e.timestamp
+                ],
+            )?;
+            record_history(db, origin, event.name(), None, e.timestamp)?;
+        }
+        ProgramEvent::FundsClaimed(e) => {
+            db.execute(
+                "INSERT INTO payouts
+                    (challenge, recipient, amount, platform_fee, kind, challenge_status, paid_at, signature)
+                 VALUES (?1, ?2, ?3, ?4, 'claim', ?5, ?6, ?7)",
+                params![
+                    e.challenge.to_string(),
+                    e.claimer.to_string(),
+                    e.amount,
+                    e.platform_fee,
+                    e.challenge_status,
+                    e.timestamp,
+                    origin.signature
+                ],
+            )?;
+            record_history(db, origin, event.name(), Some(&e.challenge), e.timestamp)?;
+        }
+        // Declared but never emitted; `FundsClaimed` covers both claim paths
+        ProgramEvent::RewardsClaimed(e) => {
+            record_history(db, origin, event.name(), Some(&e.challenge), e.timestamp)?;
+        }
+        ProgramEvent::SuccessRewardClaimed(e) => {
+            record_history(db, origin, event.name(), Some(&e.challenge), e.timestamp)?;
+        }
+        ProgramEvent::GrassConfigured(e) => {
+            db.execute(
+                "INSERT OR REPLACE INTO grass_config
+                    (id, mint, creator_reward, verifier_reward, grass_per_sol, fee_discount_bps, updated_at)
+                 VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    e.mint.to_string(),
+                    e.creator_reward,
+                    e.verifier_reward,
+                    e.grass_per_sol,
+                    e.fee_discount_bps,
+                    e.timestamp
+                ],
+            )?;
+            record_history(db, origin, event.name(), None, e.timestamp)?;
+        }
+        ProgramEvent::GrassRewarded(e) => {
+            db.execute(
+                "INSERT INTO grass_transfers (challenge, account, amount, kind, at, signature)
+                 VALUES (?1, ?2, ?3, 'reward', ?4, ?5)",
+                params![
+                    e.challenge.to_string(),
+                    e.recipient.to_string(),
+                    e.amount,
+                    e.timestamp,
+                    origin.signature
+                ],
+            )?;
+            record_history(db, origin, event.name(), Some(&e.challenge), e.timestamp)?;
+        }
+        ProgramEvent::GrassFeeBurned(e) => {
+            db.execute(
+                "INSERT INTO grass_transfers (challenge, account, amount, kind, at, signature)
+                 VALUES (?1, ?2, ?3, 'fee', ?4, ?5)",
+                params![
+                    e.challenge.to_string(),
+                    e.creator.to_string(),
+                    e.amount,
+                    e.timestamp,
+                    origin.signature
+                ],
+            )?;
+            record_history(db, origin, event.name(), Some(&e.challenge), e.timestamp)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use std::path::Path;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::error::IndexerError;
+
+/// A transaction as returned by `getTransaction` (JSON encoding), keeping only
+/// the fields the indexer reads
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedTransaction {
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub transaction: TransactionBody,
+    pub meta: TransactionMeta,
+}
+
+#[derive(Deserialize)]
+pub struct TransactionBody {
+    pub signatures: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionMeta {
+    pub err: Option<Value>,
+    #[serde(default)]
+    pub log_messages: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(Box<RecordedTransaction>),
+    Many(Vec<RecordedTransaction>),
+}
+
+impl RecordedTransaction {
+    pub fn signature(&self) -> &str {
+        self.transaction.signatures.first().map(String::as_str).unwrap_or_default()
+    }
+
+    pub fn succeeded(&self) -> bool {
+        self.meta.err.is_none()
+    }
+
+    /// Parses a recorded `getTransaction` response, or an array of them
+    pub fn parse_all(json: &str) -> Result<Vec<Self>, IndexerError> {
+        Ok(match serde_json::from_str(json)? {
+            OneOrMany::One(transaction) => vec![*transaction],
+            OneOrMany::Many(transactions) => transactions,
+        })
+    }
+
+    pub fn load_all(path: &Path) -> Result<Vec<Self>, IndexerError> {
+        Self::parse_all(&std::fs::read_to_string(path)?)
+    }
+}
@@ -0,0 +1,424 @@
+use std::path::PathBuf;
+
+use proof_of_touch_grass_indexer::{Indexer, RecordedTransaction};
+use rusqlite::params;
+
+const CREATOR: &str = "DgX9xEoN7RZGWevFVCy13JuzKsnmAx9B3VLfvoJxwqKn";
+const VERIFIER: &str = "EGq8iqXd6cz2HL32XCsK78h9wQRC2NeYLci9BvdvPneN";
+const SECOND_VERIFIER: &str = "2PKoBNFTtvCNf5N9SdrkQqpxwm8VSU9r8KtnDrYsSkTN";
+const COMPLETED_CHALLENGE: &str = "9EFrLzhFsHMuEeuex8zVeBY96XJhjjaR3jJ1JncxhTsx";
+const FAILED_CHALLENGE: &str = "FZeXoFt8joDZmdgZ7rQ9DTngKbW93R4XEiwjzHDaWj3E";
+const CANCELLED_CHALLENGE: &str = "esnFmqHTffJuYtgQhns5iSYK5nrL9q24ad5siyPRvzY";
+const TEMPLATE: &str = "AdkDYpTpr3kQgmQqYkie6yXqDSttCuMnwtviaGEn51fm";
+const RETRY_CHALLENGE: &str = "D1i1B78YnYb9H4HNjYkj7TF2hzwqaLrM9bCsyiFEpCmB";
+const SILENT_JUROR: &str = "B1TvigpdC3m3uaiurgZCPQrC6uBbv3TnTkCiqERusYxt";
+
+fn fixture(name: &str) -> Vec<RecordedTransaction> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name);
+    RecordedTransaction::load_all(&path).unwrap()
+}
+
+fn replay(indexer: &mut Indexer, name: &str) -> usize {
+    fixture(name)
+        .iter()
+        .map(|transaction| indexer.ingest(transaction).unwrap())
+        .sum()
+}
+
+fn count(indexer: &Indexer, sql: &str) -> i64 {
+    indexer.connection().query_row(sql, [], |row| row.get(0)).unwrap()
+}
+
+fn status(indexer: &Indexer, challenge: &str) -> String {
+    indexer
+        .connection()
+        .query_row(
+            "SELECT status FROM challenges WHERE address = ?1",
+            params![challenge],
+            |row| row.get(0),
+        )
+        .unwrap()
+}
+
+#[test]
+fn completed_challenge_lifecycle() {
+    let mut indexer = Indexer::open_in_memory().unwrap();
+    assert_eq!(replay(&mut indexer, "completed_challenge.json"), 24);
+
+    assert_eq!(status(&indexer, COMPLETED_CHALLENGE), "Completed");
+    assert_eq!(count(&indexer, "SELECT COUNT(*) FROM users"), 1);
+
+    let limits: (Option<i64>, Option<i64>, Option<i64>, Option<i64>) = indexer
+        .connection()
+        .query_row(
+            "SELECT max_active_challenges, max_stake, max_stake_at_risk, failure_cooldown
+             FROM users WHERE authority = ?1",
+            params![CREATOR],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .unwrap();
+    assert_eq!(limits, (Some(3), Some(5_000_000_000), None, Some(86_400)));
+
+    let (challenge_id, end_time, evidence_count, approvals, finalized_at, template): (
+        i64,
+        i64,
+        i64,
+        i64,
+        Option<i64>,
+        Option<String>,
+    ) = indexer
+        .connection()
+        .query_row(
+            "SELECT challenge_id, end_time, evidence_count, approval_count, finalized_at, template
+             FROM challenges WHERE address = ?1",
+            params![COMPLETED_CHALLENGE],
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                ))
+            },
+        )
+        .unwrap();
+    assert_eq!(challenge_id, 0);
+    assert_eq!(template.as_deref(), Some(TEMPLATE));
+    // Moved by the approved extension
+    assert_eq!(end_time, 1_767_225_600 + 172_801);
+    assert_eq!(evidence_count, 2);
+    assert_eq!(approvals, 1);
+    assert!(finalized_at.is_some());
+
+    let (curated, uses): (bool, i64) = indexer
+        .connection()
+        .query_row(
+            "SELECT curated, uses FROM templates WHERE address = ?1",
+            params![TEMPLATE],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert!(curated);
+    assert_eq!(uses, 1);
+
+    let (ipfs_hash, revision): (String, i64) = indexer
+        .connection()
+        .query_row(
+            "SELECT ipfs_hash, revision FROM evidence WHERE challenge = ?1 AND evidence_index = 0",
+            params![COMPLETED_CHALLENGE],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(ipfs_hash, "QmDayOneFixed");
+    assert_eq!(revision, 1);
+
+    let vote: String = indexer
+        .connection()
+        .query_row(
+            "SELECT vote FROM votes WHERE challenge = ?1 AND verifier = ?2",
+            params![COMPLETED_CHALLENGE, VERIFIER],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(vote, "Approve");
+
+    let (vote, changed_at): (String, Option<i64>) = indexer
+        .connection()
+        .query_row(
+            "SELECT vote, changed_at FROM votes WHERE challenge = ?1 AND verifier = ?2",
+            params![COMPLETED_CHALLENGE, SECOND_VERIFIER],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(vote, "Abstain");
+    assert_eq!(changed_at, Some(1_767_225_600 + 90_200));
+
+    let tallies: (i64, i64, i64) = indexer
+        .connection()
+        .query_row(
+            "SELECT rejection_count, abstain_count, approval_weight FROM challenges
+             WHERE address = ?1",
+            params![COMPLETED_CHALLENGE],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(tallies, (0, 1, 1));
+
+    let (recipient, amount, fee): (String, i64, i64) = indexer
+        .connection()
+        .query_row(
+            "SELECT recipient, amount, platform_fee FROM payouts WHERE challenge = ?1",
+            params![COMPLETED_CHALLENGE],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(recipient, CREATOR);
+    assert_eq!(amount, 1_002_500_000);
+    assert_eq!(fee, 2_500_000);
+}
+
+#[test]
+fn failed_transactions_and_foreign_program_data_are_ignored() {
+    let mut indexer = Indexer::open_in_memory().unwrap();
+    replay(&mut indexer, "completed_challenge.json");
+
+    // The failed submission carried "QmTooSoon"; only the successful retry is stored
+    assert_eq!(
+        count(&indexer, "SELECT COUNT(*) FROM evidence WHERE ipfs_hash = 'QmTooSoon'"),
+        0
+    );
+    assert_eq!(count(&indexer, "SELECT COUNT(*) FROM transactions"), 19);
+    assert_eq!(count(&indexer, "SELECT COUNT(*) FROM payouts"), 1);
+}
+
+#[test]
+fn platform_limits_and_scheduled_user_limits() {
+    let mut indexer = Indexer::open_in_memory().unwrap();
+    replay(&mut indexer, "completed_challenge.json");
+
+    let ceilings: (i64, i64, i64, i64) = indexer
+        .connection()
+        .query_row(
+            "SELECT max_active_challenges, max_stake, max_stake_at_risk, max_failure_cooldown
+             FROM platform_limits",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .unwrap();
+    assert_eq!(ceilings, (50, 100_000_000_000, 10_000_000_000_000, 2_592_000));
+
+    // The looser stake limit and dropped cooldown wait a day
+    let pending: (Option<i64>, Option<i64>, Option<i64>, Option<i64>) = indexer
+        .connection()
+        .query_row(
+            "SELECT pending_max_active_challenges, pending_max_stake, pending_max_stake_at_risk,
+                pending_failure_cooldown
+             FROM users WHERE authority = ?1",
+            params![CREATOR],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .unwrap();
+    assert_eq!(pending, (Some(3), Some(8_000_000_000), None, None));
+    assert_eq!(
+        count(&indexer, "SELECT pending_effective_at FROM users"),
+        1_767_312_000
+    );
+}
+
+#[test]
+fn program_output_is_not_read_as_frame_lines() {
+    let mut indexer = Indexer::open_in_memory().unwrap();
+    replay(&mut indexer, "completed_challenge.json");
+
+    // The creator's claim logs "failed verifiers: 0" and "invoke reward mint"
+    // before its two events; the verifier's GRASS claim follows it
+    let claim_events = count(
+        &indexer,
+        "SELECT COUNT(*) FROM challenge_events WHERE name IN ('FundsClaimed', 'GrassRewarded')",
+    );
+    assert_eq!(claim_events, 3);
+    assert_eq!(count(&indexer, "SELECT COUNT(*) FROM payouts"), 1);
+}
+
+#[test]
+fn failed_and_cancelled_challenges() {
+    let mut indexer = Indexer::open_in_memory().unwrap();
+    replay(&mut indexer, "failed_and_cancelled.json");
+
+    assert_eq!(status(&indexer, FAILED_CHALLENGE), "Failed");
+    assert_eq!(status(&indexer, CANCELLED_CHALLENGE), "Cancelled");
+
+    let (recipient, amount, kind): (String, i64, String) = indexer
+        .connection()
+        .query_row(
+            "SELECT recipient, amount, challenge_status FROM payouts WHERE challenge = ?1",
+            params![FAILED_CHALLENGE],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(recipient, VERIFIER);
+    assert_eq!(amount, 500_000_000);
+    assert_eq!(kind, "Failed");
+
+    let (reason, attachment): (Option<String>, Option<String>) = indexer
+        .connection()
+        .query_row(
+            "SELECT reason, attachment FROM votes WHERE challenge = ?1 AND verifier = ?2",
+            params![FAILED_CHALLENGE, VERIFIER],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(reason.as_deref(), Some("Page count is not visible"));
+    assert_eq!(attachment.as_deref(), Some("QmBlurryAnnotated"));
+
+    let (contested, overturned): (Option<String>, bool) = indexer
+        .connection()
+        .query_row(
+            "SELECT contested_verifier, overturned FROM disputes
+             WHERE challenge = ?1 AND dispute_index = 0",
+            params![FAILED_CHALLENGE],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(contested.as_deref(), Some(VERIFIER));
+    assert!(!overturned);
+
+    let (refund, penalty, kind): (i64, i64, String) = indexer
+        .connection()
+        .query_row(
+            "SELECT amount, platform_fee, kind FROM payouts WHERE challenge = ?1",
+            params![CANCELLED_CHALLENGE],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    // Paid in GRASS, so only the stake was escrowed
+    assert_eq!(refund, 980_000_000);
+    assert_eq!(penalty, 20_000_000);
+    assert_eq!(kind, "refund");
+
+    assert_eq!(status(&indexer, RETRY_CHALLENGE), "Created");
+    let link = |address: &str| -> (Option<String>, Option<String>) {
+        indexer
+            .connection()
+            .query_row(
+                "SELECT previous_challenge, retried_by FROM challenges WHERE address = ?1",
+                params![address],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap()
+    };
+    assert_eq!(link(RETRY_CHALLENGE), (Some(FAILED_CHALLENGE.to_string()), None));
+    assert_eq!(link(FAILED_CHALLENGE), (None, Some(RETRY_CHALLENGE.to_string())));
+}
+
+#[test]
+fn escalated_dispute_and_jury() {
+    let mut indexer = Indexer::open_in_memory().unwrap();
+    replay(&mut indexer, "failed_and_cancelled.json");
+
+    let (escalated_at, voting_ends, by_jury): (Option<i64>, Option<i64>, bool) = indexer
+        .connection()
+        .query_row(
+            "SELECT escalated_at, voting_ends, by_jury FROM disputes
+             WHERE challenge = ?1 AND dispute_index = 0",
+            params![FAILED_CHALLENGE],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(escalated_at, Some(1_767_225_600 + 400));
+    assert_eq!(voting_ends, Some(1_767_225_600 + 400 + 259_200));
+    assert!(by_jury);
+
+    assert_eq!(
+        count(&indexer, "SELECT COUNT(*) FROM jury_seats WHERE vote = 'Uphold' AND in_majority"),
+        2
+    );
+    let (vote, penalty): (Option<String>, i64) = indexer
+        .connection()
+        .query_row(
+            "SELECT vote, penalty FROM jury_seats WHERE challenge = ?1 AND juror = ?2",
+            params![FAILED_CHALLENGE, SILENT_JUROR],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!((vote, penalty), (None, 100_000_000));
+
+    let (stake, withdrawable_at): (i64, Option<i64>) = indexer
+        .connection()
+        .query_row(
+            "SELECT stake, withdrawable_at FROM jurors WHERE authority = ?1",
+            params![SILENT_JUROR],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(stake, 900_000_000);
+    assert_eq!(withdrawable_at, Some(1_767_225_600 + 800 + 1_209_600));
+    assert_eq!(count(&indexer, "SELECT SUM(stake) FROM jurors"), 3_000_000_000);
+}
+
+#[test]
+fn a_challenge_can_be_disputed_again() {
+    let mut indexer = Indexer::open_in_memory().unwrap();
+    replay(&mut indexer, "failed_and_cancelled.json");
+
+    let (bond, escalated_at, overturned, by_jury): (i64, Option<i64>, bool, bool) = indexer
+        .connection()
+        .query_row(
+            "SELECT bond, escalated_at, overturned, by_jury FROM disputes
+             WHERE challenge = ?1 AND dispute_index = 1",
+            params![FAILED_CHALLENGE],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .unwrap();
+    assert_eq!(bond, 40_000_000);
+    assert_eq!(escalated_at, None);
+    assert!(!overturned && !by_jury);
+    assert_eq!(
+        count(&indexer, "SELECT COUNT(*) FROM disputes WHERE resolved_at IS NOT NULL"),
+        2
+    );
+    assert_eq!(status(&indexer, FAILED_CHALLENGE), "Failed");
+}
+
+#[test]
+fn grass_configuration_rewards_and_burned_fees() {
+    let mut indexer = Indexer::open_in_memory().unwrap();
+    replay(&mut indexer, "completed_challenge.json");
+    replay(&mut indexer, "failed_and_cancelled.json");
+
+    let emission: (i64, i64, i64, i64) = indexer
+        .connection()
+        .query_row(
+            "SELECT creator_reward, verifier_reward, grass_per_sol, fee_discount_bps
+             FROM grass_config",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .unwrap();
+    assert_eq!(emission, (100_000_000, 10_000_000, 1_000_000_000, 2_000));
+
+    let transfers = |account: &str, kind: &str| -> i64 {
+        indexer
+            .connection()
+            .query_row(
+                "SELECT COALESCE(SUM(amount), 0) FROM grass_transfers
+                 WHERE account = ?1 AND kind = ?2",
+                params![account, kind],
+                |row| row.get(0),
+            )
+            .unwrap()
+    };
+    // The creator's completion, and the verifier's approval and rejection
+    assert_eq!(transfers(CREATOR, "reward"), 100_000_000);
+    assert_eq!(transfers(VERIFIER, "reward"), 20_000_000);
+    assert_eq!(transfers(CREATOR, "fee"), 4_000_000);
+
+    let platform_fee: i64 = indexer
+        .connection()
+        .query_row(
+            "SELECT platform_fee FROM challenges WHERE address = ?1",
+            params![CANCELLED_CHALLENGE],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(platform_fee, 0);
+}
+
+#[test]
+fn replaying_twice_is_idempotent() {
+    let mut indexer = Indexer::open_in_memory().unwrap();
+    replay(&mut indexer, "completed_challenge.json");
+    replay(&mut indexer, "failed_and_cancelled.json");
+    let events = count(&indexer, "SELECT COUNT(*) FROM challenge_events");
+
+    assert_eq!(replay(&mut indexer, "completed_challenge.json"), 0);
+    assert_eq!(replay(&mut indexer, "failed_and_cancelled.json"), 0);
+    assert_eq!(count(&indexer, "SELECT COUNT(*) FROM challenge_events"), events);
+    assert_eq!(count(&indexer, "SELECT COUNT(*) FROM payouts"), 3);
+    assert_eq!(count(&indexer, "SELECT COUNT(*) FROM grass_transfers"), 4);
+    assert_eq!(count(&indexer, "SELECT COUNT(*) FROM challenges"), 4);
+}
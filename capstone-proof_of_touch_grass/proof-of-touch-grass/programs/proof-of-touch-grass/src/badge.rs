@@ -0,0 +1,175 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{self, Create};
+use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+use anchor_spl::token_2022::spl_token_2022::state::Mint;
+use anchor_spl::token_2022::{self, InitializeMint2, MintTo};
+use anchor_spl::token_2022_extensions::spl_pod::optional_keys::OptionalNonZeroPubkey;
+use anchor_spl::token_2022_extensions::spl_token_metadata_interface::state::{Field, TokenMetadata};
+use anchor_spl::token_2022_extensions::{
+    metadata_pointer_initialize, non_transferable_mint_initialize, token_metadata_initialize,
+    token_metadata_update_field, MetadataPointerInitialize, NonTransferableMintInitialize,
+    TokenMetadataInitialize, TokenMetadataUpdateField,
+};
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::pda;
+use crate::state::*;
+
+/// Accounts that mint a completion badge
+pub struct BadgeAccounts<'a, 'info> {
+    /// Badge mint PDA, created here
+    pub mint: &'a AccountInfo<'info>,
+    pub mint_bump: u8,
+    /// The creator's Token-2022 associated token account for the mint, created here
+    pub token_account: &'a AccountInfo<'info>,
+    /// Program PDA holding the mint and metadata update authority of every badge
+    pub authority: &'a AccountInfo<'info>,
+    pub authority_bump: u8,
+    /// The creator, who receives the badge and pays for its accounts
+    pub creator: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+    pub associated_token_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+/// The metadata fields recorded on a badge beyond its name and symbol
+fn fields(challenge: &Challenge) -> Result<Vec<(String, String)>> {
+    let duration = challenge
+        .end_time
+        .checked_sub(challenge.start_time)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(vec![
+        (BADGE_DURATION_FIELD.to_string(), duration.to_string()),
+        (BADGE_COMPLETED_AT_FIELD.to_string(), challenge.finalized_at.to_string()),
+    ])
+}
+
+/// Mints the non-transferable badge of a completed challenge to its creator.
+///
+/// The mint is a Token-2022 mint at a PDA of the challenge, so each challenge
+/// has at most one badge. Its metadata lives on the mint itself: the
+/// challenge title as the name, plus the duration and completion date.
+pub fn mint(challenge_key: &Pubkey, challenge: &Challenge, accounts: &BadgeAccounts) -> Result<()> {
+    let BadgeAccounts {
+        mint,
+        token_account,
+        authority,
+        creator,
+        token_program,
+        associated_token_program,
+        system_program,
+        ..
+    } = *accounts;
+    let authority_key = authority.key();
+    let mint_seeds: &[&[u8]] = &[BADGE_SEED, challenge_key.as_ref(), &[accounts.mint_bump]];
+    let authority_seeds: &[&[u8]] = &[BADGE_AUTHORITY_SEED, &[accounts.authority_bump]];
+    let authority_signer = &[authority_seeds];
+
+    // The mint is created at its base size and funded for the metadata the
+    // token program appends to it
+    let fields = fields(challenge)?;
+    let metadata = TokenMetadata {
+        update_authority: OptionalNonZeroPubkey::try_from(Some(authority_key))?,
+        mint: mint.key(),
+        name: challenge.title.clone(),
+        symbol: BADGE_SYMBOL.to_string(),
+        uri: String::new(),
+        additional_metadata: fields.clone(),
+    };
+    let space = ExtensionType::try_calculate_account_len::<Mint>(&[
+        ExtensionType::NonTransferable,
+        ExtensionType::MetadataPointer,
+    ])?;
+    let funded_space = space + metadata.tlv_size_of()?;
+    pda::create_for(
+        mint,
+        space,
+        funded_space,
+        token_program.key,
+        mint_seeds,
+        creator,
+        system_program,
+    )?;
+
+    non_transferable_mint_initialize(CpiContext::new(
+        token_program.clone(),
+        NonTransferableMintInitialize {
+            token_program_id: token_program.clone(),
+            mint: mint.clone(),
+        },
+    ))?;
+    metadata_pointer_initialize(
+        CpiContext::new(
+            token_program.clone(),
+            MetadataPointerInitialize {
+                token_program_id: token_program.clone(),
+                mint: mint.clone(),
+            },
+        ),
+        Some(authority_key),
+        Some(mint.key()),
+    )?;
+    token_2022::initialize_mint2(
+        CpiContext::new(token_program.clone(), InitializeMint2 { mint: mint.clone() }),
+        0,
+        &authority_key,
+        None,
+    )?;
+
+    token_metadata_initialize(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            TokenMetadataInitialize {
+                program_id: token_program.clone(),
+                metadata: mint.clone(),
+                update_authority: authority.clone(),
+                mint_authority: authority.clone(),
+                mint: mint.clone(),
+            },
+            authority_signer,
+        ),
+        metadata.name,
+        metadata.symbol,
+        metadata.uri,
+    )?;
+    for (key, value) in fields {
+        token_metadata_update_field(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                TokenMetadataUpdateField {
+                    program_id: token_program.clone(),
+                    metadata: mint.clone(),
+                    update_authority: authority.clone(),
+                },
+                authority_signer,
+            ),
+            Field::Key(key),
+            value,
+        )?;
+    }
+
+    associated_token::create(CpiContext::new(
+        associated_token_program.clone(),
+        Create {
+            payer: creator.clone(),
+            associated_token: token_account.clone(),
+            authority: creator.clone(),
+            mint: mint.clone(),
+            system_program: system_program.clone(),
+            token_program: token_program.clone(),
+        },
+    ))?;
+    token_2022::mint_to(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            MintTo {
+                mint: mint.clone(),
+                to: token_account.clone(),
+                authority: authority.clone(),
+            },
+            authority_signer,
+        ),
+        1,
+    )
+}
@@ -3,28 +3,88 @@ use anchor_lang::prelude::*;
 // Admin pubkey (authorized to update challenge states)
 pub const ADMIN_PUBKEY: Pubkey = pubkey!("6wZQRRCWHeJriMqBpmpZF2PrJ3oVyTzEZMD5F5n388HU");
 
+// Platform wallet (receives fees and penalties)
+pub const PLATFORM_PUBKEY: Pubkey = pubkey!("EGBRkZBpQA5ZB5gfba726g5a1TFdLAv1q81pehNz9Ani");
+
 // Percentages in basis points (100 bp = 1%)
 pub const PLATFORM_FEE_BPS: u64 = 50; // 0.5% of stake
 pub const CANCEL_PENALTY_BPS: u64 = 200; // 2% of stake
 pub const CREATOR_BONUS_BPS: u64 = 25; // 0.25% of stake (half of platform fee)
 pub const SLASH_PENALTY_BPS: u64 = 2_500; // 25% of stake (slashed on failure)
+pub const EXTENSION_FEE_BPS: u64 = 100; // 1% of stake per extension (0 disables the fee)
+pub const JUROR_PENALTY_BPS: u64 = 1_000; // 10% of a minority juror's stake, paid to the majority
+pub const DISPUTE_BOND_BPS: u64 = 200; // 2% of stake, refunded only if the dispute overturns the outcome
 pub const BASIS_POINTS: u64 = 10_000; // 100% = 10,000 basis points
 
 // Time constants
 pub const DISPUTE_WINDOW: i64 = 172800; // 48 hours
+pub const MAX_EXTENSION: i64 = 1_209_600; // 14 days past the current end time
+pub const JURY_VOTING_PERIOD: i64 = 259_200; // 3 days from escalation
+pub const JUROR_UNBONDING_PERIOD: i64 = 1_209_600; // 14 days, outlasting any jury a leaving juror sits on
+pub const SECONDS_PER_HOUR: i64 = 3_600;
+pub const SECONDS_PER_DAY: i64 = 86_400;
+pub const HOURS_PER_DAY: u8 = 24;
+
+// Geofencing (coordinates are fixed-point microdegrees, 1e-6°)
+pub const MICRODEGREES_PER_DEGREE: i64 = 1_000_000;
+pub const MAX_LATITUDE: i32 = 90_000_000;
+pub const MAX_LONGITUDE: i32 = 180_000_000;
+pub const METERS_PER_DEGREE: u64 = 111_195; // mean great-circle length of one degree
 
 // Size limits
 pub const MAX_VERIFIERS: usize = 5;
+pub const MAX_VERIFIER_WEIGHT: u8 = 10;
 pub const MAX_TITLE_LEN: usize = 100;
 pub const MAX_DESCRIPTION_LEN: usize = 500;
 pub const MAX_IPFS_HASH_LEN: usize = 64;
 pub const MAX_METADATA_LEN: usize = 200;
 pub const MAX_DISPUTE_REASON_LEN: usize = 500;
+pub const MAX_JUSTIFICATION_LEN: usize = 280;
+pub const MAX_GEOFENCES: usize = 4;
+pub const MAX_HOUR_WINDOWS: usize = 4;
+
+// Juror pool
+pub const JURY_SIZE: usize = 3;
+pub const JURY_MAJORITY: u8 = 2;
+pub const MAX_JURORS: usize = 27; // escalate_dispute passes every juror account; 27 fill one legacy transaction
+pub const MIN_JUROR_STAKE: u64 = 1_000_000_000; // 1 SOL
+
+// Disputes
+pub const MIN_DISPUTE_BOND: u64 = 10_000_000; // 0.01 SOL, the bond on small stakes
+
+// Completion badges (Token-2022 metadata)
+pub const BADGE_SYMBOL: &str = "TOUCHED";
+pub const BADGE_DURATION_FIELD: &str = "duration"; // seconds from start to end time
+pub const BADGE_COMPLETED_AT_FIELD: &str = "completed_at"; // unix timestamp of completion
+
+// GRASS incentive token (SPL Token mint owned by the program)
+pub const GRASS_DECIMALS: u8 = 6;
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000; // the unit `grass_per_sol` is quoted against
+
+// Per-user ceilings until the admin sets others with set_platform_limits
+// (users can set tighter limits on themselves)
+pub const DEFAULT_MAX_ACTIVE_CHALLENGES: u32 = 50;
+pub const DEFAULT_MAX_STAKE_AMOUNT: u64 = 1_000_000_000_000; // 1,000 SOL per challenge
+pub const DEFAULT_MAX_STAKE_AT_RISK: u64 = 10_000_000_000_000; // 10,000 SOL across open challenges
+pub const DEFAULT_MAX_FAILURE_COOLDOWN: i64 = 2_592_000; // 30 days
+pub const LIMITS_LOOSENING_DELAY: i64 = 86_400; // 24 hours before raised or removed user limits apply
 
 // PDA Seeds
 pub const USER_SEED: &[u8] = b"user";
 pub const CHALLENGE_SEED: &[u8] = b"challenge";
+pub const CHALLENGE_INDEX_SEED: &[u8] = b"challenge_index";
 pub const ESCROW_SEED: &[u8] = b"escrow";
 pub const EVIDENCE_SEED: &[u8] = b"evidence";
 pub const VERIFICATION_SEED: &[u8] = b"verification";
 pub const DISPUTE_SEED: &[u8] = b"dispute";
+pub const REPUTATION_SEED: &[u8] = b"reputation";
+pub const INBOX_SEED: &[u8] = b"inbox";
+pub const EXTENSION_SEED: &[u8] = b"extension";
+pub const TEMPLATE_SEED: &[u8] = b"template";
+pub const JUROR_SEED: &[u8] = b"juror";
+pub const JUROR_POOL_SEED: &[u8] = b"juror_pool";
+pub const BADGE_SEED: &[u8] = b"badge";
+pub const BADGE_AUTHORITY_SEED: &[u8] = b"badge_authority";
+pub const GRASS_CONFIG_SEED: &[u8] = b"grass_config";
+pub const GRASS_MINT_SEED: &[u8] = b"grass_mint";
+pub const PLATFORM_CONFIG_SEED: &[u8] = b"platform_config";
@@ -12,7 +12,7 @@ pub enum ErrorCode {
     InvalidTimeRange,
     #[msg("Invalid number of verifiers (1-10 required)")]
     InvalidVerifierCount,
-    #[msg("Required approvals cannot exceed verifier count")]
+    #[msg("Required approval weight must be positive and within the verifiers' total weight")]
     InvalidApprovalCount,
     #[msg("Required proofs must be greater than 0")]
     InvalidProofCount,
@@ -54,4 +54,114 @@ pub enum ErrorCode {
     UnauthorizedAdmin,
     #[msg("Verifier has already voted")]
     AlreadyVoted,
+    #[msg("Too many geofences for a single challenge")]
+    TooManyGeofences,
+    #[msg("Geofence is malformed or has coordinates out of range")]
+    InvalidGeofence,
+    #[msg("Coordinates are out of range")]
+    InvalidCoordinates,
+    #[msg("Challenge is geofenced, evidence must include a location")]
+    LocationRequired,
+    #[msg("Evidence location is outside every allowed region")]
+    LocationOutsideGeofence,
+    #[msg("Submission rules are invalid")]
+    InvalidSubmissionRules,
+    #[msg("Minimum gap since the previous evidence has not elapsed")]
+    SubmissionTooSoon,
+    #[msg("Evidence cannot be submitted at this time of day")]
+    OutsideAllowedHours,
+    #[msg("Daily submission limit reached")]
+    DailySubmissionLimitReached,
+    #[msg("Evidence can no longer be changed")]
+    EvidenceLocked,
+    #[msg("No evidence to retract")]
+    NoEvidenceToRetract,
+    #[msg("Evidence revision limit reached")]
+    RevisionLimitReached,
+    #[msg("Arithmetic overflow or underflow")]
+    ArithmeticOverflow,
+    #[msg("Escrow balance does not match the payouts owed from it")]
+    EscrowImbalance,
+    #[msg("Verification and reputation accounts must be passed for every verifier, in order")]
+    InvalidReputationAccounts,
+    #[msg("A listed verifier is below the minimum reputation")]
+    VerifierReputationTooLow,
+    #[msg("User limits must be positive and within the platform maximums")]
+    InvalidUserLimits,
+    #[msg("Open challenge limit reached")]
+    TooManyActiveChallenges,
+    #[msg("Stake exceeds the maximum for a single challenge")]
+    StakeAboveLimit,
+    #[msg("Total stake across open challenges would exceed the limit")]
+    StakeAtRiskAboveLimit,
+    #[msg("Cooldown after a failed challenge has not elapsed")]
+    FailureCooldownActive,
+    #[msg("Inbox accounts must be passed for every verifier, in order")]
+    InvalidInboxAccounts,
+    #[msg("New end time must be later than the current one and within the maximum extension")]
+    InvalidExtension,
+    #[msg("Verifier already approved this extension")]
+    ExtensionAlreadyApproved,
+    #[msg("Challenge has already been retried")]
+    AlreadyRetried,
+    #[msg("Retry stake cannot be lower than the failed challenge's stake")]
+    RetryStakeTooLow,
+    #[msg("Template duration must be positive and its approval weight within the verifier maximum")]
+    InvalidTemplate,
+    #[msg("Vote quorum cannot exceed the number of verifiers")]
+    InvalidVerificationRules,
+    #[msg("New vote must differ from the one already cast")]
+    VoteUnchanged,
+    #[msg("Verifier weights must be empty or give each verifier a weight between 1 and the maximum")]
+    InvalidVerifierWeights,
+    #[msg("Justification reason or attachment hash too long")]
+    JustificationTooLong,
+    #[msg("Contested verification belongs to a different challenge")]
+    InvalidContestedVerification,
+    #[msg("Juror stake is below the minimum")]
+    JurorStakeTooLow,
+    #[msg("Juror pool is full")]
+    JurorPoolFull,
+    #[msg("Juror has already left the pool")]
+    JurorUnbonding,
+    #[msg("Juror stake stays bonded until the unbonding period ends and every jury they were drawn for settles")]
+    JurorStillBonded,
+    #[msg("Only the disputer or admin can escalate a dispute")]
+    UnauthorizedEscalation,
+    #[msg("Dispute has already been escalated to a jury")]
+    DisputeAlreadyEscalated,
+    #[msg("Dispute has not been escalated to a jury")]
+    DisputeNotEscalated,
+    #[msg("Not enough eligible jurors in the pool to draw a jury")]
+    NotEnoughJurors,
+    #[msg("Recent slot hashes are unavailable")]
+    SlotHashesUnavailable,
+    #[msg("Signer is not on this dispute's jury")]
+    NotOnJury,
+    #[msg("Juror has already voted on this dispute")]
+    JurorAlreadyVoted,
+    #[msg("Jury voting period has ended")]
+    JuryVotingClosed,
+    #[msg("Jury has neither reached a majority nor run out its voting period")]
+    JuryStillVoting,
+    #[msg("Juror accounts must be passed for every seat on the jury, in order")]
+    InvalidJurorAccounts,
+    #[msg("Dispute has already been resolved")]
+    DisputeAlreadyResolved,
+    #[msg("Account does not match the dispute's disputer")]
+    InvalidDisputer,
+    #[msg("Template account does not match the challenge's template")]
+    InvalidTemplateAccount,
+    #[msg("Each party can dispute a challenge once")]
+    AlreadyDisputed,
+    #[msg("GRASS needs a positive SOL rate and a discount of at most 100%")]
+    InvalidGrassConfig,
+    #[msg("GRASS accounts must all be passed, for the configured mint and the signer, or none")]
+    InvalidGrassAccounts,
+    #[msg("Verifier did not approve the completed challenge")]
+    VerifierDidNotApprove,
+    #[msg("Platform limits must all be positive")]
+    InvalidPlatformLimits,
+    #[msg("Each verifier can only be listed once")]
+    DuplicateVerifier,
 }
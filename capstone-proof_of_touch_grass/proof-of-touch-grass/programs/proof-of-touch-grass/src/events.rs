@@ -1,23 +1,93 @@
 use anchor_lang::prelude::*;
 
+use crate::state::Justification;
+
 #[event]
 pub struct UserInitialized {
     pub authority: Pubkey,
     pub timestamp: i64,
 }
 
+#[event]
+pub struct UserLimitsUpdated {
+    pub authority: Pubkey,
+    pub max_active_challenges: Option<u32>,
+    pub max_stake: Option<u64>,
+    pub max_stake_at_risk: Option<u64>,
+    pub failure_cooldown: Option<i64>,
+    pub timestamp: i64,
+}
+
+/// Limits that loosen the ones in force, which take over at `effective_at`
+#[event]
+pub struct UserLimitsScheduled {
+    pub authority: Pubkey,
+    pub max_active_challenges: Option<u32>,
+    pub max_stake: Option<u64>,
+    pub max_stake_at_risk: Option<u64>,
+    pub failure_cooldown: Option<i64>,
+    pub effective_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PlatformLimitsUpdated {
+    pub max_active_challenges: u32,
+    pub max_stake: u64,
+    pub max_stake_at_risk: u64,
+    pub max_failure_cooldown: i64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct ChallengeCreated {
     pub challenge: Pubkey,
     pub creator: Pubkey,
+    pub challenge_id: u64,
     pub title: String,
     pub stake_amount: u64,
     pub platform_fee: u64,
     pub start_time: i64,
     pub end_time: i64,
     pub required_proofs: u8,
-    pub required_approvals: u8,
+    pub required_approval_weight: u8,
     pub verifier_count: u8,
+    pub geofence_count: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ChallengeRetried {
+    pub challenge: Pubkey,
+    pub previous_challenge: Pubkey,
+    pub creator: Pubkey,
+    pub stake_amount: u64,
+    pub previous_stake_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TemplateCreated {
+    pub template: Pubkey,
+    pub author: Pubkey,
+    pub template_id: u64,
+    pub title: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TemplateCurated {
+    pub template: Pubkey,
+    pub curated: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ChallengeCreatedFromTemplate {
+    pub challenge: Pubkey,
+    pub template: Pubkey,
+    pub creator: Pubkey,
+    pub uses: u32,
     pub timestamp: i64,
 }
 
@@ -33,13 +103,53 @@ pub struct EvidenceSubmitted {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct EvidenceReplaced {
+    pub challenge: Pubkey,
+    pub evidence: Pubkey,
+    pub evidence_index: u8,
+    pub old_ipfs_hash: String,
+    pub new_ipfs_hash: String,
+    pub revision: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EvidenceRetracted {
+    pub challenge: Pubkey,
+    pub evidence: Pubkey,
+    pub ipfs_hash: String,
+    pub evidence_index: u8,
+    pub total_evidence: u8,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct VoteCast {
     pub challenge: Pubkey,
     pub verifier: Pubkey,
-    pub vote: String, // "Approve" or "Reject"
+    pub vote: String, // "Approve", "Reject" or "Abstain"
+    pub justification: Option<Justification>,
+    pub approval_count: u8,
+    pub rejection_count: u8,
+    pub abstain_count: u8,
+    pub approval_weight: u8,
+    pub rejection_weight: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoteChanged {
+    pub challenge: Pubkey,
+    pub verifier: Pubkey,
+    pub previous_vote: String,
+    pub vote: String,
+    pub justification: Option<Justification>,
     pub approval_count: u8,
     pub rejection_count: u8,
+    pub abstain_count: u8,
+    pub approval_weight: u8,
+    pub rejection_weight: u8,
     pub timestamp: i64,
 }
 
@@ -48,9 +158,9 @@ pub struct ChallengeFinalized {
     pub challenge: Pubkey,
     pub creator: Pubkey,
     pub status: String, // "Completed" or "Failed"
-    pub approval_count: u8,
-    pub rejection_count: u8,
-    pub required_approvals: u8,
+    pub approval_weight: u8,
+    pub rejection_weight: u8,
+    pub required_approval_weight: u8,
     pub timestamp: i64,
 }
 
@@ -67,9 +177,74 @@ pub struct ChallengeCancelled {
 #[event]
 pub struct DisputeFiled {
     pub challenge: Pubkey,
+    pub dispute_index: u8,
     pub disputer: Pubkey,
+    /// Lamports held on the dispute until it is resolved
+    pub bond: u64,
     pub previous_status: String,
     pub reason: String,
+    /// Verifier whose vote is contested, if the disputer names one
+    pub contested_verifier: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub challenge: Pubkey,
+    pub dispute_index: u8,
+    pub overturned: bool,
+    /// Refunded to the disputer when overturned, forfeited to the admin otherwise
+    pub bond: u64,
+    /// Decided by a jury rather than the admin
+    pub by_jury: bool,
+    pub status: String, // "Completed" or "Failed"
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ExtensionRequested {
+    pub challenge: Pubkey,
+    pub creator: Pubkey,
+    pub new_end_time: i64,
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ExtensionApproved {
+    pub challenge: Pubkey,
+    pub verifier: Pubkey,
+    pub approval_weight: u8,
+    pub required_approval_weight: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ChallengeExtended {
+    pub challenge: Pubkey,
+    pub creator: Pubkey,
+    pub old_end_time: i64,
+    pub new_end_time: i64,
+    pub verification_period_end: i64,
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ExtensionWithdrawn {
+    pub challenge: Pubkey,
+    pub creator: Pubkey,
+    pub refund: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerifierReputationUpdated {
+    pub verifier: Pubkey,
+    pub votes_cast: u32,
+    pub votes_won: u32,
+    pub votes_overturned: u32,
+    pub missed_windows: u32,
     pub timestamp: i64,
 }
 
@@ -109,3 +284,85 @@ pub struct FundsClaimed {
     pub challenge_status: String,
     pub timestamp: i64,
 }
+
+#[event]
+pub struct JurorRegistered {
+    pub juror: Pubkey,
+    pub stake: u64,
+    pub pool_size: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct JurorUnbonding {
+    pub juror: Pubkey,
+    pub withdrawable_at: i64,
+    pub pool_size: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct JurorWithdrawn {
+    pub juror: Pubkey,
+    pub stake: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeEscalated {
+    pub challenge: Pubkey,
+    pub dispute_index: u8,
+    pub escalated_by: Pubkey,
+    pub jurors: Vec<Pubkey>,
+    pub voting_ends: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct JuryVoteCast {
+    pub challenge: Pubkey,
+    pub dispute_index: u8,
+    pub juror: Pubkey,
+    pub vote: String, // "Uphold" or "Overturn"
+    pub uphold_votes: u8,
+    pub overturn_votes: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct JurorSettled {
+    pub challenge: Pubkey,
+    pub dispute_index: u8,
+    pub juror: Pubkey,
+    pub in_majority: bool,
+    pub penalty: u64,
+    pub reward: u64,
+    pub stake: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GrassConfigured {
+    pub mint: Pubkey,
+    pub creator_reward: u64,
+    pub verifier_reward: u64,
+    pub grass_per_sol: u64,
+    pub fee_discount_bps: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GrassRewarded {
+    pub challenge: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GrassFeeBurned {
+    pub challenge: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,244 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::GrassEmission;
+
+/// `amount * bps / BASIS_POINTS`, rounded down
+pub fn bps_of(amount: u64, bps: u64) -> Result<u64> {
+    let scaled = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        / BASIS_POINTS as u128;
+    u64::try_from(scaled).map_err(|_| error!(ErrorCode::ArithmeticOverflow))
+}
+
+fn add(a: u64, b: u64) -> Result<u64> {
+    a.checked_add(b).ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))
+}
+
+fn sub(a: u64, b: u64) -> Result<u64> {
+    a.checked_sub(b).ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))
+}
+
+/// Platform fee in lamports. `fee_in_grass` marks a challenge whose fee was
+/// burned in GRASS at creation, which leaves no SOL fee in its escrow.
+pub fn platform_fee(stake_amount: u64, fee_in_grass: bool) -> Result<u64> {
+    if fee_in_grass {
+        return Ok(0);
+    }
+    bps_of(stake_amount, PLATFORM_FEE_BPS)
+}
+
+/// GRASS burned in place of the platform fee: the fee converted at
+/// `grass_per_sol`, less `fee_discount_bps`
+pub fn grass_fee(stake_amount: u64, emission: &GrassEmission) -> Result<u64> {
+    let converted = (platform_fee(stake_amount, false)? as u128)
+        .checked_mul(emission.grass_per_sol as u128)
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        / LAMPORTS_PER_SOL as u128;
+    let converted = u64::try_from(converted).map_err(|_| error!(ErrorCode::ArithmeticOverflow))?;
+    bps_of(converted, sub(BASIS_POINTS, emission.fee_discount_bps)?)
+}
+
+/// Lamports moved into escrow at creation: the stake plus the platform fee
+pub fn escrow_deposit(stake_amount: u64, fee_in_grass: bool) -> Result<u64> {
+    add(stake_amount, platform_fee(stake_amount, fee_in_grass)?)
+}
+
+/// Fails unless `paid_out` is exactly what the escrow took in
+pub fn ensure_conserved(stake_amount: u64, fee_in_grass: bool, paid_out: u64) -> Result<()> {
+    require!(
+        paid_out == escrow_deposit(stake_amount, fee_in_grass)?,
+        ErrorCode::EscrowImbalance
+    );
+    Ok(())
+}
+
+/// Fails unless the escrow holds at least what is about to be paid out of it
+pub fn ensure_covered(escrow_balance: u64, owed: u64) -> Result<()> {
+    require!(escrow_balance >= owed, ErrorCode::EscrowImbalance);
+    Ok(())
+}
+
+/// Moves `amount` out of a challenge escrow, signed for with its seeds
+pub fn pay_from_escrow<'info>(
+    system_program: &Program<'info, System>,
+    escrow: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    transfer(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            Transfer {
+                from: escrow.clone(),
+                to: to.clone(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )
+}
+
+/// Split of a cancelled challenge's escrow
+pub struct CancelPayout {
+    pub refund: u64,
+    pub penalty: u64,
+}
+
+/// Full refund before the start, minus `CANCEL_PENALTY_BPS` once active
+pub fn cancel_payout(stake_amount: u64, fee_in_grass: bool, started: bool) -> Result<CancelPayout> {
+    let penalty = if started {
+        bps_of(stake_amount, CANCEL_PENALTY_BPS)?
+    } else {
+        0
+    };
+    let refund = sub(escrow_deposit(stake_amount, fee_in_grass)?, penalty)?;
+    ensure_conserved(stake_amount, fee_in_grass, add(refund, penalty)?)?;
+    Ok(CancelPayout { refund, penalty })
+}
+
+/// Fee the creator pays the platform for moving a challenge's end time
+pub fn extension_fee(stake_amount: u64) -> Result<u64> {
+    bps_of(stake_amount, EXTENSION_FEE_BPS)
+}
+
+/// Bonus a completed challenge pays its creator on top of the stake. It comes
+/// out of the SOL platform fee, so there is none when the fee was paid in GRASS.
+pub fn creator_bonus(stake_amount: u64, fee_in_grass: bool) -> Result<u64> {
+    if fee_in_grass {
+        return Ok(0);
+    }
+    bps_of(stake_amount, CREATOR_BONUS_BPS)
+}
+
+/// Split of a completed challenge's escrow
+pub struct CompletedPayout {
+    pub creator_reward: u64,
+    pub platform_fee: u64,
+}
+
+/// The creator gets the stake back plus `CREATOR_BONUS_BPS` out of the
+/// platform fee; the platform keeps the rest of its fee
+pub fn completed_payout(stake_amount: u64, fee_in_grass: bool) -> Result<CompletedPayout> {
+    let bonus = creator_bonus(stake_amount, fee_in_grass)?;
+    let creator_reward = add(stake_amount, bonus)?;
+    let platform_fee = sub(platform_fee(stake_amount, fee_in_grass)?, bonus)?;
+    ensure_conserved(stake_amount, fee_in_grass, add(creator_reward, platform_fee)?)?;
+    Ok(CompletedPayout {
+        creator_reward,
+        platform_fee,
+    })
+}
+
+/// Split of a failed challenge's escrow
+pub struct FailedPayout {
+    /// Paid to each rejecting verifier per unit of their vote weight
+    pub share_per_weight: u64,
+    /// Paid with the first claim
+    pub platform_fee: u64,
+    /// Paid with the first claim: the unslashed stake plus the remainder of
+    /// splitting the slashed stake
+    pub creator_refund: u64,
+}
+
+/// `SLASH_PENALTY_BPS` of the stake is split between the rejecting verifiers
+/// in proportion to their vote weight. The division remainder goes back to the creator instead of
+/// being stranded in escrow. With nobody rejecting (missing evidence or a
+/// failing timeout policy), the platform takes the slash.
+pub fn failed_payout(
+    stake_amount: u64,
+    fee_in_grass: bool,
+    rejection_weight: u8,
+) -> Result<FailedPayout> {
+    let slashed = bps_of(stake_amount, SLASH_PENALTY_BPS)?;
+    if rejection_weight == 0 {
+        let platform_fee = add(platform_fee(stake_amount, fee_in_grass)?, slashed)?;
+        let creator_refund = sub(stake_amount, slashed)?;
+        ensure_conserved(stake_amount, fee_in_grass, add(platform_fee, creator_refund)?)?;
+        return Ok(FailedPayout {
+            share_per_weight: 0,
+            platform_fee,
+            creator_refund,
+        });
+    }
+    let rejection_weight = rejection_weight as u64;
+    let share_per_weight = slashed
+        .checked_div(rejection_weight)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    let remainder = slashed % rejection_weight;
+    let creator_refund = add(sub(stake_amount, slashed)?, remainder)?;
+    let platform_fee = platform_fee(stake_amount, fee_in_grass)?;
+
+    let verifier_total = share_per_weight
+        .checked_mul(rejection_weight)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    ensure_conserved(
+        stake_amount,
+        fee_in_grass,
+        add(add(verifier_total, platform_fee)?, creator_refund)?,
+    )?;
+    Ok(FailedPayout {
+        share_per_weight,
+        platform_fee,
+        creator_refund,
+    })
+}
+
+/// Split of a refunded challenge's escrow: the creator gets the stake back
+/// and the platform keeps its fee
+pub struct RefundPayout {
+    pub creator_refund: u64,
+    pub platform_fee: u64,
+}
+
+pub fn refund_payout(stake_amount: u64, fee_in_grass: bool) -> Result<RefundPayout> {
+    let platform_fee = platform_fee(stake_amount, fee_in_grass)?;
+    ensure_conserved(stake_amount, fee_in_grass, add(stake_amount, platform_fee)?)?;
+    Ok(RefundPayout {
+        creator_refund: stake_amount,
+        platform_fee,
+    })
+}
+
+/// Stake a failed challenge costs its creator: what the rejecting verifiers
+/// split, or the whole slash when nobody rejected. The fee currency does not
+/// change what is slashed.
+pub fn slashed_stake(stake_amount: u64, rejection_weight: u8) -> Result<u64> {
+    sub(stake_amount, failed_payout(stake_amount, false, rejection_weight)?.creator_refund)
+}
+
+/// Bond a disputer puts down, a share of the stake with a floor so disputes
+/// over small stakes are not free
+pub fn dispute_bond(stake_amount: u64) -> Result<u64> {
+    Ok(bps_of(stake_amount, DISPUTE_BOND_BPS)?.max(MIN_DISPUTE_BOND))
+}
+
+/// Stake a juror who ends up outside the jury's majority loses
+pub fn juror_penalty(stake: u64) -> Result<u64> {
+    bps_of(stake, JUROR_PENALTY_BPS)
+}
+
+/// Split of the penalties a jury's minority paid
+pub struct JuryPayout {
+    /// Paid to each majority juror
+    pub share: u64,
+    /// Division remainder, paid on top of the first majority juror's share
+    pub remainder: u64,
+}
+
+pub fn jury_payout(penalties: u64, majority: u64) -> Result<JuryPayout> {
+    let share = penalties
+        .checked_div(majority)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    let remainder = penalties % majority;
+    let paid = add(
+        share.checked_mul(majority).ok_or(ErrorCode::ArithmeticOverflow)?,
+        remainder,
+    )?;
+    require!(paid == penalties, ErrorCode::EscrowImbalance);
+    Ok(JuryPayout { share, remainder })
+}
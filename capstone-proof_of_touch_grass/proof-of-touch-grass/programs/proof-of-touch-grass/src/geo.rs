@@ -0,0 +1,72 @@
+use crate::constants::*;
+use crate::state::GeoPoint;
+
+const COS_SCALE: i128 = 1_000_000;
+
+// cos(d°) for whole degrees 0..=90, scaled by COS_SCALE
+const COS_TABLE: [i128; 91] = [
+    1000000, 999848, 999391, 998630, 997564, 996195, 994522, 992546, 990268, 987688,
+    984808, 981627, 978148, 974370, 970296, 965926, 961262, 956305, 951057, 945519,
+    939693, 933580, 927184, 920505, 913545, 906308, 898794, 891007, 882948, 874620,
+    866025, 857167, 848048, 838671, 829038, 819152, 809017, 798636, 788011, 777146,
+    766044, 754710, 743145, 731354, 719340, 707107, 694658, 681998, 669131, 656059,
+    642788, 629320, 615661, 601815, 587785, 573576, 559193, 544639, 529919, 515038,
+    500000, 484810, 469472, 453990, 438371, 422618, 406737, 390731, 374607, 358368,
+    342020, 325568, 309017, 292372, 275637, 258819, 241922, 224951, 207912, 190809,
+    173648, 156434, 139173, 121869, 104528, 87156, 69756, 52336, 34899, 17452,
+    0,
+];
+
+/// Cosine of a latitude (microdegrees), scaled by COS_SCALE.
+/// Linearly interpolates between whole-degree table entries.
+fn cos_latitude(latitude: i32) -> i128 {
+    let per_degree = MICRODEGREES_PER_DEGREE as i128;
+    let abs = (latitude as i128).abs().min(90 * per_degree);
+    let degree = (abs / per_degree) as usize;
+    if degree >= 90 {
+        return 0;
+    }
+    let lo = COS_TABLE[degree];
+    let hi = COS_TABLE[degree + 1];
+    lo - (lo - hi) * (abs % per_degree) / per_degree
+}
+
+/// Shortest signed longitude difference in microdegrees, wrapped across the antimeridian
+fn longitude_delta(from: i32, to: i32) -> i128 {
+    let half_turn = 180 * MICRODEGREES_PER_DEGREE as i128;
+    let delta = to as i128 - from as i128;
+    if delta > half_turn {
+        delta - 2 * half_turn
+    } else if delta < -half_turn {
+        delta + 2 * half_turn
+    } else {
+        delta
+    }
+}
+
+/// Whether `point` lies within `radius_meters` of `center`.
+/// Uses an equirectangular projection, which is accurate to well under 1% for
+/// the few-kilometre radii geofences are meant for.
+pub fn within_radius(center: &GeoPoint, point: &GeoPoint, radius_meters: u32) -> bool {
+    let mean_latitude = ((center.latitude as i64 + point.latitude as i64) / 2) as i32;
+    let dy = point.latitude as i128 - center.latitude as i128;
+    let dx = longitude_delta(center.longitude, point.longitude) * cos_latitude(mean_latitude)
+        / COS_SCALE;
+
+    // Compare squared distances: (dx² + dy²)·(m/°)² <= r²·(µ°/°)²
+    let distance_sq = (dx * dx + dy * dy) as u128 * (METERS_PER_DEGREE as u128).pow(2);
+    let radius_sq = (radius_meters as u128).pow(2) * (MICRODEGREES_PER_DEGREE as u128).pow(2);
+    distance_sq <= radius_sq
+}
+
+/// Whether `point` lies inside the box spanned by `south_west` and `north_east`.
+/// A box whose west edge is east of its east edge wraps the antimeridian.
+pub fn within_box(south_west: &GeoPoint, north_east: &GeoPoint, point: &GeoPoint) -> bool {
+    let latitude_ok = point.latitude >= south_west.latitude && point.latitude <= north_east.latitude;
+    let longitude_ok = if south_west.longitude <= north_east.longitude {
+        point.longitude >= south_west.longitude && point.longitude <= north_east.longitude
+    } else {
+        point.longitude >= south_west.longitude || point.longitude <= north_east.longitude
+    };
+    latitude_ok && longitude_ok
+}
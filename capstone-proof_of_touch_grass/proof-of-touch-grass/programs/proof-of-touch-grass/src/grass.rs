@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{self, Create};
+use anchor_spl::token::{self, Burn, MintTo};
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+
+/// Accounts that mint a GRASS reward
+pub struct RewardAccounts<'a, 'info> {
+    pub config: &'a mut Account<'info, GrassConfig>,
+    pub mint: &'a AccountInfo<'info>,
+    /// The recipient's associated token account for the mint, created if missing
+    pub token_account: &'a AccountInfo<'info>,
+    /// The recipient, who pays for their token account
+    pub recipient: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+    pub associated_token_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+/// Mints `amount` GRASS to the recipient, signed for by the config PDA that
+/// holds the mint authority. A zero reward (emission switched off) mints
+/// nothing and leaves the recipient without a token account.
+pub fn reward(
+    challenge_key: &Pubkey,
+    accounts: RewardAccounts,
+    amount: u64,
+    timestamp: i64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let RewardAccounts {
+        config,
+        mint,
+        token_account,
+        recipient,
+        token_program,
+        associated_token_program,
+        system_program,
+    } = accounts;
+
+    associated_token::create_idempotent(CpiContext::new(
+        associated_token_program.clone(),
+        Create {
+            payer: recipient.clone(),
+            associated_token: token_account.clone(),
+            authority: recipient.clone(),
+            mint: mint.clone(),
+            system_program: system_program.clone(),
+            token_program: token_program.clone(),
+        },
+    ))?;
+    let config_seeds: &[&[u8]] = &[GRASS_CONFIG_SEED, &[config.bump]];
+    token::mint_to(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            MintTo {
+                mint: mint.clone(),
+                to: token_account.clone(),
+                authority: config.to_account_info(),
+            },
+            &[config_seeds],
+        ),
+        amount,
+    )?;
+    config.total_emitted = config.total_emitted
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    emit!(GrassRewarded {
+        challenge: *challenge_key,
+        recipient: recipient.key(),
+        amount,
+        timestamp,
+    });
+    Ok(())
+}
+
+/// Burns `amount` GRASS from the creator's token account in place of a
+/// challenge's SOL platform fee
+pub fn burn_fee<'info>(
+    challenge_key: &Pubkey,
+    mint: &AccountInfo<'info>,
+    token_account: &AccountInfo<'info>,
+    creator: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+    timestamp: i64,
+) -> Result<()> {
+    token::burn(
+        CpiContext::new(
+            token_program.clone(),
+            Burn {
+                mint: mint.clone(),
+                from: token_account.clone(),
+                authority: creator.clone(),
+            },
+        ),
+        amount,
+    )?;
+
+    emit!(GrassFeeBurned {
+        challenge: *challenge_key,
+        creator: creator.key(),
+        amount,
+        timestamp,
+    });
+    Ok(())
+}
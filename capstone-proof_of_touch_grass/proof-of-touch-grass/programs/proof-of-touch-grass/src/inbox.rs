@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::pda;
+use crate::state::*;
+
+fn check_address(info: &AccountInfo, verifier: &Pubkey) -> Result<u8> {
+    let (expected, bump) =
+        Pubkey::find_program_address(&[INBOX_SEED, verifier.as_ref()], &crate::ID);
+    require_keys_eq!(info.key(), expected, ErrorCode::InvalidInboxAccounts);
+    require!(info.is_writable, ErrorCode::InvalidInboxAccounts);
+    Ok(bump)
+}
+
+fn check_count(challenge: &Challenge, accounts: &[AccountInfo]) -> Result<()> {
+    require!(
+        accounts.len() == challenge.verifiers.len(),
+        ErrorCode::InvalidInboxAccounts
+    );
+    Ok(())
+}
+
+fn write(info: &AccountInfo, inbox: &VerifierInbox) -> Result<()> {
+    inbox.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])
+}
+
+/// Grows the inbox account to `space`, with `payer` covering the extra rent.
+/// Inboxes never shrink, so the room left by removed assignments is reused.
+fn grow<'info>(
+    info: &AccountInfo<'info>,
+    space: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    if space <= info.data_len() {
+        return Ok(());
+    }
+    let top_up = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(info.lamports());
+    if top_up > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: info.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    info.resize(space)?;
+    Ok(())
+}
+
+/// Adds the challenge to the inbox of every listed verifier, creating the
+/// inboxes that do not exist yet.
+///
+/// `accounts` holds the inbox of each verifier, in the challenge's verifier order.
+pub fn assign_verifiers<'info>(
+    challenge_key: &Pubkey,
+    challenge: &Challenge,
+    accounts: &[AccountInfo<'info>],
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    check_count(challenge, accounts)?;
+    let assignment = Assignment {
+        challenge: *challenge_key,
+        verification_period_end: challenge.verification_period_end,
+    };
+    for (verifier, info) in challenge.verifiers.iter().zip(accounts) {
+        let bump = check_address(info, verifier)?;
+        let mut inbox = if info.owner == &crate::ID {
+            let data = info.try_borrow_data()?;
+            VerifierInbox::try_deserialize(&mut &data[..])?
+        } else {
+            let seeds: &[&[u8]] = &[INBOX_SEED, verifier.as_ref(), &[bump]];
+            pda::create(info, VerifierInbox::space(1), seeds, payer, system_program)?;
+            VerifierInbox {
+                verifier: *verifier,
+                assignments: Vec::new(),
+                bump,
+            }
+        };
+        inbox.assignments.push(assignment.clone());
+        grow(info, VerifierInbox::space(inbox.assignments.len()), payer, system_program)?;
+        write(info, &inbox)?;
+    }
+    Ok(())
+}
+
+/// Applies `update` to the inbox of every listed verifier that has one
+fn for_each_inbox(
+    challenge: &Challenge,
+    accounts: &[AccountInfo],
+    mut update: impl FnMut(&mut VerifierInbox),
+) -> Result<()> {
+    check_count(challenge, accounts)?;
+    for (verifier, info) in challenge.verifiers.iter().zip(accounts) {
+        check_address(info, verifier)?;
+        if info.owner != &crate::ID {
+            continue;
+        }
+        let mut inbox = {
+            let data = info.try_borrow_data()?;
+            VerifierInbox::try_deserialize(&mut &data[..])?
+        };
+        update(&mut inbox);
+        write(info, &inbox)?;
+    }
+    Ok(())
+}
+
+/// Drops the challenge from the inbox of every listed verifier once it has
+/// finalized or been cancelled
+pub fn release_verifiers(
+    challenge_key: &Pubkey,
+    challenge: &Challenge,
+    accounts: &[AccountInfo],
+) -> Result<()> {
+    for_each_inbox(challenge, accounts, |inbox| {
+        inbox.assignments.retain(|assignment| assignment.challenge != *challenge_key);
+    })
+}
+
+/// Moves the challenge's deadline in the inbox of every listed verifier
+pub fn reschedule(
+    challenge_key: &Pubkey,
+    challenge: &Challenge,
+    accounts: &[AccountInfo],
+) -> Result<()> {
+    for_each_inbox(challenge, accounts, |inbox| {
+        for assignment in &mut inbox.assignments {
+            if assignment.challenge == *challenge_key {
+                assignment.verification_period_end = challenge.verification_period_end;
+            }
+        }
+    })
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::inbox;
+use crate::state::*;
+
+/// A listed verifier approves the pending extension. The approval that brings
+/// the approving weight to `required_approval_weight` applies it: the end time
+/// and verification period end both move, the held fee goes to the platform
+/// and the request closes.
+/// Remaining accounts: the inbox PDA of each listed verifier, in order.
+pub fn approve_extension(ctx: Context<ApproveExtension>) -> Result<()> {
+    let clock = Clock::get()?;
+    let challenge = &mut ctx.accounts.challenge;
+    let extension = &mut ctx.accounts.extension;
+    let challenge_key = challenge.key();
+    let verifier_key = ctx.accounts.verifier.key();
+
+    require!(
+        challenge.verifiers.contains(&verifier_key),
+        ErrorCode::UnauthorizedVerifier
+    );
+    require!(
+        challenge.status == ChallengeStatus::Created || challenge.status == ChallengeStatus::Active,
+        ErrorCode::InvalidChallengeStatus
+    );
+    require!(clock.unix_timestamp < challenge.end_time, ErrorCode::ChallengeExpired);
+    require!(
+        !extension.approvals.contains(&verifier_key),
+        ErrorCode::ExtensionAlreadyApproved
+    );
+
+    extension.approvals.push(verifier_key);
+    let approval_weight = extension
+        .approvals
+        .iter()
+        .try_fold(0u8, |total, approver| {
+            total
+                .checked_add(challenge.weight_of(approver)?)
+                .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))
+        })?;
+
+    emit!(ExtensionApproved {
+        challenge: challenge_key,
+        verifier: verifier_key,
+        approval_weight,
+        required_approval_weight: challenge.required_approval_weight,
+        timestamp: clock.unix_timestamp,
+    });
+
+    if approval_weight < challenge.required_approval_weight {
+        return Ok(());
+    }
+
+    let old_end_time = challenge.end_time;
+    let added = extension.new_end_time
+        .checked_sub(old_end_time)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    challenge.end_time = extension.new_end_time;
+    challenge.verification_period_end = challenge.verification_period_end
+        .checked_add(added)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    inbox::reschedule(&challenge_key, challenge, ctx.remaining_accounts)?;
+
+    // The request is program-owned, so the held fee moves without a CPI
+    let fee = extension.fee;
+    if fee > 0 {
+        let extension_info = extension.to_account_info();
+        let platform_info = ctx.accounts.platform.to_account_info();
+        **extension_info.try_borrow_mut_lamports()? = extension_info
+            .lamports()
+            .checked_sub(fee)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        **platform_info.try_borrow_mut_lamports()? = platform_info
+            .lamports()
+            .checked_add(fee)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+    }
+
+    emit!(ChallengeExtended {
+        challenge: challenge_key,
+        creator: challenge.creator,
+        old_end_time,
+        new_end_time: challenge.end_time,
+        verification_period_end: challenge.verification_period_end,
+        fee,
+        timestamp: clock.unix_timestamp,
+    });
+
+    extension.close(ctx.accounts.creator.to_account_info())
+}
+
+#[derive(Accounts)]
+pub struct ApproveExtension<'info> {
+    #[account(
+        mut,
+        seeds = [EXTENSION_SEED, challenge.key().as_ref()],
+        bump = extension.bump,
+    )]
+    pub extension: Account<'info, ExtensionRequest>,
+    #[account(mut)]
+    pub challenge: Account<'info, Challenge>,
+    #[account(mut, address = challenge.creator)]
+    /// CHECK: Challenge creator (receives the request's rent once it applies)
+    pub creator: AccountInfo<'info>,
+    #[account(mut, address = PLATFORM_PUBKEY)]
+    /// CHECK: Platform wallet (receives the extension fee)
+    pub platform: AccountInfo<'info>,
+    pub verifier: Signer<'info>,
+}
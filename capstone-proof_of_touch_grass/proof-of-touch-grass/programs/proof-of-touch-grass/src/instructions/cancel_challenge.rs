@@ -1,18 +1,22 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program::{transfer, Transfer};
 
 use crate::constants::*;
 use crate::errors::ErrorCode;
 use crate::events::*;
+use crate::fees;
+use crate::inbox;
 use crate::state::*;
 
 /// Creator cancels challenge before completion
+/// Remaining accounts: the inbox PDA of each listed verifier, in order.
 pub fn cancel_challenge(ctx: Context<CancelChallenge>) -> Result<()> {
     let clock = Clock::get()?;
     let challenge = &mut ctx.accounts.challenge;
+    let user = &mut ctx.accounts.user;
     let challenge_key = challenge.key();
     let creator = challenge.creator;
     let stake_amount = challenge.stake_amount;
+    let fee_in_grass = challenge.fee_paid_in_grass;
 
     require!(challenge.creator == ctx.accounts.creator.key(), ErrorCode::UnauthorizedCreator);
     require!(
@@ -20,20 +24,14 @@ pub fn cancel_challenge(ctx: Context<CancelChallenge>) -> Result<()> {
         ErrorCode::CannotCancelChallenge
     );
 
-    let platform_fee = (challenge.stake_amount * PLATFORM_FEE_BPS) / BASIS_POINTS;
-    let refund_amount: u64;
-    let penalty: u64;
-
-    // Calculate refund and penalty
-    if challenge.status == ChallengeStatus::Created {
-        // Full refund if challenge hasn't started
-        refund_amount = challenge.stake_amount + platform_fee;
-        penalty = 0;
-    } else {
-        // Active: penalty applies (2% of stake)
-        penalty = (challenge.stake_amount * CANCEL_PENALTY_BPS) / BASIS_POINTS;
-        refund_amount = challenge.stake_amount + platform_fee - penalty;
-    }
+    // Full refund if challenge hasn't started, 2% penalty once Active
+    let started = challenge.status == ChallengeStatus::Active;
+    let fees::CancelPayout {
+        refund: refund_amount,
+        penalty,
+    } = fees::cancel_payout(stake_amount, fee_in_grass, started)?;
+    let escrow_before = ctx.accounts.escrow.lamports();
+    fees::ensure_covered(escrow_before, fees::escrow_deposit(stake_amount, fee_in_grass)?)?;
 
     // Transfer refund to creator
     let escrow_seeds = &[
@@ -43,34 +41,22 @@ pub fn cancel_challenge(ctx: Context<CancelChallenge>) -> Result<()> {
     ];
     let signer_seeds = &[&escrow_seeds[..]];
 
-    transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.escrow.to_account_info(),
-                to: ctx.accounts.creator.to_account_info(),
-            },
-            signer_seeds,
-        ),
-        refund_amount,
-    )?;
+    let (system_program, escrow) = (&ctx.accounts.system_program, &ctx.accounts.escrow);
+    let creator_wallet = &ctx.accounts.creator;
+    fees::pay_from_escrow(system_program, escrow, creator_wallet, signer_seeds, refund_amount)?;
 
     // Transfer penalty to platform
     if penalty > 0 {
-        transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.escrow.to_account_info(),
-                    to: ctx.accounts.platform.to_account_info(),
-                },
-                signer_seeds,
-            ),
-            penalty,
-        )?;
+        fees::pay_from_escrow(system_program, escrow, &ctx.accounts.platform, signer_seeds, penalty)?;
     }
 
-    challenge.status = ChallengeStatus::Cancelled;
+    let paid_out = escrow_before
+        .checked_sub(ctx.accounts.escrow.lamports())
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    fees::ensure_conserved(stake_amount, fee_in_grass, paid_out)?;
+
+    challenge.finalize(user, ChallengeStatus::Cancelled, clock.unix_timestamp)?;
+    inbox::release_verifiers(&challenge_key, challenge, ctx.remaining_accounts)?;
 
     emit!(ChallengeCancelled {
         challenge: challenge_key,
@@ -95,7 +81,13 @@ pub struct CancelChallenge<'info> {
     )]
     /// CHECK: Escrow PDA
     pub escrow: AccountInfo<'info>,
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [USER_SEED, challenge.creator.as_ref()],
+        bump = user.bump,
+    )]
+    pub user: Account<'info, User>,
+    #[account(mut, address = PLATFORM_PUBKEY)]
     /// CHECK: Platform wallet (receives penalty fees)
     pub platform: AccountInfo<'info>,
     #[account(mut)]
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+
+/// A juror drawn for an escalated dispute votes to uphold or overturn the
+/// disputed outcome. The juror's stake has been bonded since the draw and
+/// stays bonded until the verdict settles.
+pub fn cast_jury_vote(ctx: Context<CastJuryVote>, vote: JuryVote) -> Result<()> {
+    let clock = Clock::get()?;
+    let juror_key = ctx.accounts.authority.key();
+    let dispute = &mut ctx.accounts.dispute;
+
+    require!(dispute.is_escalated(), ErrorCode::DisputeNotEscalated);
+    // A verdict reached early settles before every juror has voted
+    require!(!dispute.is_resolved(), ErrorCode::DisputeAlreadyResolved);
+    require!(clock.unix_timestamp < dispute.jury_deadline()?, ErrorCode::JuryVotingClosed);
+
+    let seat = dispute
+        .jury
+        .iter_mut()
+        .find(|seat| seat.juror == juror_key)
+        .ok_or(ErrorCode::NotOnJury)?;
+    require!(seat.vote.is_none(), ErrorCode::JurorAlreadyVoted);
+    seat.vote = Some(vote.clone());
+
+    let (uphold_votes, overturn_votes) = dispute.jury_tally();
+    emit!(JuryVoteCast {
+        challenge: dispute.challenge,
+        dispute_index: dispute.index,
+        juror: juror_key,
+        vote: vote.to_string(),
+        uphold_votes,
+        overturn_votes,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CastJuryVote<'info> {
+    #[account(
+        mut,
+        seeds = [DISPUTE_SEED, dispute.challenge.as_ref(), &[dispute.index]],
+        bump = dispute.bump,
+    )]
+    pub dispute: Account<'info, Dispute>,
+    #[account(
+        seeds = [JUROR_SEED, authority.key().as_ref()],
+        bump = juror.bump,
+    )]
+    pub juror: Account<'info, Juror>,
+    pub authority: Signer<'info>,
+}
@@ -1,37 +1,96 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::associated_token::{self, AssociatedToken};
+use anchor_spl::token::Token;
+use anchor_spl::token_2022::Token2022;
 
+use crate::badge;
 use crate::constants::*;
 use crate::errors::ErrorCode;
 use crate::events::*;
+use crate::fees;
+use crate::grass;
+use crate::reputation;
 use crate::state::*;
 
-/// Claim funds after challenge finalization
+/// Claim funds after challenge finalization. Refunded challenges have no
+/// verdict to dispute and can be claimed right away. The creator of a
+/// completed challenge and every verifier who voted for the final outcome
+/// are also minted their GRASS reward; approving verifiers claim only that.
+/// Until the admin has configured GRASS no reward is minted.
 pub fn claim_funds(ctx: Context<ClaimFunds>) -> Result<()> {
     let clock = Clock::get()?;
+    ctx.accounts.check_grass_accounts()?;
+    let emission = ctx.accounts
+        .grass_config
+        .as_ref()
+        .map_or_else(GrassEmission::default, |config| config.emission.clone());
     let challenge = &mut ctx.accounts.challenge;
     let challenge_key = challenge.key();
     let claimer = ctx.accounts.claimer.key();
 
     require!(
-        challenge.status == ChallengeStatus::Completed || challenge.status == ChallengeStatus::Failed,
+        matches!(
+            challenge.status,
+            ChallengeStatus::Completed | ChallengeStatus::Failed | ChallengeStatus::Refunded
+        ),
         ErrorCode::InvalidChallengeStatus
     );
     require!(
-        clock.unix_timestamp > challenge.finalized_at + DISPUTE_WINDOW,
+        challenge.status == ChallengeStatus::Refunded
+            || clock.unix_timestamp > challenge.dispute_window_end()?,
         ErrorCode::DisputeWindowNotExpired
     );
 
-    match challenge.status {
+    // The first claim of the escrow settles the outcome into every verifier's
+    // reputation. Remaining accounts: [verification, reputation] per listed verifier.
+    let approver_claim = challenge.status == ChallengeStatus::Completed && claimer != challenge.creator;
+    if !challenge.claimed && !approver_claim {
+        reputation::settle(&challenge_key, challenge, ctx.remaining_accounts, clock.unix_timestamp)?;
+    }
+
+    let grass_reward = match challenge.status {
+        ChallengeStatus::Completed if approver_claim && ctx.accounts.verification.is_some() => {
+            // APPROVER PATH: the escrow goes to the creator alone, so an
+            // approving verifier claims only their GRASS reward
+            let weight = challenge.weight_of(&claimer)? as u64;
+            let verification = match ctx.accounts.verification.as_mut() {
+                Some(v) => v,
+                None => return Err(ErrorCode::UnauthorizedVerifier.into()),
+            };
+            let (expected_verification_pda, _bump) = Pubkey::find_program_address(
+                &[
+                    VERIFICATION_SEED,
+                    challenge_key.as_ref(),
+                    claimer.as_ref(),
+                ],
+                &crate::ID,
+            );
+            require!(
+                verification.key() == expected_verification_pda,
+                ErrorCode::UnauthorizedVerifier
+            );
+            require!(verification.vote == Vote::Approve, ErrorCode::VerifierDidNotApprove);
+            require!(!verification.claimed, ErrorCode::AlreadyClaimed);
+            verification.claimed = true;
+
+            emission
+                .verifier_reward
+                .checked_mul(weight)
+                .ok_or(ErrorCode::ArithmeticOverflow)?
+        }
         ChallengeStatus::Completed => {
             // SUCCESS PATH
             require!(claimer == challenge.creator, ErrorCode::UnauthorizedCreator);
             require!(!challenge.claimed, ErrorCode::AlreadyClaimed);
 
             let stake_amount = challenge.stake_amount;
-            let bonus = (stake_amount * CREATOR_BONUS_BPS) / BASIS_POINTS;
-            let creator_reward = stake_amount + bonus;
-            let platform_fee_remaining = (stake_amount * PLATFORM_FEE_BPS) / BASIS_POINTS - bonus;
+            let fee_in_grass = challenge.fee_paid_in_grass;
+            let fees::CompletedPayout {
+                creator_reward,
+                platform_fee: platform_fee_remaining,
+            } = fees::completed_payout(stake_amount, fee_in_grass)?;
+            let escrow_before = ctx.accounts.escrow.lamports();
+            fees::ensure_covered(escrow_before, fees::escrow_deposit(stake_amount, fee_in_grass)?)?;
 
             // Transfer reward to creator
             let escrow_seeds = &[
@@ -41,34 +100,45 @@ pub fn claim_funds(ctx: Context<ClaimFunds>) -> Result<()> {
             ];
             let signer_seeds = &[&escrow_seeds[..]];
 
-            transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.system_program.to_account_info(),
-                    Transfer {
-                        from: ctx.accounts.escrow.to_account_info(),
-                        to: ctx.accounts.claimer.to_account_info(),
-                    },
-                    signer_seeds,
-                ),
-                creator_reward,
-            )?;
+            let system_program = &ctx.accounts.system_program;
+            let escrow = &ctx.accounts.escrow;
+            let creator = &ctx.accounts.claimer;
+            fees::pay_from_escrow(system_program, escrow, creator, signer_seeds, creator_reward)?;
 
             // Transfer remaining platform fee to platform
-            transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.system_program.to_account_info(),
-                    Transfer {
-                        from: ctx.accounts.escrow.to_account_info(),
-                        to: ctx.accounts.platform.to_account_info(),
-                    },
-                    signer_seeds,
-                ),
+            fees::pay_from_escrow(
+                system_program,
+                escrow,
+                &ctx.accounts.platform,
+                signer_seeds,
                 platform_fee_remaining,
             )?;
 
+            // Everything escrowed leaves with this single claim
+            let paid_out = escrow_before
+                .checked_sub(ctx.accounts.escrow.lamports())
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+            fees::ensure_conserved(stake_amount, fee_in_grass, paid_out)?;
+
+            // The outcome was recorded on the profile when the challenge finalized
             challenge.claimed = true;
-            let user = &mut ctx.accounts.user;
-            user.completed += 1;
+
+            let accounts = &ctx.accounts;
+            badge::mint(
+                &challenge_key,
+                &accounts.challenge,
+                &badge::BadgeAccounts {
+                    mint: &accounts.badge_mint,
+                    mint_bump: ctx.bumps.badge_mint,
+                    token_account: &accounts.badge_account,
+                    authority: &accounts.badge_authority,
+                    authority_bump: ctx.bumps.badge_authority,
+                    creator: accounts.claimer.as_ref(),
+                    token_program: accounts.token_2022_program.as_ref(),
+                    associated_token_program: accounts.associated_token_program.as_ref(),
+                    system_program: accounts.system_program.as_ref(),
+                },
+            )?;
 
             emit!(FundsClaimed {
                 challenge: challenge_key,
@@ -78,6 +148,59 @@ pub fn claim_funds(ctx: Context<ClaimFunds>) -> Result<()> {
                 challenge_status: "Completed".to_string(),
                 timestamp: clock.unix_timestamp,
             });
+
+            emission.creator_reward
+        }
+        ChallengeStatus::Failed | ChallengeStatus::Refunded
+            if challenge.status == ChallengeStatus::Refunded || challenge.rejection_weight == 0 =>
+        {
+            // NO-VERDICT PATH: a refund, or a failure nobody rejected.
+            // The creator settles the whole escrow in one claim.
+            require!(claimer == challenge.creator, ErrorCode::UnauthorizedCreator);
+            require!(!challenge.claimed, ErrorCode::AlreadyClaimed);
+
+            let stake_amount = challenge.stake_amount;
+            let fee_in_grass = challenge.fee_paid_in_grass;
+            let (creator_amount, platform_fee) = if challenge.status == ChallengeStatus::Refunded {
+                let payout = fees::refund_payout(stake_amount, fee_in_grass)?;
+                (payout.creator_refund, payout.platform_fee)
+            } else {
+                let payout = fees::failed_payout(stake_amount, fee_in_grass, 0)?;
+                (payout.creator_refund, payout.platform_fee)
+            };
+            let escrow_before = ctx.accounts.escrow.lamports();
+            fees::ensure_covered(escrow_before, fees::escrow_deposit(stake_amount, fee_in_grass)?)?;
+
+            let escrow_seeds = &[
+                ESCROW_SEED,
+                challenge_key.as_ref(),
+                &[challenge.escrow_bump],
+            ];
+            let signer_seeds = &[&escrow_seeds[..]];
+            let accounts = &ctx.accounts;
+            let (system_program, escrow) = (&accounts.system_program, &accounts.escrow);
+            fees::pay_from_escrow(system_program, escrow, &accounts.claimer, signer_seeds, creator_amount)?;
+            fees::pay_from_escrow(system_program, escrow, &accounts.platform, signer_seeds, platform_fee)?;
+
+            let paid_out = escrow_before
+                .checked_sub(ctx.accounts.escrow.lamports())
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+            fees::ensure_conserved(stake_amount, fee_in_grass, paid_out)?;
+
+            let challenge = &mut ctx.accounts.challenge;
+            challenge.claimed = true;
+
+            emit!(FundsClaimed {
+                challenge: challenge_key,
+                claimer,
+                amount: creator_amount,
+                platform_fee,
+                challenge_status: challenge.status.to_string(),
+                timestamp: clock.unix_timestamp,
+            });
+
+            // Nobody voted for this outcome, and the creator did not complete
+            0
         }
         ChallengeStatus::Failed => {
             // FAILURE PATH
@@ -106,12 +229,26 @@ pub fn claim_funds(ctx: Context<ClaimFunds>) -> Result<()> {
             require!(verification.vote == Vote::Reject, ErrorCode::VerifierDidNotReject);
             require!(!verification.claimed, ErrorCode::AlreadyClaimed);
 
-            let stake_amount = challenge.stake_amount;
-            let rejection_count = challenge.rejection_count;
-
-            // Calculate slashed amount and verifier share
-            let slashed_amount = (stake_amount * SLASH_PENALTY_BPS) / BASIS_POINTS;
-            let share = slashed_amount / (rejection_count as u64);
+            // Verifier share, plus the platform fee and creator refund on the first claim
+            let payout = fees::failed_payout(
+                challenge.stake_amount,
+                challenge.fee_paid_in_grass,
+                challenge.rejection_weight,
+            )?;
+            let weight = challenge.weight_of(&claimer)? as u64;
+            let share = payout.share_per_weight
+                .checked_mul(weight)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+            let owed = if challenge.claimed {
+                share
+            } else {
+                share
+                    .checked_add(payout.platform_fee)
+                    .and_then(|owed| owed.checked_add(payout.creator_refund))
+                    .ok_or(ErrorCode::ArithmeticOverflow)?
+            };
+            let escrow_before = ctx.accounts.escrow.lamports();
+            fees::ensure_covered(escrow_before, owed)?;
 
             // Transfer share to verifier
             let escrow_seeds = &[
@@ -121,55 +258,28 @@ pub fn claim_funds(ctx: Context<ClaimFunds>) -> Result<()> {
             ];
             let signer_seeds = &[&escrow_seeds[..]];
 
-            transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.system_program.to_account_info(),
-                    Transfer {
-                        from: ctx.accounts.escrow.to_account_info(),
-                        to: ctx.accounts.claimer.to_account_info(),
-                    },
-                    signer_seeds,
-                ),
-                share,
-            )?;
+            let system_program = &ctx.accounts.system_program;
+            let escrow = &ctx.accounts.escrow;
+            fees::pay_from_escrow(system_program, escrow, &ctx.accounts.claimer, signer_seeds, share)?;
 
             // If this is the first claim, transfer platform fee and creator refund
             if !challenge.claimed {
-                let platform_fee = (stake_amount * PLATFORM_FEE_BPS) / BASIS_POINTS;
-                let creator_refund = stake_amount - slashed_amount;
-
-                transfer(
-                    CpiContext::new_with_signer(
-                        ctx.accounts.system_program.to_account_info(),
-                        Transfer {
-                            from: ctx.accounts.escrow.to_account_info(),
-                            to: ctx.accounts.platform.to_account_info(),
-                        },
-                        signer_seeds,
-                    ),
-                    platform_fee,
-                )?;
+                let platform = &ctx.accounts.platform;
+                fees::pay_from_escrow(system_program, escrow, platform, signer_seeds, payout.platform_fee)?;
 
                 // Transfer creator refund to creator wallet
-                transfer(
-                    CpiContext::new_with_signer(
-                        ctx.accounts.system_program.to_account_info(),
-                        Transfer {
-                            from: ctx.accounts.escrow.to_account_info(),
-                            to: ctx.accounts.creator.to_account_info(),
-                        },
-                        signer_seeds,
-                    ),
-                    creator_refund,
-                )?;
+                let creator = &ctx.accounts.creator;
+                fees::pay_from_escrow(system_program, escrow, creator, signer_seeds, payout.creator_refund)?;
 
+                // The outcome was recorded on the profile when the challenge finalized
                 challenge.claimed = true;
-
-                // Update user.failed only on first claim
-                let user = &mut ctx.accounts.user;
-                user.failed += 1;
             }
 
+            let paid_out = escrow_before
+                .checked_sub(ctx.accounts.escrow.lamports())
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+            require!(paid_out == owed, ErrorCode::EscrowImbalance);
+
             // Mark this verification as claimed
             let verification_mut = match ctx.accounts.verification.as_mut() {
                 Some(v) => v,
@@ -185,15 +295,64 @@ pub fn claim_funds(ctx: Context<ClaimFunds>) -> Result<()> {
                 challenge_status: "Failed".to_string(),
                 timestamp: clock.unix_timestamp,
             });
+
+            emission
+                .verifier_reward
+                .checked_mul(weight)
+                .ok_or(ErrorCode::ArithmeticOverflow)?
         }
         _ => {
             return Err(ErrorCode::InvalidChallengeStatus.into());
         }
-    }
+    };
 
+    let accounts = &mut *ctx.accounts;
+    if let (Some(config), Some(mint), Some(token_account)) = (
+        &mut accounts.grass_config,
+        &accounts.grass_mint,
+        &accounts.grass_account,
+    ) {
+        grass::reward(
+            &challenge_key,
+            grass::RewardAccounts {
+                config,
+                mint,
+                token_account,
+                recipient: accounts.claimer.as_ref(),
+                token_program: accounts.token_program.as_ref(),
+                associated_token_program: accounts.associated_token_program.as_ref(),
+                system_program: accounts.system_program.as_ref(),
+            },
+            grass_reward,
+            clock.unix_timestamp,
+        )?;
+    }
     Ok(())
 }
 
+impl ClaimFunds<'_> {
+    /// The GRASS accounts are left out until the admin has configured GRASS.
+    /// Once passed they must all be, for the configured mint and the
+    /// claimer's associated token account.
+    fn check_grass_accounts(&self) -> Result<()> {
+        match (&self.grass_config, &self.grass_mint, &self.grass_account) {
+            (None, None, None) => Ok(()),
+            (Some(config), Some(mint), Some(token_account)) => {
+                let expected = associated_token::get_associated_token_address(
+                    &self.claimer.key(),
+                    &config.mint,
+                );
+                require!(
+                    mint.key() == config.mint && token_account.key() == expected,
+                    ErrorCode::InvalidGrassAccounts
+                );
+                Ok(())
+            }
+            _ => err!(ErrorCode::InvalidGrassAccounts),
+        }
+    }
+}
+
 #[derive(Accounts)]
 pub struct ClaimFunds<'info> {
     #[account(mut)]
@@ -211,10 +370,10 @@ pub struct ClaimFunds<'info> {
         bump = user.bump,
     )]
     pub user: Account<'info, User>,
-    #[account(mut)]
+    #[account(mut, address = challenge.creator)]
     /// CHECK: Creator wallet (receives refund on failed challenges)
     pub creator: AccountInfo<'info>,
-    #[account(mut)]
+    #[account(mut, address = PLATFORM_PUBKEY)]
     /// CHECK: Platform wallet (receives fees)
     pub platform: AccountInfo<'info>,
     #[account(mut)]
@@ -223,5 +382,34 @@ pub struct ClaimFunds<'info> {
     /// CHECK: Manually validated in instruction logic
     #[account(mut)]
     pub verification: Option<Account<'info, Verification>>,
+    #[account(mut, seeds = [BADGE_SEED, challenge.key().as_ref()], bump)]
+    /// CHECK: Completion badge mint, created by the claim of a completed challenge
+    pub badge_mint: AccountInfo<'info>,
+    #[account(
+        mut,
+        address = associated_token::get_associated_token_address_with_program_id(
+            &challenge.creator,
+            &badge_mint.key(),
+            &token_2022_program.key(),
+        ),
+    )]
+    /// CHECK: The creator's Token-2022 associated token account for the badge
+    pub badge_account: AccountInfo<'info>,
+    #[account(seeds = [BADGE_AUTHORITY_SEED], bump)]
+    /// CHECK: Mint and metadata authority of every badge
+    pub badge_authority: AccountInfo<'info>,
+    // GRASS accounts, passed once the admin has configured GRASS
+    #[account(mut, seeds = [GRASS_CONFIG_SEED], bump = grass_config.bump)]
+    pub grass_config: Option<Box<Account<'info, GrassConfig>>>,
+    #[account(mut)]
+    /// CHECK: GRASS mint, minted to by the config PDA; checked against the config
+    pub grass_mint: Option<AccountInfo<'info>>,
+    #[account(mut)]
+    /// CHECK: The claimer's GRASS associated token account, created with their
+    /// first reward; checked against the config
+    pub grass_account: Option<AccountInfo<'info>>,
     pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
 }
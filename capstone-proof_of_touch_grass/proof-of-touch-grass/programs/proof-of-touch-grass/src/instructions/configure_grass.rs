@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token};
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+
+/// Admin-only: creates the GRASS mint and its config on the first call, and
+/// sets the emission parameters on every call. The config PDA is the mint
+/// authority, so GRASS is only ever minted by the program.
+pub fn configure_grass(ctx: Context<ConfigureGrass>, emission: GrassEmission) -> Result<()> {
+    require!(
+        ctx.accounts.admin.key() == ADMIN_PUBKEY,
+        ErrorCode::UnauthorizedAdmin
+    );
+    require!(emission.is_valid(), ErrorCode::InvalidGrassConfig);
+
+    let mint = ctx.accounts.grass_mint.key();
+    let config = &mut ctx.accounts.grass_config;
+    config.mint = mint;
+    config.emission = emission.clone();
+    config.bump = ctx.bumps.grass_config;
+    config.mint_bump = ctx.bumps.grass_mint;
+
+    emit!(GrassConfigured {
+        mint,
+        creator_reward: emission.creator_reward,
+        verifier_reward: emission.verifier_reward,
+        grass_per_sol: emission.grass_per_sol,
+        fee_discount_bps: emission.fee_discount_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ConfigureGrass<'info> {
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + GrassConfig::INIT_SPACE,
+        seeds = [GRASS_CONFIG_SEED],
+        bump
+    )]
+    pub grass_config: Account<'info, GrassConfig>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [GRASS_MINT_SEED],
+        bump,
+        mint::decimals = GRASS_DECIMALS,
+        mint::authority = grass_config,
+    )]
+    pub grass_mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -1,14 +1,21 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token::{Mint, Token, TokenAccount};
 
 use crate::constants::*;
 use crate::errors::ErrorCode;
 use crate::events::*;
+use crate::fees;
+use crate::grass;
+use crate::inbox;
+use crate::reputation;
 use crate::state::*;
 
 /// Creates a challenge with stake and parameters
-pub fn create_challenge(
-    ctx: Context<CreateChallenge>,
+#[allow(clippy::too_many_arguments)]
+pub fn create_challenge<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreateChallenge<'info>>,
+    challenge_id: u64,
     title: String,
     description: String,
     stake_amount: u64,
@@ -16,85 +23,322 @@ pub fn create_challenge(
     end_time: i64,
     verification_period: i64,
     required_proofs: u8,
-    required_approvals: u8,
+    required_approval_weight: u8,
     verifiers: Vec<Pubkey>,
+    verifier_weights: Vec<u8>,
+    geofences: Vec<Geofence>,
+    submission_rules: SubmissionRules,
+    verification_rules: VerificationRules,
+    min_verifier_reputation: Option<u32>,
 ) -> Result<()> {
-    require!(title.len() <= MAX_TITLE_LEN, ErrorCode::TitleTooLong);
-    require!(description.len() <= MAX_DESCRIPTION_LEN, ErrorCode::DescriptionTooLong);
-    require!(stake_amount > 0, ErrorCode::InvalidStakeAmount);
-    require!(end_time > start_time, ErrorCode::InvalidTimeRange);
-    require!(verifiers.len() > 0 && verifiers.len() <= MAX_VERIFIERS, ErrorCode::InvalidVerifierCount);
-    require!(required_approvals as usize <= verifiers.len(), ErrorCode::InvalidApprovalCount);
-    require!(required_proofs > 0, ErrorCode::InvalidProofCount);
-
-    let user = &mut ctx.accounts.user;
-    let clock = Clock::get()?;
-
-    ctx.accounts.challenge.set_inner(Challenge {
-        creator: ctx.accounts.creator.key(),
-        title: title.clone(),
+    let accounts = verifier_accounts(ctx.remaining_accounts, &verifiers, min_verifier_reputation)?;
+    let params = ChallengeParams {
+        title,
         description,
         stake_amount,
         start_time,
         end_time,
-        verification_period_end: end_time + verification_period,
+        verification_period,
         required_proofs,
-        required_approvals,
-        verifiers: verifiers.clone(),
-        status: ChallengeStatus::Created,
-        evidence_count: 0,
-        approval_count: 0,
-        rejection_count: 0,
-        finalized_at: 0,
-        claimed: false,
-        bump: ctx.bumps.challenge,
-        escrow_bump: ctx.bumps.escrow,
-    });
-
-    // Calculate platform fee
-    let platform_fee = (stake_amount * PLATFORM_FEE_BPS) / BASIS_POINTS;
-
-    // Transfer stake + platform fee to escrow
-    let total_amount = stake_amount + platform_fee;
-    transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.creator.to_account_info(),
-                to: ctx.accounts.escrow.to_account_info(),
-            },
-        ),
-        total_amount,
-    )?;
-
-    // Update user stats
-    user.total_challenges += 1;
-    user.total_staked += stake_amount;
-
-    emit!(ChallengeCreated {
-        challenge: ctx.accounts.challenge.key(),
-        creator: ctx.accounts.challenge.creator,
-        title: title.clone(),
-        stake_amount,
-        platform_fee,
-        start_time,
-        end_time,
-        required_proofs,
-        required_approvals,
-        verifier_count: verifiers.len() as u8,
-        timestamp: clock.unix_timestamp,
-    });
+        required_approval_weight,
+        verifiers,
+        verifier_weights,
+        geofences,
+        submission_rules,
+        verification_rules,
+        min_verifier_reputation,
+        previous_challenge: None,
+        template: None,
+    };
+    ctx.accounts.open(&ctx.bumps, accounts, challenge_id, params)
+}
+
+/// The remaining accounts of a new challenge
+pub struct VerifierAccounts<'a, 'info> {
+    /// The inbox PDA of each verifier, in order
+    pub inboxes: &'a [AccountInfo<'info>],
+    /// The reputation PDA of each verifier, in order
+    pub reputations: &'a [AccountInfo<'info>],
+}
+
+/// Splits the remaining accounts of a new challenge: the inbox PDA of each
+/// verifier, in order, followed by their reputation PDAs. Checks the minimum
+/// reputation, if one is set, against the records as they stand.
+pub fn verifier_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    verifiers: &[Pubkey],
+    min_verifier_reputation: Option<u32>,
+) -> Result<VerifierAccounts<'a, 'info>> {
+    require!(
+        remaining_accounts.len() >= verifiers.len(),
+        ErrorCode::InvalidInboxAccounts
+    );
+    let (inboxes, reputations) = remaining_accounts.split_at(verifiers.len());
+    require!(
+        reputations.len() == verifiers.len(),
+        ErrorCode::InvalidReputationAccounts
+    );
+    if let Some(min_score) = min_verifier_reputation {
+        for (verifier, info) in verifiers.iter().zip(reputations) {
+            let score = reputation::load(info, verifier)?.map_or(0, |rep| rep.score());
+            require!(score >= min_score, ErrorCode::VerifierReputationTooLow);
+        }
+    }
+    Ok(VerifierAccounts {
+        inboxes,
+        reputations,
+    })
+}
+
+/// Everything a new challenge is created from
+pub struct ChallengeParams {
+    pub title: String,
+    pub description: String,
+    pub stake_amount: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub verification_period: i64,
+    pub required_proofs: u8,
+    pub required_approval_weight: u8,
+    pub verifiers: Vec<Pubkey>,
+    /// One weight per verifier, or empty to weigh every vote equally
+    pub verifier_weights: Vec<u8>,
+    pub geofences: Vec<Geofence>,
+    pub submission_rules: SubmissionRules,
+    pub verification_rules: VerificationRules,
+    pub min_verifier_reputation: Option<u32>,
+    pub previous_challenge: Option<Pubkey>,
+    pub template: Option<Pubkey>,
+}
+
+impl ChallengeParams {
+    /// The weight of each verifier's vote, in `verifiers` order
+    fn weights(&self) -> Vec<u8> {
+        if self.verifier_weights.is_empty() {
+            vec![1; self.verifiers.len()]
+        } else {
+            self.verifier_weights.clone()
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        let verifier_count = self.verifiers.len();
+        require!(self.title.len() <= MAX_TITLE_LEN, ErrorCode::TitleTooLong);
+        require!(self.description.len() <= MAX_DESCRIPTION_LEN, ErrorCode::DescriptionTooLong);
+        require!(self.stake_amount > 0, ErrorCode::InvalidStakeAmount);
+        require!(self.end_time > self.start_time, ErrorCode::InvalidTimeRange);
+        require!(verifier_count > 0 && verifier_count <= MAX_VERIFIERS, ErrorCode::InvalidVerifierCount);
+        // A verifier listed twice would count twice toward the approval weight
+        require!(
+            self.verifiers
+                .iter()
+                .enumerate()
+                .all(|(position, verifier)| !self.verifiers[..position].contains(verifier)),
+            ErrorCode::DuplicateVerifier
+        );
+        let weights = self.weights();
+        require!(
+            weights.len() == verifier_count
+                && weights.iter().all(|weight| (1..=MAX_VERIFIER_WEIGHT).contains(weight)),
+            ErrorCode::InvalidVerifierWeights
+        );
+        let total_weight: u8 = weights.iter().sum();
+        // With no approvals required, any single approval would apply an extension
+        require!(
+            self.required_approval_weight > 0 && self.required_approval_weight <= total_weight,
+            ErrorCode::InvalidApprovalCount
+        );
+        require!(self.required_proofs > 0, ErrorCode::InvalidProofCount);
+        require!(self.geofences.len() <= MAX_GEOFENCES, ErrorCode::TooManyGeofences);
+        require!(self.geofences.iter().all(Geofence::is_valid), ErrorCode::InvalidGeofence);
+        require!(self.submission_rules.is_valid(), ErrorCode::InvalidSubmissionRules);
+        require!(
+            self.verification_rules.is_valid(verifier_count),
+            ErrorCode::InvalidVerificationRules
+        );
+        Ok(())
+    }
+}
 
-    Ok(())
+impl<'info> CreateChallenge<'info> {
+    /// Whether the GRASS accounts were passed to pay the platform fee in
+    /// GRASS. They must all be passed, for the configured mint and a token
+    /// account of the creator's, or none at all.
+    fn fee_in_grass(&self) -> Result<bool> {
+        match (
+            &self.grass_config,
+            &self.grass_mint,
+            &self.grass_fee_account,
+            &self.token_program,
+        ) {
+            (None, None, None, None) => Ok(false),
+            (Some(config), Some(mint), Some(fee_account), Some(_)) => {
+                require!(
+                    mint.key() == config.mint
+                        && fee_account.mint == config.mint
+                        && fee_account.owner == self.creator.key(),
+                    ErrorCode::InvalidGrassAccounts
+                );
+                Ok(true)
+            }
+            _ => err!(ErrorCode::InvalidGrassAccounts),
+        }
+    }
+
+    /// Validates and funds a new challenge, then records it on the creator's
+    /// profile, their challenge index and each verifier's inbox, creating the
+    /// verifiers' reputation records where missing. A platform fee paid in
+    /// GRASS is burned at the discounted rate and the escrow takes only the stake.
+    pub fn open(
+        &mut self,
+        bumps: &CreateChallengeBumps,
+        accounts: VerifierAccounts<'_, 'info>,
+        challenge_id: u64,
+        params: ChallengeParams,
+    ) -> Result<()> {
+        params.validate()?;
+        let verifier_weights = params.weights();
+
+        let clock = Clock::get()?;
+        let stake_amount = params.stake_amount;
+        let fee_in_grass = self.fee_in_grass()?;
+        let platform_limits = PlatformConfig::limits(&self.platform_config)?;
+        self.user.check_new_challenge(&platform_limits, stake_amount, clock.unix_timestamp)?;
+        let verification_period_end = params.end_time
+            .checked_add(params.verification_period)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        self.challenge.set_inner(Challenge {
+            creator: self.creator.key(),
+            challenge_id,
+            previous_challenge: params.previous_challenge,
+            template: params.template,
+            title: params.title.clone(),
+            description: params.description,
+            stake_amount,
+            fee_paid_in_grass: fee_in_grass,
+            start_time: params.start_time,
+            end_time: params.end_time,
+            verification_period_end,
+            required_proofs: params.required_proofs,
+            required_approval_weight: params.required_approval_weight,
+            verifiers: params.verifiers.clone(),
+            verifier_weights,
+            min_verifier_reputation: params.min_verifier_reputation,
+            geofences: params.geofences.clone(),
+            submission_rules: params.submission_rules,
+            verification_rules: params.verification_rules,
+            status: ChallengeStatus::Created,
+            evidence_count: 0,
+            last_evidence_at: 0,
+            submission_day: 0,
+            submissions_today: 0,
+            approval_count: 0,
+            rejection_count: 0,
+            abstain_count: 0,
+            approval_weight: 0,
+            rejection_weight: 0,
+            finalized_at: 0,
+            claimed: false,
+            dispute_count: 0,
+            disputers: Vec::new(),
+            dispute_resolved_at: 0,
+            retried_by: None,
+            bump: bumps.challenge,
+            escrow_bump: bumps.escrow,
+        });
+
+        // Calculate platform fee
+        let platform_fee = fees::platform_fee(stake_amount, fee_in_grass)?;
+        if let (Some(config), Some(mint), Some(fee_account), Some(token_program)) = (
+            &self.grass_config,
+            &self.grass_mint,
+            &self.grass_fee_account,
+            &self.token_program,
+        ) {
+            grass::burn_fee(
+                &self.challenge.key(),
+                &mint.to_account_info(),
+                &fee_account.to_account_info(),
+                &self.creator.to_account_info(),
+                &token_program.to_account_info(),
+                fees::grass_fee(stake_amount, &config.emission)?,
+                clock.unix_timestamp,
+            )?;
+        }
+
+        // Transfer stake + platform fee to escrow
+        let total_amount = fees::escrow_deposit(stake_amount, fee_in_grass)?;
+        transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.creator.to_account_info(),
+                    to: self.escrow.to_account_info(),
+                },
+            ),
+            total_amount,
+        )?;
+
+        // `init` already rejected a reused ID, so the index stays duplicate-free
+        self.challenge_index.challenge_ids.push(challenge_id);
+        inbox::assign_verifiers(
+            &self.challenge.key(),
+            &self.challenge,
+            accounts.inboxes,
+            &self.creator.to_account_info(),
+            &self.system_program.to_account_info(),
+        )?;
+        reputation::open_records(
+            &self.challenge.verifiers,
+            accounts.reputations,
+            &self.creator.to_account_info(),
+            &self.system_program.to_account_info(),
+        )?;
+
+        // Update user stats
+        let user = &mut self.user;
+        user.total_challenges = user.total_challenges
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        user.active_challenges = user.active_challenges
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        user.total_staked = user.total_staked
+            .checked_add(stake_amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        user.staked_at_risk = user.staked_at_risk
+            .checked_add(stake_amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        emit!(ChallengeCreated {
+            challenge: self.challenge.key(),
+            creator: self.challenge.creator,
+            challenge_id,
+            title: params.title,
+            stake_amount,
+            platform_fee,
+            start_time: params.start_time,
+            end_time: params.end_time,
+            required_proofs: params.required_proofs,
+            required_approval_weight: params.required_approval_weight,
+            verifier_count: params.verifiers.len() as u8,
+            geofence_count: params.geofences.len() as u8,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
 }
 
 #[derive(Accounts)]
+#[instruction(challenge_id: u64)]
 pub struct CreateChallenge<'info> {
     #[account(
         init,
         payer = creator,
         space = 8 + Challenge::INIT_SPACE,
-        seeds = [CHALLENGE_SEED, creator.key().as_ref(), &user.total_challenges.to_le_bytes()],
+        seeds = [CHALLENGE_SEED, creator.key().as_ref(), &challenge_id.to_le_bytes()],
         bump
     )]
     pub challenge: Account<'info, Challenge>,
@@ -111,7 +355,28 @@ pub struct CreateChallenge<'info> {
         bump = user.bump,
     )]
     pub user: Account<'info, User>,
+    #[account(
+        mut,
+        seeds = [CHALLENGE_INDEX_SEED, creator.key().as_ref()],
+        bump = challenge_index.bump,
+        realloc = ChallengeIndex::space(challenge_index.challenge_ids.len() + 1),
+        realloc::payer = creator,
+        realloc::zero = false,
+    )]
+    pub challenge_index: Account<'info, ChallengeIndex>,
     #[account(mut)]
     pub creator: Signer<'info>,
     pub system_program: Program<'info, System>,
+    /// CHECK: Platform limits; empty until the admin runs set_platform_limits
+    #[account(seeds = [PLATFORM_CONFIG_SEED], bump)]
+    pub platform_config: UncheckedAccount<'info>,
+    // GRASS accounts, passed to pay the platform fee in GRASS
+    #[account(seeds = [GRASS_CONFIG_SEED], bump = grass_config.bump)]
+    pub grass_config: Option<Box<Account<'info, GrassConfig>>>,
+    #[account(mut)]
+    pub grass_mint: Option<Box<Account<'info, Mint>>>,
+    /// The creator's GRASS token account the fee is burned from
+    #[account(mut)]
+    pub grass_fee_account: Option<Box<Account<'info, TokenAccount>>>,
+    pub token_program: Option<Program<'info, Token>>,
 }
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+
+use super::create_challenge::*;
+
+/// Creates a challenge from a template's terms. The creator supplies only the
+/// stake, start time and verifiers with their weights; the end time is `start_time` plus the
+/// template's duration.
+/// Remaining accounts: as for `create_challenge`.
+pub fn create_challenge_from_template<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreateChallengeFromTemplate<'info>>,
+    challenge_id: u64,
+    stake_amount: u64,
+    start_time: i64,
+    verifiers: Vec<Pubkey>,
+    verifier_weights: Vec<u8>,
+) -> Result<()> {
+    let template = &ctx.accounts.template;
+    let template_key = template.key();
+    let accounts = verifier_accounts(
+        ctx.remaining_accounts,
+        &verifiers,
+        template.min_verifier_reputation,
+    )?;
+    let end_time = start_time
+        .checked_add(template.duration)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    let params = ChallengeParams {
+        title: template.title.clone(),
+        description: template.description.clone(),
+        stake_amount,
+        start_time,
+        end_time,
+        verification_period: template.verification_period,
+        required_proofs: template.required_proofs,
+        required_approval_weight: template.required_approval_weight,
+        verifiers,
+        verifier_weights,
+        geofences: template.geofences.clone(),
+        submission_rules: template.submission_rules.clone(),
+        verification_rules: template.verification_rules.clone(),
+        min_verifier_reputation: template.min_verifier_reputation,
+        previous_challenge: None,
+        template: Some(template_key),
+    };
+    ctx.accounts
+        .challenge
+        .open(&ctx.bumps.challenge, accounts, challenge_id, params)?;
+
+    let template = &mut ctx.accounts.template;
+    template.uses = template.uses
+        .checked_add(1)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    emit!(ChallengeCreatedFromTemplate {
+        challenge: ctx.accounts.challenge.challenge.key(),
+        template: template_key,
+        creator: ctx.accounts.challenge.creator.key(),
+        uses: template.uses,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateChallengeFromTemplate<'info> {
+    /// The new challenge, created exactly as `create_challenge` creates one
+    pub challenge: CreateChallenge<'info>,
+    #[account(mut)]
+    pub template: Account<'info, ChallengeTemplate>,
+}
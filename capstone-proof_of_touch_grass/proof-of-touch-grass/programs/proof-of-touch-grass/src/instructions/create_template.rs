@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+
+/// Publishes reusable challenge terms under the author's `template_id`.
+/// The verifiers' weights are checked against `required_approval_weight`, and
+/// their count against the vote quorum, when a challenge is created from the
+/// template.
+#[allow(clippy::too_many_arguments)]
+pub fn create_template(
+    ctx: Context<CreateTemplate>,
+    template_id: u64,
+    title: String,
+    description: String,
+    duration: i64,
+    verification_period: i64,
+    required_proofs: u8,
+    required_approval_weight: u8,
+    geofences: Vec<Geofence>,
+    submission_rules: SubmissionRules,
+    verification_rules: VerificationRules,
+    min_verifier_reputation: Option<u32>,
+) -> Result<()> {
+    require!(title.len() <= MAX_TITLE_LEN, ErrorCode::TitleTooLong);
+    require!(description.len() <= MAX_DESCRIPTION_LEN, ErrorCode::DescriptionTooLong);
+    require!(
+        duration > 0
+            && verification_period > 0
+            && required_approval_weight > 0
+            && required_approval_weight as usize <= MAX_VERIFIERS * MAX_VERIFIER_WEIGHT as usize,
+        ErrorCode::InvalidTemplate
+    );
+    require!(required_proofs > 0, ErrorCode::InvalidProofCount);
+    require!(geofences.len() <= MAX_GEOFENCES, ErrorCode::TooManyGeofences);
+    require!(geofences.iter().all(Geofence::is_valid), ErrorCode::InvalidGeofence);
+    require!(submission_rules.is_valid(), ErrorCode::InvalidSubmissionRules);
+    require!(
+        verification_rules.is_valid(MAX_VERIFIERS),
+        ErrorCode::InvalidVerificationRules
+    );
+
+    let clock = Clock::get()?;
+    let author = ctx.accounts.author.key();
+    ctx.accounts.template.set_inner(ChallengeTemplate {
+        author,
+        template_id,
+        curated: false,
+        title: title.clone(),
+        description,
+        duration,
+        verification_period,
+        required_proofs,
+        required_approval_weight,
+        geofences,
+        submission_rules,
+        verification_rules,
+        min_verifier_reputation,
+        uses: 0,
+        created_at: clock.unix_timestamp,
+        bump: ctx.bumps.template,
+    });
+
+    emit!(TemplateCreated {
+        template: ctx.accounts.template.key(),
+        author,
+        template_id,
+        title,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(template_id: u64)]
+pub struct CreateTemplate<'info> {
+    #[account(
+        init,
+        payer = author,
+        space = 8 + ChallengeTemplate::INIT_SPACE,
+        seeds = [TEMPLATE_SEED, author.key().as_ref(), &template_id.to_le_bytes()],
+        bump
+    )]
+    pub template: Account<'info, ChallengeTemplate>,
+    #[account(mut)]
+    pub author: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
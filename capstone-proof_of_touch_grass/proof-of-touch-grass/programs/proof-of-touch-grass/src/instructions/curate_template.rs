@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+
+/// Admin-only: marks a template as curated, or withdraws the mark
+pub fn curate_template(ctx: Context<CurateTemplate>, curated: bool) -> Result<()> {
+    require!(
+        ctx.accounts.admin.key() == ADMIN_PUBKEY,
+        ErrorCode::UnauthorizedAdmin
+    );
+
+    let template = &mut ctx.accounts.template;
+    template.curated = curated;
+
+    emit!(TemplateCurated {
+        template: template.key(),
+        curated,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CurateTemplate<'info> {
+    #[account(mut)]
+    pub template: Account<'info, ChallengeTemplate>,
+    pub admin: Signer<'info>,
+}
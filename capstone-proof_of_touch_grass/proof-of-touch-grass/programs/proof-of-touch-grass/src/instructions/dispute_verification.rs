@@ -1,11 +1,16 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
 
 use crate::constants::*;
 use crate::errors::ErrorCode;
 use crate::events::*;
+use crate::fees;
 use crate::state::*;
 
-/// Disputes verification result within dispute window
+/// Disputes verification result within dispute window, optionally naming the
+/// verification whose vote is contested. The disputer bonds a share of the
+/// stake on the dispute, refunded only if the outcome is overturned. Each
+/// eligible party can file one dispute, with one open at a time.
 pub fn dispute_verification(
     ctx: Context<DisputeVerification>,
     reason: String,
@@ -23,7 +28,7 @@ pub fn dispute_verification(
         ErrorCode::InvalidChallengeStatus
     );
     require!(
-        clock.unix_timestamp <= challenge.finalized_at + DISPUTE_WINDOW,
+        clock.unix_timestamp <= challenge.dispute_window_end()?,
         ErrorCode::DisputeWindowExpired
     );
 
@@ -31,25 +36,54 @@ pub fn dispute_verification(
     let is_creator = challenge.creator == disputer_key;
     let is_verifier = challenge.verifiers.contains(&disputer_key);
     require!(is_creator || is_verifier, ErrorCode::UnauthorizedDisputer);
+    require!(!challenge.disputers.contains(&disputer_key), ErrorCode::AlreadyDisputed);
 
     let previous_status = challenge.status.to_string();
+    let contested = ctx.accounts.contested_verification.as_ref();
+    let index = challenge.dispute_count;
+    let bond = fees::dispute_bond(challenge.stake_amount)?;
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.disputer.to_account_info(),
+                to: ctx.accounts.dispute.to_account_info(),
+            },
+        ),
+        bond,
+    )?;
 
     ctx.accounts.dispute.set_inner(Dispute {
         challenge: challenge_key,
+        index,
         disputer: disputer_key,
+        bond,
         reason: reason.clone(),
+        contested_verification: contested.map(|verification| verification.key()),
+        previous_status: challenge.status.clone(),
         timestamp: clock.unix_timestamp,
+        escalated_at: 0,
+        jury: Vec::new(),
+        resolved_at: 0,
         bump: ctx.bumps.dispute,
     });
 
     // Lock escrow and update status
     challenge.status = ChallengeStatus::Disputed;
+    challenge.disputers.push(disputer_key);
+    challenge.dispute_count = index
+        .checked_add(1)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
 
     emit!(DisputeFiled {
         challenge: challenge_key,
+        dispute_index: index,
         disputer: disputer_key,
+        bond,
         previous_status,
         reason,
+        contested_verifier: contested.map(|verification| verification.verifier),
         timestamp: clock.unix_timestamp,
     });
 
@@ -62,7 +96,7 @@ pub struct DisputeVerification<'info> {
         init,
         payer = disputer,
         space = 8 + Dispute::INIT_SPACE,
-        seeds = [DISPUTE_SEED, challenge.key().as_ref()],
+        seeds = [DISPUTE_SEED, challenge.key().as_ref(), &[challenge.dispute_count]],
         bump
     )]
     pub dispute: Account<'info, Dispute>,
@@ -70,5 +104,10 @@ pub struct DisputeVerification<'info> {
     pub challenge: Account<'info, Challenge>,
     #[account(mut)]
     pub disputer: Signer<'info>,
+    #[account(
+        constraint = contested_verification.challenge == challenge.key()
+            @ ErrorCode::InvalidContestedVerification
+    )]
+    pub contested_verification: Option<Account<'info, Verification>>,
     pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::slot_hashes;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::jury;
+use crate::state::*;
+
+/// Hands a dispute to a jury instead of the admin. The jury is drawn
+/// pseudo-randomly from the juror pool, seeded by the most recent slot hash,
+/// leaving out the challenge's creator and verifiers. Each drawn juror stays
+/// bonded until the verdict settles.
+/// Remaining accounts: the juror PDA of every juror in the pool, in pool order.
+pub fn escalate_dispute<'info>(ctx: Context<'_, '_, '_, 'info, EscalateDispute<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let challenge = &ctx.accounts.challenge;
+    let dispute_key = ctx.accounts.dispute.key();
+    let dispute = &mut ctx.accounts.dispute;
+    let signer = ctx.accounts.signer.key();
+
+    require!(
+        signer == dispute.disputer || signer == ADMIN_PUBKEY,
+        ErrorCode::UnauthorizedEscalation
+    );
+    require!(challenge.status == ChallengeStatus::Disputed, ErrorCode::InvalidChallengeStatus);
+    require!(!dispute.is_resolved(), ErrorCode::DisputeAlreadyResolved);
+    require!(!dispute.is_escalated(), ErrorCode::DisputeAlreadyEscalated);
+
+    let mut excluded = challenge.verifiers.clone();
+    excluded.push(challenge.creator);
+    let seed = jury::seed(&ctx.accounts.slot_hashes, &dispute_key)?;
+    let pool = &ctx.accounts.juror_pool.jurors;
+    let jurors = jury::draw(pool, &excluded, seed)?;
+    jury::bond(pool, &jurors, ctx.remaining_accounts)?;
+
+    dispute.escalated_at = clock.unix_timestamp;
+    dispute.jury = jurors
+        .iter()
+        .map(|juror| JurySeat {
+            juror: *juror,
+            vote: None,
+        })
+        .collect();
+
+    emit!(DisputeEscalated {
+        challenge: challenge.key(),
+        dispute_index: dispute.index,
+        escalated_by: signer,
+        jurors,
+        voting_ends: dispute.jury_deadline()?,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct EscalateDispute<'info> {
+    #[account(
+        mut,
+        seeds = [DISPUTE_SEED, challenge.key().as_ref(), &[dispute.index]],
+        bump = dispute.bump,
+    )]
+    pub dispute: Account<'info, Dispute>,
+    pub challenge: Account<'info, Challenge>,
+    #[account(
+        seeds = [JUROR_POOL_SEED],
+        bump = juror_pool.bump,
+    )]
+    pub juror_pool: Account<'info, JurorPool>,
+    #[account(address = slot_hashes::ID)]
+    /// CHECK: SlotHashes sysvar, read raw as it is too large to deserialize
+    pub slot_hashes: AccountInfo<'info>,
+    pub signer: Signer<'info>,
+}
@@ -13,10 +13,31 @@ pub fn initialize_user(ctx: Context<InitializeUser>) -> Result<()> {
         total_challenges: 0,
         completed: 0,
         failed: 0,
+        cancelled: 0,
+        refunded: 0,
+        retries: 0,
+        redemptions: 0,
+        active_challenges: 0,
+        current_streak: 0,
+        longest_streak: 0,
         total_staked: 0,
+        total_won: 0,
+        total_lost: 0,
+        total_forfeited: 0,
+        staked_at_risk: 0,
+        limits: UserLimits::default(),
+        pending_limits: None,
+        pending_limits_at: 0,
+        cooldown_until: 0,
         bump: ctx.bumps.user,
     });
 
+    ctx.accounts.challenge_index.set_inner(ChallengeIndex {
+        authority: ctx.accounts.authority.key(),
+        challenge_ids: Vec::new(),
+        bump: ctx.bumps.challenge_index,
+    });
+
     emit!(UserInitialized {
         authority: ctx.accounts.user.authority,
         timestamp: clock.unix_timestamp,
@@ -35,6 +56,14 @@ pub struct InitializeUser<'info> {
         bump
     )]
     pub user: Account<'info, User>,
+    #[account(
+        init,
+        payer = authority,
+        space = ChallengeIndex::space(0),
+        seeds = [CHALLENGE_INDEX_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub challenge_index: Account<'info, ChallengeIndex>,
     #[account(mut)]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -1,17 +1,57 @@
 pub mod initialize_user;
+pub mod set_user_limits;
 pub mod create_challenge;
+pub mod retry_challenge;
+pub mod create_template;
+pub mod curate_template;
+pub mod create_challenge_from_template;
 pub mod update_challenge_state;
 pub mod submit_evidence;
+pub mod replace_evidence;
+pub mod retract_evidence;
 pub mod verify_evidence;
+pub mod update_vote;
 pub mod cancel_challenge;
+pub mod request_extension;
+pub mod approve_extension;
+pub mod withdraw_extension;
 pub mod dispute_verification;
+pub mod resolve_dispute;
+pub mod register_juror;
+pub mod unregister_juror;
+pub mod withdraw_juror_stake;
+pub mod escalate_dispute;
+pub mod cast_jury_vote;
+pub mod settle_jury;
 pub mod claim_funds;
+pub mod configure_grass;
+pub mod set_platform_limits;
 
 pub use initialize_user::*;
+pub use set_user_limits::*;
 pub use create_challenge::*;
+pub use retry_challenge::*;
+pub use create_template::*;
+pub use curate_template::*;
+pub use create_challenge_from_template::*;
 pub use update_challenge_state::*;
 pub use submit_evidence::*;
+pub use replace_evidence::*;
+pub use retract_evidence::*;
 pub use verify_evidence::*;
+pub use update_vote::*;
 pub use cancel_challenge::*;
+pub use request_extension::*;
+pub use approve_extension::*;
+pub use withdraw_extension::*;
 pub use dispute_verification::*;
+pub use resolve_dispute::*;
+pub use register_juror::*;
+pub use unregister_juror::*;
+pub use withdraw_juror_stake::*;
+pub use escalate_dispute::*;
+pub use cast_jury_vote::*;
+pub use settle_jury::*;
 pub use claim_funds::*;
+pub use configure_grass::*;
+pub use set_platform_limits::*;
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+
+/// Stakes lamports to join the juror pool. The stake is held on the juror
+/// account itself and moves with the verdicts the juror votes on.
+pub fn register_juror(ctx: Context<RegisterJuror>, stake: u64) -> Result<()> {
+    require!(stake >= MIN_JUROR_STAKE, ErrorCode::JurorStakeTooLow);
+
+    let clock = Clock::get()?;
+    let authority = ctx.accounts.authority.key();
+    let pool = &mut ctx.accounts.juror_pool;
+    require!(pool.jurors.len() < MAX_JURORS, ErrorCode::JurorPoolFull);
+    pool.jurors.push(authority);
+    pool.bump = ctx.bumps.juror_pool;
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.juror.to_account_info(),
+            },
+        ),
+        stake,
+    )?;
+
+    ctx.accounts.juror.set_inner(Juror {
+        authority,
+        stake,
+        pending_verdicts: 0,
+        cases: 0,
+        majority_votes: 0,
+        minority_votes: 0,
+        registered_at: clock.unix_timestamp,
+        unbonding_at: 0,
+        bump: ctx.bumps.juror,
+    });
+
+    emit!(JurorRegistered {
+        juror: authority,
+        stake,
+        pool_size: ctx.accounts.juror_pool.jurors.len() as u32,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RegisterJuror<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Juror::INIT_SPACE,
+        seeds = [JUROR_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub juror: Account<'info, Juror>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + JurorPool::INIT_SPACE,
+        seeds = [JUROR_POOL_SEED],
+        bump
+    )]
+    pub juror_pool: Account<'info, JurorPool>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+
+/// Swaps the content of submitted evidence before verification starts.
+/// The evidence keeps its index and original timestamp; `revision` counts the swaps.
+pub fn replace_evidence(
+    ctx: Context<ReplaceEvidence>,
+    evidence_index: u8,
+    ipfs_hash: String,
+    metadata: String,
+    location: Option<GeoPoint>,
+) -> Result<()> {
+    require!(ipfs_hash.len() <= MAX_IPFS_HASH_LEN, ErrorCode::IpfsHashTooLong);
+    require!(metadata.len() <= MAX_METADATA_LEN, ErrorCode::MetadataTooLong);
+
+    let clock = Clock::get()?;
+    let challenge = &ctx.accounts.challenge;
+    let evidence = &mut ctx.accounts.evidence;
+
+    require!(challenge.creator == ctx.accounts.submitter.key(), ErrorCode::UnauthorizedSubmitter);
+    require!(challenge.evidence_editable(), ErrorCode::EvidenceLocked);
+    require!(clock.unix_timestamp < challenge.end_time, ErrorCode::ChallengeExpired);
+    challenge.check_location(location.as_ref())?;
+
+    let old_ipfs_hash = std::mem::replace(&mut evidence.ipfs_hash, ipfs_hash.clone());
+    evidence.metadata = metadata;
+    evidence.location = location;
+    evidence.revision = evidence
+        .revision
+        .checked_add(1)
+        .ok_or(ErrorCode::RevisionLimitReached)?;
+
+    emit!(EvidenceReplaced {
+        challenge: challenge.key(),
+        evidence: evidence.key(),
+        evidence_index,
+        old_ipfs_hash,
+        new_ipfs_hash: ipfs_hash,
+        revision: evidence.revision,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(evidence_index: u8)]
+pub struct ReplaceEvidence<'info> {
+    #[account(
+        mut,
+        seeds = [EVIDENCE_SEED, challenge.key().as_ref(), &evidence_index.to_le_bytes()],
+        bump = evidence.bump,
+    )]
+    pub evidence: Account<'info, Evidence>,
+    pub challenge: Account<'info, Challenge>,
+    pub submitter: Signer<'info>,
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::fees;
+use crate::state::*;
+
+/// Creator proposes a later end time, up to `MAX_EXTENSION` past the current one.
+/// The extension fee is held in the request until it applies.
+pub fn request_extension(ctx: Context<RequestExtension>, new_end_time: i64) -> Result<()> {
+    let clock = Clock::get()?;
+    let challenge = &ctx.accounts.challenge;
+    let challenge_key = challenge.key();
+
+    require!(challenge.creator == ctx.accounts.creator.key(), ErrorCode::UnauthorizedCreator);
+    require!(
+        challenge.status == ChallengeStatus::Created || challenge.status == ChallengeStatus::Active,
+        ErrorCode::InvalidChallengeStatus
+    );
+    require!(clock.unix_timestamp < challenge.end_time, ErrorCode::ChallengeExpired);
+    let max_end_time = challenge.end_time
+        .checked_add(MAX_EXTENSION)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    require!(
+        new_end_time > challenge.end_time && new_end_time <= max_end_time,
+        ErrorCode::InvalidExtension
+    );
+
+    let fee = fees::extension_fee(challenge.stake_amount)?;
+    if fee > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: ctx.accounts.extension.to_account_info(),
+                },
+            ),
+            fee,
+        )?;
+    }
+
+    ctx.accounts.extension.set_inner(ExtensionRequest {
+        challenge: challenge_key,
+        new_end_time,
+        fee,
+        approvals: Vec::new(),
+        requested_at: clock.unix_timestamp,
+        bump: ctx.bumps.extension,
+    });
+
+    emit!(ExtensionRequested {
+        challenge: challenge_key,
+        creator: challenge.creator,
+        new_end_time,
+        fee,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RequestExtension<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + ExtensionRequest::INIT_SPACE,
+        seeds = [EXTENSION_SEED, challenge.key().as_ref()],
+        bump
+    )]
+    pub extension: Account<'info, ExtensionRequest>,
+    pub challenge: Account<'info, Challenge>,
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
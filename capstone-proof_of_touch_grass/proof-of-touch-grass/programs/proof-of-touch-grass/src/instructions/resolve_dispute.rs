@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::reputation;
+use crate::state::*;
+
+/// Settles a disputed challenge (admin-only) that has not gone to a jury
+/// - upheld: the disputed outcome stands and the bond goes to the platform
+/// - overturned: Completed ⇄ Failed, the disputer gets the bond back, and
+///   every verifier who voted for the reversed outcome gets an overturned
+///   vote on their reputation.
+///   Remaining accounts: [verification, reputation] per listed verifier.
+pub fn resolve_dispute(ctx: Context<ResolveDispute>, overturn: bool) -> Result<()> {
+    let clock = Clock::get()?;
+    let accounts = ctx.accounts;
+    let challenge = &mut accounts.challenge;
+
+    require!(
+        accounts.admin.key() == ADMIN_PUBKEY,
+        ErrorCode::UnauthorizedAdmin
+    );
+    require!(challenge.status == ChallengeStatus::Disputed, ErrorCode::InvalidChallengeStatus);
+    require!(!accounts.dispute.is_resolved(), ErrorCode::DisputeAlreadyResolved);
+    require!(!accounts.dispute.is_escalated(), ErrorCode::DisputeAlreadyEscalated);
+
+    settle_dispute(
+        challenge,
+        &mut accounts.user,
+        &mut accounts.dispute,
+        overturn,
+        false,
+        ctx.remaining_accounts,
+        clock.unix_timestamp,
+    )?;
+
+    let bond_to = if overturn {
+        accounts.disputer.to_account_info()
+    } else {
+        accounts.platform.to_account_info()
+    };
+    release_bond(&accounts.dispute, &bond_to)
+}
+
+/// Moves a disputed challenge to its final status: the disputed outcome when
+/// upheld, the reverse when overturned, recording the overturned votes on the
+/// verifiers' reputations (`reputation_accounts`, used only when overturned).
+/// Marks the dispute resolved, restarting the dispute window; the caller
+/// releases its bond.
+pub fn settle_dispute(
+    challenge: &mut Account<Challenge>,
+    user: &mut User,
+    dispute: &mut Dispute,
+    overturn: bool,
+    by_jury: bool,
+    reputation_accounts: &[AccountInfo],
+    timestamp: i64,
+) -> Result<()> {
+    let challenge_key = challenge.key();
+    let disputed_status = dispute.previous_status.clone();
+    let status = if !overturn {
+        disputed_status
+    } else {
+        let (status, overturned_vote) = match disputed_status {
+            ChallengeStatus::Completed => (ChallengeStatus::Failed, Vote::Approve),
+            ChallengeStatus::Failed => (ChallengeStatus::Completed, Vote::Reject),
+            _ => return err!(ErrorCode::InvalidChallengeStatus),
+        };
+        user.reverse_outcome(challenge, &status, timestamp)?;
+        reputation::record_overturned(
+            &challenge_key,
+            challenge,
+            overturned_vote,
+            reputation_accounts,
+            timestamp,
+        )?;
+        status
+    };
+
+    challenge.status = status;
+    challenge.dispute_resolved_at = timestamp;
+    dispute.resolved_at = timestamp;
+
+    emit!(DisputeResolved {
+        challenge: challenge_key,
+        dispute_index: dispute.index,
+        overturned: overturn,
+        bond: dispute.bond,
+        by_jury,
+        status: challenge.status.to_string(),
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// Pays the bond held on a resolved dispute to `recipient`. Dispute accounts
+/// are program-owned, so the bond moves without a CPI.
+pub fn release_bond(dispute: &Account<Dispute>, recipient: &AccountInfo) -> Result<()> {
+    let info = dispute.to_account_info();
+    **info.try_borrow_mut_lamports()? = info
+        .lamports()
+        .checked_sub(dispute.bond)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    **recipient.try_borrow_mut_lamports()? = recipient
+        .lamports()
+        .checked_add(dispute.bond)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(mut)]
+    pub challenge: Account<'info, Challenge>,
+    #[account(
+        mut,
+        seeds = [DISPUTE_SEED, challenge.key().as_ref(), &[dispute.index]],
+        bump = dispute.bump,
+    )]
+    pub dispute: Account<'info, Dispute>,
+    #[account(
+        mut,
+        seeds = [USER_SEED, challenge.creator.as_ref()],
+        bump = user.bump,
+    )]
+    pub user: Account<'info, User>,
+    #[account(mut, address = dispute.disputer @ ErrorCode::InvalidDisputer)]
+    /// CHECK: Disputer wallet (receives the bond back when overturned)
+    pub disputer: AccountInfo<'info>,
+    #[account(mut, address = PLATFORM_PUBKEY)]
+    /// CHECK: Platform wallet (receives the bond when upheld)
+    pub platform: AccountInfo<'info>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
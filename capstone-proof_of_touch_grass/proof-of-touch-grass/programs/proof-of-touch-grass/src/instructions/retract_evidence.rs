@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+
+/// Removes the most recently submitted evidence before verification starts.
+/// Retracting from PendingVerification reopens the challenge for submissions.
+/// Retracted submissions still count toward the daily submission limit, but
+/// not toward the minimum interval before the next one.
+pub fn retract_evidence(ctx: Context<RetractEvidence>) -> Result<()> {
+    let clock = Clock::get()?;
+    let challenge = &mut ctx.accounts.challenge;
+
+    require!(challenge.creator == ctx.accounts.submitter.key(), ErrorCode::UnauthorizedSubmitter);
+    require!(challenge.evidence_count > 0, ErrorCode::NoEvidenceToRetract);
+    require!(challenge.evidence_editable(), ErrorCode::EvidenceLocked);
+    require!(clock.unix_timestamp < challenge.end_time, ErrorCode::ChallengeExpired);
+
+    challenge.evidence_count = challenge.evidence_count
+        .checked_sub(1)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    // The retracted proof was already at least `min_interval` after the one
+    // before it, so the resubmission only has to wait on that earlier proof,
+    // which it always does
+    challenge.last_evidence_at = 0;
+    if challenge.status == ChallengeStatus::PendingVerification {
+        challenge.status = ChallengeStatus::Active;
+    }
+
+    emit!(EvidenceRetracted {
+        challenge: challenge.key(),
+        evidence: ctx.accounts.evidence.key(),
+        ipfs_hash: ctx.accounts.evidence.ipfs_hash.clone(),
+        evidence_index: ctx.accounts.evidence.evidence_index,
+        total_evidence: challenge.evidence_count,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RetractEvidence<'info> {
+    #[account(
+        mut,
+        close = submitter,
+        seeds = [
+            EVIDENCE_SEED,
+            challenge.key().as_ref(),
+            &challenge.evidence_count.saturating_sub(1).to_le_bytes()
+        ],
+        bump = evidence.bump,
+    )]
+    pub evidence: Account<'info, Evidence>,
+    #[account(mut)]
+    pub challenge: Account<'info, Challenge>,
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+}
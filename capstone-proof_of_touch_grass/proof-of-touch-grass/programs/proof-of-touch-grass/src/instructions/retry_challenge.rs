@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+
+use super::create_challenge::*;
+
+/// Creates a rematch of a failed challenge once its dispute window has closed.
+/// The retry copies the title, description, verifiers and their weights, the
+/// proof count and approval weight, geofences, submission rules and durations, starts at `start_time`
+/// and stakes at least as much as the failed challenge did. The verifiers must
+/// still meet the failed challenge's minimum reputation, and a retry of a
+/// templated challenge counts as a use of the template, which must be passed.
+/// Remaining accounts: as for `create_challenge`.
+pub fn retry_challenge<'info>(
+    ctx: Context<'_, '_, '_, 'info, RetryChallenge<'info>>,
+    challenge_id: u64,
+    stake_amount: u64,
+    start_time: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let previous = &ctx.accounts.previous_challenge;
+    let previous_key = previous.key();
+    let previous_stake_amount = previous.stake_amount;
+
+    require!(
+        previous.creator == ctx.accounts.retry.creator.key(),
+        ErrorCode::UnauthorizedCreator
+    );
+    require!(previous.status == ChallengeStatus::Failed, ErrorCode::InvalidChallengeStatus);
+    require!(
+        clock.unix_timestamp > previous.dispute_window_end()?,
+        ErrorCode::DisputeWindowNotExpired
+    );
+    require!(previous.retried_by.is_none(), ErrorCode::AlreadyRetried);
+    require!(
+        ctx.accounts.template.as_ref().map(|template| template.key()) == previous.template,
+        ErrorCode::InvalidTemplateAccount
+    );
+    require!(stake_amount >= previous_stake_amount, ErrorCode::RetryStakeTooLow);
+
+    let duration = previous.end_time
+        .checked_sub(previous.start_time)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    let verification_period = previous.verification_period_end
+        .checked_sub(previous.end_time)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    let end_time = start_time
+        .checked_add(duration)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    let params = ChallengeParams {
+        title: previous.title.clone(),
+        description: previous.description.clone(),
+        stake_amount,
+        start_time,
+        end_time,
+        verification_period,
+        required_proofs: previous.required_proofs,
+        required_approval_weight: previous.required_approval_weight,
+        verifiers: previous.verifiers.clone(),
+        verifier_weights: previous.verifier_weights.clone(),
+        geofences: previous.geofences.clone(),
+        submission_rules: previous.submission_rules.clone(),
+        verification_rules: previous.verification_rules.clone(),
+        min_verifier_reputation: previous.min_verifier_reputation,
+        previous_challenge: Some(previous_key),
+        template: previous.template,
+    };
+    let accounts = verifier_accounts(
+        ctx.remaining_accounts,
+        &params.verifiers,
+        params.min_verifier_reputation,
+    )?;
+    ctx.accounts
+        .retry
+        .open(&ctx.bumps.retry, accounts, challenge_id, params)?;
+
+    let challenge_key = ctx.accounts.retry.challenge.key();
+    ctx.accounts.previous_challenge.retried_by = Some(challenge_key);
+    if let Some(template) = &mut ctx.accounts.template {
+        template.uses = template.uses
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+    }
+    let user = &mut ctx.accounts.retry.user;
+    user.retries = user.retries
+        .checked_add(1)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    emit!(ChallengeRetried {
+        challenge: challenge_key,
+        previous_challenge: previous_key,
+        creator: user.authority,
+        stake_amount,
+        previous_stake_amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RetryChallenge<'info> {
+    /// The new challenge, created exactly as `create_challenge` creates one
+    pub retry: CreateChallenge<'info>,
+    #[account(mut)]
+    pub previous_challenge: Account<'info, Challenge>,
+    /// The failed challenge's template, required when it has one
+    #[account(mut)]
+    pub template: Option<Account<'info, ChallengeTemplate>>,
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+
+/// Admin-only: sets the ceilings on every user's open challenges, stake and
+/// failure cooldown, creating the config on the first call. Until then the
+/// `DEFAULT_MAX_*` constants apply. Limits users already set above a lowered
+/// ceiling stay stored but are capped at it.
+pub fn set_platform_limits(ctx: Context<SetPlatformLimits>, limits: PlatformLimits) -> Result<()> {
+    require!(
+        ctx.accounts.admin.key() == ADMIN_PUBKEY,
+        ErrorCode::UnauthorizedAdmin
+    );
+    require!(limits.is_valid(), ErrorCode::InvalidPlatformLimits);
+
+    let config = &mut ctx.accounts.platform_config;
+    config.limits = limits.clone();
+    config.bump = ctx.bumps.platform_config;
+
+    emit!(PlatformLimitsUpdated {
+        max_active_challenges: limits.max_active_challenges,
+        max_stake: limits.max_stake,
+        max_stake_at_risk: limits.max_stake_at_risk,
+        max_failure_cooldown: limits.max_failure_cooldown,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPlatformLimits<'info> {
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + PlatformConfig::INIT_SPACE,
+        seeds = [PLATFORM_CONFIG_SEED],
+        bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+
+/// Replaces the limits a user has set on themselves. Tighter limits apply at
+/// once; looser ones are scheduled and take over after
+/// `LIMITS_LOOSENING_DELAY`, so they cannot be lifted in the same breath as
+/// a risky challenge. A cooldown already running keeps its end time; the new
+/// cooldown applies from the next failure.
+pub fn set_user_limits(ctx: Context<SetUserLimits>, limits: UserLimits) -> Result<()> {
+    let clock = Clock::get()?;
+    let platform_limits = PlatformConfig::limits(&ctx.accounts.platform_config)?;
+    require!(limits.is_valid(&platform_limits), ErrorCode::InvalidUserLimits);
+
+    let user = &mut ctx.accounts.user;
+    let effective_at = user.set_limits(limits, clock.unix_timestamp)?;
+
+    emit!(UserLimitsUpdated {
+        authority: user.authority,
+        max_active_challenges: user.limits.max_active_challenges,
+        max_stake: user.limits.max_stake,
+        max_stake_at_risk: user.limits.max_stake_at_risk,
+        failure_cooldown: user.limits.failure_cooldown,
+        timestamp: clock.unix_timestamp,
+    });
+    if let (Some(pending), Some(effective_at)) = (&user.pending_limits, effective_at) {
+        emit!(UserLimitsScheduled {
+            authority: user.authority,
+            max_active_challenges: pending.max_active_challenges,
+            max_stake: pending.max_stake,
+            max_stake_at_risk: pending.max_stake_at_risk,
+            failure_cooldown: pending.failure_cooldown,
+            effective_at,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetUserLimits<'info> {
+    #[account(
+        mut,
+        seeds = [USER_SEED, authority.key().as_ref()],
+        bump = user.bump,
+    )]
+    pub user: Account<'info, User>,
+    /// CHECK: Platform limits; empty until the admin runs set_platform_limits
+    #[account(seeds = [PLATFORM_CONFIG_SEED], bump)]
+    pub platform_config: UncheckedAccount<'info>,
+    pub authority: Signer<'info>,
+}
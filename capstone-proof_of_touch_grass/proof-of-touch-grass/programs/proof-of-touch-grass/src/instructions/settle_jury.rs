@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::jury;
+use crate::state::*;
+
+use super::resolve_dispute::{release_bond, settle_dispute};
+
+/// Applies a jury's verdict once a side holds a majority of the seats or the
+/// voting period has run out, then settles the jurors' stakes and the bond:
+/// back to the disputer on an overturn, to the platform otherwise. Anyone can
+/// call it.
+/// Remaining accounts: the juror PDA of each seat, in seat order, then
+/// [verification, reputation] per listed verifier for an overturn.
+pub fn settle_jury(ctx: Context<SettleJury>) -> Result<()> {
+    let clock = Clock::get()?;
+    let accounts = ctx.accounts;
+    let dispute = &mut accounts.dispute;
+
+    require!(
+        accounts.challenge.status == ChallengeStatus::Disputed,
+        ErrorCode::InvalidChallengeStatus
+    );
+    require!(!dispute.is_resolved(), ErrorCode::DisputeAlreadyResolved);
+    require!(dispute.is_escalated(), ErrorCode::DisputeNotEscalated);
+    let verdict = dispute
+        .jury_verdict(clock.unix_timestamp)?
+        .ok_or(ErrorCode::JuryStillVoting)?;
+
+    require!(
+        ctx.remaining_accounts.len() >= dispute.jury.len(),
+        ErrorCode::InvalidJurorAccounts
+    );
+    let (jurors, reputations) = ctx.remaining_accounts.split_at(dispute.jury.len());
+    let challenge_key = accounts.challenge.key();
+    jury::settle(&challenge_key, dispute, &verdict, jurors, clock.unix_timestamp)?;
+
+    let overturn = verdict == JuryVote::Overturn;
+    settle_dispute(
+        &mut accounts.challenge,
+        &mut accounts.user,
+        dispute,
+        overturn,
+        true,
+        reputations,
+        clock.unix_timestamp,
+    )?;
+
+    let bond_to = if overturn {
+        accounts.disputer.to_account_info()
+    } else {
+        accounts.platform.to_account_info()
+    };
+    release_bond(&accounts.dispute, &bond_to)
+}
+
+#[derive(Accounts)]
+pub struct SettleJury<'info> {
+    #[account(mut)]
+    pub challenge: Account<'info, Challenge>,
+    #[account(
+        mut,
+        seeds = [DISPUTE_SEED, challenge.key().as_ref(), &[dispute.index]],
+        bump = dispute.bump,
+    )]
+    pub dispute: Account<'info, Dispute>,
+    #[account(
+        mut,
+        seeds = [USER_SEED, challenge.creator.as_ref()],
+        bump = user.bump,
+    )]
+    pub user: Account<'info, User>,
+    #[account(mut, address = dispute.disputer @ ErrorCode::InvalidDisputer)]
+    /// CHECK: Disputer wallet (receives the bond back when overturned)
+    pub disputer: AccountInfo<'info>,
+    #[account(mut, address = PLATFORM_PUBKEY)]
+    /// CHECK: Platform wallet (receives the bond when upheld)
+    pub platform: AccountInfo<'info>,
+}
@@ -10,6 +10,7 @@ pub fn submit_evidence(
     ctx: Context<SubmitEvidence>,
     ipfs_hash: String,
     metadata: String,
+    location: Option<GeoPoint>,
 ) -> Result<()> {
     require!(ipfs_hash.len() <= MAX_IPFS_HASH_LEN, ErrorCode::IpfsHashTooLong);
     require!(metadata.len() <= MAX_METADATA_LEN, ErrorCode::MetadataTooLong);
@@ -26,18 +27,56 @@ pub fn submit_evidence(
     require!(clock.unix_timestamp < challenge.end_time, ErrorCode::ChallengeExpired);
     require!(challenge.evidence_count < challenge.required_proofs, ErrorCode::AllEvidenceSubmitted);
 
+    challenge.check_location(location.as_ref())?;
+
+    // Cadence rules
+    let now = clock.unix_timestamp;
+    let today = now.div_euclid(SECONDS_PER_DAY);
+    let rules = &challenge.submission_rules;
+    if let Some(min_interval) = rules.min_interval {
+        require!(
+            challenge.evidence_count == 0
+                || now.saturating_sub(challenge.last_evidence_at) >= min_interval,
+            ErrorCode::SubmissionTooSoon
+        );
+    }
+    if !rules.allowed_hours.is_empty() {
+        let hour = (now.rem_euclid(SECONDS_PER_DAY) / SECONDS_PER_HOUR) as u8;
+        require!(
+            rules.allowed_hours.iter().any(|window| window.contains(hour)),
+            ErrorCode::OutsideAllowedHours
+        );
+    }
+    let submissions_today = if challenge.submission_day == today {
+        challenge.submissions_today
+    } else {
+        0
+    };
+    if let Some(max_per_day) = rules.max_per_day {
+        require!(submissions_today < max_per_day, ErrorCode::DailySubmissionLimitReached);
+    }
+
     let evidence_index = challenge.evidence_count;
 
     ctx.accounts.evidence.set_inner(Evidence {
         challenge: challenge_key,
         ipfs_hash: ipfs_hash.clone(),
         metadata,
+        location,
         timestamp: clock.unix_timestamp,
         evidence_index,
+        revision: 0,
         bump: ctx.bumps.evidence,
     });
 
-    challenge.evidence_count += 1;
+    challenge.evidence_count = challenge.evidence_count
+        .checked_add(1)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    challenge.last_evidence_at = now;
+    challenge.submission_day = today;
+    challenge.submissions_today = submissions_today
+        .checked_add(1)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
 
     // If all evidence submitted, transition to PendingVerification status
     if challenge.evidence_count == challenge.required_proofs {
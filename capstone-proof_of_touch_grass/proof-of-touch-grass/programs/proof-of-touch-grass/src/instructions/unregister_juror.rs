@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+
+/// Leaves the juror pool so no new jury is drawn with this juror. The stake
+/// stays bonded for `JUROR_UNBONDING_PERIOD`, and the juror still votes on
+/// the juries they already sit on.
+pub fn unregister_juror(ctx: Context<UnregisterJuror>) -> Result<()> {
+    let clock = Clock::get()?;
+    let juror = &mut ctx.accounts.juror;
+    require!(juror.unbonding_at == 0, ErrorCode::JurorUnbonding);
+
+    juror.unbonding_at = clock.unix_timestamp
+        .checked_add(JUROR_UNBONDING_PERIOD)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    let pool = &mut ctx.accounts.juror_pool;
+    pool.jurors.retain(|authority| *authority != juror.authority);
+
+    emit!(JurorUnbonding {
+        juror: juror.authority,
+        withdrawable_at: juror.unbonding_at,
+        pool_size: pool.jurors.len() as u32,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UnregisterJuror<'info> {
+    #[account(
+        mut,
+        seeds = [JUROR_SEED, authority.key().as_ref()],
+        bump = juror.bump,
+    )]
+    pub juror: Account<'info, Juror>,
+    #[account(
+        mut,
+        seeds = [JUROR_POOL_SEED],
+        bump = juror_pool.bump,
+    )]
+    pub juror_pool: Account<'info, JurorPool>,
+    pub authority: Signer<'info>,
+}
@@ -3,13 +3,16 @@ use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::errors::ErrorCode;
 use crate::events::*;
+use crate::inbox;
 use crate::state::*;
 
 /// Updates challenge state based on time progression (admin-only)
 /// - Created → Active (when start_time reached)
 /// - Active → Failed (when end_time passed without enough evidence)
 /// - Active → PendingVerification (when end_time passed with all evidence)
-/// - PendingVerification → Completed (when verification_period_end passed - innocent until proven guilty)
+/// - PendingVerification → the timeout policy's outcome (when verification_period_end passed
+///   without a valid verdict; Completed by default - innocent until proven guilty)
+///   Remaining accounts: the inbox PDA of each listed verifier, cleared on finalization.
 pub fn update_challenge_state(ctx: Context<UpdateChallengeState>) -> Result<()> {
     let clock = Clock::get()?;
     let challenge = &mut ctx.accounts.challenge;
@@ -41,10 +44,9 @@ pub fn update_challenge_state(ctx: Context<UpdateChallengeState>) -> Result<()>
             if clock.unix_timestamp > challenge.end_time {
                 if challenge.evidence_count < challenge.required_proofs {
                     // Not enough evidence submitted → Failed
-                    challenge.status = ChallengeStatus::Failed;
-                    challenge.finalized_at = clock.unix_timestamp;
+                    challenge.finalize(user, ChallengeStatus::Failed, clock.unix_timestamp)?;
+                    inbox::release_verifiers(&challenge_key, challenge, ctx.remaining_accounts)?;
                     new_status = ChallengeStatus::Failed;
-                    user.failed += 1;
                     state_changed = true;
                 } else {
                     // All evidence submitted → PendingVerification
@@ -55,12 +57,13 @@ pub fn update_challenge_state(ctx: Context<UpdateChallengeState>) -> Result<()>
             }
         }
         ChallengeStatus::PendingVerification => {
-            // PendingVerification → Completed when verification_period_end is passed
+            // PendingVerification → timeout outcome when verification_period_end is passed.
+            // A reached threshold with quorum would already have finalized the challenge.
             if clock.unix_timestamp > challenge.verification_period_end {
-                challenge.status = ChallengeStatus::Completed;
-                challenge.finalized_at = clock.unix_timestamp;
-                new_status = ChallengeStatus::Completed;
-                user.completed += 1;
+                let outcome = challenge.verification_rules.timeout_policy.outcome();
+                challenge.finalize(user, outcome.clone(), clock.unix_timestamp)?;
+                inbox::release_verifiers(&challenge_key, challenge, ctx.remaining_accounts)?;
+                new_status = outcome;
                 state_changed = true;
             }
         }
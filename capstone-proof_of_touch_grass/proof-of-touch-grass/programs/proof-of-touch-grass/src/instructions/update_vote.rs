@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+
+use super::verify_evidence::finalize_if_decided;
+
+/// Verifier revises a vote already cast, while the challenge is still
+/// pending verification. The new justification replaces the old one. The
+/// change can finalize the challenge the same way a new vote does.
+/// Remaining accounts: the inbox PDA of each listed verifier, cleared when
+/// the change finalizes the challenge.
+pub fn update_vote(
+    ctx: Context<UpdateVote>,
+    vote: Vote,
+    justification: Option<Justification>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let challenge = &mut ctx.accounts.challenge;
+    let user = &mut ctx.accounts.user;
+    let verification = &mut ctx.accounts.verification;
+    let challenge_key = challenge.key();
+
+    require!(challenge.status == ChallengeStatus::PendingVerification, ErrorCode::InvalidChallengeStatus);
+    require!(clock.unix_timestamp < challenge.verification_period_end, ErrorCode::VerificationPeriodExpired);
+    require!(verification.vote != vote, ErrorCode::VoteUnchanged);
+    require!(
+        justification.as_ref().is_none_or(Justification::is_valid),
+        ErrorCode::JustificationTooLong
+    );
+
+    let previous_vote = verification.vote.to_string();
+    let weight = challenge.weight_of(&verification.verifier)?;
+    challenge.uncount_vote(&verification.vote, weight)?;
+    challenge.count_vote(&vote, weight)?;
+    verification.vote = vote;
+    verification.justification = justification.clone();
+    verification.timestamp = clock.unix_timestamp;
+
+    finalize_if_decided(
+        &challenge_key,
+        challenge,
+        user,
+        ctx.remaining_accounts,
+        clock.unix_timestamp,
+    )?;
+
+    emit!(VoteChanged {
+        challenge: challenge_key,
+        verifier: verification.verifier,
+        previous_vote,
+        vote: verification.vote.to_string(),
+        justification,
+        approval_count: challenge.approval_count,
+        rejection_count: challenge.rejection_count,
+        abstain_count: challenge.abstain_count,
+        approval_weight: challenge.approval_weight,
+        rejection_weight: challenge.rejection_weight,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateVote<'info> {
+    #[account(
+        mut,
+        seeds = [VERIFICATION_SEED, challenge.key().as_ref(), verifier.key().as_ref()],
+        bump = verification.bump,
+    )]
+    pub verification: Account<'info, Verification>,
+    #[account(mut)]
+    pub challenge: Account<'info, Challenge>,
+    #[account(
+        mut,
+        seeds = [USER_SEED, challenge.creator.as_ref()],
+        bump = user.bump,
+    )]
+    pub user: Account<'info, User>,
+    pub verifier: Signer<'info>,
+}
@@ -3,12 +3,19 @@ use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::errors::ErrorCode;
 use crate::events::*;
+use crate::inbox;
+use crate::reputation;
 use crate::state::*;
 
-/// Verifiers vote to approve or reject evidence
+/// Verifiers vote to approve, reject or abstain. Once the vote quorum is
+/// reached, meeting either threshold finalizes the challenge early.
+/// A vote can carry a justification the creator gets to see.
+/// Remaining accounts: the inbox PDA of each listed verifier, cleared when
+/// the vote finalizes the challenge.
 pub fn verify_evidence(
     ctx: Context<VerifyEvidence>,
     vote: Vote,
+    justification: Option<Justification>,
 ) -> Result<()> {
     let clock = Clock::get()?;
     let challenge = &mut ctx.accounts.challenge;
@@ -18,79 +25,86 @@ pub fn verify_evidence(
 
     require!(challenge.status == ChallengeStatus::PendingVerification, ErrorCode::InvalidChallengeStatus);
     require!(clock.unix_timestamp < challenge.verification_period_end, ErrorCode::VerificationPeriodExpired);
+    let weight = challenge.weight_of(&verifier_key)?;
     require!(
-        challenge.verifiers.contains(&verifier_key),
-        ErrorCode::UnauthorizedVerifier
+        justification.as_ref().is_none_or(Justification::is_valid),
+        ErrorCode::JustificationTooLong
     );
 
     ctx.accounts.verification.set_inner(Verification {
         challenge: challenge_key,
         verifier: verifier_key,
         vote: vote.clone(),
+        justification: justification.clone(),
         timestamp: clock.unix_timestamp,
         claimed: false,
         bump: ctx.bumps.verification,
     });
 
-    // Update vote counts
-    match vote {
-        Vote::Approve => {
-            challenge.approval_count += 1;
-        }
-        Vote::Reject => {
-            challenge.rejection_count += 1;
-        }
+    // First vote ever creates the verifier's reputation record
+    let reputation = &mut ctx.accounts.reputation;
+    if reputation.verifier == Pubkey::default() {
+        reputation.verifier = verifier_key;
+        reputation.bump = ctx.bumps.reputation;
     }
+    reputation.votes_cast = reputation.votes_cast
+        .checked_add(1)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    reputation::emit_updated(reputation, clock.unix_timestamp);
 
-    let total_verifiers = challenge.verifiers.len() as u8;
-    let required_approvals = challenge.required_approvals;
-    let max_possible_rejections = total_verifiers - required_approvals;
-
-    // Check if approval threshold met → Completed
-    if challenge.approval_count >= required_approvals {
-        challenge.status = ChallengeStatus::Completed;
-        challenge.finalized_at = clock.unix_timestamp;
-        user.completed += 1;
-
-        emit!(ChallengeFinalized {
-            challenge: challenge_key,
-            creator: challenge.creator,
-            status: "Completed".to_string(),
-            approval_count: challenge.approval_count,
-            rejection_count: challenge.rejection_count,
-            required_approvals,
-            timestamp: clock.unix_timestamp,
-        });
-    }
-    // Check if rejection threshold met → Failed
-    else if challenge.rejection_count > max_possible_rejections {
-        challenge.status = ChallengeStatus::Failed;
-        challenge.finalized_at = clock.unix_timestamp;
-        user.failed += 1;
-
-        emit!(ChallengeFinalized {
-            challenge: challenge_key,
-            creator: challenge.creator,
-            status: "Failed".to_string(),
-            approval_count: challenge.approval_count,
-            rejection_count: challenge.rejection_count,
-            required_approvals,
-            timestamp: clock.unix_timestamp,
-        });
-    }
+    challenge.count_vote(&vote, weight)?;
+    finalize_if_decided(
+        &challenge_key,
+        challenge,
+        user,
+        ctx.remaining_accounts,
+        clock.unix_timestamp,
+    )?;
 
     emit!(VoteCast {
         challenge: challenge_key,
         verifier: verifier_key,
         vote: ctx.accounts.verification.vote.to_string(),
+        justification,
         approval_count: challenge.approval_count,
         rejection_count: challenge.rejection_count,
+        abstain_count: challenge.abstain_count,
+        approval_weight: challenge.approval_weight,
+        rejection_weight: challenge.rejection_weight,
         timestamp: clock.unix_timestamp,
     });
 
     Ok(())
 }
 
+/// Finalizes the challenge if the votes so far settle an outcome, releasing
+/// the verifiers' inbox entries
+pub fn finalize_if_decided(
+    challenge_key: &Pubkey,
+    challenge: &mut Challenge,
+    user: &mut User,
+    inboxes: &[AccountInfo],
+    timestamp: i64,
+) -> Result<()> {
+    let Some(outcome) = challenge.decided_outcome()? else {
+        return Ok(());
+    };
+    let status = outcome.to_string();
+    challenge.finalize(user, outcome, timestamp)?;
+    inbox::release_verifiers(challenge_key, challenge, inboxes)?;
+
+    emit!(ChallengeFinalized {
+        challenge: *challenge_key,
+        creator: challenge.creator,
+        status,
+        approval_weight: challenge.approval_weight,
+        rejection_weight: challenge.rejection_weight,
+        required_approval_weight: challenge.required_approval_weight,
+        timestamp,
+    });
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct VerifyEvidence<'info> {
     #[account(
@@ -101,6 +115,14 @@ pub struct VerifyEvidence<'info> {
         bump
     )]
     pub verification: Account<'info, Verification>,
+    #[account(
+        init_if_needed,
+        payer = verifier,
+        space = 8 + VerifierReputation::INIT_SPACE,
+        seeds = [REPUTATION_SEED, verifier.key().as_ref()],
+        bump
+    )]
+    pub reputation: Account<'info, VerifierReputation>,
     #[account(mut)]
     pub challenge: Account<'info, Challenge>,
     #[account(
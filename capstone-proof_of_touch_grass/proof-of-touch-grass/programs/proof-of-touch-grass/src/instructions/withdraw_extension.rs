@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+
+/// Creator withdraws a pending extension, getting the held fee and rent back
+pub fn withdraw_extension(ctx: Context<WithdrawExtension>) -> Result<()> {
+    let clock = Clock::get()?;
+    let challenge = &ctx.accounts.challenge;
+
+    require!(challenge.creator == ctx.accounts.creator.key(), ErrorCode::UnauthorizedCreator);
+
+    emit!(ExtensionWithdrawn {
+        challenge: challenge.key(),
+        creator: challenge.creator,
+        refund: ctx.accounts.extension.fee,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawExtension<'info> {
+    #[account(
+        mut,
+        seeds = [EXTENSION_SEED, challenge.key().as_ref()],
+        bump = extension.bump,
+        close = creator,
+    )]
+    pub extension: Account<'info, ExtensionRequest>,
+    pub challenge: Account<'info, Challenge>,
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
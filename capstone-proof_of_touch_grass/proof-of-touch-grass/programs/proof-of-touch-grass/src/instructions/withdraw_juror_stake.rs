@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+
+/// Closes the juror account of a juror who has left the pool, returning the
+/// stake and rent once unbonding is over and every jury they were drawn for
+/// has settled
+pub fn withdraw_juror_stake(ctx: Context<WithdrawJurorStake>) -> Result<()> {
+    let clock = Clock::get()?;
+    let juror = &ctx.accounts.juror;
+    require!(
+        juror.unbonding_at > 0
+            && clock.unix_timestamp >= juror.unbonding_at
+            && juror.pending_verdicts == 0,
+        ErrorCode::JurorStillBonded
+    );
+
+    emit!(JurorWithdrawn {
+        juror: juror.authority,
+        stake: juror.stake,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawJurorStake<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [JUROR_SEED, authority.key().as_ref()],
+        bump = juror.bump,
+    )]
+    pub juror: Account<'info, Juror>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,201 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::fees;
+use crate::state::*;
+
+/// splitmix64 finalizer: spreads every input bit over the whole output
+fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+/// Seed for drawing the jury of `dispute`: the most recent slot hash folded
+/// with the dispute address, so disputes escalated in the same slot draw
+/// different juries
+pub fn seed(slot_hashes: &AccountInfo, dispute: &Pubkey) -> Result<u64> {
+    let data = slot_hashes.try_borrow_data()?;
+    // A u64 entry count, then (slot, hash) entries, most recent first
+    require!(
+        data.len() >= 48 && data[..8] != [0; 8],
+        ErrorCode::SlotHashesUnavailable
+    );
+    let recent_hash = &data[16..48];
+    Ok(recent_hash
+        .chunks(8)
+        .chain(dispute.as_ref().chunks(8))
+        .fold(0, |seed, chunk| {
+            let mut word = [0; 8];
+            word.copy_from_slice(chunk);
+            mix(seed ^ u64::from_le_bytes(word))
+        }))
+}
+
+/// Draws `JURY_SIZE` distinct jurors from `pool`, leaving out `excluded`
+pub fn draw(pool: &[Pubkey], excluded: &[Pubkey], mut seed: u64) -> Result<Vec<Pubkey>> {
+    let mut eligible: Vec<Pubkey> = pool
+        .iter()
+        .filter(|juror| !excluded.contains(juror))
+        .copied()
+        .collect();
+    require!(eligible.len() >= JURY_SIZE, ErrorCode::NotEnoughJurors);
+
+    let mut jury = Vec::with_capacity(JURY_SIZE);
+    for _ in 0..JURY_SIZE {
+        seed = mix(seed);
+        let index = (seed % eligible.len() as u64) as usize;
+        jury.push(eligible.swap_remove(index));
+    }
+    Ok(jury)
+}
+
+/// Reads the juror account passed for `juror`
+fn load(info: &AccountInfo, juror: &Pubkey) -> Result<Juror> {
+    let (expected, _) = Pubkey::find_program_address(&[JUROR_SEED, juror.as_ref()], &crate::ID);
+    require_keys_eq!(info.key(), expected, ErrorCode::InvalidJurorAccounts);
+    require!(
+        info.owner == &crate::ID && info.is_writable,
+        ErrorCode::InvalidJurorAccounts
+    );
+    let data = info.try_borrow_data()?;
+    Juror::try_deserialize(&mut &data[..])
+}
+
+/// Bonds every juror in `jury` until its verdict settles. `accounts` holds the
+/// juror PDA of each juror in `pool`, in pool order, as the draw can't be
+/// known before the transaction lands.
+pub fn bond(pool: &[Pubkey], jury: &[Pubkey], accounts: &[AccountInfo]) -> Result<()> {
+    require!(accounts.len() == pool.len(), ErrorCode::InvalidJurorAccounts);
+    for (authority, info) in pool.iter().zip(accounts) {
+        if !jury.contains(authority) {
+            continue;
+        }
+        let mut juror = load(info, authority)?;
+        juror.pending_verdicts = juror.pending_verdicts
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        juror.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+    }
+    Ok(())
+}
+
+fn move_lamports(info: &AccountInfo, penalty: u64, reward: u64) -> Result<()> {
+    **info.try_borrow_mut_lamports()? = info
+        .lamports()
+        .checked_sub(penalty)
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        .checked_add(reward)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(())
+}
+
+/// Settles every seat on the jury once `verdict` is in, releasing each juror's
+/// bond.
+///
+/// Jurors who voted for the verdict are the majority. Those who voted against
+/// it each lose `JUROR_PENALTY_BPS` of their stake, split evenly between the
+/// majority, as do those who never voted once voting has closed. A juror who
+/// hadn't voted when an early majority decided is neither. With nobody in the
+/// majority no stake moves. `accounts` holds the juror PDA of each seat, in
+/// seat order.
+pub fn settle(
+    challenge_key: &Pubkey,
+    dispute: &Dispute,
+    verdict: &JuryVote,
+    accounts: &[AccountInfo],
+    timestamp: i64,
+) -> Result<()> {
+    require!(
+        accounts.len() == dispute.jury.len(),
+        ErrorCode::InvalidJurorAccounts
+    );
+    let jurors = dispute
+        .jury
+        .iter()
+        .zip(accounts)
+        .map(|(seat, info)| load(info, &seat.juror))
+        .collect::<Result<Vec<_>>>()?;
+    let voting_closed = timestamp >= dispute.jury_deadline()?;
+    let in_majority = |seat: &JurySeat| seat.vote.as_ref() == Some(verdict);
+    // A missing vote only counts against the juror once voting has closed
+    let in_minority = |seat: &JurySeat| match &seat.vote {
+        Some(vote) => vote != verdict,
+        None => voting_closed,
+    };
+    let majority = dispute.jury.iter().filter(|seat| in_majority(seat)).count() as u64;
+
+    let mut penalties = vec![0; jurors.len()];
+    if majority > 0 {
+        for ((seat, juror), penalty) in dispute.jury.iter().zip(&jurors).zip(&mut penalties) {
+            if in_minority(seat) {
+                *penalty = fees::juror_penalty(juror.stake)?;
+            }
+        }
+    }
+    let payout = match majority {
+        0 => None,
+        majority => Some(fees::jury_payout(penalties.iter().sum(), majority)?),
+    };
+
+    let mut first_in_majority = true;
+    for (((seat, mut juror), info), penalty) in dispute
+        .jury
+        .iter()
+        .zip(jurors)
+        .zip(accounts)
+        .zip(penalties)
+    {
+        let majority_vote = in_majority(seat);
+        let reward = match (&payout, majority_vote) {
+            (Some(payout), true) if first_in_majority => {
+                first_in_majority = false;
+                payout.share
+                    .checked_add(payout.remainder)
+                    .ok_or(ErrorCode::ArithmeticOverflow)?
+            }
+            (Some(payout), true) => payout.share,
+            _ => 0,
+        };
+
+        juror.stake = juror.stake
+            .checked_sub(penalty)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .checked_add(reward)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        juror.cases = juror.cases
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        if majority_vote {
+            juror.majority_votes = juror.majority_votes
+                .checked_add(1)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+        } else if in_minority(seat) {
+            juror.minority_votes = juror.minority_votes
+                .checked_add(1)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+        }
+        juror.pending_verdicts = juror.pending_verdicts
+            .checked_sub(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        // Juror accounts are program-owned, so stake moves without a CPI
+        move_lamports(info, penalty, reward)?;
+        juror.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        emit!(JurorSettled {
+            challenge: *challenge_key,
+            dispute_index: dispute.index,
+            juror: seat.juror,
+            in_majority: majority_vote,
+            penalty,
+            reward,
+            stake: juror.stake,
+            timestamp,
+        });
+    }
+    Ok(())
+}
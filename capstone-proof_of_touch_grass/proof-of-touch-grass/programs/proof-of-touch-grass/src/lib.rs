@@ -2,10 +2,18 @@ use anchor_lang::prelude::*;
 
 declare_id!("5RR71WDjqBEwcuNT5AALw5y3nXFKL1sDJT3SHpkQmjm2");
 
+pub mod badge;
 pub mod constants;
 pub mod errors;
 pub mod events;
+pub mod fees;
+pub mod geo;
+pub mod grass;
+pub mod inbox;
 pub mod instructions;
+pub mod jury;
+pub mod pda;
+pub mod reputation;
 pub mod state;
 
 use instructions::*;
@@ -19,9 +27,16 @@ pub mod proof_of_touch_grass {
         instructions::initialize_user::initialize_user(ctx)
     }
 
+    /// Sets the caller's own limits on open challenges, stake and post-failure cooldown
+    pub fn set_user_limits(ctx: Context<SetUserLimits>, limits: state::UserLimits) -> Result<()> {
+        instructions::set_user_limits::set_user_limits(ctx, limits)
+    }
+
     /// Creates a challenge with stake and parameters
-    pub fn create_challenge(
-        ctx: Context<CreateChallenge>,
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_challenge<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateChallenge<'info>>,
+        challenge_id: u64,
         title: String,
         description: String,
         stake_amount: u64,
@@ -29,11 +44,17 @@ pub mod proof_of_touch_grass {
         end_time: i64,
         verification_period: i64,
         required_proofs: u8,
-        required_approvals: u8,
+        required_approval_weight: u8,
         verifiers: Vec<Pubkey>,
+        verifier_weights: Vec<u8>,
+        geofences: Vec<state::Geofence>,
+        submission_rules: state::SubmissionRules,
+        verification_rules: state::VerificationRules,
+        min_verifier_reputation: Option<u32>,
     ) -> Result<()> {
         instructions::create_challenge::create_challenge(
             ctx,
+            challenge_id,
             title,
             description,
             stake_amount,
@@ -41,8 +62,79 @@ pub mod proof_of_touch_grass {
             end_time,
             verification_period,
             required_proofs,
-            required_approvals,
+            required_approval_weight,
             verifiers,
+            verifier_weights,
+            geofences,
+            submission_rules,
+            verification_rules,
+            min_verifier_reputation,
+        )
+    }
+
+    /// Creates a rematch of a settled failed challenge, at the same or a higher stake
+    pub fn retry_challenge<'info>(
+        ctx: Context<'_, '_, '_, 'info, RetryChallenge<'info>>,
+        challenge_id: u64,
+        stake_amount: u64,
+        start_time: i64,
+    ) -> Result<()> {
+        instructions::retry_challenge::retry_challenge(ctx, challenge_id, stake_amount, start_time)
+    }
+
+    /// Publishes reusable challenge terms that anyone can create challenges from
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_template(
+        ctx: Context<CreateTemplate>,
+        template_id: u64,
+        title: String,
+        description: String,
+        duration: i64,
+        verification_period: i64,
+        required_proofs: u8,
+        required_approval_weight: u8,
+        geofences: Vec<state::Geofence>,
+        submission_rules: state::SubmissionRules,
+        verification_rules: state::VerificationRules,
+        min_verifier_reputation: Option<u32>,
+    ) -> Result<()> {
+        instructions::create_template::create_template(
+            ctx,
+            template_id,
+            title,
+            description,
+            duration,
+            verification_period,
+            required_proofs,
+            required_approval_weight,
+            geofences,
+            submission_rules,
+            verification_rules,
+            min_verifier_reputation,
+        )
+    }
+
+    /// Admin-only: Marks a template as curated or withdraws the mark
+    pub fn curate_template(ctx: Context<CurateTemplate>, curated: bool) -> Result<()> {
+        instructions::curate_template::curate_template(ctx, curated)
+    }
+
+    /// Creates a challenge from a template, given only stake, start time and verifiers
+    pub fn create_challenge_from_template<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateChallengeFromTemplate<'info>>,
+        challenge_id: u64,
+        stake_amount: u64,
+        start_time: i64,
+        verifiers: Vec<Pubkey>,
+        verifier_weights: Vec<u8>,
+    ) -> Result<()> {
+        instructions::create_challenge_from_template::create_challenge_from_template(
+            ctx,
+            challenge_id,
+            stake_amount,
+            start_time,
+            verifiers,
+            verifier_weights,
         )
     }
 
@@ -56,16 +148,43 @@ pub mod proof_of_touch_grass {
         ctx: Context<SubmitEvidence>,
         ipfs_hash: String,
         metadata: String,
+        location: Option<state::GeoPoint>,
     ) -> Result<()> {
-        instructions::submit_evidence::submit_evidence(ctx, ipfs_hash, metadata)
+        instructions::submit_evidence::submit_evidence(ctx, ipfs_hash, metadata, location)
     }
 
-    /// Verifiers vote to approve or reject evidence
+    /// Swaps the content of submitted evidence before verification starts
+    pub fn replace_evidence(
+        ctx: Context<ReplaceEvidence>,
+        evidence_index: u8,
+        ipfs_hash: String,
+        metadata: String,
+        location: Option<state::GeoPoint>,
+    ) -> Result<()> {
+        instructions::replace_evidence::replace_evidence(ctx, evidence_index, ipfs_hash, metadata, location)
+    }
+
+    /// Removes the most recently submitted evidence before verification starts
+    pub fn retract_evidence(ctx: Context<RetractEvidence>) -> Result<()> {
+        instructions::retract_evidence::retract_evidence(ctx)
+    }
+
+    /// Verifiers vote to approve, reject or abstain on evidence
     pub fn verify_evidence(
         ctx: Context<VerifyEvidence>,
         vote: state::Vote,
+        justification: Option<state::Justification>,
+    ) -> Result<()> {
+        instructions::verify_evidence::verify_evidence(ctx, vote, justification)
+    }
+
+    /// Verifier revises their vote before the challenge is finalized
+    pub fn update_vote(
+        ctx: Context<UpdateVote>,
+        vote: state::Vote,
+        justification: Option<state::Justification>,
     ) -> Result<()> {
-        instructions::verify_evidence::verify_evidence(ctx, vote)
+        instructions::update_vote::update_vote(ctx, vote, justification)
     }
 
     /// Creator cancels challenge before completion
@@ -73,6 +192,21 @@ pub mod proof_of_touch_grass {
         instructions::cancel_challenge::cancel_challenge(ctx)
     }
 
+    /// Creator proposes a later end time for verifiers to approve
+    pub fn request_extension(ctx: Context<RequestExtension>, new_end_time: i64) -> Result<()> {
+        instructions::request_extension::request_extension(ctx, new_end_time)
+    }
+
+    /// Verifier approves the pending extension, applying it once enough have
+    pub fn approve_extension(ctx: Context<ApproveExtension>) -> Result<()> {
+        instructions::approve_extension::approve_extension(ctx)
+    }
+
+    /// Creator withdraws a pending extension and gets the held fee back
+    pub fn withdraw_extension(ctx: Context<WithdrawExtension>) -> Result<()> {
+        instructions::withdraw_extension::withdraw_extension(ctx)
+    }
+
     /// Disputes verification result within dispute window
     pub fn dispute_verification(
         ctx: Context<DisputeVerification>,
@@ -81,8 +215,55 @@ pub mod proof_of_touch_grass {
         instructions::dispute_verification::dispute_verification(ctx, reason)
     }
 
+    /// Admin-only: Upholds or overturns a disputed outcome
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, overturn: bool) -> Result<()> {
+        instructions::resolve_dispute::resolve_dispute(ctx, overturn)
+    }
+
+    /// Stakes lamports to join the juror pool
+    pub fn register_juror(ctx: Context<RegisterJuror>, stake: u64) -> Result<()> {
+        instructions::register_juror::register_juror(ctx, stake)
+    }
+
+    /// Leaves the juror pool, starting the unbonding period
+    pub fn unregister_juror(ctx: Context<UnregisterJuror>) -> Result<()> {
+        instructions::unregister_juror::unregister_juror(ctx)
+    }
+
+    /// Returns an unbonded juror's stake and closes their juror account
+    pub fn withdraw_juror_stake(ctx: Context<WithdrawJurorStake>) -> Result<()> {
+        instructions::withdraw_juror_stake::withdraw_juror_stake(ctx)
+    }
+
+    /// Hands a dispute to a jury drawn from the juror pool (disputer or admin)
+    pub fn escalate_dispute<'info>(
+        ctx: Context<'_, '_, '_, 'info, EscalateDispute<'info>>,
+    ) -> Result<()> {
+        instructions::escalate_dispute::escalate_dispute(ctx)
+    }
+
+    /// A juror votes to uphold or overturn an escalated dispute
+    pub fn cast_jury_vote(ctx: Context<CastJuryVote>, vote: state::JuryVote) -> Result<()> {
+        instructions::cast_jury_vote::cast_jury_vote(ctx, vote)
+    }
+
+    /// Applies a jury's verdict and settles the jurors' stakes
+    pub fn settle_jury(ctx: Context<SettleJury>) -> Result<()> {
+        instructions::settle_jury::settle_jury(ctx)
+    }
+
     /// Claim funds after challenge finalization (creator or verifiers)
     pub fn claim_funds(ctx: Context<ClaimFunds>) -> Result<()> {
         instructions::claim_funds::claim_funds(ctx)
     }
+
+    /// Admin-only: Creates the GRASS mint on first use and sets its emission parameters
+    pub fn configure_grass(ctx: Context<ConfigureGrass>, emission: state::GrassEmission) -> Result<()> {
+        instructions::configure_grass::configure_grass(ctx, emission)
+    }
+
+    /// Admin-only: Sets the platform ceilings on every user's limits
+    pub fn set_platform_limits(ctx: Context<SetPlatformLimits>, limits: state::PlatformLimits) -> Result<()> {
+        instructions::set_platform_limits::set_platform_limits(ctx, limits)
+    }
 }
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{
+    allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+};
+
+/// Creates the program account at `space`, the way anchor's `init` does,
+/// including when someone has already sent lamports to the address
+pub(crate) fn create<'info>(
+    info: &AccountInfo<'info>,
+    space: usize,
+    seeds: &[&[u8]],
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    create_for(info, space, space, &crate::ID, seeds, payer, system_program)
+}
+
+/// Creates the PDA at `space` for `owner`, funded for `funded_space` so the
+/// owner can grow it without a top-up
+pub(crate) fn create_for<'info>(
+    info: &AccountInfo<'info>,
+    space: usize,
+    funded_space: usize,
+    owner: &Pubkey,
+    seeds: &[&[u8]],
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(funded_space);
+    let signer_seeds = &[seeds];
+    if info.lamports() == 0 {
+        return create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount {
+                    from: payer.clone(),
+                    to: info.clone(),
+                },
+                signer_seeds,
+            ),
+            rent,
+            space as u64,
+            owner,
+        );
+    }
+    let top_up = rent.saturating_sub(info.lamports());
+    if top_up > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: info.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate {
+                account_to_allocate: info.clone(),
+            },
+            signer_seeds,
+        ),
+        space as u64,
+    )?;
+    assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Assign {
+                account_to_assign: info.clone(),
+            },
+            signer_seeds,
+        ),
+        owner,
+    )
+}
@@ -0,0 +1,172 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::pda;
+use crate::state::*;
+
+/// Reads the reputation account passed for `verifier`, `None` if it was never created
+pub fn load(info: &AccountInfo, verifier: &Pubkey) -> Result<Option<VerifierReputation>> {
+    let (expected, _) =
+        Pubkey::find_program_address(&[REPUTATION_SEED, verifier.as_ref()], &crate::ID);
+    require_keys_eq!(info.key(), expected, ErrorCode::InvalidReputationAccounts);
+    if info.owner != &crate::ID {
+        return Ok(None);
+    }
+    let data = info.try_borrow_data()?;
+    Ok(Some(VerifierReputation::try_deserialize(&mut &data[..])?))
+}
+
+/// Creates the reputation record of every verifier of a new challenge that
+/// does not have one yet, so settling the challenge always has a record to
+/// count a missed window against.
+///
+/// `accounts` holds the reputation PDA of each verifier, in order.
+pub fn open_records<'info>(
+    verifiers: &[Pubkey],
+    accounts: &[AccountInfo<'info>],
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    require!(
+        accounts.len() == verifiers.len(),
+        ErrorCode::InvalidReputationAccounts
+    );
+    for (verifier, info) in verifiers.iter().zip(accounts) {
+        let (expected, bump) =
+            Pubkey::find_program_address(&[REPUTATION_SEED, verifier.as_ref()], &crate::ID);
+        require_keys_eq!(info.key(), expected, ErrorCode::InvalidReputationAccounts);
+        if info.owner == &crate::ID {
+            continue;
+        }
+        require!(info.is_writable, ErrorCode::InvalidReputationAccounts);
+        let seeds: &[&[u8]] = &[REPUTATION_SEED, verifier.as_ref(), &[bump]];
+        pda::create(
+            info,
+            8 + VerifierReputation::INIT_SPACE,
+            seeds,
+            payer,
+            system_program,
+        )?;
+        let reputation = VerifierReputation {
+            verifier: *verifier,
+            votes_cast: 0,
+            votes_won: 0,
+            votes_overturned: 0,
+            missed_windows: 0,
+            bump,
+        };
+        reputation.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+    }
+    Ok(())
+}
+
+/// Vote recorded by `verifier` on the challenge, `None` if they never voted
+fn load_vote(info: &AccountInfo, challenge: &Pubkey, verifier: &Pubkey) -> Result<Option<Vote>> {
+    let (expected, _) = Pubkey::find_program_address(
+        &[VERIFICATION_SEED, challenge.as_ref(), verifier.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(info.key(), expected, ErrorCode::InvalidReputationAccounts);
+    if info.owner != &crate::ID {
+        return Ok(None);
+    }
+    let data = info.try_borrow_data()?;
+    Ok(Some(Verification::try_deserialize(&mut &data[..])?.vote))
+}
+
+fn increment(counter: &mut u32) -> Result<bool> {
+    *counter = counter.checked_add(1).ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(true)
+}
+
+pub fn emit_updated(reputation: &VerifierReputation, timestamp: i64) {
+    emit!(VerifierReputationUpdated {
+        verifier: reputation.verifier,
+        votes_cast: reputation.votes_cast,
+        votes_won: reputation.votes_won,
+        votes_overturned: reputation.votes_overturned,
+        missed_windows: reputation.missed_windows,
+        timestamp,
+    });
+}
+
+/// Applies `update` to the reputation of every listed verifier, writing back
+/// the ones it reports as changed.
+///
+/// `accounts` holds a `[verification, reputation]` pair per verifier, in the
+/// challenge's verifier order. Every record exists, as opening the challenge
+/// created the missing ones.
+fn for_each_verifier(
+    challenge_key: &Pubkey,
+    challenge: &Challenge,
+    accounts: &[AccountInfo],
+    timestamp: i64,
+    mut update: impl FnMut(Option<Vote>, &mut VerifierReputation) -> Result<bool>,
+) -> Result<()> {
+    require!(
+        accounts.len() == challenge.verifiers.len() * 2,
+        ErrorCode::InvalidReputationAccounts
+    );
+    for (verifier, pair) in challenge.verifiers.iter().zip(accounts.chunks(2)) {
+        let vote = load_vote(&pair[0], challenge_key, verifier)?;
+        let mut reputation =
+            load(&pair[1], verifier)?.ok_or(ErrorCode::InvalidReputationAccounts)?;
+        if !update(vote, &mut reputation)? {
+            continue;
+        }
+        require!(pair[1].is_writable, ErrorCode::InvalidReputationAccounts);
+        reputation.try_serialize(&mut &mut pair[1].try_borrow_mut_data()?[..])?;
+        emit_updated(&reputation, timestamp);
+    }
+    Ok(())
+}
+
+/// Credits verifiers whose vote matches the settled outcome, and records a
+/// missed window for those who let a verification period run out. A refund
+/// settles no outcome, so no vote wins.
+pub fn settle(
+    challenge_key: &Pubkey,
+    challenge: &Challenge,
+    accounts: &[AccountInfo],
+    timestamp: i64,
+) -> Result<()> {
+    let winning_vote = match challenge.status {
+        ChallengeStatus::Completed => Some(Vote::Approve),
+        ChallengeStatus::Failed => Some(Vote::Reject),
+        ChallengeStatus::Refunded => None,
+        _ => return err!(ErrorCode::InvalidChallengeStatus),
+    };
+    // Early finalization or missing evidence cut the window short, so staying
+    // silent only counts as missed when the period actually ran out
+    let window_ran_out = challenge.evidence_count >= challenge.required_proofs
+        && challenge.finalized_at > challenge.verification_period_end;
+
+    for_each_verifier(challenge_key, challenge, accounts, timestamp, |vote, reputation| {
+        match vote {
+            Some(vote) if Some(&vote) == winning_vote.as_ref() => {
+                increment(&mut reputation.votes_won)
+            }
+            None if window_ran_out => increment(&mut reputation.missed_windows),
+            _ => Ok(false),
+        }
+    })
+}
+
+/// Records an overturned vote for every verifier who voted `overturned_vote`
+pub fn record_overturned(
+    challenge_key: &Pubkey,
+    challenge: &Challenge,
+    overturned_vote: Vote,
+    accounts: &[AccountInfo],
+    timestamp: i64,
+) -> Result<()> {
+    for_each_verifier(challenge_key, challenge, accounts, timestamp, |vote, reputation| {
+        if vote.as_ref() == Some(&overturned_vote) {
+            increment(&mut reputation.votes_overturned)
+        } else {
+            Ok(false)
+        }
+    })
+}
@@ -1,5 +1,10 @@
 use anchor_lang::prelude::*;
+use std::fmt;
+
 use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::fees;
+use crate::geo;
 
 /// User profile and statistics
 #[account]
@@ -9,37 +14,486 @@ pub struct User {
     pub total_challenges: u32,
     pub completed: u32,
     pub failed: u32,
+    pub cancelled: u32,
+    /// Challenges whose verification timed out under a refund policy
+    pub refunded: u32,
+    /// Challenges created as a retry of a failed one
+    pub retries: u32,
+    /// Retries that completed
+    pub redemptions: u32,
+    /// Challenges created but not yet completed, failed or cancelled
+    pub active_challenges: u32,
+    /// Consecutive completions since the last failure
+    pub current_streak: u32,
+    pub longest_streak: u32,
     pub total_staked: u64,
+    /// Creator bonuses earned on completed challenges
+    pub total_won: u64,
+    /// Stake slashed on failed challenges
+    pub total_lost: u64,
+    /// Penalties paid for cancelling active challenges
+    pub total_forfeited: u64,
+    /// Stake locked in challenges that are still open
+    pub staked_at_risk: u64,
+    pub limits: UserLimits,
+    /// Looser limits waiting out `LIMITS_LOOSENING_DELAY`
+    pub pending_limits: Option<UserLimits>,
+    /// When `pending_limits` take over from `limits`
+    pub pending_limits_at: i64,
+    /// No new challenges before this time, set by `limits.failure_cooldown`
+    pub cooldown_until: i64,
+    pub bump: u8,
+}
+
+impl User {
+    /// Completed share of finalized challenges, in basis points (0 before any)
+    pub fn success_rate_bps(&self) -> u64 {
+        let finalized = u64::from(self.completed) + u64::from(self.failed);
+        if finalized == 0 {
+            return 0;
+        }
+        u64::from(self.completed) * BASIS_POINTS / finalized
+    }
+
+    /// The limits in force at `now`: scheduled ones count once their delay has passed
+    pub fn limits_at(&self, now: i64) -> &UserLimits {
+        match &self.pending_limits {
+            Some(pending) if now >= self.pending_limits_at => pending,
+            _ => &self.limits,
+        }
+    }
+
+    /// Replaces the user's limits. Whatever tightens the current limits
+    /// applies at once; the full set, if it loosens anything, is scheduled to
+    /// take over after `LIMITS_LOOSENING_DELAY` and replaces any set already
+    /// waiting. Returns the time it takes over, if scheduled.
+    pub fn set_limits(&mut self, limits: UserLimits, now: i64) -> Result<Option<i64>> {
+        self.limits = self.limits_at(now).clone();
+        self.limits = limits.strictest(&self.limits);
+        if self.limits == limits {
+            self.pending_limits = None;
+            self.pending_limits_at = 0;
+            return Ok(None);
+        }
+        self.pending_limits = Some(limits);
+        self.pending_limits_at = now
+            .checked_add(LIMITS_LOOSENING_DELAY)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(Some(self.pending_limits_at))
+    }
+
+    /// Checks a new challenge against the user's own limits and the platform
+    /// ceilings, whichever is lower
+    pub fn check_new_challenge(
+        &self,
+        platform: &PlatformLimits,
+        stake_amount: u64,
+        now: i64,
+    ) -> Result<()> {
+        let limits = self.limits_at(now);
+        require!(now >= self.cooldown_until, ErrorCode::FailureCooldownActive);
+        require!(
+            self.active_challenges
+                < limits.max_active_challenges.map_or(platform.max_active_challenges, |max| {
+                    max.min(platform.max_active_challenges)
+                }),
+            ErrorCode::TooManyActiveChallenges
+        );
+        require!(
+            stake_amount <= limits.max_stake.map_or(platform.max_stake, |max| max.min(platform.max_stake)),
+            ErrorCode::StakeAboveLimit
+        );
+        let at_risk = self.staked_at_risk
+            .checked_add(stake_amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(
+            at_risk
+                <= limits.max_stake_at_risk.map_or(platform.max_stake_at_risk, |max| {
+                    max.min(platform.max_stake_at_risk)
+                }),
+            ErrorCode::StakeAtRiskAboveLimit
+        );
+        Ok(())
+    }
+
+    fn record_completed(&mut self, challenge: &Challenge) -> Result<()> {
+        let stake_amount = challenge.stake_amount;
+        self.completed = increment(self.completed)?;
+        if challenge.previous_challenge.is_some() {
+            self.redemptions = increment(self.redemptions)?;
+        }
+        self.current_streak = increment(self.current_streak)?;
+        self.longest_streak = self.longest_streak.max(self.current_streak);
+        self.total_won = self.total_won
+            .checked_add(fees::creator_bonus(stake_amount, challenge.fee_paid_in_grass)?)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    fn record_failed(&mut self, stake_amount: u64, rejection_weight: u8, timestamp: i64) -> Result<()> {
+        self.failed = increment(self.failed)?;
+        self.current_streak = 0;
+        if let Some(cooldown) = self.limits_at(timestamp).failure_cooldown {
+            self.cooldown_until = timestamp
+                .checked_add(cooldown)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+        }
+        self.total_lost = self.total_lost
+            .checked_add(fees::slashed_stake(stake_amount, rejection_weight)?)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Swaps a challenge's recorded outcome for the one a dispute resolution
+    /// settled on. The streak can only be adjusted from its current value, so
+    /// reversing an older outcome treats it as the most recent one.
+    pub fn reverse_outcome(
+        &mut self,
+        challenge: &Challenge,
+        to: &ChallengeStatus,
+        timestamp: i64,
+    ) -> Result<()> {
+        let stake_amount = challenge.stake_amount;
+        match to {
+            ChallengeStatus::Completed => {
+                self.failed = self.failed.checked_sub(1).ok_or(ErrorCode::ArithmeticOverflow)?;
+                self.total_lost = self.total_lost
+                    .checked_sub(fees::slashed_stake(stake_amount, challenge.rejection_weight)?)
+                    .ok_or(ErrorCode::ArithmeticOverflow)?;
+                self.record_completed(challenge)
+            }
+            ChallengeStatus::Failed => {
+                self.completed = self.completed
+                    .checked_sub(1)
+                    .ok_or(ErrorCode::ArithmeticOverflow)?;
+                self.total_won = self.total_won
+                    .checked_sub(fees::creator_bonus(stake_amount, challenge.fee_paid_in_grass)?)
+                    .ok_or(ErrorCode::ArithmeticOverflow)?;
+                if challenge.previous_challenge.is_some() {
+                    self.redemptions = self.redemptions
+                        .checked_sub(1)
+                        .ok_or(ErrorCode::ArithmeticOverflow)?;
+                }
+                self.record_failed(stake_amount, challenge.rejection_weight, timestamp)
+            }
+            _ => err!(ErrorCode::InvalidChallengeStatus),
+        }
+    }
+}
+
+/// Limits a user sets on themselves; `None` leaves the platform ceiling in place.
+/// Tightening applies at once, loosening after `LIMITS_LOOSENING_DELAY`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct UserLimits {
+    pub max_active_challenges: Option<u32>,
+    /// Largest stake for a single challenge, in lamports
+    pub max_stake: Option<u64>,
+    /// Largest total stake across open challenges, in lamports
+    pub max_stake_at_risk: Option<u64>,
+    /// Seconds after a failed challenge before another can be created
+    pub failure_cooldown: Option<i64>,
+}
+
+impl UserLimits {
+    pub fn is_valid(&self, platform: &PlatformLimits) -> bool {
+        self.max_active_challenges
+            .is_none_or(|max| max > 0 && max <= platform.max_active_challenges)
+            && self.max_stake.is_none_or(|max| max > 0 && max <= platform.max_stake)
+            && self.max_stake_at_risk.is_none_or(|max| max > 0 && max <= platform.max_stake_at_risk)
+            && self.failure_cooldown
+                .is_none_or(|cooldown| cooldown > 0 && cooldown <= platform.max_failure_cooldown)
+    }
+
+    /// Field by field, the stricter of these limits and `other`
+    pub fn strictest(&self, other: &UserLimits) -> UserLimits {
+        fn lower<T: Ord>(a: Option<T>, b: Option<T>) -> Option<T> {
+            match (a, b) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            }
+        }
+        UserLimits {
+            max_active_challenges: lower(self.max_active_challenges, other.max_active_challenges),
+            max_stake: lower(self.max_stake, other.max_stake),
+            max_stake_at_risk: lower(self.max_stake_at_risk, other.max_stake_at_risk),
+            failure_cooldown: match (self.failure_cooldown, other.failure_cooldown) {
+                (Some(a), Some(b)) => Some(a.max(b)),
+                (a, b) => a.or(b),
+            },
+        }
+    }
+}
+
+/// Ceilings on every user's open challenges, stake and failure cooldown
+#[account]
+#[derive(InitSpace)]
+pub struct PlatformConfig {
+    pub limits: PlatformLimits,
+    pub bump: u8,
+}
+
+impl PlatformConfig {
+    /// The ceilings stored at the config PDA, or the defaults until the admin
+    /// has set any. The caller checks the account's address.
+    pub fn limits(account: &AccountInfo) -> Result<PlatformLimits> {
+        if account.data_is_empty() {
+            return Ok(PlatformLimits::default());
+        }
+        require_keys_eq!(
+            *account.owner,
+            crate::ID,
+            anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram
+        );
+        let data = account.try_borrow_data()?;
+        Ok(PlatformConfig::try_deserialize(&mut &data[..])?.limits)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct PlatformLimits {
+    pub max_active_challenges: u32,
+    /// Largest stake for a single challenge, in lamports
+    pub max_stake: u64,
+    /// Largest total stake across a user's open challenges, in lamports
+    pub max_stake_at_risk: u64,
+    /// Longest failure cooldown a user can set, in seconds
+    pub max_failure_cooldown: i64,
+}
+
+impl Default for PlatformLimits {
+    fn default() -> Self {
+        Self {
+            max_active_challenges: DEFAULT_MAX_ACTIVE_CHALLENGES,
+            max_stake: DEFAULT_MAX_STAKE_AMOUNT,
+            max_stake_at_risk: DEFAULT_MAX_STAKE_AT_RISK,
+            max_failure_cooldown: DEFAULT_MAX_FAILURE_COOLDOWN,
+        }
+    }
+}
+
+impl PlatformLimits {
+    pub fn is_valid(&self) -> bool {
+        self.max_active_challenges > 0
+            && self.max_stake > 0
+            && self.max_stake_at_risk > 0
+            && self.max_failure_cooldown > 0
+    }
+}
+
+fn increment(counter: u32) -> Result<u32> {
+    counter.checked_add(1).ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))
+}
+
+/// IDs of every challenge a user has created, in creation order
+#[account]
+pub struct ChallengeIndex {
+    pub authority: Pubkey,
+    pub challenge_ids: Vec<u64>,
     pub bump: u8,
 }
 
+impl ChallengeIndex {
+    /// Account size, discriminator included, when holding `count` IDs
+    pub fn space(count: usize) -> usize {
+        8 + 32 + 4 + count * 8 + 1
+    }
+}
+
 /// Challenge with stake parameters
 #[account]
 #[derive(InitSpace)]
 pub struct Challenge {
     pub creator: Pubkey,
+    /// Creator-chosen ID, unique per creator and part of the PDA seeds
+    pub challenge_id: u64,
+    /// The failed challenge this one retries
+    pub previous_challenge: Option<Pubkey>,
+    /// The template this challenge was created from
+    pub template: Option<Pubkey>,
     #[max_len(MAX_TITLE_LEN)]
     pub title: String,
     #[max_len(MAX_DESCRIPTION_LEN)]
     pub description: String,
     pub stake_amount: u64,
+    /// The platform fee was burned in GRASS, so the escrow holds only the stake
+    pub fee_paid_in_grass: bool,
     pub start_time: i64,
     pub end_time: i64,
     pub verification_period_end: i64,
     pub required_proofs: u8,
-    pub required_approvals: u8,
+    /// Total weight of approving verifiers needed to complete
+    pub required_approval_weight: u8,
     #[max_len(MAX_VERIFIERS)]
     pub verifiers: Vec<Pubkey>,
+    /// How much each verifier's vote counts, in `verifiers` order
+    #[max_len(MAX_VERIFIERS)]
+    pub verifier_weights: Vec<u8>,
+    /// Minimum reputation score each verifier needed, checked again on retry
+    pub min_verifier_reputation: Option<u32>,
+    #[max_len(MAX_GEOFENCES)]
+    pub geofences: Vec<Geofence>,
+    pub submission_rules: SubmissionRules,
+    pub verification_rules: VerificationRules,
     pub status: ChallengeStatus,
     pub evidence_count: u8,
+    pub last_evidence_at: i64,
+    pub submission_day: i64,
+    pub submissions_today: u8,
     pub approval_count: u8,
     pub rejection_count: u8,
+    /// Votes that count toward the quorum without backing either outcome
+    pub abstain_count: u8,
+    pub approval_weight: u8,
+    pub rejection_weight: u8,
     pub finalized_at: i64,
     pub claimed: bool,
+    /// Disputes filed so far; the latest is open while the status is Disputed
+    pub dispute_count: u8,
+    /// Parties who have filed a dispute, each allowed one
+    #[max_len(MAX_VERIFIERS + 1)]
+    pub disputers: Vec<Pubkey>,
+    /// When the latest dispute was resolved, 0 before any
+    pub dispute_resolved_at: i64,
+    /// The retry created after this challenge failed, at most one
+    pub retried_by: Option<Pubkey>,
     pub bump: u8,
     pub escrow_bump: u8,
 }
 
+impl Challenge {
+    /// Validates a reported location; geofenced challenges need one inside an allowed region
+    pub fn check_location(&self, location: Option<&GeoPoint>) -> Result<()> {
+        if let Some(point) = location {
+            require!(point.is_valid(), ErrorCode::InvalidCoordinates);
+        }
+        if !self.geofences.is_empty() {
+            let point = location.ok_or(ErrorCode::LocationRequired)?;
+            require!(
+                self.geofences.iter().any(|fence| fence.contains(point)),
+                ErrorCode::LocationOutsideGeofence
+            );
+        }
+        Ok(())
+    }
+
+    /// Moves the challenge to a terminal status and records the outcome on the
+    /// creator's profile. Every transition out of Created, Active or
+    /// PendingVerification goes through here, so each outcome is counted once.
+    pub fn finalize(&mut self, user: &mut User, status: ChallengeStatus, timestamp: i64) -> Result<()> {
+        match status {
+            ChallengeStatus::Completed => user.record_completed(self)?,
+            ChallengeStatus::Failed => {
+                user.record_failed(self.stake_amount, self.rejection_weight, timestamp)?
+            }
+            ChallengeStatus::Refunded => user.refunded = increment(user.refunded)?,
+            ChallengeStatus::Cancelled => {
+                let started = self.status == ChallengeStatus::Active;
+                user.cancelled = increment(user.cancelled)?;
+                user.total_forfeited = user.total_forfeited
+                    .checked_add(fees::cancel_payout(self.stake_amount, self.fee_paid_in_grass, started)?.penalty)
+                    .ok_or(ErrorCode::ArithmeticOverflow)?;
+            }
+            _ => return err!(ErrorCode::InvalidChallengeStatus),
+        }
+        user.active_challenges = user.active_challenges
+            .checked_sub(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        user.staked_at_risk = user.staked_at_risk
+            .checked_sub(self.stake_amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.status = status;
+        self.finalized_at = timestamp;
+        Ok(())
+    }
+
+    /// Last second a finalized challenge can be disputed. A resolved dispute
+    /// restarts the window, so the parties who haven't disputed yet can
+    /// still contest the outcome it settled on.
+    pub fn dispute_window_end(&self) -> Result<i64> {
+        self.finalized_at
+            .max(self.dispute_resolved_at)
+            .checked_add(DISPUTE_WINDOW)
+            .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))
+    }
+
+    /// Evidence may be revised while Active, or once all proofs are in but before any vote
+    pub fn evidence_editable(&self) -> bool {
+        match self.status {
+            ChallengeStatus::Active => true,
+            ChallengeStatus::PendingVerification => self.votes_cast() == 0,
+            _ => false,
+        }
+    }
+
+    /// Every vote on the challenge, abstentions included
+    pub fn votes_cast(&self) -> u8 {
+        self.approval_count
+            .saturating_add(self.rejection_count)
+            .saturating_add(self.abstain_count)
+    }
+
+    /// Vote weight of a listed verifier
+    pub fn weight_of(&self, verifier: &Pubkey) -> Result<u8> {
+        let index = self
+            .verifiers
+            .iter()
+            .position(|listed| listed == verifier)
+            .ok_or(ErrorCode::UnauthorizedVerifier)?;
+        Ok(self.verifier_weights[index])
+    }
+
+    pub fn total_weight(&self) -> u8 {
+        self.verifier_weights.iter().fold(0, |total, weight| total.saturating_add(*weight))
+    }
+
+    fn vote_tally(&mut self, vote: &Vote) -> (&mut u8, Option<&mut u8>) {
+        match vote {
+            Vote::Approve => (&mut self.approval_count, Some(&mut self.approval_weight)),
+            Vote::Reject => (&mut self.rejection_count, Some(&mut self.rejection_weight)),
+            Vote::Abstain => (&mut self.abstain_count, None),
+        }
+    }
+
+    /// Adds a vote of `weight` to the tallies
+    pub fn count_vote(&mut self, vote: &Vote, weight: u8) -> Result<()> {
+        let (count, tally) = self.vote_tally(vote);
+        *count = count.checked_add(1).ok_or(ErrorCode::ArithmeticOverflow)?;
+        if let Some(tally) = tally {
+            *tally = tally.checked_add(weight).ok_or(ErrorCode::ArithmeticOverflow)?;
+        }
+        Ok(())
+    }
+
+    /// Takes a vote of `weight` back off the tallies
+    pub fn uncount_vote(&mut self, vote: &Vote, weight: u8) -> Result<()> {
+        let (count, tally) = self.vote_tally(vote);
+        *count = count.checked_sub(1).ok_or(ErrorCode::ArithmeticOverflow)?;
+        if let Some(tally) = tally {
+            *tally = tally.checked_sub(weight).ok_or(ErrorCode::ArithmeticOverflow)?;
+        }
+        Ok(())
+    }
+
+    /// The outcome the votes so far settle on, if the quorum is in and either
+    /// weight threshold is met. Abstentions only count toward the quorum.
+    pub fn decided_outcome(&self) -> Result<Option<ChallengeStatus>> {
+        let max_possible_rejection_weight = self
+            .total_weight()
+            .checked_sub(self.required_approval_weight)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        if !self.verification_rules.quorum_reached(self.votes_cast()) {
+            return Ok(None);
+        }
+        Ok(if self.approval_weight >= self.required_approval_weight {
+            Some(ChallengeStatus::Completed)
+        } else if self.rejection_weight > max_possible_rejection_weight {
+            Some(ChallengeStatus::Failed)
+        } else {
+            None
+        })
+    }
+}
+
 /// Submitted evidence with metadata
 #[account]
 #[derive(InitSpace)]
@@ -49,8 +503,10 @@ pub struct Evidence {
     pub ipfs_hash: String,
     #[max_len(MAX_METADATA_LEN)]
     pub metadata: String,
+    pub location: Option<GeoPoint>,
     pub timestamp: i64,
     pub evidence_index: u8,
+    pub revision: u8,
     pub bump: u8,
 }
 
@@ -61,25 +517,266 @@ pub struct Verification {
     pub challenge: Pubkey,
     pub verifier: Pubkey,
     pub vote: Vote,
+    /// Why the verifier voted this way, replaced when the vote changes
+    pub justification: Option<Justification>,
     pub timestamp: i64,
     pub claimed: bool,
     pub bump: u8,
 }
 
-/// Dispute filed against verification
+/// A verifier's reasons for a vote
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct Justification {
+    #[max_len(MAX_JUSTIFICATION_LEN)]
+    pub reason: String,
+    /// Content hash of supporting material, such as an annotated screenshot
+    #[max_len(MAX_IPFS_HASH_LEN)]
+    pub attachment: Option<String>,
+}
+
+impl Justification {
+    pub fn is_valid(&self) -> bool {
+        self.reason.len() <= MAX_JUSTIFICATION_LEN
+            && self
+                .attachment
+                .as_ref()
+                .is_none_or(|hash| hash.len() <= MAX_IPFS_HASH_LEN)
+    }
+}
+
+/// Dispute filed against verification. A challenge can be disputed again
+/// once its open dispute is resolved; each dispute has its own index.
 #[account]
 #[derive(InitSpace)]
 pub struct Dispute {
     pub challenge: Pubkey,
+    /// Position among the challenge's disputes, from 0
+    pub index: u8,
     pub disputer: Pubkey,
+    /// Lamports held on this account on top of its rent until resolution
+    pub bond: u64,
     #[max_len(MAX_DISPUTE_REASON_LEN)]
     pub reason: String,
+    /// The verification whose vote the disputer contests, if they name one
+    pub contested_verification: Option<Pubkey>,
+    /// Outcome under dispute, restored if the dispute is rejected
+    pub previous_status: ChallengeStatus,
     pub timestamp: i64,
+    /// When the dispute went to a jury, 0 while the admin can still resolve it
+    pub escalated_at: i64,
+    #[max_len(JURY_SIZE)]
+    pub jury: Vec<JurySeat>,
+    /// 0 while the dispute is open
+    pub resolved_at: i64,
+    pub bump: u8,
+}
+
+impl Dispute {
+    pub fn is_escalated(&self) -> bool {
+        self.escalated_at > 0
+    }
+
+    pub fn is_resolved(&self) -> bool {
+        self.resolved_at > 0
+    }
+
+    pub fn jury_deadline(&self) -> Result<i64> {
+        self.escalated_at
+            .checked_add(JURY_VOTING_PERIOD)
+            .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))
+    }
+
+    /// Jury votes cast so far, as `(uphold, overturn)`
+    pub fn jury_tally(&self) -> (u8, u8) {
+        self.jury.iter().fold((0, 0), |(uphold, overturn), seat| match seat.vote {
+            Some(JuryVote::Uphold) => (uphold + 1, overturn),
+            Some(JuryVote::Overturn) => (uphold, overturn + 1),
+            None => (uphold, overturn),
+        })
+    }
+
+    /// The jury's verdict once a side has a majority of the seats or the
+    /// voting period has run out, `None` while it is still open. Without a
+    /// majority of the votes cast the disputed outcome stands.
+    pub fn jury_verdict(&self, now: i64) -> Result<Option<JuryVote>> {
+        let (uphold, overturn) = self.jury_tally();
+        if overturn >= JURY_MAJORITY {
+            return Ok(Some(JuryVote::Overturn));
+        }
+        if uphold >= JURY_MAJORITY {
+            return Ok(Some(JuryVote::Uphold));
+        }
+        if now < self.jury_deadline()? {
+            return Ok(None);
+        }
+        Ok(Some(if overturn > uphold {
+            JuryVote::Overturn
+        } else {
+            JuryVote::Uphold
+        }))
+    }
+}
+
+/// A juror drawn for an escalated dispute and their vote, once cast
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct JurySeat {
+    pub juror: Pubkey,
+    pub vote: Option<JuryVote>,
+}
+
+/// A creator's pending proposal to move a challenge's end time, applied once
+/// the verifiers approving it carry `required_approval_weight`
+#[account]
+#[derive(InitSpace)]
+pub struct ExtensionRequest {
+    pub challenge: Pubkey,
+    pub new_end_time: i64,
+    /// Held in this account until the extension applies, refunded on withdrawal
+    pub fee: u64,
+    #[max_len(MAX_VERIFIERS)]
+    pub approvals: Vec<Pubkey>,
+    pub requested_at: i64,
+    pub bump: u8,
+}
+
+/// Reusable challenge terms. Anyone can publish one; the admin marks the
+/// ones it vouches for as curated.
+#[account]
+#[derive(InitSpace)]
+pub struct ChallengeTemplate {
+    pub author: Pubkey,
+    /// Author-chosen ID, unique per author and part of the PDA seeds
+    pub template_id: u64,
+    pub curated: bool,
+    #[max_len(MAX_TITLE_LEN)]
+    pub title: String,
+    #[max_len(MAX_DESCRIPTION_LEN)]
+    pub description: String,
+    /// Seconds from start to end
+    pub duration: i64,
+    pub verification_period: i64,
+    pub required_proofs: u8,
+    pub required_approval_weight: u8,
+    #[max_len(MAX_GEOFENCES)]
+    pub geofences: Vec<Geofence>,
+    pub submission_rules: SubmissionRules,
+    pub verification_rules: VerificationRules,
+    pub min_verifier_reputation: Option<u32>,
+    /// Challenges created from this template
+    pub uses: u32,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+/// Track record of a verifier across every challenge they were listed on
+#[account]
+#[derive(InitSpace)]
+pub struct VerifierReputation {
+    pub verifier: Pubkey,
+    pub votes_cast: u32,
+    /// Votes matching the outcome the challenge was settled with
+    pub votes_won: u32,
+    /// Votes on an outcome a dispute resolution reversed
+    pub votes_overturned: u32,
+    /// Verification periods that ran out without a vote from this verifier
+    pub missed_windows: u32,
+    pub bump: u8,
+}
+
+impl VerifierReputation {
+    /// Winning votes, less one for every overturned vote and missed window
+    pub fn score(&self) -> u32 {
+        self.votes_won
+            .saturating_sub(self.votes_overturned)
+            .saturating_sub(self.missed_windows)
+    }
+}
+
+/// A user staking to sit on juries for escalated disputes
+#[account]
+#[derive(InitSpace)]
+pub struct Juror {
+    pub authority: Pubkey,
+    /// Lamports held on this account on top of its rent, moved by jury settlements
+    pub stake: u64,
+    /// Juries this juror was drawn for whose verdict has not been settled yet
+    pub pending_verdicts: u8,
+    /// Settled juries this juror sat on
+    pub cases: u32,
+    pub majority_votes: u32,
+    /// Votes against the verdict, and seats still without a vote when voting closed
+    pub minority_votes: u32,
+    pub registered_at: i64,
+    /// When the stake can be withdrawn, 0 while the juror is in the pool
+    pub unbonding_at: i64,
+    pub bump: u8,
+}
+
+/// Every juror that can be drawn for a jury
+#[account]
+#[derive(InitSpace)]
+pub struct JurorPool {
+    #[max_len(MAX_JURORS)]
+    pub jurors: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+/// The GRASS mint and the parameters it is emitted and spent by
+#[account]
+#[derive(InitSpace)]
+pub struct GrassConfig {
+    /// The program-owned mint, whose mint authority is this account
+    pub mint: Pubkey,
+    pub emission: GrassEmission,
+    /// GRASS minted as rewards so far, in base units
+    pub total_emitted: u64,
+    pub bump: u8,
+    pub mint_bump: u8,
+}
+
+/// GRASS amounts are in base units (`GRASS_DECIMALS` places)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct GrassEmission {
+    /// Minted to the creator when a completed challenge is claimed
+    pub creator_reward: u64,
+    /// Minted to a verifier who voted for the final outcome, per unit of vote weight
+    pub verifier_reward: u64,
+    /// GRASS that stands in for one SOL of platform fee
+    pub grass_per_sol: u64,
+    /// Discount on platform fees paid in GRASS
+    pub fee_discount_bps: u64,
+}
+
+impl GrassEmission {
+    pub fn is_valid(&self) -> bool {
+        self.grass_per_sol > 0 && self.fee_discount_bps <= BASIS_POINTS
+    }
+}
+
+/// Open assignments of a verifier, readable as an inbox in one account fetch
+#[account]
+pub struct VerifierInbox {
+    pub verifier: Pubkey,
+    pub assignments: Vec<Assignment>,
     pub bump: u8,
 }
 
+impl VerifierInbox {
+    /// Account size, discriminator included, when holding `count` assignments
+    pub fn space(count: usize) -> usize {
+        8 + 32 + 4 + count * Assignment::INIT_SPACE + 1
+    }
+}
+
+/// A challenge naming the verifier that has not finalized yet
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct Assignment {
+    pub challenge: Pubkey,
+    pub verification_period_end: i64,
+}
+
 /// Challenge lifecycle state
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
 pub enum ChallengeStatus {
     Created,
     Active,
@@ -88,34 +785,193 @@ pub enum ChallengeStatus {
     Failed,
     Cancelled,
     Disputed,
+    /// Verification timed out under `TimeoutPolicy::Refund`
+    Refunded,
+}
+
+impl fmt::Display for ChallengeStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            ChallengeStatus::Created => "Created",
+            ChallengeStatus::Active => "Active",
+            ChallengeStatus::PendingVerification => "PendingVerification",
+            ChallengeStatus::Completed => "Completed",
+            ChallengeStatus::Failed => "Failed",
+            ChallengeStatus::Cancelled => "Cancelled",
+            ChallengeStatus::Disputed => "Disputed",
+            ChallengeStatus::Refunded => "Refunded",
+        };
+        f.write_str(name)
+    }
 }
 
-impl ChallengeStatus {
-    pub fn to_string(&self) -> String {
+/// How a challenge without a valid verdict settles once its verification
+/// period runs out
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum TimeoutPolicy {
+    /// Innocent until proven guilty
+    #[default]
+    Completed,
+    Failed,
+    /// The creator gets the stake back and the outcome counts as neither
+    Refund,
+}
+
+impl TimeoutPolicy {
+    pub fn outcome(&self) -> ChallengeStatus {
         match self {
-            ChallengeStatus::Created => "Created".to_string(),
-            ChallengeStatus::Active => "Active".to_string(),
-            ChallengeStatus::PendingVerification => "PendingVerification".to_string(),
-            ChallengeStatus::Completed => "Completed".to_string(),
-            ChallengeStatus::Failed => "Failed".to_string(),
-            ChallengeStatus::Cancelled => "Cancelled".to_string(),
-            ChallengeStatus::Disputed => "Disputed".to_string(),
+            TimeoutPolicy::Completed => ChallengeStatus::Completed,
+            TimeoutPolicy::Failed => ChallengeStatus::Failed,
+            TimeoutPolicy::Refund => ChallengeStatus::Refunded,
         }
     }
 }
 
 /// Verifier decision type
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
 pub enum Vote {
     Approve,
     Reject,
+    Abstain,
+}
+
+impl fmt::Display for Vote {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Vote::Approve => "Approve",
+            Vote::Reject => "Reject",
+            Vote::Abstain => "Abstain",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A juror's decision on an escalated dispute
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub enum JuryVote {
+    /// The disputed outcome stands
+    Uphold,
+    /// Completed ⇄ Failed
+    Overturn,
+}
+
+impl fmt::Display for JuryVote {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            JuryVote::Uphold => "Uphold",
+            JuryVote::Overturn => "Overturn",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Coordinates in fixed-point microdegrees (1e-6°)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct GeoPoint {
+    pub latitude: i32,
+    pub longitude: i32,
+}
+
+impl GeoPoint {
+    pub fn is_valid(&self) -> bool {
+        (-MAX_LATITUDE..=MAX_LATITUDE).contains(&self.latitude)
+            && (-MAX_LONGITUDE..=MAX_LONGITUDE).contains(&self.longitude)
+    }
+}
+
+/// Region evidence must be captured in
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum Geofence {
+    Circle { center: GeoPoint, radius_meters: u32 },
+    BoundingBox { south_west: GeoPoint, north_east: GeoPoint },
 }
 
-impl Vote {
-    pub fn to_string(&self) -> String {
+impl Geofence {
+    pub fn is_valid(&self) -> bool {
         match self {
-            Vote::Approve => "Approve".to_string(),
-            Vote::Reject => "Reject".to_string(),
+            Geofence::Circle { center, radius_meters } => center.is_valid() && *radius_meters > 0,
+            Geofence::BoundingBox { south_west, north_east } => {
+                south_west.is_valid()
+                    && north_east.is_valid()
+                    && south_west.latitude <= north_east.latitude
+            }
+        }
+    }
+
+    pub fn contains(&self, point: &GeoPoint) -> bool {
+        match self {
+            Geofence::Circle { center, radius_meters } => {
+                geo::within_radius(center, point, *radius_meters)
+            }
+            Geofence::BoundingBox { south_west, north_east } => {
+                geo::within_box(south_west, north_east, point)
+            }
+        }
+    }
+}
+
+/// UTC hour range [start_hour, end_hour), wrapping past midnight when start > end
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct HourWindow {
+    pub start_hour: u8,
+    pub end_hour: u8,
+}
+
+impl HourWindow {
+    pub fn is_valid(&self) -> bool {
+        self.start_hour < HOURS_PER_DAY
+            && self.end_hour <= HOURS_PER_DAY
+            && self.start_hour != self.end_hour
+    }
+
+    pub fn contains(&self, hour: u8) -> bool {
+        if self.start_hour < self.end_hour {
+            hour >= self.start_hour && hour < self.end_hour
+        } else {
+            hour >= self.start_hour || hour < self.end_hour
         }
     }
 }
+
+/// Optional cadence rules for evidence submission
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct SubmissionRules {
+    /// Minimum seconds between consecutive evidence timestamps
+    pub min_interval: Option<i64>,
+    /// Maximum evidence submissions per UTC day
+    pub max_per_day: Option<u8>,
+    /// UTC hours evidence may be submitted in (empty = any time)
+    #[max_len(MAX_HOUR_WINDOWS)]
+    pub allowed_hours: Vec<HourWindow>,
+}
+
+/// How votes turn into an outcome. With the defaults, a challenge nobody
+/// votes on completes when its verification period ends.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct VerificationRules {
+    /// Outcome when the period ends without a valid verdict
+    pub timeout_policy: TimeoutPolicy,
+    /// Votes, abstentions included, that must be cast before an approval or
+    /// rejection threshold counts (0 = no quorum)
+    pub min_votes: u8,
+}
+
+impl VerificationRules {
+    pub fn is_valid(&self, verifier_count: usize) -> bool {
+        self.min_votes as usize <= verifier_count
+    }
+
+    /// Whether enough verifiers have voted for a threshold to decide
+    pub fn quorum_reached(&self, votes_cast: u8) -> bool {
+        votes_cast >= self.min_votes
+    }
+}
+
+impl SubmissionRules {
+    pub fn is_valid(&self) -> bool {
+        self.min_interval.is_none_or(|interval| interval > 0)
+            && self.max_per_day.is_none_or(|max| max > 0)
+            && self.allowed_hours.len() <= MAX_HOUR_WINDOWS
+            && self.allowed_hours.iter().all(HourWindow::is_valid)
+    }
+}
@@ -1,7 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program, BN } from "@coral-xyz/anchor";
 import { ProofOfTouchGrass } from "../target/types/proof_of_touch_grass";
-import { PublicKey, Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { PublicKey, Keypair, LAMPORTS_PER_SOL, SYSVAR_SLOT_HASHES_PUBKEY } from "@solana/web3.js";
 import { assert } from "chai";
 import * as fs from "fs";
 import * as os from "os";
@@ -19,9 +19,23 @@ describe("Proof of Touch Grass Tests", () => {
   let verifier1: Keypair;
   let verifier2: Keypair;
   let verifier3: Keypair;
-  let platform: Keypair;
+
+  // PLATFORM_PUBKEY in constants.rs: fees, penalties and bonds can only go here
+  const platform = new PublicKey("EGBRkZBpQA5ZB5gfba726g5a1TFdLAv1q81pehNz9Ani");
+  const TOKEN_PROGRAM_ID = new PublicKey("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+  const TOKEN_2022_PROGRAM_ID = new PublicKey("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+  const ASSOCIATED_TOKEN_PROGRAM_ID = new PublicKey("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
 
   const STAKE_AMOUNT = new BN(10 * LAMPORTS_PER_SOL);
+  // One GRASS in base units; 100 per completion, 10 per winning vote weight,
+  // 100 GRASS to the SOL and 20% off fees paid in GRASS
+  const GRASS = 1_000_000;
+  const EMISSION = {
+    creatorReward: new BN(100 * GRASS),
+    verifierReward: new BN(10 * GRASS),
+    grassPerSol: new BN(100 * GRASS),
+    feeDiscountBps: new BN(2_000),
+  };
   const DISPUTE_WINDOW = 5;
 
   async function airdrop(pubkey: PublicKey, amount = 100 * LAMPORTS_PER_SOL) {
@@ -41,20 +55,29 @@ describe("Proof of Touch Grass Tests", () => {
     return pda;
   }
 
-  async function getNextChallengePda(authority: PublicKey): Promise<PublicKey> {
-    const userPda = getUserPda(authority);
-    const user = await program.account.user.fetch(userPda);
-    const [challengePda] = PublicKey.findProgramAddressSync(
+  function getChallengeIndexPda(authority: PublicKey): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("challenge_index"), authority.toBuffer()],
+      program.programId
+    );
+    return pda;
+  }
+
+  function getChallengePda(authority: PublicKey, challengeId: BN): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync(
       [
         Buffer.from("challenge"),
         authority.toBuffer(),
-        new BN(user.totalChallenges).toArrayLike(Buffer, "le", 4),
+        challengeId.toArrayLike(Buffer, "le", 8),
       ],
       program.programId
     );
-    return challengePda;
+    return pda;
   }
 
+  // Challenge IDs only need to be unique per creator
+  let nextChallengeId = 1;
+
   function getEscrowPda(challenge: PublicKey): PublicKey {
     const [pda] = PublicKey.findProgramAddressSync(
       [Buffer.from("escrow"), challenge.toBuffer()],
@@ -87,14 +110,160 @@ describe("Proof of Touch Grass Tests", () => {
     return pda;
   }
 
-  function getDisputePda(challenge: PublicKey): PublicKey {
+  function getReputationPda(verifier: PublicKey): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reputation"), verifier.toBuffer()],
+      program.programId
+    );
+    return pda;
+  }
+
+  function getBadgeMintPda(challenge: PublicKey): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("badge"), challenge.toBuffer()],
+      program.programId
+    );
+    return pda;
+  }
+
+  // The creator's Token-2022 associated token account for the challenge's badge
+  function getBadgeAccount(creatorPubkey: PublicKey, challenge: PublicKey): PublicKey {
+    const [address] = PublicKey.findProgramAddressSync(
+      [creatorPubkey.toBuffer(), TOKEN_2022_PROGRAM_ID.toBuffer(), getBadgeMintPda(challenge).toBuffer()],
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+    return address;
+  }
+
+  function getGrassConfigPda(): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync([Buffer.from("grass_config")], program.programId);
+    return pda;
+  }
+
+  function getGrassMintPda(): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync([Buffer.from("grass_mint")], program.programId);
+    return pda;
+  }
+
+  // The owner's classic SPL associated token account for GRASS
+  function getGrassAccount(owner: PublicKey): PublicKey {
+    const [address] = PublicKey.findProgramAddressSync(
+      [owner.toBuffer(), TOKEN_PROGRAM_ID.toBuffer(), getGrassMintPda().toBuffer()],
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+    return address;
+  }
+
+  async function grassBalance(owner: PublicKey): Promise<number> {
+    const account = await provider.connection.getAccountInfo(getGrassAccount(owner));
+    if (account === null) {
+      return 0;
+    }
+    const balance = await provider.connection.getTokenAccountBalance(getGrassAccount(owner));
+    return Number(balance.value.amount);
+  }
+
+  // The optional accounts of create_challenge, for a fee paid in SOL or in GRASS
+  function grassFeeAccounts(creatorPubkey: PublicKey, payFeeInGrass: boolean) {
+    if (!payFeeInGrass) {
+      return { grassConfig: null, grassMint: null, grassFeeAccount: null, tokenProgram: null };
+    }
+    return {
+      grassConfig: getGrassConfigPda(),
+      grassMint: getGrassMintPda(),
+      grassFeeAccount: getGrassAccount(creatorPubkey),
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+  }
+
+  // [verification, reputation] per listed verifier, settled by the first claim
+  async function reputationAccounts(challenge: PublicKey) {
+    const { verifiers } = await program.account.challenge.fetch(challenge);
+    return verifiers.flatMap((verifier: PublicKey) => [
+      { pubkey: getVerificationPda(challenge, verifier), isSigner: false, isWritable: false },
+      { pubkey: getReputationPda(verifier), isSigner: false, isWritable: true },
+    ]);
+  }
+
+  function getInboxPda(verifier: PublicKey): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("inbox"), verifier.toBuffer()],
+      program.programId
+    );
+    return pda;
+  }
+
+  // Inbox of each verifier, in order, cleared once the challenge finalizes
+  function inboxAccounts(verifiers: PublicKey[]) {
+    return verifiers.map((verifier) => ({
+      pubkey: getInboxPda(verifier),
+      isSigner: false,
+      isWritable: true,
+    }));
+  }
+
+  // Inboxes, then the reputation records a new challenge creates where missing
+  function newChallengeAccounts(verifiers: PublicKey[]) {
+    return [
+      ...inboxAccounts(verifiers),
+      ...verifiers.map((verifier) => ({
+        pubkey: getReputationPda(verifier),
+        isSigner: false,
+        isWritable: true,
+      })),
+    ];
+  }
+
+  async function challengeInboxAccounts(challenge: PublicKey) {
+    const { verifiers } = await program.account.challenge.fetch(challenge);
+    return inboxAccounts(verifiers);
+  }
+
+  function getExtensionPda(challenge: PublicKey): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("extension"), challenge.toBuffer()],
+      program.programId
+    );
+    return pda;
+  }
+
+  function getTemplatePda(author: PublicKey, templateId: BN): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("template"),
+        author.toBuffer(),
+        templateId.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    return pda;
+  }
+
+  // Each dispute on a challenge has its own index, from the challenge's dispute count
+  function getDisputePda(challenge: PublicKey, disputeIndex = 0): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("dispute"), challenge.toBuffer(), new Uint8Array([disputeIndex])],
+      program.programId
+    );
+    return pda;
+  }
+  function getJurorPda(authority: PublicKey): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("juror"), authority.toBuffer()],
+      program.programId
+    );
+    return pda;
+  }
+
+  function getJurorPoolPda(): PublicKey {
     const [pda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("dispute"), challenge.toBuffer()],
+      [Buffer.from("juror_pool")],
       program.programId
     );
     return pda;
   }
 
+
   async function createChallenge(
     creatorKeypair: Keypair,
     options: {
@@ -105,8 +274,14 @@ describe("Proof of Touch Grass Tests", () => {
       endTime?: BN;
       verificationPeriod?: BN;
       requiredProofs?: number;
-      requiredApprovals?: number;
+      requiredApprovalWeight?: number;
       verifiers?: PublicKey[];
+      verifierWeights?: number[];
+      geofences?: object[];
+      submissionRules?: object;
+      verificationRules?: object;
+      minVerifierReputation?: number | null;
+      payFeeInGrass?: boolean;
     } = {}
   ): Promise<PublicKey> {
     const now = Math.floor(Date.now() / 1000);
@@ -118,17 +293,25 @@ describe("Proof of Touch Grass Tests", () => {
       endTime: new BN(now + 3600),
       verificationPeriod: new BN(300),
       requiredProofs: 2,
-      requiredApprovals: 2,
+      requiredApprovalWeight: 2,
       verifiers: [verifier1.publicKey, verifier2.publicKey],
+      verifierWeights: [],
+      geofences: [],
+      submissionRules: { minInterval: null, maxPerDay: null, allowedHours: [] },
+      verificationRules: { timeoutPolicy: { completed: {} }, minVotes: 0 },
+      minVerifierReputation: null,
+      payFeeInGrass: false,
     };
     const params = { ...defaults, ...options };
 
     const userPda = getUserPda(creatorKeypair.publicKey);
-    const challengePda = await getNextChallengePda(creatorKeypair.publicKey);
+    const challengeId = new BN(nextChallengeId++);
+    const challengePda = getChallengePda(creatorKeypair.publicKey, challengeId);
     const escrowPda = getEscrowPda(challengePda);
 
     await program.methods
       .createChallenge(
+        challengeId,
         params.title,
         params.description,
         params.stakeAmount,
@@ -136,15 +319,23 @@ describe("Proof of Touch Grass Tests", () => {
         params.endTime,
         params.verificationPeriod,
         params.requiredProofs,
-        params.requiredApprovals,
-        params.verifiers
+        params.requiredApprovalWeight,
+        params.verifiers,
+        Buffer.from(params.verifierWeights),
+        params.geofences,
+        params.submissionRules,
+        params.verificationRules,
+        params.minVerifierReputation
       )
       .accounts({
         challenge: challengePda,
         escrow: escrowPda,
         user: userPda,
+        challengeIndex: getChallengeIndexPda(creatorKeypair.publicKey),
         creator: creatorKeypair.publicKey,
+        ...grassFeeAccounts(creatorKeypair.publicKey, params.payFeeInGrass),
       })
+      .remainingAccounts(newChallengeAccounts(params.verifiers))
       .signers([creatorKeypair])
       .rpc();
 
@@ -160,6 +351,7 @@ describe("Proof of Touch Grass Tests", () => {
         user: userPda,
         admin: admin.publicKey,
       })
+      .remainingAccounts(await challengeInboxAccounts(challenge))
       .signers([admin])
       .rpc();
   }
@@ -169,11 +361,12 @@ describe("Proof of Touch Grass Tests", () => {
     submitter: Keypair,
     evidenceIndex: number,
     ipfsHash: string = "QmTestHash",
-    metadata: string = "Test evidence"
+    metadata: string = "Test evidence",
+    location: { latitude: number; longitude: number } | null = null
   ): Promise<PublicKey> {
     const evidencePda = getEvidencePda(challenge, evidenceIndex);
     await program.methods
-      .submitEvidence(ipfsHash, metadata)
+      .submitEvidence(ipfsHash, metadata, location)
       .accounts({
         evidence: evidencePda,
         challenge,
@@ -193,13 +386,15 @@ describe("Proof of Touch Grass Tests", () => {
     const verificationPda = getVerificationPda(challenge, verifier.publicKey);
     const userPda = getUserPda(creatorPubkey);
     await program.methods
-      .verifyEvidence(approve ? { approve: {} } : { reject: {} })
+      .verifyEvidence(approve ? { approve: {} } : { reject: {} }, null)
       .accounts({
         verification: verificationPda,
+        reputation: getReputationPda(verifier.publicKey),
         challenge,
         user: userPda,
         verifier: verifier.publicKey,
       })
+      .remainingAccounts(await challengeInboxAccounts(challenge))
       .signers([verifier])
       .rpc();
     return verificationPda;
@@ -210,7 +405,6 @@ describe("Proof of Touch Grass Tests", () => {
     verifier1 = Keypair.generate();
     verifier2 = Keypair.generate();
     verifier3 = Keypair.generate();
-    platform = Keypair.generate();
 
     await Promise.all([
       airdrop(creator.publicKey),
@@ -219,6 +413,12 @@ describe("Proof of Touch Grass Tests", () => {
       airdrop(verifier3.publicKey),
       airdrop(admin.publicKey),
     ]);
+
+    await program.methods
+      .configureGrass(EMISSION)
+      .accounts({ admin: admin.publicKey })
+      .signers([admin])
+      .rpc();
   });
 
   describe("1. Initialize User", () => {
@@ -234,6 +434,7 @@ describe("Proof of Touch Grass Tests", () => {
         .initializeUser()
         .accounts({
           user: userPda,
+          challengeIndex: getChallengeIndexPda(creator.publicKey),
           authority: creator.publicKey,
         })
         .signers([creator])
@@ -244,6 +445,9 @@ describe("Proof of Touch Grass Tests", () => {
       assert.equal(user.totalChallenges, 0);
       assert.equal(user.completed, 0);
       assert.equal(user.failed, 0);
+      assert.equal(user.cancelled, 0);
+      assert.equal(user.activeChallenges, 0);
+      assert.equal(user.currentStreak, 0);
       assert.equal(user.totalStaked.toNumber(), 0);
     });
 
@@ -253,6 +457,7 @@ describe("Proof of Touch Grass Tests", () => {
           .initializeUser()
           .accounts({
             user: userPda,
+            challengeIndex: getChallengeIndexPda(creator.publicKey),
             authority: creator.publicKey,
           })
           .signers([creator])
@@ -286,6 +491,14 @@ describe("Proof of Touch Grass Tests", () => {
       const userPda = getUserPda(creator.publicKey);
       const user = await program.account.user.fetch(userPda);
       assert.equal(user.totalChallenges, 1);
+
+      const index = await program.account.challengeIndex.fetch(
+        getChallengeIndexPda(creator.publicKey)
+      );
+      assert.deepEqual(
+        index.challengeIds.map((id: BN) => id.toNumber()),
+        [challenge.challengeId.toNumber()]
+      );
     });
 
     it("Fails with invalid stake amount", async () => {
@@ -295,7 +508,7 @@ describe("Proof of Touch Grass Tests", () => {
           description: "Invalid stake",
           stakeAmount: new BN(0),
           requiredProofs: 1,
-          requiredApprovals: 1,
+          requiredApprovalWeight: 1,
           verifiers: [verifier1.publicKey],
         });
         assert.fail("Should have failed");
@@ -304,6 +517,56 @@ describe("Proof of Touch Grass Tests", () => {
       }
     });
 
+    it("Enforces the creator's own stake limit", async () => {
+      // A fresh creator, since lifting the limit again only takes effect a day later
+      const cautious = Keypair.generate();
+      await airdrop(cautious.publicKey);
+      await program.methods
+        .initializeUser()
+        .accounts({
+          user: getUserPda(cautious.publicKey),
+          challengeIndex: getChallengeIndexPda(cautious.publicKey),
+          authority: cautious.publicKey,
+        })
+        .signers([cautious])
+        .rpc();
+
+      const setLimits = (maxStake: BN | null) =>
+        program.methods
+          .setUserLimits({
+            maxActiveChallenges: null,
+            maxStake,
+            maxStakeAtRisk: null,
+            failureCooldown: null,
+          })
+          .accounts({
+            user: getUserPda(cautious.publicKey),
+            authority: cautious.publicKey,
+          })
+          .signers([cautious])
+          .rpc();
+
+      await setLimits(STAKE_AMOUNT.subn(1));
+      try {
+        await createChallenge(cautious, { title: "Too Rich" });
+        assert.fail("Should have failed");
+      } catch (err) {
+        assert.include(err.toString(), "StakeAboveLimit");
+      }
+
+      // Lifting the limit is only scheduled
+      await setLimits(null);
+      const user = await program.account.user.fetch(getUserPda(cautious.publicKey));
+      assert.equal(user.limits.maxStake.toString(), STAKE_AMOUNT.subn(1).toString());
+      assert.isNull(user.pendingLimits.maxStake);
+      try {
+        await createChallenge(cautious, { title: "Still Too Rich" });
+        assert.fail("Should have failed");
+      } catch (err) {
+        assert.include(err.toString(), "StakeAboveLimit");
+      }
+    });
+
     it("Fails with invalid time range", async () => {
       const now = Math.floor(Date.now() / 1000);
       try {
@@ -313,7 +576,7 @@ describe("Proof of Touch Grass Tests", () => {
           startTime: new BN(now + 3600),
           endTime: new BN(now + 10),
           requiredProofs: 1,
-          requiredApprovals: 1,
+          requiredApprovalWeight: 1,
           verifiers: [verifier1.publicKey],
         });
         assert.fail("Should have failed");
@@ -350,6 +613,7 @@ describe("Proof of Touch Grass Tests", () => {
             user: userPda,
             admin: creator.publicKey,
           })
+          .remainingAccounts(await challengeInboxAccounts(challengePda))
           .signers([creator])
           .rpc();
         assert.fail("Should have failed");
@@ -444,6 +708,9 @@ describe("Proof of Touch Grass Tests", () => {
 
       await verifyEvidence(challengePda, verifier1, creator.publicKey, true);
 
+      const inboxBefore = await program.account.verifierInbox.fetch(getInboxPda(verifier3.publicKey));
+      assert.ok(inboxBefore.assignments.some((a) => a.challenge.equals(challengePda)));
+
       await verifyEvidence(challengePda, verifier2, creator.publicKey, true);
 
       const challenge = await program.account.challenge.fetch(challengePda);
@@ -451,9 +718,14 @@ describe("Proof of Touch Grass Tests", () => {
       assert.ok(challenge.status.completed);
       assert.isAbove(challenge.finalizedAt.toNumber(), 0);
 
+      // Finalizing clears the challenge from every verifier's inbox
+      const inboxAfter = await program.account.verifierInbox.fetch(getInboxPda(verifier3.publicKey));
+      assert.notOk(inboxAfter.assignments.some((a) => a.challenge.equals(challengePda)));
+
       const userPda = getUserPda(creator.publicKey);
       const user = await program.account.user.fetch(userPda);
       assert.isAtLeast(user.completed, 1);
+      assert.isAtLeast(user.currentStreak, 1);
     });
 
     it("Rejects vote from non-verifier", async () => {
@@ -481,7 +753,7 @@ describe("Proof of Touch Grass Tests", () => {
       const challengePda = await createChallenge(creator, {
         title: "Rejection Test",
         requiredProofs: 2,
-        requiredApprovals: 2,
+        requiredApprovalWeight: 2,
         verifiers: [verifier1.publicKey, verifier2.publicKey, verifier3.publicKey],
       });
 
@@ -506,7 +778,7 @@ describe("Proof of Touch Grass Tests", () => {
       const challengePda = await createChallenge(creator, {
         title: "Threshold Test",
         requiredProofs: 2,
-        requiredApprovals: 3,
+        requiredApprovalWeight: 3,
         verifiers: [verifier1.publicKey, verifier2.publicKey, verifier3.publicKey],
       });
 
@@ -530,7 +802,7 @@ describe("Proof of Touch Grass Tests", () => {
         endTime: new BN(now + 2),
         verificationPeriod: new BN(3),
         requiredProofs: 2,
-        requiredApprovals: 2,
+        requiredApprovalWeight: 2,
         verifiers: [verifier1.publicKey, verifier2.publicKey],
       });
 
@@ -551,6 +823,7 @@ describe("Proof of Touch Grass Tests", () => {
           user: userPda,
           admin: admin.publicKey,
         })
+        .remainingAccounts(await challengeInboxAccounts(challengePda))
         .signers([admin])
         .rpc();
 
@@ -573,7 +846,7 @@ describe("Proof of Touch Grass Tests", () => {
       const challengePda = await createChallenge(creator, {
         title: "Claim Test",
         requiredProofs: 1,
-        requiredApprovals: 1,
+        requiredApprovalWeight: 1,
         verifiers: [verifier1.publicKey],
       });
 
@@ -592,10 +865,15 @@ describe("Proof of Touch Grass Tests", () => {
             escrow: escrowPda,
             user: userPda,
             creator: creator.publicKey,
-            platform: platform.publicKey,
+            platform: platform,
             claimer: creator.publicKey,
             verification: null,
+            badgeAccount: getBadgeAccount(creator.publicKey, challengePda),
+            grassConfig: getGrassConfigPda(),
+            grassMint: getGrassMintPda(),
+            grassAccount: getGrassAccount(creator.publicKey),
           })
+          .remainingAccounts(await reputationAccounts(challengePda))
           .signers([creator])
           .rpc();
         assert.fail("Should have failed");
@@ -608,7 +886,7 @@ describe("Proof of Touch Grass Tests", () => {
       const challengePda = await createChallenge(creator, {
         title: "Claim Test 2",
         requiredProofs: 1,
-        requiredApprovals: 1,
+        requiredApprovalWeight: 1,
         verifiers: [verifier1.publicKey],
       });
 
@@ -629,10 +907,15 @@ describe("Proof of Touch Grass Tests", () => {
           escrow: escrowPda,
           user: userPda,
           creator: creator.publicKey,
-          platform: platform.publicKey,
+          platform: platform,
           claimer: creator.publicKey,
           verification: null,
+          badgeAccount: getBadgeAccount(creator.publicKey, challengePda),
+          grassConfig: getGrassConfigPda(),
+          grassMint: getGrassMintPda(),
+          grassAccount: getGrassAccount(creator.publicKey),
         })
+        .remainingAccounts(await reputationAccounts(challengePda))
         .signers([creator])
         .rpc();
 
@@ -644,13 +927,18 @@ describe("Proof of Touch Grass Tests", () => {
 
       const challenge = await program.account.challenge.fetch(challengePda);
       assert.equal(challenge.claimed, true);
+
+      const badge = await provider.connection.getTokenAccountBalance(
+        getBadgeAccount(creator.publicKey, challengePda)
+      );
+      assert.equal(badge.value.amount, "1");
     });
 
     it("Verifiers claim rewards after successful rejection", async () => {
       const challengePda = await createChallenge(creator, {
         title: "Verifier Claim Test",
         requiredProofs: 1,
-        requiredApprovals: 2,
+        requiredApprovalWeight: 2,
         verifiers: [verifier1.publicKey, verifier2.publicKey, verifier3.publicKey],
       });
 
@@ -671,7 +959,7 @@ describe("Proof of Touch Grass Tests", () => {
 
       const verifier1BalanceBefore = await provider.connection.getBalance(verifier1.publicKey);
       const creatorBalanceBefore = await provider.connection.getBalance(creator.publicKey);
-      const platformBalanceBefore = await provider.connection.getBalance(platform.publicKey);
+      const platformBalanceBefore = await provider.connection.getBalance(platform);
 
       await program.methods
         .claimFunds()
@@ -680,16 +968,21 @@ describe("Proof of Touch Grass Tests", () => {
           escrow: escrowPda,
           user: userPda,
           creator: creator.publicKey,
-          platform: platform.publicKey,
+          platform: platform,
           claimer: verifier1.publicKey,
           verification: verificationPda,
+          badgeAccount: getBadgeAccount(creator.publicKey, challengePda),
+          grassConfig: getGrassConfigPda(),
+          grassMint: getGrassMintPda(),
+          grassAccount: getGrassAccount(verifier1.publicKey),
         })
+        .remainingAccounts(await reputationAccounts(challengePda))
         .signers([verifier1])
         .rpc();
 
       const verifier1BalanceAfter = await provider.connection.getBalance(verifier1.publicKey);
       const creatorBalanceAfter = await provider.connection.getBalance(creator.publicKey);
-      const platformBalanceAfter = await provider.connection.getBalance(platform.publicKey);
+      const platformBalanceAfter = await provider.connection.getBalance(platform);
 
       const verifier1Reward = verifier1BalanceAfter - verifier1BalanceBefore;
       const creatorRefund = creatorBalanceAfter - creatorBalanceBefore;
@@ -716,10 +1009,15 @@ describe("Proof of Touch Grass Tests", () => {
           escrow: escrowPda,
           user: userPda,
           creator: creator.publicKey,
-          platform: platform.publicKey,
+          platform: platform,
           claimer: verifier2.publicKey,
           verification: verificationPda2,
+          badgeAccount: getBadgeAccount(creator.publicKey, challengePda),
+          grassConfig: getGrassConfigPda(),
+          grassMint: getGrassMintPda(),
+          grassAccount: getGrassAccount(verifier2.publicKey),
         })
+        .remainingAccounts(await reputationAccounts(challengePda))
         .signers([verifier2])
         .rpc();
 
@@ -733,7 +1031,7 @@ describe("Proof of Touch Grass Tests", () => {
       const challengePda = await createChallenge(creator, {
         title: "Non-Voter Test",
         requiredProofs: 1,
-        requiredApprovals: 2,
+        requiredApprovalWeight: 2,
         verifiers: [verifier1.publicKey, verifier2.publicKey, verifier3.publicKey],
       });
 
@@ -761,10 +1059,15 @@ describe("Proof of Touch Grass Tests", () => {
           escrow: escrowPda,
           user: userPda,
           creator: creator.publicKey,
-          platform: platform.publicKey,
+          platform: platform,
           claimer: verifier1.publicKey,
           verification: verification1Pda,
+          badgeAccount: getBadgeAccount(creator.publicKey, challengePda),
+          grassConfig: getGrassConfigPda(),
+          grassMint: getGrassMintPda(),
+          grassAccount: getGrassAccount(verifier1.publicKey),
         })
+        .remainingAccounts(await reputationAccounts(challengePda))
         .signers([verifier1])
         .rpc();
 
@@ -783,10 +1086,15 @@ describe("Proof of Touch Grass Tests", () => {
             escrow: escrowPda,
             user: userPda,
             creator: creator.publicKey,
-            platform: platform.publicKey,
+            platform: platform,
             claimer: verifier3.publicKey,
             verification: verification3Pda,
+            badgeAccount: getBadgeAccount(creator.publicKey, challengePda),
+            grassConfig: getGrassConfigPda(),
+            grassMint: getGrassMintPda(),
+            grassAccount: getGrassAccount(verifier3.publicKey),
           })
+          .remainingAccounts(await reputationAccounts(challengePda))
           .signers([verifier3])
           .rpc();
         assert.fail("Should have failed - non-voter shouldn't be able to claim");
@@ -808,7 +1116,7 @@ describe("Proof of Touch Grass Tests", () => {
         startTime: new BN(now + 3600),
         endTime: new BN(now + 7200),
         requiredProofs: 1,
-        requiredApprovals: 1,
+        requiredApprovalWeight: 1,
         verifiers: [verifier1.publicKey],
       });
 
@@ -820,9 +1128,11 @@ describe("Proof of Touch Grass Tests", () => {
         .accounts({
           challenge: challengePda,
           escrow: escrowPda,
-          platform: platform.publicKey,
+          user: getUserPda(creator.publicKey),
+          platform: platform,
           creator: creator.publicKey,
         })
+        .remainingAccounts(await challengeInboxAccounts(challengePda))
         .signers([creator])
         .rpc();
 
@@ -840,7 +1150,7 @@ describe("Proof of Touch Grass Tests", () => {
       const challengePda = await createChallenge(creator, {
         title: "Cancel Active Test",
         requiredProofs: 1,
-        requiredApprovals: 1,
+        requiredApprovalWeight: 1,
         verifiers: [verifier1.publicKey],
       });
 
@@ -854,9 +1164,11 @@ describe("Proof of Touch Grass Tests", () => {
         .accounts({
           challenge: challengePda,
           escrow: escrowPda,
-          platform: platform.publicKey,
+          user: getUserPda(creator.publicKey),
+          platform: platform,
           creator: creator.publicKey,
         })
+        .remainingAccounts(await challengeInboxAccounts(challengePda))
         .signers([creator])
         .rpc();
 
@@ -877,7 +1189,7 @@ describe("Proof of Touch Grass Tests", () => {
         startTime: new BN(now + 3600),
         endTime: new BN(now + 7200),
         requiredProofs: 1,
-        requiredApprovals: 1,
+        requiredApprovalWeight: 1,
         verifiers: [verifier1.publicKey],
       });
 
@@ -888,9 +1200,11 @@ describe("Proof of Touch Grass Tests", () => {
           .accounts({
             challenge: challengePda,
             escrow: escrowPda,
-            platform: platform.publicKey,
+            user: getUserPda(creator.publicKey),
+            platform: platform,
             creator: verifier1.publicKey,
           })
+          .remainingAccounts(await challengeInboxAccounts(challengePda))
           .signers([verifier1])
           .rpc();
         assert.fail("Should have failed");
@@ -909,7 +1223,7 @@ describe("Proof of Touch Grass Tests", () => {
       const challengePda = await createChallenge(creator, {
         title: "Dispute Test",
         requiredProofs: 1,
-        requiredApprovals: 1,
+        requiredApprovalWeight: 1,
         verifiers: [verifier1.publicKey],
       });
 
@@ -924,6 +1238,7 @@ describe("Proof of Touch Grass Tests", () => {
           dispute: disputePda,
           challenge: challengePda,
           disputer: creator.publicKey,
+          contestedVerification: null,
         })
         .signers([creator])
         .rpc();
@@ -934,13 +1249,37 @@ describe("Proof of Touch Grass Tests", () => {
       const dispute = await program.account.dispute.fetch(disputePda);
       assert.equal(dispute.disputer.toString(), creator.publicKey.toString());
       assert.equal(dispute.reason, "Evidence appears fraudulent");
+      // 2% of the stake, held on the dispute until it is resolved
+      assert.equal(dispute.bond.toString(), STAKE_AMOUNT.divn(50).toString());
+      assert.equal(challenge.disputeCount, 1);
+
+      // Upheld: the outcome stands and the bond goes to the platform
+      const platformBefore = await provider.connection.getBalance(platform);
+      await program.methods
+        .resolveDispute(false)
+        .accounts({
+          challenge: challengePda,
+          dispute: disputePda,
+          user: getUserPda(creator.publicKey),
+          disputer: creator.publicKey,
+          platform,
+          admin: admin.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+
+      const resolved = await program.account.challenge.fetch(challengePda);
+      assert.ok(resolved.status.completed);
+      assert.ok(resolved.disputers[0].equals(creator.publicKey));
+      const platformAfter = await provider.connection.getBalance(platform);
+      assert.equal(platformAfter, platformBefore + dispute.bond.toNumber());
     });
 
     it("Fails to dispute after window expires", async () => {
       const challengePda = await createChallenge(creator, {
         title: "Dispute Expired Test",
         requiredProofs: 1,
-        requiredApprovals: 1,
+        requiredApprovalWeight: 1,
         verifiers: [verifier1.publicKey],
       });
 
@@ -958,6 +1297,7 @@ describe("Proof of Touch Grass Tests", () => {
             dispute: disputePda,
             challenge: challengePda,
             disputer: creator.publicKey,
+            contestedVerification: null,
           })
           .signers([creator])
           .rpc();
@@ -967,4 +1307,755 @@ describe("Proof of Touch Grass Tests", () => {
       }
     });
   });
+
+  describe("9. Geofencing", () => {
+    // Central Park, New York (microdegrees)
+    const park = { latitude: 40_782_865, longitude: -73_965_355 };
+    const geofences = [{ circle: { center: park, radiusMeters: 1_000 } }];
+
+    before(async () => {
+      await airdrop(creator.publicKey);
+    });
+
+    it("Accepts evidence captured inside the geofence", async () => {
+      const challengePda = await createChallenge(creator, {
+        title: "Geofence Inside Test",
+        requiredProofs: 1,
+        requiredApprovalWeight: 1,
+        verifiers: [verifier1.publicKey],
+        geofences,
+      });
+      await activateChallenge(challengePda, creator.publicKey);
+
+      const nearby = { latitude: 40_785_091, longitude: -73_968_285 };
+      const evidencePda = await submitEvidence(challengePda, creator, 0, "QmPark", "Park walk", nearby);
+
+      const evidence = await program.account.evidence.fetch(evidencePda);
+      assert.equal(evidence.location.latitude, nearby.latitude);
+      assert.equal(evidence.location.longitude, nearby.longitude);
+    });
+
+    it("Rejects evidence captured outside every geofence", async () => {
+      const challengePda = await createChallenge(creator, {
+        title: "Geofence Outside Test",
+        requiredProofs: 1,
+        requiredApprovalWeight: 1,
+        verifiers: [verifier1.publicKey],
+        geofences,
+      });
+      await activateChallenge(challengePda, creator.publicKey);
+
+      // Times Square, ~3 km away
+      const faraway = { latitude: 40_758_896, longitude: -73_985_130 };
+      try {
+        await submitEvidence(challengePda, creator, 0, "QmCouch", "Definitely outside", faraway);
+        assert.fail("Should have failed");
+      } catch (err) {
+        assert.include(err.toString(), "LocationOutsideGeofence");
+      }
+    });
+
+    it("Rejects geofenced evidence without a location", async () => {
+      const challengePda = await createChallenge(creator, {
+        title: "Geofence Missing Location Test",
+        requiredProofs: 1,
+        requiredApprovalWeight: 1,
+        verifiers: [verifier1.publicKey],
+        geofences,
+      });
+      await activateChallenge(challengePda, creator.publicKey);
+
+      try {
+        await submitEvidence(challengePda, creator, 0);
+        assert.fail("Should have failed");
+      } catch (err) {
+        assert.include(err.toString(), "LocationRequired");
+      }
+    });
+  });
+
+  describe("10. Submission Rules", () => {
+    before(async () => {
+      await airdrop(creator.publicKey);
+    });
+
+    it("Rejects evidence submitted before the minimum gap", async () => {
+      const challengePda = await createChallenge(creator, {
+        title: "Min Interval Test",
+        submissionRules: { minInterval: new BN(3600), maxPerDay: null, allowedHours: [] },
+      });
+      await activateChallenge(challengePda, creator.publicKey);
+      await submitEvidence(challengePda, creator, 0);
+
+      try {
+        await submitEvidence(challengePda, creator, 1);
+        assert.fail("Should have failed");
+      } catch (err) {
+        assert.include(err.toString(), "SubmissionTooSoon");
+      }
+    });
+
+    it("Rejects evidence beyond the daily limit", async () => {
+      const challengePda = await createChallenge(creator, {
+        title: "Daily Limit Test",
+        requiredProofs: 3,
+        submissionRules: { minInterval: null, maxPerDay: 1, allowedHours: [] },
+      });
+      await activateChallenge(challengePda, creator.publicKey);
+      await submitEvidence(challengePda, creator, 0);
+
+      try {
+        await submitEvidence(challengePda, creator, 1);
+        assert.fail("Should have failed");
+      } catch (err) {
+        assert.include(err.toString(), "DailySubmissionLimitReached");
+      }
+    });
+
+    it("Rejects evidence outside the allowed hours", async () => {
+      // Window covering every UTC hour except the current one
+      const hour = new Date().getUTCHours();
+      const challengePda = await createChallenge(creator, {
+        title: "Allowed Hours Test",
+        submissionRules: {
+          minInterval: null,
+          maxPerDay: null,
+          allowedHours: [{ startHour: (hour + 1) % 24, endHour: hour }],
+        },
+      });
+      await activateChallenge(challengePda, creator.publicKey);
+
+      try {
+        await submitEvidence(challengePda, creator, 0);
+        assert.fail("Should have failed");
+      } catch (err) {
+        assert.include(err.toString(), "OutsideAllowedHours");
+      }
+    });
+  });
+
+  describe("11. Replace & Retract Evidence", () => {
+    before(async () => {
+      await airdrop(creator.publicKey);
+    });
+
+    it("Replaces evidence in place and bumps the revision", async () => {
+      const challengePda = await createChallenge(creator, {
+        title: "Replace Evidence Test",
+      });
+      await activateChallenge(challengePda, creator.publicKey);
+      const evidencePda = await submitEvidence(challengePda, creator, 0, "QmWrongUpload", "Oops");
+
+      await program.methods
+        .replaceEvidence(0, "QmRightUpload", "Fixed", null)
+        .accounts({
+          evidence: evidencePda,
+          challenge: challengePda,
+          submitter: creator.publicKey,
+        })
+        .signers([creator])
+        .rpc();
+
+      const evidence = await program.account.evidence.fetch(evidencePda);
+      assert.equal(evidence.ipfsHash, "QmRightUpload");
+      assert.equal(evidence.metadata, "Fixed");
+      assert.equal(evidence.evidenceIndex, 0);
+      assert.equal(evidence.revision, 1);
+    });
+
+    it("Retracts the last evidence and reopens the challenge", async () => {
+      const challengePda = await createChallenge(creator, {
+        title: "Retract Evidence Test",
+      });
+      await activateChallenge(challengePda, creator.publicKey);
+      await submitEvidence(challengePda, creator, 0, "QmHash1", "Evidence 1");
+      const lastEvidence = await submitEvidence(challengePda, creator, 1, "QmHash2", "Evidence 2");
+
+      await program.methods
+        .retractEvidence()
+        .accounts({
+          evidence: lastEvidence,
+          challenge: challengePda,
+          submitter: creator.publicKey,
+        })
+        .signers([creator])
+        .rpc();
+
+      const challenge = await program.account.challenge.fetch(challengePda);
+      assert.equal(challenge.evidenceCount, 1);
+      assert.ok(challenge.status.active);
+      assert.isNull(await provider.connection.getAccountInfo(lastEvidence));
+    });
+
+    it("Fails to replace evidence once voting has started", async () => {
+      const challengePda = await createChallenge(creator, {
+        title: "Locked Evidence Test",
+        requiredProofs: 1,
+      });
+      await activateChallenge(challengePda, creator.publicKey);
+      const evidencePda = await submitEvidence(challengePda, creator, 0);
+      await verifyEvidence(challengePda, verifier1, creator.publicKey, true);
+
+      try {
+        await program.methods
+          .replaceEvidence(0, "QmTooLate", "Too late", null)
+          .accounts({
+            evidence: evidencePda,
+            challenge: challengePda,
+            submitter: creator.publicKey,
+          })
+          .signers([creator])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        assert.include(err.toString(), "EvidenceLocked");
+      }
+    });
+  });
+
+  describe("12. Extend Challenge", () => {
+    before(async () => {
+      await airdrop(creator.publicKey);
+    });
+
+    async function approveExtension(challenge: PublicKey, verifier: Keypair) {
+      await program.methods
+        .approveExtension()
+        .accounts({
+          extension: getExtensionPda(challenge),
+          challenge,
+          creator: creator.publicKey,
+          platform: platform,
+          verifier: verifier.publicKey,
+        })
+        .remainingAccounts(await challengeInboxAccounts(challenge))
+        .signers([verifier])
+        .rpc();
+    }
+
+    it("Moves the end time once every required verifier approves", async () => {
+      const challengePda = await createChallenge(creator, {
+        title: "Extension Test",
+      });
+      await activateChallenge(challengePda, creator.publicKey);
+      const before = await program.account.challenge.fetch(challengePda);
+      const newEndTime = before.endTime.add(new BN(86400));
+
+      await program.methods
+        .requestExtension(newEndTime)
+        .accounts({
+          extension: getExtensionPda(challengePda),
+          challenge: challengePda,
+          creator: creator.publicKey,
+        })
+        .signers([creator])
+        .rpc();
+
+      await approveExtension(challengePda, verifier1);
+      let challenge = await program.account.challenge.fetch(challengePda);
+      assert.ok(challenge.endTime.eq(before.endTime));
+
+      await approveExtension(challengePda, verifier2);
+      challenge = await program.account.challenge.fetch(challengePda);
+      assert.ok(challenge.endTime.eq(newEndTime));
+      assert.ok(
+        challenge.verificationPeriodEnd.eq(before.verificationPeriodEnd.add(new BN(86400)))
+      );
+      assert.isNull(await provider.connection.getAccountInfo(getExtensionPda(challengePda)));
+    });
+
+    it("Rejects an extension beyond the maximum", async () => {
+      const challengePda = await createChallenge(creator, {
+        title: "Extension Limit Test",
+      });
+      const { endTime } = await program.account.challenge.fetch(challengePda);
+
+      try {
+        await program.methods
+          .requestExtension(endTime.add(new BN(15 * 86400)))
+          .accounts({
+            extension: getExtensionPda(challengePda),
+            challenge: challengePda,
+            creator: creator.publicKey,
+          })
+          .signers([creator])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        assert.include(err.toString(), "InvalidExtension");
+      }
+    });
+  });
+
+  describe("13. Retry Challenge", () => {
+    it("Retries a failed challenge once the dispute window closes", async () => {
+      const failedPda = await createChallenge(creator, {
+        title: "Retry Test",
+        requiredProofs: 1,
+        requiredApprovalWeight: 2,
+      });
+      await activateChallenge(failedPda, creator.publicKey);
+      await submitEvidence(failedPda, creator, 0, "QmHash1", "Evidence 1");
+      await verifyEvidence(failedPda, verifier1, creator.publicKey, false);
+      await verifyEvidence(failedPda, verifier2, creator.publicKey, false);
+      await sleep(DISPUTE_WINDOW + 1);
+
+      const challengeId = new BN(nextChallengeId++);
+      const retryPda = getChallengePda(creator.publicKey, challengeId);
+      const now = Math.floor(Date.now() / 1000);
+
+      await program.methods
+        .retryChallenge(challengeId, STAKE_AMOUNT, new BN(now))
+        .accounts({
+          retry: {
+            challenge: retryPda,
+            escrow: getEscrowPda(retryPda),
+            user: getUserPda(creator.publicKey),
+            challengeIndex: getChallengeIndexPda(creator.publicKey),
+            creator: creator.publicKey,
+            ...grassFeeAccounts(creator.publicKey, false),
+          },
+          previousChallenge: failedPda,
+          template: null,
+        })
+        .remainingAccounts(newChallengeAccounts([verifier1.publicKey, verifier2.publicKey]))
+        .signers([creator])
+        .rpc();
+
+      const failed = await program.account.challenge.fetch(failedPda);
+      const retry = await program.account.challenge.fetch(retryPda);
+      assert.ok(failed.retriedBy.equals(retryPda));
+      assert.ok(retry.previousChallenge.equals(failedPda));
+      assert.equal(retry.title, "Retry Test");
+      assert.ok(retry.status.created);
+
+      const user = await program.account.user.fetch(getUserPda(creator.publicKey));
+      assert.isAtLeast(user.retries, 1);
+    });
+  });
+
+  describe("14. Challenge Templates", () => {
+    const templateId = new BN(1);
+    let templatePda: PublicKey;
+
+    before(async () => {
+      templatePda = getTemplatePda(verifier3.publicKey, templateId);
+      await program.methods
+        .createTemplate(
+          templateId,
+          "Template Test",
+          "Walk in the park",
+          new BN(3600),
+          new BN(300),
+          2,
+          2,
+          [],
+          { minInterval: null, maxPerDay: null, allowedHours: [] },
+          { timeoutPolicy: { completed: {} }, minVotes: 0 },
+          null
+        )
+        .accounts({
+          template: templatePda,
+          author: verifier3.publicKey,
+        })
+        .signers([verifier3])
+        .rpc();
+    });
+
+    it("Creates a challenge from a template", async () => {
+      const challengeId = new BN(nextChallengeId++);
+      const challengePda = getChallengePda(creator.publicKey, challengeId);
+      const verifiers = [verifier1.publicKey, verifier2.publicKey];
+      const now = Math.floor(Date.now() / 1000);
+
+      await program.methods
+        .createChallengeFromTemplate(challengeId, STAKE_AMOUNT, new BN(now), verifiers, Buffer.from([]))
+        .accounts({
+          challenge: {
+            challenge: challengePda,
+            escrow: getEscrowPda(challengePda),
+            user: getUserPda(creator.publicKey),
+            challengeIndex: getChallengeIndexPda(creator.publicKey),
+            creator: creator.publicKey,
+            ...grassFeeAccounts(creator.publicKey, false),
+          },
+          template: templatePda,
+        })
+        .remainingAccounts(newChallengeAccounts(verifiers))
+        .signers([creator])
+        .rpc();
+
+      const challenge = await program.account.challenge.fetch(challengePda);
+      assert.ok(challenge.template.equals(templatePda));
+      assert.equal(challenge.title, "Template Test");
+      assert.ok(challenge.endTime.eq(new BN(now + 3600)));
+
+      const template = await program.account.challengeTemplate.fetch(templatePda);
+      assert.equal(template.uses, 1);
+    });
+
+    it("Only the admin curates templates", async () => {
+      try {
+        await program.methods
+          .curateTemplate(true)
+          .accounts({ template: templatePda, admin: verifier3.publicKey })
+          .signers([verifier3])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        assert.include(err.toString(), "UnauthorizedAdmin");
+      }
+
+      await program.methods
+        .curateTemplate(true)
+        .accounts({ template: templatePda, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      const template = await program.account.challengeTemplate.fetch(templatePda);
+      assert.isTrue(template.curated);
+    });
+  });
+
+  describe("15. Timeout Policy & Quorum", () => {
+    it("Refunds the stake when nobody votes under a refund policy", async () => {
+      const now = Math.floor(Date.now() / 1000);
+      const challengePda = await createChallenge(creator, {
+        title: "Refund Policy Test",
+        startTime: new BN(now - 100),
+        endTime: new BN(now + 2),
+        verificationPeriod: new BN(3),
+        verificationRules: { timeoutPolicy: { refund: {} }, minVotes: 2 },
+      });
+
+      await activateChallenge(challengePda, creator.publicKey);
+      await submitEvidence(challengePda, creator, 0, "QmHash1", "Evidence 1");
+      await submitEvidence(challengePda, creator, 1, "QmHash2", "Evidence 2");
+      await sleep(6);
+
+      const userPda = getUserPda(creator.publicKey);
+      await program.methods
+        .updateChallengeState()
+        .accounts({
+          challenge: challengePda,
+          user: userPda,
+          admin: admin.publicKey,
+        })
+        .remainingAccounts(await challengeInboxAccounts(challengePda))
+        .signers([admin])
+        .rpc();
+
+      const challenge = await program.account.challenge.fetch(challengePda);
+      assert.ok(challenge.status.refunded);
+      const user = await program.account.user.fetch(userPda);
+      assert.isAtLeast(user.refunded, 1);
+    });
+  });
+
+  describe("16. Abstain & Vote Changes", () => {
+    it("Abstains, then changes the vote to approve and finalizes", async () => {
+      const challengePda = await createChallenge(creator, {
+        title: "Vote Change Test",
+        verifiers: [verifier1.publicKey, verifier2.publicKey, verifier3.publicKey],
+      });
+
+      await activateChallenge(challengePda, creator.publicKey);
+      await submitEvidence(challengePda, creator, 0, "QmHash1", "Evidence 1");
+      await submitEvidence(challengePda, creator, 1, "QmHash2", "Evidence 2");
+
+      const userPda = getUserPda(creator.publicKey);
+      await program.methods
+        .verifyEvidence({ abstain: {} }, null)
+        .accounts({
+          verification: getVerificationPda(challengePda, verifier2.publicKey),
+          reputation: getReputationPda(verifier2.publicKey),
+          challenge: challengePda,
+          user: userPda,
+          verifier: verifier2.publicKey,
+        })
+        .remainingAccounts(await challengeInboxAccounts(challengePda))
+        .signers([verifier2])
+        .rpc();
+      await verifyEvidence(challengePda, verifier1, creator.publicKey, true);
+
+      let challenge = await program.account.challenge.fetch(challengePda);
+      assert.equal(challenge.approvalCount, 1);
+      assert.equal(challenge.abstainCount, 1);
+      assert.ok(challenge.status.pendingVerification);
+
+      await program.methods
+        .updateVote({ approve: {} }, null)
+        .accounts({
+          verification: getVerificationPda(challengePda, verifier2.publicKey),
+          challenge: challengePda,
+          user: userPda,
+          verifier: verifier2.publicKey,
+        })
+        .remainingAccounts(await challengeInboxAccounts(challengePda))
+        .signers([verifier2])
+        .rpc();
+
+      challenge = await program.account.challenge.fetch(challengePda);
+      assert.equal(challenge.approvalCount, 2);
+      assert.equal(challenge.abstainCount, 0);
+      assert.ok(challenge.status.completed);
+    });
+  });
+
+  describe("17. Weighted Verifiers", () => {
+    it("Completes on a single heavy approval", async () => {
+      const challengePda = await createChallenge(creator, {
+        title: "Weighted Test",
+        requiredApprovalWeight: 3,
+        verifiers: [verifier1.publicKey, verifier2.publicKey, verifier3.publicKey],
+        verifierWeights: [3, 1, 1],
+      });
+
+      await activateChallenge(challengePda, creator.publicKey);
+      await submitEvidence(challengePda, creator, 0, "QmHash1", "Evidence 1");
+      await submitEvidence(challengePda, creator, 1, "QmHash2", "Evidence 2");
+      await verifyEvidence(challengePda, verifier1, creator.publicKey, true);
+
+      const challenge = await program.account.challenge.fetch(challengePda);
+      assert.deepEqual(Array.from(challenge.verifierWeights), [3, 1, 1]);
+      assert.equal(challenge.approvalCount, 1);
+      assert.equal(challenge.approvalWeight, 3);
+      assert.ok(challenge.status.completed);
+    });
+  });
+
+  describe("18. Vote Justifications", () => {
+    it("Rejects with a justification that a dispute then contests", async () => {
+      const challengePda = await createChallenge(creator, {
+        title: "Justification Test",
+        requiredProofs: 1,
+        requiredApprovalWeight: 1,
+        verifiers: [verifier1.publicKey],
+      });
+
+      await activateChallenge(challengePda, creator.publicKey);
+      await submitEvidence(challengePda, creator, 0);
+
+      const verificationPda = getVerificationPda(challengePda, verifier1.publicKey);
+      await program.methods
+        .verifyEvidence(
+          { reject: {} },
+          { reason: "The page count isn't readable", attachment: "QmAnnotated" }
+        )
+        .accounts({
+          verification: verificationPda,
+          reputation: getReputationPda(verifier1.publicKey),
+          challenge: challengePda,
+          user: getUserPda(creator.publicKey),
+          verifier: verifier1.publicKey,
+        })
+        .remainingAccounts(await challengeInboxAccounts(challengePda))
+        .signers([verifier1])
+        .rpc();
+
+      const verification = await program.account.verification.fetch(verificationPda);
+      assert.equal(verification.justification.reason, "The page count isn't readable");
+      assert.equal(verification.justification.attachment, "QmAnnotated");
+
+      const disputePda = getDisputePda(challengePda);
+      await program.methods
+        .disputeVerification("The count is in the second photo")
+        .accounts({
+          dispute: disputePda,
+          challenge: challengePda,
+          disputer: creator.publicKey,
+          contestedVerification: verificationPda,
+        })
+        .signers([creator])
+        .rpc();
+
+      const dispute = await program.account.dispute.fetch(disputePda);
+      assert.equal(dispute.contestedVerification.toString(), verificationPda.toString());
+    });
+  });
+
+  describe("19. Jury Escalation", () => {
+    const jurors: Keypair[] = [];
+    const JUROR_STAKE = new BN(LAMPORTS_PER_SOL);
+
+    before(async () => {
+      for (let i = 0; i < 3; i++) {
+        const juror = Keypair.generate();
+        await airdrop(juror.publicKey, 10 * LAMPORTS_PER_SOL);
+        await program.methods
+          .registerJuror(JUROR_STAKE)
+          .accounts({
+            juror: getJurorPda(juror.publicKey),
+            jurorPool: getJurorPoolPda(),
+            authority: juror.publicKey,
+          })
+          .signers([juror])
+          .rpc();
+        jurors.push(juror);
+      }
+    });
+
+    it("A jury overturns an escalated dispute", async () => {
+      const challengePda = await createChallenge(creator, {
+        title: "Jury Test",
+        requiredProofs: 1,
+        requiredApprovalWeight: 1,
+        verifiers: [verifier1.publicKey],
+      });
+
+      await activateChallenge(challengePda, creator.publicKey);
+      await submitEvidence(challengePda, creator, 0);
+      await verifyEvidence(challengePda, verifier1, creator.publicKey, false);
+
+      const disputePda = getDisputePda(challengePda);
+      await program.methods
+        .disputeVerification("The photo shows the park")
+        .accounts({
+          dispute: disputePda,
+          challenge: challengePda,
+          disputer: creator.publicKey,
+          contestedVerification: null,
+        })
+        .signers([creator])
+        .rpc();
+
+      const pool = await program.account.jurorPool.fetch(getJurorPoolPda());
+      await program.methods
+        .escalateDispute()
+        .accounts({
+          dispute: disputePda,
+          challenge: challengePda,
+          jurorPool: getJurorPoolPda(),
+          slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+          signer: creator.publicKey,
+        })
+        .remainingAccounts(
+          pool.jurors.map((juror: PublicKey) => ({
+            pubkey: getJurorPda(juror),
+            isSigner: false,
+            isWritable: true,
+          }))
+        )
+        .signers([creator])
+        .rpc();
+
+      const { jury } = await program.account.dispute.fetch(disputePda);
+      assert.equal(jury.length, 3);
+      const seated = jury.map((seat: { juror: PublicKey }) =>
+        jurors.find((juror) => juror.publicKey.equals(seat.juror))
+      );
+
+      for (const juror of seated.slice(0, 2)) {
+        await program.methods
+          .castJuryVote({ overturn: {} })
+          .accounts({
+            dispute: disputePda,
+            juror: getJurorPda(juror.publicKey),
+            authority: juror.publicKey,
+          })
+          .signers([juror])
+          .rpc();
+      }
+
+      await program.methods
+        .settleJury()
+        .accounts({
+          challenge: challengePda,
+          dispute: disputePda,
+          user: getUserPda(creator.publicKey),
+          disputer: creator.publicKey,
+          platform,
+        })
+        .remainingAccounts([
+          ...seated.map((juror) => ({
+            pubkey: getJurorPda(juror.publicKey),
+            isSigner: false,
+            isWritable: true,
+          })),
+          ...(await reputationAccounts(challengePda)),
+        ])
+        .rpc();
+
+      const challenge = await program.account.challenge.fetch(challengePda);
+      assert.ok(challenge.status.completed);
+
+      // The verdict came before voting closed, so the silent juror keeps their stake
+      const silent = await program.account.juror.fetch(getJurorPda(seated[2].publicKey));
+      assert.equal(silent.stake.toString(), JUROR_STAKE.toString());
+      assert.equal(silent.minorityVotes, 0);
+      assert.equal(silent.pendingVerdicts, 0);
+    });
+  });
+
+  describe("20. GRASS Rewards & Fees", () => {
+    async function claim(challengePda: PublicKey, claimer: Keypair, asVerifier: boolean) {
+      await program.methods
+        .claimFunds()
+        .accounts({
+          challenge: challengePda,
+          escrow: getEscrowPda(challengePda),
+          user: getUserPda(creator.publicKey),
+          creator: creator.publicKey,
+          platform: platform,
+          claimer: claimer.publicKey,
+          verification: asVerifier ? getVerificationPda(challengePda, claimer.publicKey) : null,
+          badgeAccount: getBadgeAccount(creator.publicKey, challengePda),
+          grassConfig: getGrassConfigPda(),
+          grassMint: getGrassMintPda(),
+          grassAccount: getGrassAccount(claimer.publicKey),
+        })
+        .remainingAccounts(await reputationAccounts(challengePda))
+        .signers([claimer])
+        .rpc();
+    }
+
+    it("The program's config PDA is the mint authority", async () => {
+      const config = await program.account.grassConfig.fetch(getGrassConfigPda());
+      assert.ok(config.mint.equals(getGrassMintPda()));
+      assert.equal(config.emission.creatorReward.toString(), EMISSION.creatorReward.toString());
+
+      const mint = await provider.connection.getParsedAccountInfo(getGrassMintPda());
+      const info = (mint.value.data as { parsed: { info: { mintAuthority: string } } }).parsed.info;
+      assert.equal(info.mintAuthority, getGrassConfigPda().toBase58());
+    });
+
+    it("Rewards the creator and the approving verifier of a completed challenge", async () => {
+      const challengePda = await createChallenge(creator, {
+        title: "GRASS Reward Test",
+        requiredProofs: 1,
+        requiredApprovalWeight: 1,
+        verifiers: [verifier1.publicKey],
+      });
+      await activateChallenge(challengePda, creator.publicKey);
+      await submitEvidence(challengePda, creator, 0);
+      await verifyEvidence(challengePda, verifier1, creator.publicKey, true);
+      await sleep(DISPUTE_WINDOW + 1);
+
+      const creatorBefore = await grassBalance(creator.publicKey);
+      const verifierBefore = await grassBalance(verifier1.publicKey);
+      await claim(challengePda, creator, false);
+      await claim(challengePda, verifier1, true);
+
+      assert.equal(await grassBalance(creator.publicKey), creatorBefore + 100 * GRASS);
+      assert.equal(await grassBalance(verifier1.publicKey), verifierBefore + 10 * GRASS);
+    });
+
+    it("Burns the discounted fee in GRASS and escrows only the stake", async () => {
+      const grassBefore = await grassBalance(creator.publicKey);
+      const challengePda = await createChallenge(creator, {
+        title: "GRASS Fee Test",
+        requiredProofs: 1,
+        requiredApprovalWeight: 1,
+        verifiers: [verifier1.publicKey],
+        payFeeInGrass: true,
+      });
+
+      // 0.5 SOL of fee at 100 GRASS to the SOL, less 20%
+      assert.equal(await grassBalance(creator.publicKey), grassBefore - 40 * GRASS);
+      const challenge = await program.account.challenge.fetch(challengePda);
+      assert.ok(challenge.feePaidInGrass);
+
+      const escrow = await provider.connection.getBalance(getEscrowPda(challengePda));
+      assert.equal(escrow, STAKE_AMOUNT.toNumber());
+    });
+  });
 });